@@ -0,0 +1,132 @@
+//! Benchmarks of the hot paths of the rules engine and of the computer opponent, built on [`criterion`]
+//!
+//! The benchmarks are run with `cargo bench`; `cargo bench -- perft` only runs those whose name contains `perft`.
+//! Criterion compares each run with the previous one and reports the significant changes.
+
+use blobwars::{
+    ai::{Ai, Difficulty, Personality},
+    game::{Board, Player},
+};
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+
+/// The positions the benchmarks are run on, with the player to move
+const POSITIONS: [(&str, &str, Player); 3] = [
+    (
+        "start",
+        "B.....R/......./......./......./......./......./R.....B",
+        Player::Blue,
+    ),
+    (
+        "gaps",
+        "B.....R/......./..#.#../......./..#.#../......./R.....B",
+        Player::Blue,
+    ),
+    (
+        "midgame",
+        "BBB.RRR/BB..RR./B.#B#.R/.BBRR../R.#B#.B/RR..BB./RRR.BBB",
+        Player::Red,
+    ),
+];
+
+/// The depth of the perft benchmarks
+const PERFT_DEPTH: usize = 3;
+
+fn positions() -> impl Iterator<Item = (&'static str, Board, Player)> {
+    POSITIONS.into_iter().map(|(name, board, player)| {
+        let board = board.parse().expect("The position is malformed");
+        (name, board, player)
+    })
+}
+
+/// Measure the generation of the legal moves, in moves per second
+fn moves(c: &mut Criterion) {
+    let mut group = c.benchmark_group("moves");
+
+    for (name, board, player) in positions() {
+        group.throughput(Throughput::Elements(board.moves(player).count() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &board, |b, board| {
+            b.iter(|| black_box(board).moves(player).count());
+        });
+    }
+
+    group.finish();
+}
+
+/// Measure the application of every legal move to a copy of the board, in moves per second
+fn jump(c: &mut Criterion) {
+    let mut group = c.benchmark_group("jump");
+
+    for (name, board, player) in positions() {
+        let moves = board.moves(player).collect::<Vec<_>>();
+
+        group.throughput(Throughput::Elements(moves.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &board, |b, board| {
+            b.iter(|| {
+                for candidate in &moves {
+                    let mut next = black_box(board).clone();
+                    black_box(next.jump(candidate.from, candidate.to));
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Measure the enumeration of the positions a few plies ahead, in nodes per second
+fn perft(c: &mut Criterion) {
+    let mut group = c.benchmark_group("perft");
+    group.sample_size(20);
+
+    for (name, board, player) in positions() {
+        group.throughput(Throughput::Elements(board.perft(player, PERFT_DEPTH)));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &board, |b, board| {
+            b.iter(|| black_box(board).perft(player, PERFT_DEPTH));
+        });
+    }
+
+    group.finish();
+}
+
+/// Measure the evaluation of every legal move, in moves per second
+fn evaluate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("evaluate");
+    let weights = Personality::Aggressive.weights();
+
+    for (name, board, player) in positions() {
+        let moves = board.moves(player).collect::<Vec<_>>();
+
+        group.throughput(Throughput::Elements(moves.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &board, |b, board| {
+            b.iter(|| {
+                for &candidate in &moves {
+                    black_box(weights.evaluate(black_box(board), player, candidate));
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Measure the choice of a move by the computer opponent
+fn choose(c: &mut Criterion) {
+    let mut group = c.benchmark_group("choose");
+
+    for (name, board, player) in positions() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &board, |b, board| {
+            b.iter(|| {
+                Ai::with_seed(Personality::Aggressive, 0)
+                    .with_difficulty(Difficulty::Hard)
+                    .choose(black_box(board), player)
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, moves, jump, perft, evaluate, choose);
+criterion_main!(benches);
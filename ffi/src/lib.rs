@@ -0,0 +1,5 @@
+//! The shared library of the C interface of the game engine
+//!
+//! See [`blobwars::ffi`] for the functions it exports and `include/blobwars.h` for their declarations.
+
+pub use blobwars::ffi::*;
@@ -0,0 +1,263 @@
+#ifndef BLOBWARS_H
+#define BLOBWARS_H
+
+/* This file is generated by cbindgen from src/ffi.rs; do not edit it manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/* The state of a [cell](CellState) */
+typedef enum BlobwarsCell {
+  /* The cell is [free](CellState::Free) */
+  BlobwarsCell_Free = 0,
+  /* The cell is [restricted](CellState::Restricted), or out of the board */
+  BlobwarsCell_Restricted = 1,
+  /* The cell is occupied by the [blue player](Player::Blue) */
+  BlobwarsCell_Blue = 2,
+  /* The cell is occupied by the [red player](Player::Red) */
+  BlobwarsCell_Red = 3,
+} BlobwarsCell;
+
+/* A [difficulty level](Difficulty) of the computer opponent */
+typedef enum BlobwarsDifficulty {
+  /* See [`Difficulty::Easy`] */
+  BlobwarsDifficulty_Easy = 0,
+  /* See [`Difficulty::Normal`] */
+  BlobwarsDifficulty_Normal = 1,
+  /* See [`Difficulty::Hard`] */
+  BlobwarsDifficulty_Hard = 2,
+} BlobwarsDifficulty;
+
+/* A [personality](Personality) of the computer opponent */
+typedef enum BlobwarsPersonality {
+  /* See [`Personality::Aggressive`] */
+  BlobwarsPersonality_Aggressive = 0,
+  /* See [`Personality::Territorial`] */
+  BlobwarsPersonality_Territorial = 1,
+  /* See [`Personality::Defensive`] */
+  BlobwarsPersonality_Defensive = 2,
+  /* See [`Personality::Chaotic`] */
+  BlobwarsPersonality_Chaotic = 3,
+} BlobwarsPersonality;
+
+/* A [player](Player) */
+typedef enum BlobwarsPlayer {
+  /* The [blue player](Player::Blue) */
+  BlobwarsPlayer_Blue = 0,
+  /* The [red player](Player::Red) */
+  BlobwarsPlayer_Red = 1,
+} BlobwarsPlayer;
+
+/* The status returned by the functions of this interface */
+typedef enum BlobwarsStatus {
+  /* The call has succeeded */
+  BlobwarsStatus_Ok = 0,
+  /* The move is illegal and has not been played */
+  BlobwarsStatus_IllegalMove = 1,
+  /* No move can be chosen since the player has to pass */
+  BlobwarsStatus_Pass = 2,
+  /* The player is not a value of [`BlobwarsPlayer`] */
+  BlobwarsStatus_InvalidPlayer = -1,
+  /* The personality is not a value of [`BlobwarsPersonality`] */
+  BlobwarsStatus_InvalidPersonality = -2,
+  /* The difficulty level is not a value of [`BlobwarsDifficulty`] */
+  BlobwarsStatus_InvalidDifficulty = -3,
+  /* The number of cells of the board overflows `size_t` */
+  BlobwarsStatus_InvalidSize = -4,
+} BlobwarsStatus;
+
+/* An opaque [board](Board) */
+typedef struct BlobwarsBoard BlobwarsBoard;
+
+/* A [move](Move) of a _blob_ */
+typedef struct BlobwarsMove {
+  /* The row of the departure point */
+  size_t from_row;
+  /* The column of the departure point */
+  size_t from_column;
+  /* The row of the destination point */
+  size_t to_row;
+  /* The column of the destination point */
+  size_t to_column;
+} BlobwarsMove;
+
+/*
+ Generate a new board
+
+ # Parameters
+
+ - `height` - The number of rows
+ - `width` - The number of columns
+ - `density` - The percentage of restricted cells
+ - `seed` - The seed of the random generation
+ - `board` - Where the board, to be released with [`blobwars_board_free()`], is written
+
+ # Return
+
+ [`BlobwarsStatus::Ok`], or [`BlobwarsStatus::InvalidSize`] and nothing is written
+
+ # Safety
+
+ `board` must point to a writable pointer.
+ */
+BlobwarsStatus blobwars_board_generate(size_t height,
+                                       size_t width,
+                                       uint8_t density,
+                                       uint64_t seed,
+                                       BlobwarsBoard **board);
+
+/*
+ Parse a board
+
+ The rows are separated by slashes and the cells are written `B`, `R`, `#` and `.`, as in `B.#/..R`.
+
+ # Parameter
+
+ - `text` - The board as a null-terminated string
+
+ # Return
+
+ The board, to be released with [`blobwars_board_free()`], or a null pointer if `text` is malformed
+
+ # Safety
+
+ `text` must be either null or a valid null-terminated string.
+ */
+BlobwarsBoard *blobwars_board_parse(const char *text);
+
+/*
+ Release a board
+
+ # Parameter
+
+ - `board` - The board; nothing is done if it is null
+
+ # Safety
+
+ `board` must be either null or a board returned by this library which has not been released yet.
+ */
+void blobwars_board_free(BlobwarsBoard *board);
+
+/*
+ Get the number of rows of a board
+
+ # Safety
+
+ `board` must be a valid board returned by this library.
+ */
+size_t blobwars_board_height(const BlobwarsBoard *board);
+
+/*
+ Get the number of columns of a board
+
+ # Safety
+
+ `board` must be a valid board returned by this library.
+ */
+size_t blobwars_board_width(const BlobwarsBoard *board);
+
+/*
+ Get the state of a cell
+
+ The cells out of the board are reported as restricted.
+
+ # Safety
+
+ `board` must be a valid board returned by this library.
+ */
+BlobwarsCell blobwars_board_get(const BlobwarsBoard *board, size_t row, size_t column);
+
+/*
+ Get the number of _blobs_ of a player
+
+ # Parameters
+
+ - `board` - The board
+ - `player` - The player, as a [`BlobwarsPlayer`]
+ - `score` - Where the number of _blobs_ is written
+
+ # Return
+
+ [`BlobwarsStatus::Ok`], or [`BlobwarsStatus::InvalidPlayer`] and nothing is written
+
+ # Safety
+
+ `board` must be a valid board returned by this library and `score` must point to a writable `size_t`.
+ */
+BlobwarsStatus blobwars_board_score(const BlobwarsBoard *board, int player, size_t *score);
+
+/*
+ Play a move
+
+ # Parameters
+
+ - `board` - The board
+ - `player` - The player moving one of their _blobs_, as a [`BlobwarsPlayer`]
+ - `chosen` - The move
+
+ # Return
+
+ [`BlobwarsStatus::Ok`] if the move has been played; otherwise, [`BlobwarsStatus::IllegalMove`] or [`BlobwarsStatus::InvalidPlayer`] and the board is left unchanged.
+
+ # Safety
+
+ `board` must be a valid board returned by this library.
+ */
+BlobwarsStatus blobwars_board_play(BlobwarsBoard *board, int player, BlobwarsMove chosen);
+
+/*
+ List the legal moves of a player
+
+ # Parameters
+
+ - `board` - The board
+ - `player` - The player, as a [`BlobwarsPlayer`]
+ - `moves` - The array the moves are written to; it may be null if `capacity` is zero
+ - `capacity` - The length of `moves`
+ - `count` - Where the number of legal moves is written; it may exceed `capacity`, only the first `capacity` moves being written
+
+ # Return
+
+ [`BlobwarsStatus::Ok`], or [`BlobwarsStatus::InvalidPlayer`] and nothing is written
+
+ # Safety
+
+ `board` must be a valid board returned by this library, `moves` must point to at least `capacity` writable moves and `count` must point to a writable `size_t`.
+ */
+BlobwarsStatus blobwars_board_moves(const BlobwarsBoard *board,
+                                    int player,
+                                    BlobwarsMove *moves,
+                                    size_t capacity,
+                                    size_t *count);
+
+/*
+ Choose the move of the computer opponent
+
+ # Parameters
+
+ - `board` - The board
+ - `player` - The player controlled by the computer opponent, as a [`BlobwarsPlayer`]
+ - `personality` - The personality of the computer opponent, as a [`BlobwarsPersonality`]
+ - `difficulty` - The difficulty level of the computer opponent, as a [`BlobwarsDifficulty`]
+ - `seed` - The seed used to pick among the best moves
+ - `chosen` - Where the chosen move is written
+
+ # Return
+
+ [`BlobwarsStatus::Ok`] if a move has been chosen, [`BlobwarsStatus::Pass`] if the player has to pass, or the status reporting an invalid argument; the move is only written in the first case.
+
+ # Safety
+
+ `board` must be a valid board returned by this library and `chosen` must point to a writable move.
+ */
+BlobwarsStatus blobwars_best_move(const BlobwarsBoard *board,
+                                  int player,
+                                  int personality,
+                                  int difficulty,
+                                  uint64_t seed,
+                                  BlobwarsMove *chosen);
+
+#endif /* BLOBWARS_H */
@@ -0,0 +1,769 @@
+//! The implementation of the computer opponent and its [personalities](Personality)
+
+use crate::{
+    game::{Board, CellState, History, INLINE_MOVES, Move, MoveList, Player, display_index},
+    rng::Rng,
+};
+
+#[cfg(feature = "tui")]
+use crate::hooks::Hooks;
+
+use smallvec::SmallVec;
+
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{self, Receiver},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A personality of the computer opponent
+///
+/// A personality is a preset of [evaluation weights](Weights) combined with a [temperature](Self::temperature) used to select a move.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Personality {
+    /// Favors the moves converting as many _blobs_ as possible
+    #[default]
+    Aggressive,
+
+    /// Favors the moves extending its influence over free cells
+    Territorial,
+
+    /// Favors the moves limiting what the opponent can convert in return
+    Defensive,
+
+    /// Plays almost randomly
+    Chaotic,
+}
+
+impl Personality {
+    /// All the available personalities
+    pub const ALL: [Self; 4] = [
+        Self::Aggressive,
+        Self::Territorial,
+        Self::Defensive,
+        Self::Chaotic,
+    ];
+
+    /// Get the [evaluation weights](Weights) of this personality
+    pub fn weights(self) -> Weights {
+        match self {
+            Self::Aggressive => Weights {
+                material: 4,
+                territory: 0,
+                exposure: 1,
+                jump: 1,
+            },
+
+            Self::Territorial => Weights {
+                material: 2,
+                territory: 3,
+                exposure: 1,
+                jump: 2,
+            },
+
+            Self::Defensive => Weights {
+                material: 2,
+                territory: 1,
+                exposure: 4,
+                jump: 2,
+            },
+
+            Self::Chaotic => Weights {
+                material: 1,
+                territory: 0,
+                exposure: 0,
+                jump: 0,
+            },
+        }
+    }
+
+    /// Get the move-selection temperature of this personality
+    ///
+    /// The higher the temperature is, the more random the selected move is.
+    /// A null temperature always selects one of the best moves.
+    pub fn temperature(self) -> f64 {
+        match self {
+            Self::Aggressive => 0.5,
+            Self::Territorial => 0.5,
+            Self::Defensive => 0.25,
+            Self::Chaotic => 8.,
+        }
+    }
+
+    /// Get the next personality
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::ai::Personality;
+    ///
+    /// assert_eq!(Personality::Aggressive.next(), Personality::Territorial);
+    /// assert_eq!(Personality::Chaotic.next(), Personality::Aggressive);
+    /// ```
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|&item| item == self)
+            .unwrap_or_default();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Get the previous personality
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::ai::Personality;
+    ///
+    /// assert_eq!(Personality::Territorial.previous(), Personality::Aggressive);
+    /// assert_eq!(Personality::Aggressive.previous(), Personality::Chaotic);
+    /// ```
+    pub fn previous(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|&item| item == self)
+            .unwrap_or_default();
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+impl Display for Personality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Aggressive => write!(f, "Aggressive"),
+            Self::Territorial => write!(f, "Territorial"),
+            Self::Defensive => write!(f, "Defensive"),
+            Self::Chaotic => write!(f, "Chaotic"),
+        }
+    }
+}
+
+impl FromStr for Personality {
+    type Err = &'static str;
+
+    /// Parse a personality from its name
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::ai::Personality;
+    ///
+    /// assert_eq!("chaotic".parse(), Ok(Personality::Chaotic));
+    /// assert!("unknown".parse::<Personality>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|personality| personality.to_string().eq_ignore_ascii_case(s))
+            .ok_or("The name is not recognized as a valid personality")
+    }
+}
+
+/// A difficulty level of the computer opponent
+///
+/// The difficulty scales the [temperature](Personality::temperature) of the personality.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    /// The opponent often plays suboptimal moves
+    Easy,
+
+    /// The opponent plays as its personality dictates
+    #[default]
+    Normal,
+
+    /// The opponent always plays one of its best moves
+    Hard,
+}
+
+impl Difficulty {
+    /// All the available difficulty levels
+    pub const ALL: [Self; 3] = [Self::Easy, Self::Normal, Self::Hard];
+
+    /// Get the factor applied to the [temperature](Personality::temperature) of the personality
+    pub fn temperature_factor(self) -> f64 {
+        match self {
+            Self::Easy => 4.,
+            Self::Normal => 1.,
+            Self::Hard => 0.,
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Easy => write!(f, "Easy"),
+            Self::Normal => write!(f, "Normal"),
+            Self::Hard => write!(f, "Hard"),
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = &'static str;
+
+    /// Parse a difficulty level from its name
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::ai::Difficulty;
+    ///
+    /// assert_eq!("Hard".parse(), Ok(Difficulty::Hard));
+    /// assert!("unknown".parse::<Difficulty>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.to_string().eq_ignore_ascii_case(s))
+            .ok_or("The name is not recognized as a valid difficulty")
+    }
+}
+
+/// Weights used to evaluate a [move](Move)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Weights {
+    /// Weight of the difference between the two players' scores
+    ///
+    /// The points scored on the [hill](Board::with_hill) count as _blobs_.
+    pub material: i32,
+
+    /// Weight of the number of free cells adjacent to the player's _blobs_
+    pub territory: i32,
+
+    /// Penalty applied for each _blob_ the opponent can convert with its best reply
+    pub exposure: i32,
+
+    /// Penalty applied to jumps since they leave a hole behind them
+    pub jump: i32,
+}
+
+impl Weights {
+    /// Evaluate a [move](Move)
+    ///
+    /// # Parameters
+    ///
+    /// - `board` - The [board](Board) before the move
+    /// - `player` - The [player](Player) making the move
+    /// - `candidate` - The evaluated [move](Move)
+    ///
+    /// # Return
+    ///
+    /// The higher the returned value is, the better the move is; if the move is illegal, `None` is returned.
+    /// The evaluation is negated in [_misère_](crate::game::RuleSet::misere), where the player with the fewest _blobs_ wins.
+    pub fn evaluate(self, board: &Board, player: Player, candidate: Move) -> Option<i32> {
+        let mut next = board.clone();
+        self.evaluate_with(&mut next, &mut History::default(), player, candidate)
+    }
+
+    /// Evaluate a [move](Move) by [making](Board::make_move) it on a board, which is left unchanged
+    fn evaluate_with(
+        self,
+        board: &mut Board,
+        history: &mut History,
+        player: Player,
+        candidate: Move,
+    ) -> Option<i32> {
+        let jump = (board.jump_distance(candidate.from, candidate.to) > Some(1)) as i32;
+
+        if !board.make_move(candidate, history) {
+            return None;
+        }
+
+        let score = board.score();
+        let material = (score.get(player) + score.points(player)) as i32
+            - (score.get(-player) + score.points(-player)) as i32;
+
+        let territory = if self.territory != 0 {
+            territory(board, player)
+        } else {
+            0
+        };
+
+        let exposure = if self.exposure != 0 {
+            exposure(board, history, player)
+        } else {
+            0
+        };
+
+        board.unmake_move(history);
+
+        let value = self.material * material + self.territory * territory
+            - self.exposure * exposure
+            - self.jump * jump;
+
+        Some(if board.rules().misere { -value } else { value })
+    }
+}
+
+fn territory(board: &Board, player: Player) -> i32 {
+    (0..board.height())
+        .flat_map(|i| (0..board.width()).map(move |j| (i, j)))
+        .filter(|&(i, j)| board.get(i, j) == Some(CellState::Free))
+        .filter(|&(i, j)| {
+            board
+                .neighbors(i, j, 1)
+                .any(|cell| cell == CellState::Player(player))
+        })
+        .count() as i32
+}
+
+fn exposure(board: &mut Board, history: &mut History, player: Player) -> i32 {
+    let before = board.score().get(player);
+    let replies = board.moves(-player).collect::<MoveList>();
+
+    replies
+        .into_iter()
+        .map(|reply| {
+            board.make_move(reply, history);
+            let lost = before.saturating_sub(board.score().get(player));
+            board.unmake_move(history);
+            lost as i32
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+/// The computer opponent
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Ai {
+    personality: Personality,
+    difficulty: Difficulty,
+    rng: Rng,
+    #[cfg(feature = "tui")]
+    hooks: Hooks,
+}
+
+impl Ai {
+    /// Create a new [`Ai`] with a random seed
+    ///
+    /// # Parameter
+    ///
+    /// - `personality` - The [personality](Personality) of the opponent
+    pub fn new(personality: Personality) -> Self {
+        Self {
+            personality,
+            difficulty: Difficulty::default(),
+            rng: Rng::default(),
+            #[cfg(feature = "tui")]
+            hooks: Hooks::default(),
+        }
+    }
+
+    /// Create a new [`Ai`] with the given seed
+    ///
+    /// # Parameters
+    ///
+    /// - `personality` - The [personality](Personality) of the opponent
+    /// - `seed` - The seed of the underlying [`Rng`]
+    pub fn with_seed(personality: Personality, seed: u64) -> Self {
+        Self {
+            personality,
+            difficulty: Difficulty::default(),
+            rng: Rng::new(seed),
+            #[cfg(feature = "tui")]
+            hooks: Hooks::default(),
+        }
+    }
+
+    /// Set the [random number generator](Rng) breaking the ties between the moves
+    pub fn with_rng(self, rng: Rng) -> Self {
+        Self { rng, ..self }
+    }
+
+    /// Set the [difficulty](Difficulty) of the opponent
+    pub fn with_difficulty(self, difficulty: Difficulty) -> Self {
+        Self { difficulty, ..self }
+    }
+
+    /// Set the [hooks](Hooks) choosing the moves of the opponent
+    ///
+    /// The opponent plays the moves chosen by the `choose_move` function of the script, if any, and its own choice otherwise.
+    #[cfg(feature = "tui")]
+    pub fn with_hooks(self, hooks: Hooks) -> Self {
+        Self { hooks, ..self }
+    }
+
+    /// Get the [personality](Personality) of the opponent
+    pub fn personality(&self) -> Personality {
+        self.personality
+    }
+
+    /// Get the [difficulty](Difficulty) of the opponent
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Choose a [move](Move) to play
+    ///
+    /// Each legal move is [evaluated](Weights::evaluate) and one of them is drawn according to a softmax distribution whose temperature is given by the [personality](Personality::temperature) and scaled by the [difficulty](Difficulty::temperature_factor).
+    ///
+    /// # Parameters
+    ///
+    /// - `board` - The current [board](Board)
+    /// - `player` - The [player](Player) controlled by the opponent
+    ///
+    /// # Return
+    ///
+    /// If the player cannot play, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     ai::{Ai, Personality},
+    ///     game::{Board, CellState::*, Move, Player::*},
+    /// };
+    ///
+    /// #[rustfmt::skip]
+    /// let board = vec![
+    ///     Player(Red), Free, Player(Blue),
+    ///     Restricted,  Free, Player(Blue),
+    ///     Restricted,  Free, Free,
+    /// ];
+    ///
+    /// let board = Board::new(3, 3, board);
+    /// let mut ai = Ai::with_seed(Personality::Aggressive, 0);
+    ///
+    /// assert_eq!(
+    ///     ai.choose(&board, Red),
+    ///     Some(Move { from: (0, 0), to: (0, 1) }),
+    /// );
+    /// ```
+    pub fn choose(&mut self, board: &Board, player: Player) -> Option<Move> {
+        self.choose_unless(board, player, &AtomicBool::new(false), &mut |_| ())
+    }
+
+    /// Choose a [move](Move) to play in the background
+    ///
+    /// The search is run on a dedicated thread so that the caller is not blocked.
+    ///
+    /// # Parameters
+    ///
+    /// - `board` - The current [board](Board)
+    /// - `player` - The [player](Player) controlled by the opponent
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     ai::{Ai, Personality},
+    ///     game::{Board, CellState::*, Move, Player::*},
+    /// };
+    ///
+    /// let board = Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+    /// let thinking = Ai::new(Personality::Aggressive).think(board, Red);
+    ///
+    /// let (_ai, chosen) = loop {
+    ///     if let Some(result) = thinking.try_result() {
+    ///         break result;
+    ///     }
+    /// };
+    ///
+    /// assert_eq!(chosen, Some(Move { from: (0, 0), to: (0, 1) }));
+    /// ```
+    pub fn think(&self, board: Board, player: Player) -> Thinking {
+        let (sender, receiver) = mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let progress = Arc::new(SharedProgress::default());
+
+        let mut ai = self.clone();
+        let flag = cancelled.clone();
+        let shared = progress.clone();
+        let span = tracing::Span::current();
+
+        thread::spawn(move || {
+            let _span = span.entered();
+
+            let chosen = ai.choose_unless(&board, player, &flag, &mut |progress| {
+                shared.nodes.store(progress.nodes, Ordering::Relaxed);
+            });
+
+            let _ = sender.send((ai, chosen));
+        });
+
+        Thinking {
+            result: Arc::new(Mutex::new(receiver)),
+            cancelled,
+            progress,
+            started: Instant::now(),
+        }
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        name = "search",
+        skip_all,
+        fields(personality = %self.personality, difficulty = %self.difficulty, %player)
+    )]
+    fn choose_unless(
+        &mut self,
+        board: &Board,
+        player: Player,
+        cancelled: &AtomicBool,
+        on_progress: &mut dyn FnMut(Progress),
+    ) -> Option<Move> {
+        #[cfg(feature = "tui")]
+        if let Some(chosen) = self.hooks.choose_move(board, player) {
+            tracing::debug!(
+                "The script chooses {}{}",
+                display_index(chosen.from),
+                display_index(chosen.to)
+            );
+
+            return Some(chosen);
+        }
+
+        let weights = self.personality.weights();
+        let mut nodes = 0;
+
+        // The candidates are evaluated on a single copy of the board, the moves being undone after each evaluation
+        let mut scratch = board.clone();
+        let mut history = History::default();
+
+        let candidates = board
+            .moves(player)
+            .take_while(|_| !cancelled.load(Ordering::Relaxed))
+            .filter_map(|candidate| {
+                nodes += 1;
+                on_progress(Progress { nodes });
+                let value = weights.evaluate_with(&mut scratch, &mut history, player, candidate)?;
+                Some((candidate, value))
+            })
+            .collect::<SmallVec<[_; INLINE_MOVES]>>();
+
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let best = candidates.iter().map(|&(_, value)| value).max()?;
+        let temperature = self.personality.temperature() * self.difficulty.temperature_factor();
+
+        let weights = candidates
+            .iter()
+            .map(|&(_, value)| {
+                if temperature > 0. {
+                    ((value - best) as f64 / temperature).exp()
+                } else {
+                    (value == best) as u8 as f64
+                }
+            })
+            .collect::<SmallVec<[_; INLINE_MOVES]>>();
+
+        let mut threshold = self.rng.next_f64() * weights.iter().sum::<f64>();
+
+        let index = weights
+            .iter()
+            .position(|weight| {
+                threshold -= weight;
+                threshold < 0.
+            })
+            .unwrap_or(candidates.len() - 1);
+
+        let (chosen, value) = candidates[index];
+
+        tracing::debug!(
+            "The {} {} opponent chooses {}{} valued {value} among {nodes} moves valued at most {best}",
+            self.personality,
+            self.difficulty,
+            display_index(chosen.from),
+            display_index(chosen.to)
+        );
+
+        Some(chosen)
+    }
+}
+
+/// The result of a [search](Thinking): the updated [opponent](Ai) and the chosen [move](Move), if any
+pub type SearchResult = (Ai, Option<Move>);
+
+/// The progress of a search
+///
+/// The search only looks one ply ahead, the replies of the other player being weighed by the [evaluation](Weights), so its progress is measured in positions rather than in depth.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Progress {
+    /// The number of positions evaluated so far
+    pub nodes: u64,
+}
+
+#[derive(Debug, Default)]
+struct SharedProgress {
+    nodes: AtomicU64,
+}
+
+/// A search run in the background by the [computer opponent](Ai)
+///
+/// Instances of this `struct` are created by [`Ai::think()`].
+#[derive(Clone, Debug)]
+pub struct Thinking {
+    result: Arc<Mutex<Receiver<SearchResult>>>,
+    cancelled: Arc<AtomicBool>,
+    progress: Arc<SharedProgress>,
+    started: Instant,
+}
+
+impl Thinking {
+    /// Get the time elapsed since the beginning of the search
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Get the current [progress](Progress) of the search
+    pub fn progress(&self) -> Progress {
+        Progress {
+            nodes: self.progress.nodes.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Cancel the search
+    ///
+    /// Once cancelled, the search ends as soon as possible without choosing any [move](Move).
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Get the result of the search if it is over
+    ///
+    /// # Return
+    ///
+    /// If the search is over, the updated [opponent](Ai) and the chosen [move](Move) are returned; otherwise, `None` is returned.
+    pub fn try_result(&self) -> Option<SearchResult> {
+        self.result.lock().ok()?.try_recv().ok()
+    }
+
+    /// Wait for the end of the search
+    ///
+    /// # Return
+    ///
+    /// The updated [opponent](Ai) and the chosen [move](Move) are returned, unless the result has already been taken.
+    pub fn wait(&self) -> Option<SearchResult> {
+        self.result.lock().ok()?.recv().ok()
+    }
+}
+
+impl PartialEq for Thinking {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.cancelled, &other.cancelled)
+    }
+}
+
+impl Eq for Thinking {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{CellState::*, Player::*, RuleSet};
+
+    fn board() -> Board {
+        #[rustfmt::skip]
+        let board = vec![
+            Player(Red), Free, Free,         Free,         Free,
+            Free,        Free, Free,         Free,         Free,
+            Free,        Free, Player(Blue), Player(Blue), Free,
+            Free,        Free, Player(Blue), Free,         Free,
+            Free,        Free, Free,         Free,         Free,
+        ];
+
+        Board::new(5, 5, board)
+    }
+
+    #[test]
+    fn aggressive_captures() {
+        let board = board();
+        let mut ai = Ai::with_seed(Personality::Aggressive, 42);
+
+        for _ in 0..16 {
+            let candidate = ai.choose(&board, Red).unwrap();
+            let mut next = board.clone();
+            next.jump(candidate.from, candidate.to);
+            pretty_assertions::assert_eq!(next.score().blue, 1);
+        }
+    }
+
+    #[test]
+    fn misere_avoids_captures() {
+        let board = board().with_rules(RuleSet {
+            misere: true,
+            ..RuleSet::default()
+        });
+        let mut ai = Ai::with_seed(Personality::Aggressive, 42);
+
+        for _ in 0..16 {
+            let candidate = ai.choose(&board, Red).unwrap();
+            let mut next = board.clone();
+            next.jump(candidate.from, candidate.to);
+            pretty_assertions::assert_eq!(next.score().blue, 3);
+        }
+    }
+
+    #[test]
+    fn chaotic_varies() {
+        let board = board();
+        let mut ai = Ai::with_seed(Personality::Chaotic, 42);
+
+        let first = ai.choose(&board, Red);
+        assert!((0..32).any(|_| ai.choose(&board, Red) != first));
+    }
+
+    #[test]
+    fn no_move() {
+        let board = Board::new(1, 2, vec![Player(Red), Player(Blue)]);
+        let mut ai = Ai::with_seed(Personality::Defensive, 42);
+
+        pretty_assertions::assert_eq!(ai.choose(&board, Red), None);
+    }
+
+    #[test]
+    fn think() {
+        let board = board();
+        let mut ai = Ai::with_seed(Personality::Defensive, 3);
+        let thinking = ai.think(board.clone(), Red);
+
+        let (tested, chosen) = loop {
+            if let Some(result) = thinking.try_result() {
+                break result;
+            }
+
+            thread::sleep(Duration::from_millis(1));
+        };
+
+        pretty_assertions::assert_eq!(chosen, ai.choose(&board, Red));
+        pretty_assertions::assert_eq!(tested, ai);
+
+        pretty_assertions::assert_eq!(
+            thinking.progress(),
+            Progress {
+                nodes: board.moves(Red).count() as u64,
+            }
+        );
+    }
+
+    #[test]
+    fn cancel() {
+        let board = board();
+        let mut ai = Ai::with_seed(Personality::Defensive, 3);
+
+        pretty_assertions::assert_eq!(
+            ai.choose_unless(&board, Red, &AtomicBool::new(true), &mut |_| ()),
+            None
+        );
+    }
+
+    #[test]
+    fn same_seed() {
+        let board = board();
+        let mut a = Ai::with_seed(Personality::Territorial, 7);
+        let mut b = Ai::with_seed(Personality::Territorial, 7);
+
+        for _ in 0..8 {
+            pretty_assertions::assert_eq!(a.choose(&board, Red), b.choose(&board, Red));
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! The implementation of [`Animation`]
+
+use crate::game::{Board, CellState, Index, Move, Player};
+use std::time::Duration;
+
+/// The animation of a [move](Move)
+///
+/// The moved _blob_ travels cell by cell from the departure point to the destination point, then the converted _blobs_ flash before taking their new color.
+/// An animation does not modify the [board](Board): it only changes the way its cells are shown until it is [finished](Self::is_finished).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Animation {
+    player: Player,
+    path: Vec<Index>,
+    converted: Vec<Index>,
+    elapsed: Duration,
+}
+
+impl Animation {
+    /// The time spent by the moved _blob_ on each cell of its path
+    pub const STEP: Duration = Duration::from_millis(80);
+
+    /// The duration of the flashing of the converted _blobs_
+    pub const FLASH: Duration = Duration::from_millis(320);
+
+    const BLINK: Duration = Duration::from_millis(80);
+
+    /// Create a new [`Animation`]
+    ///
+    /// # Parameters
+    ///
+    /// - `board` - The [board](Board) **before** the move is played
+    /// - `chosen` - The played [move](Move)
+    /// - `player` - The [player](Player) who plays the move
+    pub fn new(board: &Board, chosen: Move, player: Player) -> Self {
+        let Move { from, to } = chosen;
+        let mut path = vec![from];
+        let mut current = from;
+
+        while current != to {
+            current = (step_toward(current.0, to.0), step_toward(current.1, to.1));
+            path.push(current);
+        }
+
+        let converted = (to.0.saturating_sub(1)..=to.0 + 1)
+            .flat_map(|i| (to.1.saturating_sub(1)..=to.1 + 1).map(move |j| (i, j)))
+            .filter(|&(i, j)| board.get(i, j) == Some(CellState::Player(-player)))
+            .collect();
+
+        Self {
+            player,
+            path,
+            converted,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Create a new [`Animation`] only flashing the _blobs_ converted by a move
+    ///
+    /// The moved _blob_ is directly shown on its destination point.
+    ///
+    /// # Parameters
+    ///
+    /// - `board` - The [board](Board) **before** the move is played
+    /// - `chosen` - The played [move](Move)
+    /// - `player` - The [player](Player) who plays the move
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     animation::Animation,
+    ///     game::{Board, CellState::*, Move, Player::*},
+    /// };
+    ///
+    /// let board = Board::new(1, 4, vec![Player(Blue), Free, Free, Player(Red)]);
+    /// let chosen = Move { from: (0, 0), to: (0, 2) };
+    /// let animation = Animation::capture(&board, chosen, Blue);
+    ///
+    /// assert_eq!(animation.cell((0, 2), Player(Blue)), Player(Blue));
+    /// assert!(animation.is_flashing((0, 3)));
+    /// ```
+    pub fn capture(board: &Board, chosen: Move, player: Player) -> Self {
+        Self {
+            path: vec![chosen.to],
+            ..Self::new(board, chosen, player)
+        }
+    }
+
+    fn travel(&self) -> Duration {
+        Self::STEP * (self.path.len() - 1) as u32
+    }
+
+    fn duration(&self) -> Duration {
+        if self.converted.is_empty() {
+            self.travel()
+        } else {
+            self.travel() + Self::FLASH
+        }
+    }
+
+    /// Advance the animation
+    ///
+    /// # Parameter
+    ///
+    /// - `elapsed` - The time elapsed since the last tick
+    pub fn tick(&mut self, elapsed: Duration) {
+        self.elapsed += elapsed;
+    }
+
+    /// Check if the animation is over
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration()
+    }
+
+    /// Get the state of a cell as it must be shown
+    ///
+    /// # Parameters
+    ///
+    /// - `index` - The [position](Index) of the cell
+    /// - `actual` - The [state](CellState) of the cell in the board **after** the move
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     animation::Animation,
+    ///     game::{Board, CellState::*, Move, Player::*},
+    /// };
+    ///
+    /// let board = Board::new(1, 4, vec![Player(Blue), Free, Free, Player(Red)]);
+    /// let chosen = Move { from: (0, 0), to: (0, 2) };
+    /// let mut animation = Animation::new(&board, chosen, Blue);
+    ///
+    /// assert_eq!(animation.cell((0, 0), Free), Player(Blue));
+    /// assert_eq!(animation.cell((0, 3), Player(Blue)), Player(Red));
+    ///
+    /// animation.tick(Animation::STEP);
+    /// assert_eq!(animation.cell((0, 0), Free), Free);
+    /// assert_eq!(animation.cell((0, 1), Free), Player(Blue));
+    /// assert_eq!(animation.cell((0, 2), Player(Blue)), Free);
+    ///
+    /// animation.tick(Animation::STEP);
+    /// assert_eq!(animation.cell((0, 3), Player(Blue)), Player(Blue));
+    /// assert!(animation.is_flashing((0, 3)));
+    /// ```
+    pub fn cell(&self, index: Index, actual: CellState) -> CellState {
+        if self.elapsed < self.travel() {
+            let position = self.path[(self.elapsed.as_millis() / Self::STEP.as_millis()) as usize];
+
+            if index == position {
+                return CellState::Player(self.player);
+            } else if Some(&index) == self.path.last() {
+                return CellState::Free;
+            } else if self.converted.contains(&index) {
+                return CellState::Player(-self.player);
+            }
+        }
+
+        actual
+    }
+
+    /// Check if a cell must be flashed
+    ///
+    /// # Parameter
+    ///
+    /// - `index` - The [position](Index) of the cell
+    pub fn is_flashing(&self, index: Index) -> bool {
+        self.elapsed >= self.travel()
+            && !self.is_finished()
+            && self.converted.contains(&index)
+            && ((self.elapsed - self.travel()).as_millis() / Self::BLINK.as_millis())
+                .is_multiple_of(2)
+    }
+}
+
+fn step_toward(value: usize, target: usize) -> usize {
+    match value.cmp(&target) {
+        std::cmp::Ordering::Less => value + 1,
+        std::cmp::Ordering::Equal => value,
+        std::cmp::Ordering::Greater => value - 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::CellState::*;
+    use crate::game::Player::*;
+
+    #[test]
+    fn spread() {
+        let board = Board::new(1, 2, vec![Player(Red), Free]);
+        let chosen = Move {
+            from: (0, 0),
+            to: (0, 1),
+        };
+
+        let mut animation = Animation::new(&board, chosen, Red);
+        pretty_assertions::assert_eq!(animation.cell((0, 1), Player(Red)), Free);
+        assert!(!animation.is_finished());
+
+        animation.tick(Animation::STEP);
+        pretty_assertions::assert_eq!(animation.cell((0, 1), Player(Red)), Player(Red));
+        assert!(animation.is_finished());
+    }
+
+    #[test]
+    fn diagonal() {
+        let board = Board::free(3, 3);
+        let chosen = Move {
+            from: (2, 0),
+            to: (0, 2),
+        };
+
+        let animation = Animation::new(&board, chosen, Blue);
+        pretty_assertions::assert_eq!(animation.path, vec![(2, 0), (1, 1), (0, 2)]);
+        assert!(animation.converted.is_empty());
+    }
+
+    #[test]
+    fn flash() {
+        let board = Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+        let chosen = Move {
+            from: (0, 0),
+            to: (0, 1),
+        };
+
+        let mut animation = Animation::new(&board, chosen, Red);
+        animation.tick(Animation::STEP);
+        assert!(animation.is_flashing((0, 2)));
+
+        animation.tick(Animation::FLASH / 4);
+        assert!(!animation.is_flashing((0, 2)));
+
+        animation.tick(Animation::FLASH);
+        assert!(!animation.is_flashing((0, 2)));
+        assert!(animation.is_finished());
+    }
+}
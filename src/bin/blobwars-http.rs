@@ -0,0 +1,60 @@
+use blobwars::{config::Config, http::HttpServer, logging::FileLogger, rng::Rng};
+use clap::Parser;
+use std::error::Error;
+use tracing::level_filters::LevelFilter;
+
+const AFTER_HELP: &str = "\
+Endpoints:
+  GET  /games             List the identifiers of the games
+  POST /games             Create a game and get the tokens of its seats
+  GET  /games/ID          Get the state of a game
+  POST /games/ID/moves    Play the ply of the body, such as {\"ply\":\"a1b3\"} or
+                          {\"ply\":\"pass\"}, with the token of the seat to move
+                          as \"Authorization: Bearer TOKEN\"
+
+The games where no ply has been played for an hour are removed; no game is
+created while too many others are being played.";
+
+/// The options of the HTTP game server
+#[derive(Debug, Parser)]
+#[command(
+    name = "blobwars-http",
+    version,
+    about = "Host games of blobwars through HTTP, on boards generated from its configuration",
+    long_about = None,
+    after_help = AFTER_HELP
+)]
+struct Options {
+    /// The seed the boards are generated from
+    #[arg(long, value_name = "SEED", help = "Generate the boards from SEED")]
+    seed: Option<u64>,
+
+    /// The address to listen on
+    #[arg(
+        value_name = "ADDRESS",
+        default_value = HttpServer::DEFAULT_ADDRESS,
+        help = "Listen on ADDRESS"
+    )]
+    address: String,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = Options::parse();
+    let level = FileLogger::level(None).max(LevelFilter::INFO);
+
+    if let Some(path) = FileLogger::path() {
+        match FileLogger::new(&path.with_file_name("http.log"), level) {
+            Ok(logger) => logger.install(),
+            Err(error) => eprintln!("blobwars-http: the log file cannot be opened: {error}"),
+        }
+    }
+
+    let server = HttpServer::bind(options.address)?
+        .with_config(Config::load()?)
+        .with_rng(options.seed.map(Rng::new).unwrap_or_default());
+
+    println!("blobwars-http listens on {}", server.local_addr()?);
+
+    server.run()?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use blobwars::{config::Config, logging::FileLogger, server::Server};
+use clap::Parser;
+use std::error::Error;
+use tracing::level_filters::LevelFilter;
+
+const AFTER_HELP: &str = "\
+To let people play with a mere `ssh`, run the server next to an SSH daemon
+and force the command of a dedicated account, each session getting its own
+terminal; for instance, with OpenSSH:
+
+  Match User blobwars
+      ForceCommand blobwars-ssh --server 127.0.0.1:4444
+      PermitTTY yes
+
+so that `ssh -t blobwars@play.example.com -- --create friday` opens the
+game friday and `ssh -t blobwars@play.example.com -- --enter friday` joins it.
+blobwars-ssh refuses any other command asked by the client.";
+
+/// The options of the network game server
+#[derive(Debug, Parser)]
+#[command(
+    name = "blobwars-server",
+    version,
+    about = "Host the network games of blobwars, on boards generated from its configuration",
+    long_about = None,
+    after_help = AFTER_HELP
+)]
+struct Options {
+    /// The address to listen on
+    #[arg(
+        value_name = "ADDRESS",
+        default_value = Server::DEFAULT_ADDRESS,
+        help = "Listen on ADDRESS"
+    )]
+    address: String,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = Options::parse();
+    let level = FileLogger::level(None).max(LevelFilter::INFO);
+
+    if let Some(path) = FileLogger::path() {
+        match FileLogger::new(&path.with_file_name("server.log"), level) {
+            Ok(logger) => logger.install(),
+            Err(error) => eprintln!("blobwars-server: the log file cannot be opened: {error}"),
+        }
+    }
+
+    let server = Server::bind(options.address)?.with_config(Config::load()?);
+    println!("blobwars-server listens on {}", server.local_addr()?);
+
+    server.run()?;
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use blobwars::ssh::SshRequest;
+use clap::Parser;
+use std::{env, error::Error, path::PathBuf, process::Command};
+
+/// The command forced to the SSH clients of a blobwars server
+#[derive(Debug, Parser)]
+#[command(
+    name = "blobwars-ssh",
+    version,
+    about = "Serve the games of a blobwars server to SSH clients",
+    long_about = None,
+    after_help = "Set it as the ForceCommand of a dedicated SSH account; the clients may only ask \
+                  for nothing, `--create NAME` or `--enter NAME`."
+)]
+struct Options {
+    /// The address of the server
+    #[arg(
+        long,
+        value_name = "ADDR",
+        default_value = "127.0.0.1:4444",
+        help = "Play on the server at ADDR"
+    )]
+    server: String,
+
+    /// The game to run
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Run the game from FILE instead of the blobwars next to this command"
+    )]
+    blobwars: Option<PathBuf>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = Options::parse();
+
+    let request = env::var("SSH_ORIGINAL_COMMAND")
+        .unwrap_or_default()
+        .parse::<SshRequest>()
+        .map_err(|error| {
+            format!("{error}; usage: ssh -t HOST [-- --create NAME | --enter NAME]")
+        })?;
+
+    let program = match options.blobwars {
+        Some(path) => path,
+        None => env::current_exe()?.with_file_name(format!("blobwars{}", env::consts::EXE_SUFFIX)),
+    };
+
+    let mut command = Command::new(program);
+    command.args(request.args(&options.server));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+
+        Err(command.exec().into())
+    }
+
+    #[cfg(not(unix))]
+    {
+        let status = command.status()?;
+        std::process::exit(status.code().unwrap_or(1));
+    }
+}
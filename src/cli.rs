@@ -0,0 +1,429 @@
+//! The implementation of [`Options`]
+
+use crate::{
+    ai::Difficulty, config::Config, net::tls::Fingerprint, tournament::Engine, widgets::ThemeName,
+};
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use tracing::level_filters::LevelFilter;
+
+/// The options given on the command line
+///
+/// The options override the values of the [configuration](Config) for the current session only.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Parser)]
+#[command(
+    name = "blobwars",
+    version,
+    about = "A strategy game where blobs spread over the board and capture their neighbors",
+    long_about = None
+)]
+pub struct Options {
+    /// The [task](Task) run instead of starting the application, if any
+    #[command(subcommand)]
+    pub task: Option<Task>,
+
+    /// The configuration file used instead of the [default one](Config::path)
+    #[arg(long, value_name = "FILE", help = "Use FILE as the configuration file")]
+    pub config: Option<PathBuf>,
+
+    /// The size of the generated boards as `(height, width)`
+    #[arg(
+        long,
+        value_name = "WxH",
+        value_parser = parse_size,
+        help = "Generate boards of W columns and H rows"
+    )]
+    pub size: Option<(usize, usize)>,
+
+    /// The file holding the board to play on instead of a generated one
+    #[arg(long, value_name = "FILE", help = "Play on the board written in FILE")]
+    pub map: Option<PathBuf>,
+
+    /// The [difficulty](Difficulty) of the computer opponent to play against
+    #[arg(
+        long,
+        value_name = "LEVEL",
+        help = "Play against the computer (Easy, Normal or Hard)"
+    )]
+    pub vs_ai: Option<Difficulty>,
+
+    /// The [saved game](crate::save::SavedGame) to resume
+    #[arg(long, value_name = "SAVE", help = "Resume the game saved in SAVE")]
+    pub load: Option<PathBuf>,
+
+    /// The [theme](ThemeName) used to colorize the application
+    #[arg(long, value_name = "NAME", help = "Use the NAME theme")]
+    pub theme: Option<ThemeName>,
+
+    /// The seed every random choice is drawn from
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Draw every random choice from the seed N"
+    )]
+    pub seed: Option<u64>,
+
+    /// The [record](crate::record::GameRecord) of the game to replay, or its [portable version](crate::pgn::PortableGame)
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Replay the game recorded in FILE, which may be a portable game record (PGN) such as the ones of Ataxx programs"
+    )]
+    pub replay: Option<PathBuf>,
+
+    /// The [portable game](crate::pgn::PortableGame) to store in the [game database](crate::database::GameDatabase)
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Store the portable game record FILE in the game database"
+    )]
+    pub import: Option<PathBuf>,
+
+    /// The address to host a [network game](crate::net::Connection::host) on, or the URL of the [relay](crate::net::Connection::relay) to host it through
+    #[arg(
+        long,
+        value_name = "ADDR",
+        help = "Host a network game on ADDR, such as 0.0.0.0:4444, or through the relay at ADDR, such as ws://relay:8080/room"
+    )]
+    pub host: Option<String>,
+
+    /// The address of the [network game](crate::net::Connection::join) to join, or the URL of the [relay](crate::net::Connection::relay) to join it through
+    #[arg(
+        long,
+        value_name = "ADDR",
+        help = "Join the network game hosted on ADDR or through the relay"
+    )]
+    pub join: Option<String>,
+
+    /// The [certificate](crate::net::tls::Certificate) presented by the host of a network game over TLS
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires_all = ["host", "key"],
+        help = "Encrypt the hosted game with TLS, presenting the certificate in FILE (PEM)"
+    )]
+    pub cert: Option<PathBuf>,
+
+    /// The private key of the [certificate](Self::cert)
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "cert",
+        help = "Read the private key of the certificate from FILE (PEM)"
+    )]
+    pub key: Option<PathBuf>,
+
+    /// The [fingerprint](Fingerprint) of the certificate of the joined host or of the relay, pinned instead of trusting the usual authorities
+    #[arg(
+        long,
+        value_name = "SHA256",
+        help = "Encrypt the joined game with TLS, or trust the wss:// relay, only if its certificate has the SHA-256 fingerprint SHA256"
+    )]
+    pub pin: Option<Fingerprint>,
+
+    /// The address of the [server](crate::server::Server) to play on
+    #[arg(
+        long,
+        value_name = "ADDR",
+        help = "List the open games of the server at ADDR"
+    )]
+    pub server: Option<String>,
+
+    /// The name of the game to create on the server
+    #[arg(
+        long,
+        value_name = "NAME",
+        requires = "server",
+        help = "Open the game NAME on the server and wait for a player"
+    )]
+    pub create: Option<String>,
+
+    /// The name of the game to join on the server
+    #[arg(
+        long,
+        value_name = "NAME",
+        requires = "server",
+        conflicts_with = "create",
+        help = "Join the open game NAME on the server"
+    )]
+    pub enter: Option<String>,
+
+    /// The [level](LevelFilter) of the records written to the [log file](crate::logging::FileLogger::path)
+    #[arg(
+        long,
+        value_name = "LVL",
+        help = "Log from LVL (off, error, warn, info, debug or trace)"
+    )]
+    pub log_level: Option<LevelFilter>,
+
+    /// Whether the game is played on the standard streams instead of the terminal user interface
+    #[arg(
+        long,
+        help = "Play on the standard input and output without user interface"
+    )]
+    pub headless: bool,
+
+    /// Whether every source of events runs in its own asynchronous task instead of being polled
+    #[arg(
+        long,
+        help = "Run the terminal, the clock, the computer opponent and the network in separate tasks"
+    )]
+    pub threaded: bool,
+
+    /// The [engines](Engine) taking part in the tournament, written `personality[:difficulty]`
+    #[arg(
+        long,
+        value_name = "LIST",
+        value_delimiter = ',',
+        help_heading = "Tournament and self-play options",
+        help = "Engines written personality[:difficulty], separated by commas"
+    )]
+    pub engines: Vec<Engine>,
+
+    /// Whether the tournament is a [gauntlet](crate::tournament::Format::Gauntlet) instead of a round-robin
+    #[arg(
+        long,
+        help_heading = "Tournament and self-play options",
+        help = "Play the first engine against the others only"
+    )]
+    pub gauntlet: bool,
+
+    /// The number of opening maps played by each pairing of the tournament
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = parse_count,
+        help_heading = "Tournament and self-play options",
+        help = "Play N opening maps per pairing"
+    )]
+    pub rounds: Option<usize>,
+
+    /// The number of games of the tournament played in parallel
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = parse_count,
+        help_heading = "Tournament and self-play options",
+        help = "Play N games in parallel"
+    )]
+    pub threads: Option<usize>,
+
+    /// The number of self-play games
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = parse_count,
+        help_heading = "Tournament and self-play options",
+        help = "Play N self-play games"
+    )]
+    pub games: Option<usize>,
+
+    /// The file the results of the tournament, the samples of the self-play games, the snapshots and the movies are written to, in a format chosen from its extension
+    #[arg(
+        long,
+        value_name = "FILE",
+        help_heading = "Tournament and self-play options",
+        help = "Write the results to FILE (CSV, or JSON if it ends in .json)"
+    )]
+    pub output: Option<PathBuf>,
+}
+
+/// A task run from the command line instead of starting the application
+#[derive(Clone, Debug, PartialEq, Eq, Subcommand)]
+pub enum Task {
+    /// Write the default configuration and key bindings
+    #[command(about = "Write the default configuration and key bindings", long_about = None)]
+    WriteConfig,
+
+    /// Print the [statistics](crate::stats::Statistics) of the games played
+    #[command(about = "Print the statistics of the games played", long_about = None)]
+    Stats,
+
+    /// Print the game given with `--load` or `--replay`, or the last finished game, in a [portable format](crate::pgn::PortableGame)
+    #[command(about = "Print the game given with --load or --replay, or the last one, as PGN", long_about = None)]
+    Export,
+
+    /// Draw a position as a [snapshot](crate::snapshot::Snapshot)
+    ///
+    /// The position is the one of the game given with `--load` or `--replay`, of `--map`, or of the last finished game.
+    /// It is drawn to `--output` (SVG, or PNG if it ends in `.png`) or as SVG on the standard output.
+    #[command(about = "Draw the position of --load, --replay, --map or the last game as SVG or PNG", long_about = None)]
+    Snapshot,
+
+    /// Animate a recorded game as a [movie](crate::movie::Movie), one frame per ply
+    ///
+    /// The movie is written to `--output` (APNG, or an asciinema cast if it ends in `.cast`), by default to the recorded game with the `.png` extension.
+    #[command(about = "Animate the game recorded in FILE as APNG or as an asciinema cast", long_about = None)]
+    RenderReplay {
+        /// The recorded game
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+    },
+
+    /// Run a [tournament](crate::tournament::Tournament) between computer opponents
+    #[command(about = "Run a tournament between computer opponents", long_about = None)]
+    Tournament,
+
+    /// Play [self-play](crate::selfplay::SelfPlay) games of the first engine against itself
+    ///
+    /// Every position is written with the chosen move, its score and the result to `--output` (binary, or CSV if it ends in `.csv`) or as CSV on the standard output.
+    #[command(about = "Play the first engine against itself and write its positions as samples", long_about = None)]
+    Selfplay,
+}
+
+impl Options {
+    /// Override the values of a [configuration](Config) with the options
+    ///
+    /// # Parameter
+    ///
+    /// - `config` - The [configuration](Config) loaded from the configuration file
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{ai::Difficulty, cli::Options, config::Config};
+    /// use clap::Parser;
+    ///
+    /// let options = Options::try_parse_from(["blobwars", "--vs-ai", "easy"]).unwrap();
+    /// let config = options.apply(Config::default());
+    ///
+    /// assert!(config.opponent.is_some());
+    /// assert_eq!(config.difficulty, Difficulty::Easy);
+    /// ```
+    pub fn apply(&self, config: Config) -> Config {
+        let mut config = config;
+
+        if let Some((height, width)) = self.size {
+            config.height = height;
+            config.width = width;
+        }
+
+        if let Some(difficulty) = self.vs_ai {
+            config.opponent = Some(config.opponent.unwrap_or_default());
+            config.difficulty = difficulty;
+        }
+
+        if let Some(theme) = self.theme {
+            config.theme = theme;
+        }
+
+        config
+    }
+}
+
+fn parse_count(s: &str) -> Result<usize, &'static str> {
+    s.parse()
+        .ok()
+        .filter(|&count| count > 0)
+        .ok_or("A count is not a positive number")
+}
+
+fn parse_size(s: &str) -> Result<(usize, usize), &'static str> {
+    let (width, height) = s
+        .split_once(['x', 'X'])
+        .ok_or("The size is not written `WxH`")?;
+
+    let parse = |value: &str| {
+        value
+            .trim()
+            .parse()
+            .ok()
+            .filter(|value| Config::SIZES.contains(value))
+            .ok_or("The size is out of bounds")
+    };
+
+    Ok((parse(height)?, parse(width)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let args = [
+            "blobwars",
+            "--config",
+            "blobwars.toml",
+            "--size=10x6",
+            "--vs-ai",
+            "hard",
+            "--map",
+            "map.txt",
+            "--seed=7",
+            "--games",
+            "20",
+            "--engines",
+            "Aggressive,Defensive:Easy",
+            "--theme",
+            "Monochrome",
+            "--replay",
+            "last.toml",
+            "--log-level=debug",
+            "--join",
+            "192.168.1.2:4444",
+            "--pin",
+            "66:45:FA:4D:70:09:92:F2:94:E3:FB:BB:01:64:79:C9:63:B8:11:36:47:CA:4B:14:64:66:68:6B:42:4C:F0:7C",
+            "render-replay",
+            "game.bw",
+        ];
+
+        let expected = Options {
+            task: Some(Task::RenderReplay {
+                file: "game.bw".into(),
+            }),
+            config: Some("blobwars.toml".into()),
+            size: Some((6, 10)),
+            vs_ai: Some(Difficulty::Hard),
+            games: Some(20),
+            engines: vec![
+                "Aggressive".parse().unwrap(),
+                "Defensive:Easy".parse().unwrap(),
+            ],
+            map: Some("map.txt".into()),
+            seed: Some(7),
+            theme: Some(ThemeName::Monochrome),
+            replay: Some("last.toml".into()),
+            log_level: Some(LevelFilter::DEBUG),
+            join: Some("192.168.1.2:4444".to_owned()),
+            pin: Some(
+                "6645fa4d700992f294e3fbbb016479c963b8113647ca4b146466686b424cf07c"
+                    .parse()
+                    .unwrap(),
+            ),
+            ..Options::default()
+        };
+
+        pretty_assertions::assert_eq!(Options::try_parse_from(args).unwrap(), expected);
+
+        pretty_assertions::assert_eq!(
+            Options::try_parse_from(["blobwars", "write-config"])
+                .unwrap()
+                .task,
+            Some(Task::WriteConfig)
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let parse = |args: &[&str]| Options::try_parse_from([&["blobwars"], args].concat());
+
+        assert!(parse(&["--size", "8"]).is_err());
+        assert!(parse(&["--size", "99x8"]).is_err());
+        assert!(parse(&["--colour", "red"]).is_err());
+        assert!(parse(&["--seed", "-1"]).is_err());
+        assert!(parse(&["play"]).is_err());
+        assert!(parse(&["render-replay"]).is_err());
+        assert!(parse(&["stats", "export"]).is_err());
+        assert!(parse(&["--rounds", "0"]).is_err());
+        assert!(parse(&["--engines", "clever"]).is_err());
+        assert!(parse(&["--create", "game"]).is_err());
+    }
+
+    #[test]
+    fn usage() {
+        use clap::CommandFactory;
+
+        Options::command().debug_assert();
+    }
+}
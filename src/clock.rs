@@ -0,0 +1,277 @@
+//! The implementation of [`Clock`] and its [time controls](TimeControl)
+
+use crate::game::Player;
+
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+    time::Duration,
+};
+
+/// A time control used by a [`Clock`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimeControl {
+    /// Each player has a fixed amount of time for the whole game
+    Absolute(Duration),
+
+    /// Each player starts with `base` and gains `increment` after each of their moves
+    Increment {
+        /// The initial amount of time
+        base: Duration,
+
+        /// The amount of time added after each move
+        increment: Duration,
+    },
+
+    /// Each player has a fixed amount of time for each move
+    PerMove(Duration),
+}
+
+impl TimeControl {
+    /// Some commonly used time controls
+    pub const PRESETS: [Self; 6] = [
+        Self::Absolute(Duration::from_secs(60)),
+        Self::Absolute(Duration::from_secs(300)),
+        Self::Increment {
+            base: Duration::from_secs(180),
+            increment: Duration::from_secs(2),
+        },
+        Self::Increment {
+            base: Duration::from_secs(300),
+            increment: Duration::from_secs(3),
+        },
+        Self::PerMove(Duration::from_secs(10)),
+        Self::PerMove(Duration::from_secs(30)),
+    ];
+
+    /// Get the amount of time each player starts with
+    pub fn initial(self) -> Duration {
+        match self {
+            Self::Absolute(duration) | Self::PerMove(duration) => duration,
+            Self::Increment { base, .. } => base,
+        }
+    }
+}
+
+impl Display for TimeControl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Absolute(duration) => write!(f, "{}s", duration.as_secs()),
+
+            Self::Increment { base, increment } => {
+                write!(f, "{}s+{}s", base.as_secs(), increment.as_secs())
+            }
+
+            Self::PerMove(duration) => write!(f, "{}s/move", duration.as_secs()),
+        }
+    }
+}
+
+impl FromStr for TimeControl {
+    type Err = &'static str;
+
+    /// Parse a time control
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::clock::TimeControl;
+    /// use std::time::Duration;
+    ///
+    /// assert_eq!("300s".parse(), Ok(TimeControl::Absolute(Duration::from_secs(300))));
+    /// assert_eq!("10s/move".parse(), Ok(TimeControl::PerMove(Duration::from_secs(10))));
+    ///
+    /// assert_eq!(
+    ///     "180s+2s".parse(),
+    ///     Ok(TimeControl::Increment {
+    ///         base: Duration::from_secs(180),
+    ///         increment: Duration::from_secs(2),
+    ///     })
+    /// );
+    ///
+    /// assert!("soon".parse::<TimeControl>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(duration) = s.strip_suffix("/move") {
+            Ok(Self::PerMove(parse_seconds(duration)?))
+        } else if let Some((base, increment)) = s.split_once('+') {
+            Ok(Self::Increment {
+                base: parse_seconds(base)?,
+                increment: parse_seconds(increment)?,
+            })
+        } else {
+            Ok(Self::Absolute(parse_seconds(s)?))
+        }
+    }
+}
+
+fn parse_seconds(value: &str) -> Result<Duration, &'static str> {
+    value
+        .trim()
+        .strip_suffix('s')
+        .and_then(|seconds| seconds.parse().ok())
+        .filter(|&seconds| seconds > 0)
+        .map(Duration::from_secs)
+        .ok_or("A duration is invalid")
+}
+
+/// A chess clock
+///
+/// A clock keeps the remaining time of both players according to a [time control](TimeControl).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Clock {
+    control: TimeControl,
+    red: Duration,
+    blue: Duration,
+}
+
+impl Clock {
+    /// Create a new [`Clock`]
+    ///
+    /// # Parameter
+    ///
+    /// - `control` - The [time control](TimeControl) to use
+    pub fn new(control: TimeControl) -> Self {
+        Self {
+            control,
+            red: control.initial(),
+            blue: control.initial(),
+        }
+    }
+
+    /// Set the remaining time of a [player](Player)
+    ///
+    /// # Parameters
+    ///
+    /// - `player` - The [player](Player) whose remaining time is set
+    /// - `remaining` - The remaining time of the player
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     clock::{Clock, TimeControl},
+    ///     game::Player,
+    /// };
+    ///
+    /// use std::time::Duration;
+    ///
+    /// let clock = Clock::new(TimeControl::Absolute(Duration::from_secs(60)))
+    ///     .with_remaining(Player::Red, Duration::from_secs(12));
+    ///
+    /// assert_eq!(clock.remaining(Player::Red), Duration::from_secs(12));
+    /// assert_eq!(clock.remaining(Player::Blue), Duration::from_secs(60));
+    /// ```
+    pub fn with_remaining(mut self, player: Player, remaining: Duration) -> Self {
+        *self.remaining_mut(player) = remaining;
+        self
+    }
+
+    /// Get the [time control](TimeControl) of this clock
+    pub fn control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// Get the remaining time of a [player](Player)
+    pub fn remaining(&self, player: Player) -> Duration {
+        match player {
+            Player::Blue => self.blue,
+            Player::Red => self.red,
+        }
+    }
+
+    fn remaining_mut(&mut self, player: Player) -> &mut Duration {
+        match player {
+            Player::Blue => &mut self.blue,
+            Player::Red => &mut self.red,
+        }
+    }
+
+    /// Decrement the remaining time of the [player](Player) to move
+    ///
+    /// # Parameters
+    ///
+    /// - `player` - The [player](Player) whose clock is running
+    /// - `elapsed` - The time elapsed since the last tick
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     clock::{Clock, TimeControl},
+    ///     game::Player,
+    /// };
+    ///
+    /// use std::time::Duration;
+    ///
+    /// let mut clock = Clock::new(TimeControl::Absolute(Duration::from_secs(2)));
+    /// clock.tick(Player::Blue, Duration::from_secs(1));
+    /// assert_eq!(clock.remaining(Player::Blue), Duration::from_secs(1));
+    /// assert_eq!(clock.flagged(), None);
+    ///
+    /// clock.tick(Player::Blue, Duration::from_secs(3));
+    /// assert_eq!(clock.flagged(), Some(Player::Blue));
+    /// ```
+    pub fn tick(&mut self, player: Player, elapsed: Duration) {
+        let remaining = self.remaining_mut(player);
+        *remaining = remaining.saturating_sub(elapsed);
+    }
+
+    /// Notify the clock that a [player](Player) has played
+    ///
+    /// Depending on the [time control](TimeControl), the remaining time of the player may be increased or reset.
+    pub fn end_turn(&mut self, player: Player) {
+        if self.flagged().is_some() {
+            return;
+        }
+
+        match self.control {
+            TimeControl::Absolute(_) => (),
+            TimeControl::Increment { increment, .. } => *self.remaining_mut(player) += increment,
+            TimeControl::PerMove(duration) => *self.remaining_mut(player) = duration,
+        }
+    }
+
+    /// Get the [player](Player) who has run out of time, if any
+    pub fn flagged(&self) -> Option<Player> {
+        [Player::Blue, Player::Red]
+            .into_iter()
+            .find(|&player| self.remaining(player).is_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn increment() {
+        let mut clock = Clock::new(TimeControl::Increment {
+            base: Duration::from_secs(10),
+            increment: Duration::from_secs(2),
+        });
+
+        clock.tick(Player::Red, Duration::from_secs(5));
+        clock.end_turn(Player::Red);
+
+        pretty_assertions::assert_eq!(clock.remaining(Player::Red), Duration::from_secs(7));
+        pretty_assertions::assert_eq!(clock.remaining(Player::Blue), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn per_move() {
+        let mut clock = Clock::new(TimeControl::PerMove(Duration::from_secs(10)));
+
+        clock.tick(Player::Blue, Duration::from_secs(9));
+        clock.end_turn(Player::Blue);
+
+        pretty_assertions::assert_eq!(clock.remaining(Player::Blue), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn round_trip() {
+        for control in TimeControl::PRESETS {
+            pretty_assertions::assert_eq!(control.to_string().parse(), Ok(control));
+        }
+    }
+}
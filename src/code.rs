@@ -0,0 +1,323 @@
+//! The shareable codes of the positions
+//!
+//! A code holds the dimensions of the [board](Board), its cells, its [rules](RuleSet) and the [player](Player) to move, so that another player can load the exact same position.
+//! The cells are packed two bits each, followed by the rules when they are not the default ones and by a checksum, and the whole is written in the Crockford base 32 alphabet, by groups of four characters separated by dashes.
+//! The codes are read case-insensitively and the letters `I`, `L` and `O` are read as `1`, `1` and `0`.
+
+use crate::game::{Board, CellState, Player, RuleSet, Topology};
+
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+const GROUP: usize = 4;
+
+/// The length of the rules, in bytes
+const RULES: usize = 5;
+
+/// Write the code of a position
+///
+/// # Parameters
+///
+/// - `board` - The [board](Board) of the position
+/// - `player` - The [player](Player) to move
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::{code, game::{Board, Player}};
+///
+/// let board: Board = "B.#/..R".parse().unwrap();
+/// let code = code::encode(&board, Player::Red).unwrap();
+///
+/// assert_eq!(code::decode(&code), Ok((board, Player::Red)));
+///
+/// let board: Board = "B.#/..R topology=torus limit=50".parse().unwrap();
+/// let code = code::encode(&board, Player::Red).unwrap();
+///
+/// assert_eq!(code::decode(&code), Ok((board, Player::Red)));
+/// ```
+pub fn encode(board: &Board, player: Player) -> Result<String, &'static str> {
+    let height = u8::try_from(board.height()).map_err(|_| "The board is too large")?;
+    let width = u8::try_from(board.width()).map_err(|_| "The board is too large")?;
+
+    let shielded = (0..board.height())
+        .any(|row| (0..board.width()).any(|column| board.is_shielded(row, column)));
+
+    if board.power_ups().next().is_some() || board.hill().next().is_some() || shielded {
+        return Err("The power-ups and the hill cannot be written in a code");
+    }
+
+    let mut bytes = vec![height, width, u8::from(player == Player::Red)];
+
+    let cells = (0..board.height())
+        .flat_map(|row| (0..board.width()).map(move |column| (row, column)))
+        .map(|(row, column)| match board.get(row, column) {
+            Some(CellState::Restricted) => 1,
+            Some(CellState::Player(Player::Blue)) => 2,
+            Some(CellState::Player(Player::Red)) => 3,
+            _ => 0,
+        })
+        .collect::<Vec<u8>>();
+
+    bytes.extend(cells.chunks(4).map(|chunk| {
+        chunk
+            .iter()
+            .enumerate()
+            .fold(0, |byte, (i, cell)| byte | cell << (6 - 2 * i))
+    }));
+
+    if board.rules() != RuleSet::default() {
+        bytes.extend(encode_rules(board.rules())?);
+    }
+
+    bytes.push(checksum(&bytes));
+
+    let code = to_base32(&bytes);
+
+    Ok(code
+        .as_bytes()
+        .chunks(GROUP)
+        .map(|group| String::from_utf8_lossy(group).into_owned())
+        .collect::<Vec<_>>()
+        .join("-"))
+}
+
+/// Read the code of a position
+///
+/// # Parameter
+///
+/// - `code` - The code; the dashes and the whitespaces are ignored
+///
+/// # Return
+///
+/// The [board](Board) and the [player](Player) to move
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::code;
+///
+/// assert!(code::decode("not a code").is_err());
+/// ```
+pub fn decode(code: &str) -> Result<(Board, Player), &'static str> {
+    let bytes = from_base32(code)?;
+
+    let (&sum, bytes) = bytes.split_last().ok_or("The code is empty")?;
+
+    if checksum(bytes) != sum {
+        return Err("The code is mistyped");
+    }
+
+    let [height, width, player, cells @ ..] = bytes else {
+        return Err("The code is truncated");
+    };
+
+    let (height, width) = (usize::from(*height), usize::from(*width));
+
+    if height == 0 || width == 0 {
+        return Err("The code is truncated");
+    }
+
+    let (cells, rules) = cells
+        .split_at_checked((height * width).div_ceil(4))
+        .ok_or("The code is truncated")?;
+
+    let rules = match rules {
+        [] => RuleSet::default(),
+        rules => decode_rules(rules.try_into().map_err(|_| "The code is truncated")?)?,
+    };
+
+    let player = match player {
+        0 => Player::Blue,
+        1 => Player::Red,
+        _ => return Err("The code names an unknown player"),
+    };
+
+    let board = cells
+        .iter()
+        .flat_map(|byte| (0..4).map(move |i| byte >> (6 - 2 * i) & 0b11))
+        .take(height * width)
+        .map(|cell| match cell {
+            1 => CellState::Restricted,
+            2 => CellState::Player(Player::Blue),
+            3 => CellState::Player(Player::Red),
+            _ => CellState::Free,
+        })
+        .collect();
+
+    Ok((Board::new(height, width, board).with_rules(rules), player))
+}
+
+/// Pack the rules as the jump distance, the conversion radius, the topology and the misère flag in a byte, followed by the move limit and the goal on two bytes each, `0` standing for none
+fn encode_rules(rules: RuleSet) -> Result<[u8; RULES], &'static str> {
+    let limit = |limit: Option<usize>| {
+        u16::try_from(limit.unwrap_or_default())
+            .map_err(|_| "The move limit or the goal is too large to be written in a code")
+    };
+
+    let flags = (rules.jump_distance as u8) << 4
+        | (rules.conversion_radius as u8) << 2
+        | u8::from(rules.topology == Topology::Torus) << 1
+        | u8::from(rules.misere);
+
+    let [limit_high, limit_low] = limit(rules.move_limit)?.to_be_bytes();
+    let [goal_high, goal_low] = limit(rules.goal)?.to_be_bytes();
+
+    Ok([flags, limit_high, limit_low, goal_high, goal_low])
+}
+
+fn decode_rules(bytes: [u8; RULES]) -> Result<RuleSet, &'static str> {
+    let [flags, limit_high, limit_low, goal_high, goal_low] = bytes;
+    let limit =
+        |high, low| Some(usize::from(u16::from_be_bytes([high, low]))).filter(|&limit| limit > 0);
+
+    let rules = RuleSet {
+        jump_distance: usize::from(flags >> 4),
+        conversion_radius: usize::from(flags >> 2 & 0b11),
+        topology: if flags & 0b10 == 0 {
+            Topology::Flat
+        } else {
+            Topology::Torus
+        },
+        misere: flags & 0b1 != 0,
+        move_limit: limit(limit_high, limit_low),
+        goal: limit(goal_high, goal_low),
+    };
+
+    if !RuleSet::JUMP_DISTANCES.contains(&rules.jump_distance)
+        || !RuleSet::CONVERSION_RADII.contains(&rules.conversion_radius)
+    {
+        return Err("The code holds invalid rules");
+    }
+
+    Ok(rules)
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0x5a_u8, |sum, &byte| sum.rotate_left(3) ^ byte)
+}
+
+fn to_base32(bytes: &[u8]) -> String {
+    let mut code = String::new();
+    let (mut buffer, mut bits) = (0_u32, 0);
+
+    for &byte in bytes {
+        buffer = buffer << 8 | u32::from(byte);
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            code.push(ALPHABET[(buffer >> bits & 0b11111) as usize] as char);
+        }
+    }
+
+    if bits > 0 {
+        code.push(ALPHABET[(buffer << (5 - bits) & 0b11111) as usize] as char);
+    }
+
+    code
+}
+
+fn from_base32(code: &str) -> Result<Vec<u8>, &'static str> {
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0_u32, 0);
+
+    for c in code.chars().filter(|&c| c != '-' && !c.is_whitespace()) {
+        let c = match c.to_ascii_uppercase() {
+            'I' | 'L' => '1',
+            'O' => '0',
+            c => c,
+        };
+
+        let value = ALPHABET
+            .iter()
+            .position(|&symbol| symbol as char == c)
+            .ok_or("The code contains an unknown character")?;
+
+        buffer = buffer << 5 | value as u32;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits & 0xff) as u8);
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let board: Board = "B.#.R/#...#/R.#.B".parse().unwrap();
+        let code = encode(&board, Player::Blue).unwrap();
+
+        assert!(code.split('-').all(|group| group.len() <= GROUP));
+        pretty_assertions::assert_eq!(decode(&code), Ok((board.clone(), Player::Blue)));
+        pretty_assertions::assert_eq!(
+            decode(&code.to_lowercase().replace('-', " ")),
+            Ok((board, Player::Blue))
+        );
+    }
+
+    #[test]
+    fn rules() {
+        let board: Board = "B.#.R/#...#/R.#.B".parse().unwrap();
+        let default = encode(&board, Player::Red).unwrap();
+
+        for rules in [
+            "jump=3",
+            "radius=3 topology=torus",
+            "misere=true limit=200",
+            "jump=4 radius=2 topology=torus limit=50 goal=3 misere=true",
+        ] {
+            let board = board.clone().with_rules(rules.parse().unwrap());
+            let code = encode(&board, Player::Red).unwrap();
+
+            assert_ne!(code, default);
+            pretty_assertions::assert_eq!(decode(&code), Ok((board, Player::Red)));
+        }
+
+        let board = board.with_rules(RuleSet {
+            move_limit: Some(70_000),
+            ..RuleSet::default()
+        });
+
+        pretty_assertions::assert_eq!(
+            encode(&board, Player::Red),
+            Err("The move limit or the goal is too large to be written in a code")
+        );
+    }
+
+    #[test]
+    fn mistyped() {
+        let board: Board = "B..R".parse().unwrap();
+        let code = encode(&board, Player::Blue).unwrap();
+
+        let mut typo = code.into_bytes();
+        typo[2] = if typo[2] == b'7' { b'8' } else { b'7' };
+
+        pretty_assertions::assert_eq!(
+            decode(&String::from_utf8(typo).unwrap()),
+            Err("The code is mistyped")
+        );
+
+        pretty_assertions::assert_eq!(decode(""), Err("The code is empty"));
+    }
+
+    #[test]
+    fn power_ups() {
+        for board in ["B+.R", "b..R", "B..R hill=b1"] {
+            let board: Board = board.parse().unwrap();
+
+            pretty_assertions::assert_eq!(
+                encode(&board, Player::Blue),
+                Err("The power-ups and the hill cannot be written in a code")
+            );
+        }
+    }
+}
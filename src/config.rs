@@ -0,0 +1,420 @@
+//! The implementation of [`Config`]
+
+use crate::{
+    ai::{Difficulty, Personality},
+    clock::TimeControl,
+    game::{RuleSet, Topology},
+    keymap::Keymap,
+    snapshot::SnapshotFormat,
+    widgets::{
+        LayoutPreset, Symbols, ThemeName,
+        board::{Flip, Grid, RenderStyle},
+    },
+};
+
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize, de};
+use serde_with::{DisplayFromStr, serde_as};
+use toml::Table;
+
+use std::{
+    fmt::{Display, Formatter},
+    fs,
+    io::{self, ErrorKind},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The configuration of the application
+///
+/// The configuration is persisted in a TOML [file](Self::path), shared with the [key bindings](crate::keymap::Keymap) written in its `keys` table.
+/// The optional settings are written by omitting their key.
+#[serde_as]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// The height of the generated boards
+    pub height: usize,
+
+    /// The width of the generated boards
+    pub width: usize,
+
+    /// The percentage of restricted cells in the generated boards
+    pub density: u8,
+
+    /// The [personality](Personality) of the computer opponent
+    ///
+    /// If it is `None`, the game is played by two humans.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub opponent: Option<Personality>,
+
+    /// The [difficulty](Difficulty) of the computer opponent
+    #[serde_as(as = "DisplayFromStr")]
+    pub difficulty: Difficulty,
+
+    /// The [time control](TimeControl) of the games
+    ///
+    /// If it is `None`, the games are not timed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub clock: Option<TimeControl>,
+
+    /// The name of the [theme](crate::widgets::Theme) used to colorize text
+    #[serde_as(as = "DisplayFromStr")]
+    pub theme: ThemeName,
+
+    /// The [symbols](Symbols) used to show the cells of the board
+    #[serde_as(as = "DisplayFromStr")]
+    pub symbols: Symbols,
+
+    /// The [style](RenderStyle) used to render the cells of the board
+    #[serde_as(as = "DisplayFromStr")]
+    pub render_style: RenderStyle,
+
+    /// The [grid](Grid) drawn on the board
+    #[serde_as(as = "DisplayFromStr")]
+    pub grid: Grid,
+
+    /// Whether the colorblind mode is enabled
+    ///
+    /// This mode uses a [palette](crate::widgets::Theme::colorblind) safe for colorblind people and gives each player its own symbol.
+    pub colorblind: bool,
+
+    /// Whether the cursor wraps around the edges of the board
+    pub wrap: bool,
+
+    /// Whether the players may pass even when they have a legal move
+    pub free_pass: bool,
+
+    /// The [rules](RuleSet) the games are played with
+    ///
+    /// They apply to the loaded maps too, unless the maps set their own rules.
+    #[serde(flatten, with = "Rules")]
+    pub rules: RuleSet,
+
+    /// The [orientation](Flip) of the board
+    #[serde_as(as = "DisplayFromStr")]
+    pub flip: Flip,
+
+    /// The [preset](LayoutPreset) of the layout of the game screen
+    #[serde_as(as = "DisplayFromStr")]
+    pub layout: LayoutPreset,
+
+    /// The [format](SnapshotFormat) of the exported images of the positions
+    #[serde_as(as = "DisplayFromStr")]
+    pub snapshot_format: SnapshotFormat,
+}
+
+impl Config {
+    /// The allowed sizes of the generated boards
+    pub const SIZES: RangeInclusive<usize> = 4..=16;
+
+    /// The allowed densities of restricted cells
+    pub const DENSITIES: RangeInclusive<u8> = 0..=40;
+
+    /// Get the path of the configuration file
+    ///
+    /// The file is located in the configuration directory of the platform, such as `$XDG_CONFIG_HOME/blobwars` on Linux,
+    /// `~/Library/Application Support/blobwars` on macOS or `%APPDATA%\blobwars\config` on Windows.
+    pub fn path() -> Option<PathBuf> {
+        ProjectDirs::from("", "", "blobwars")
+            .map(|directories| directories.config_dir().join("blobwars.toml"))
+    }
+
+    /// Load the configuration file
+    ///
+    /// If there is no configuration directory or the file does not exist, the default configuration is returned.
+    /// A malformed file is reported as an error of kind [`ErrorKind::InvalidData`].
+    pub fn load() -> io::Result<Self> {
+        match Self::path() {
+            Some(path) => Self::read(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Save the configuration file
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or(io::Error::new(
+            ErrorKind::NotFound,
+            "No configuration directory was found",
+        ))?;
+
+        self.write(&path)
+    }
+
+    /// Read a configuration from a given file
+    ///
+    /// If the file does not exist, the default configuration is returned.
+    ///
+    /// # Parameter
+    ///
+    /// - `path` - The path of the file
+    pub fn read(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => content
+                .parse()
+                .map_err(|error| io::Error::new(ErrorKind::InvalidData, error)),
+
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Write the configuration to a given file
+    ///
+    /// The missing parent directories are created and the [key bindings](crate::keymap::Keymap) already written in the file are kept.
+    ///
+    /// # Parameter
+    ///
+    /// - `path` - The path of the file
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut table = Table::try_from(self).map_err(io::Error::other)?;
+
+        if let Some(keys) = read_table(path)?.remove(Keymap::TABLE) {
+            table.insert(Keymap::TABLE.to_owned(), keys);
+        }
+
+        write_table(path, &table)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            height: 8,
+            width: 8,
+            density: 0,
+            opponent: None,
+            difficulty: Difficulty::default(),
+            clock: None,
+            theme: ThemeName::default(),
+            symbols: Symbols::default(),
+            render_style: RenderStyle::default(),
+            grid: Grid::Off,
+            colorblind: false,
+            wrap: false,
+            free_pass: false,
+            rules: RuleSet::default(),
+            flip: Flip::Off,
+            layout: LayoutPreset::Comfortable,
+            snapshot_format: SnapshotFormat::Svg,
+        }
+    }
+}
+
+impl Display for Config {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&toml::to_string(self).map_err(|_| std::fmt::Error)?)
+    }
+}
+
+impl FromStr for Config {
+    type Err = toml::de::Error;
+
+    /// Parse a configuration
+    ///
+    /// Missing keys keep their default value and unknown keys, such as the [key bindings](crate::keymap::Keymap), are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{ai::Personality, config::Config};
+    ///
+    /// let config: Config = "height = 7\nopponent = \"Defensive\"".parse().unwrap();
+    ///
+    /// assert_eq!(config.height, 7);
+    /// assert_eq!(config.width, 8);
+    /// assert_eq!(config.opponent, Some(Personality::Defensive));
+    /// assert_eq!(config.rules.move_limit, None);
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let config = toml::from_str::<Self>(s)?;
+
+        for (valid, error) in [
+            (
+                Self::SIZES.contains(&config.height),
+                "The height is out of range",
+            ),
+            (
+                Self::SIZES.contains(&config.width),
+                "The width is out of range",
+            ),
+            (
+                Self::DENSITIES.contains(&config.density),
+                "The density is out of range",
+            ),
+            (
+                RuleSet::JUMP_DISTANCES.contains(&config.rules.jump_distance),
+                "The jump distance is out of range",
+            ),
+            (
+                RuleSet::CONVERSION_RADII.contains(&config.rules.conversion_radius),
+                "The conversion radius is out of range",
+            ),
+            (config.rules.move_limit != Some(0), "The move limit is zero"),
+            (config.rules.goal != Some(0), "The goal is zero"),
+        ] {
+            if !valid {
+                return Err(de::Error::custom(error));
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// The [rules](RuleSet) as written in the configuration file, along with the other keys
+///
+/// A rule without limit is written by omitting its key.
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "RuleSet")]
+struct Rules {
+    #[serde(default = "default_jump_distance")]
+    jump_distance: usize,
+
+    #[serde(default = "default_conversion_radius")]
+    conversion_radius: usize,
+
+    #[serde(default)]
+    #[serde_as(as = "DisplayFromStr")]
+    topology: Topology,
+
+    #[serde(default)]
+    misere: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    move_limit: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    goal: Option<usize>,
+}
+
+fn default_jump_distance() -> usize {
+    RuleSet::default().jump_distance
+}
+
+fn default_conversion_radius() -> usize {
+    RuleSet::default().conversion_radius
+}
+
+/// Read a TOML file as a table
+///
+/// A missing file is read as an empty table.
+pub(crate) fn read_table(path: &Path) -> io::Result<Table> {
+    match fs::read_to_string(path) {
+        Ok(content) => content
+            .parse()
+            .map_err(|error| io::Error::new(ErrorKind::InvalidData, error)),
+
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(Table::new()),
+        Err(error) => Err(error),
+    }
+}
+
+/// Write a table to a TOML file
+///
+/// The missing parent directories are created.
+pub(crate) fn write_table(path: &Path, table: &Table) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let content = toml::to_string(table).map_err(io::Error::other)?;
+    fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let config = Config {
+            height: 6,
+            width: 10,
+            density: 15,
+            opponent: Some(Personality::Territorial),
+            difficulty: Difficulty::Hard,
+            clock: Some(TimeControl::PRESETS[2]),
+            theme: ThemeName::Monochrome,
+            symbols: Symbols::PRESETS[2],
+            render_style: RenderStyle::Braille,
+            grid: Grid::Both,
+            colorblind: true,
+            wrap: true,
+            free_pass: true,
+            rules: RuleSet {
+                jump_distance: 3,
+                conversion_radius: 2,
+                topology: Topology::Torus,
+                misere: true,
+                move_limit: Some(100),
+                goal: None,
+            },
+            flip: Flip::Auto,
+            layout: LayoutPreset::Compact,
+            snapshot_format: SnapshotFormat::Png,
+        };
+
+        pretty_assertions::assert_eq!(config.to_string().parse::<Config>().ok(), Some(config));
+    }
+
+    #[test]
+    fn no_limit() {
+        let written = Config::default().to_string();
+        let table = written.parse::<Table>().unwrap();
+
+        assert!(!table.contains_key("opponent"));
+        assert!(!table.contains_key("clock"));
+        assert!(!table.contains_key("move_limit"));
+        assert!(!table.contains_key("goal"));
+
+        let limited = "move_limit = 100".parse::<Config>().unwrap();
+        pretty_assertions::assert_eq!(limited.rules.move_limit, Some(100));
+
+        assert!("move_limit = \"none\"".parse::<Config>().is_err());
+        assert!("move_limit = 0".parse::<Config>().is_err());
+    }
+
+    #[test]
+    fn keys() {
+        let path = std::env::temp_dir()
+            .join(format!("blobwars-config-{}", std::process::id()))
+            .join("blobwars.toml");
+
+        let mut keymap = Keymap::default();
+        keymap.bind(crate::Command::Exit, ["Ctrl+x".parse().unwrap()]);
+        keymap.write(&path).unwrap();
+
+        let config = Config {
+            wrap: true,
+            ..Config::default()
+        };
+
+        config.write(&path).unwrap();
+
+        let read = (
+            Config::read(&path).ok(),
+            Keymap::read(&path).ok().map(|keymap| keymap.to_string()),
+        );
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        pretty_assertions::assert_eq!(read, (Some(config), Some(keymap.to_string())));
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert!("height = 2".parse::<Config>().is_err());
+        assert!("density = 90".parse::<Config>().is_err());
+    }
+
+    #[test]
+    fn malformed() {
+        assert!("height".parse::<Config>().is_err());
+        assert!("opponent = \"Nobody\"".parse::<Config>().is_err());
+        assert!(Config::read(Path::new("/nonexistent/blobwars.toml")).is_ok());
+    }
+}
@@ -0,0 +1,463 @@
+//! The implementation of [`GameDatabase`]
+
+use crate::{
+    game::{Move, Outcome, Player},
+    pgn::PortableGame,
+    record::{GameRecord, display_ply},
+    save::SavedGame,
+    widgets::board::BoardState,
+};
+
+use rusqlite::{Connection, Params, Row, params};
+
+use std::{
+    fmt::{Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// A finished game stored in the [database](GameDatabase)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchivedGame {
+    /// The name of the player of each color, as `(blue, red)`
+    pub players: (String, String),
+
+    /// The [outcome](Outcome) of the game
+    pub outcome: Outcome,
+
+    /// The [record](GameRecord) of the game, holding the map and the full move list
+    pub record: GameRecord,
+
+    /// The time the game finished at, in seconds since the Unix epoch
+    pub date: u64,
+
+    /// The time spent playing the game
+    pub duration: Duration,
+}
+
+impl ArchivedGame {
+    /// Archive the game of a [board](BoardState), if it is over
+    ///
+    /// The players are [named](BoardState::player_name) after their nature.
+    ///
+    /// # Parameter
+    ///
+    /// - `state` - The [state](BoardState) of the board of the finished game
+    pub fn from_state(state: &BoardState) -> Option<Self> {
+        let outcome = state.outcome()?;
+
+        let date = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        Some(Self {
+            players: (
+                state.player_name(Player::Blue),
+                state.player_name(Player::Red),
+            ),
+            outcome,
+            record: state.record().clone(),
+            date,
+            duration: state.duration(),
+        })
+    }
+
+    /// Archive an imported [portable game](PortableGame), if it is over
+    ///
+    /// The players are named after the `Blue` and `Red` tags and the date is read from the `Date` tag; the duration is unknown.
+    ///
+    /// # Parameter
+    ///
+    /// - `game` - The [portable game](PortableGame)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{database::ArchivedGame, pgn::PortableGame};
+    ///
+    /// let game: PortableGame = "[Blue \"Bob\"]\n[Board \"R.B\"]\n[First \"Red\"]\n\n1. a1b1 0-1".parse().unwrap();
+    /// let game = ArchivedGame::from_portable(game).unwrap();
+    ///
+    /// assert_eq!(game.players, ("Bob".to_owned(), "?".to_owned()));
+    /// ```
+    pub fn from_portable(game: PortableGame) -> Option<Self> {
+        let name = |tag| game.tag(tag).unwrap_or("?").to_owned();
+
+        Some(Self {
+            players: (name("Blue"), name("Red")),
+            outcome: game.outcome?,
+            date: game.date().unwrap_or_default(),
+            duration: Duration::ZERO,
+            record: game.record,
+        })
+    }
+
+    /// Get the name of the player of a given color
+    ///
+    /// # Parameter
+    ///
+    /// - `player` - The color of the player
+    pub fn player(&self, player: Player) -> &str {
+        match player {
+            Player::Blue => &self.players.0,
+            Player::Red => &self.players.1,
+        }
+    }
+
+    /// Check if the game starts with some plies
+    ///
+    /// # Parameter
+    ///
+    /// - `opening` - The first plies; `None` stands for a pass
+    pub fn starts_with(&self, opening: &[Option<Move>]) -> bool {
+        self.record.plies.starts_with(opening)
+    }
+}
+
+impl Display for ArchivedGame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "blue = \"{}\"", self.players.0)?;
+        writeln!(f, "red = \"{}\"", self.players.1)?;
+        writeln!(f, "result = \"{}\"", result(self.outcome))?;
+        writeln!(f, "date = {}", self.date)?;
+        writeln!(f, "duration = {}", self.duration.as_millis())?;
+        write!(f, "{}", self.record)
+    }
+}
+
+impl FromStr for ArchivedGame {
+    type Err = &'static str;
+
+    /// Parse an archived game
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{database::ArchivedGame, game::{Outcome, Player}};
+    ///
+    /// let game = "blue = \"Human\"\nred = \"Remote\"\nresult = \"Red\"\ndate = 0\nduration = 1500\n\
+    ///     height = 1\nwidth = 3\nboard = \"R.B\"\nfirst = \"Red\"\nplies = \"a1b1\"";
+    ///
+    /// let game = game.parse::<ArchivedGame>().unwrap();
+    ///
+    /// assert_eq!(game.player(Player::Red), "Remote");
+    /// assert_eq!(game.outcome, Outcome::Win(Player::Red));
+    /// assert_eq!(game.record.plies.len(), 1);
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let record = s.parse()?;
+
+        let (mut blue, mut red, mut outcome) = (String::new(), String::new(), None);
+        let (mut date, mut duration) = (0, Duration::ZERO);
+
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or("A line is not a `key = value` pair")?;
+
+            let value = value.trim().trim_matches('"');
+
+            match key.trim() {
+                "blue" => blue = value.to_owned(),
+                "red" => red = value.to_owned(),
+
+                "result" => {
+                    outcome = Some(match value {
+                        "Draw" => Outcome::Draw,
+                        _ => Outcome::Win(value.parse()?),
+                    });
+                }
+
+                "date" => date = value.parse().map_err(|_| "The date is invalid")?,
+
+                "duration" => {
+                    duration = value
+                        .parse()
+                        .map(Duration::from_millis)
+                        .map_err(|_| "The duration is invalid")?;
+                }
+
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            players: (blue, red),
+            outcome: outcome.ok_or("The result is missing")?,
+            record,
+            date,
+            duration,
+        })
+    }
+}
+
+/// The database of the finished games
+///
+/// The [games](ArchivedGame) are stored in an embedded SQLite database, each in a row of the `games` table holding its players, its result, its date, its duration, its [record](GameRecord) and its move list.
+/// The queries are run by SQLite, the games being indexed by date.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameDatabase {
+    path: PathBuf,
+}
+
+impl GameDatabase {
+    /// The schema of the database, applied whenever it is opened
+    const SCHEMA: &str = "
+        CREATE TABLE IF NOT EXISTS games (
+            id INTEGER PRIMARY KEY,
+            blue TEXT NOT NULL,
+            red TEXT NOT NULL,
+            result TEXT NOT NULL,
+            date INTEGER NOT NULL,
+            duration INTEGER NOT NULL,
+            record TEXT NOT NULL,
+            plies TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS games_by_date ON games (date);
+    ";
+
+    /// The columns read to [restore](Self::read) a game
+    const COLUMNS: &str = "blue, red, result, date, duration, record";
+
+    /// Open the database stored in a given file
+    ///
+    /// The file and its directory are created when the database is first used.
+    ///
+    /// # Parameter
+    ///
+    /// - `path` - The path of the SQLite database
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Open the database of the data directory
+    ///
+    /// The database is located next to the [save directory](SavedGame::directory).
+    pub fn open() -> Option<Self> {
+        SavedGame::directory().map(|directory| Self::new(directory.with_file_name("games.sqlite3")))
+    }
+
+    /// Get the path of the SQLite database
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Store a finished game
+    ///
+    /// # Parameter
+    ///
+    /// - `game` - The [game](ArchivedGame) to store
+    ///
+    /// # Return
+    ///
+    /// The identifier of the stored game
+    pub fn insert(&self, game: &ArchivedGame) -> io::Result<i64> {
+        let connection = self.connect()?;
+
+        connection
+            .execute(
+                "INSERT INTO games (blue, red, result, date, duration, record, plies) \
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    game.players.0,
+                    game.players.1,
+                    result(game.outcome),
+                    i64::try_from(game.date).map_err(invalid)?,
+                    i64::try_from(game.duration.as_millis()).map_err(invalid)?,
+                    game.record.to_string(),
+                    plies(&game.record.plies),
+                ],
+            )
+            .map_err(io::Error::other)?;
+
+        Ok(connection.last_insert_rowid())
+    }
+
+    /// Get every stored game
+    ///
+    /// The most recent games come first; the malformed rows are skipped.
+    pub fn games(&self) -> io::Result<Vec<ArchivedGame>> {
+        self.select("TRUE", params![])
+    }
+
+    /// Get the stored games played by a given player, the most recent first
+    ///
+    /// # Parameter
+    ///
+    /// - `name` - The name of the player, as written in the [archived games](ArchivedGame::players)
+    pub fn with_player(&self, name: &str) -> io::Result<Vec<ArchivedGame>> {
+        self.select("blue = ?1 OR red = ?1", params![name])
+    }
+
+    /// Get the stored games starting with some plies, the most recent first
+    ///
+    /// # Parameter
+    ///
+    /// - `opening` - The first plies; `None` stands for a pass
+    pub fn with_opening(&self, opening: &[Option<Move>]) -> io::Result<Vec<ArchivedGame>> {
+        self.select(
+            "?1 = '' OR plies = ?1 OR plies LIKE ?1 || ' %'",
+            params![plies(opening)],
+        )
+    }
+
+    /// Open a connection to the database, creating its schema if needed
+    fn connect(&self) -> io::Result<Connection> {
+        if let Some(directory) = self.path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+
+        let connection = Connection::open(&self.path).map_err(io::Error::other)?;
+        connection
+            .execute_batch(Self::SCHEMA)
+            .map_err(io::Error::other)?;
+
+        Ok(connection)
+    }
+
+    /// Get the stored games matching a condition, the most recent first
+    ///
+    /// # Parameters
+    ///
+    /// - `condition` - The `WHERE` clause of the query
+    /// - `params` - The parameters of the condition
+    fn select(&self, condition: &str, params: impl Params) -> io::Result<Vec<ArchivedGame>> {
+        let connection = self.connect()?;
+
+        let mut statement = connection
+            .prepare(&format!(
+                "SELECT {} FROM games WHERE {condition} ORDER BY date DESC, id DESC",
+                Self::COLUMNS
+            ))
+            .map_err(io::Error::other)?;
+
+        let games = statement
+            .query_map(params, |row| Ok(Self::read(row)))
+            .map_err(io::Error::other)?
+            .filter_map(|game| game.ok().flatten())
+            .collect();
+
+        Ok(games)
+    }
+
+    /// Restore a game from a row made of the [columns](Self::COLUMNS), if it is well-formed
+    fn read(row: &Row) -> Option<ArchivedGame> {
+        let result = row.get::<_, String>(2).ok()?;
+
+        Some(ArchivedGame {
+            players: (row.get(0).ok()?, row.get(1).ok()?),
+            outcome: match result.as_str() {
+                "Draw" => Outcome::Draw,
+                player => Outcome::Win(player.parse().ok()?),
+            },
+            date: u64::try_from(row.get::<_, i64>(3).ok()?).ok()?,
+            duration: Duration::from_millis(u64::try_from(row.get::<_, i64>(4).ok()?).ok()?),
+            record: row.get::<_, String>(5).ok()?.parse().ok()?,
+        })
+    }
+}
+
+/// Write an [outcome](Outcome) the way it is stored
+fn result(outcome: Outcome) -> String {
+    match outcome {
+        Outcome::Win(player) => player.to_string(),
+        Outcome::Draw => "Draw".to_owned(),
+    }
+}
+
+/// Write some plies the way they are stored, separated by spaces
+fn plies(plies: &[Option<Move>]) -> String {
+    plies
+        .iter()
+        .map(|&ply| display_ply(ply))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn invalid<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidInput, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Board, CellState};
+
+    fn game(date: u64, outcome: Outcome, plies: Vec<Option<Move>>) -> ArchivedGame {
+        let mut record = GameRecord::new(
+            Board::new(
+                1,
+                3,
+                vec![
+                    CellState::Player(Player::Red),
+                    CellState::Free,
+                    CellState::Player(Player::Blue),
+                ],
+            ),
+            Player::Red,
+        );
+
+        record.plies = plies;
+
+        ArchivedGame {
+            players: ("Human".to_owned(), "Computer (Aggressive, Hard)".to_owned()),
+            outcome,
+            record,
+            date,
+            duration: Duration::from_millis(4200),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let game = game(
+            1_700_000_000,
+            Outcome::Draw,
+            vec![Some(Move {
+                from: (0, 0),
+                to: (0, 1),
+            })],
+        );
+
+        pretty_assertions::assert_eq!(game.to_string().parse(), Ok(game));
+    }
+
+    #[test]
+    fn query() {
+        let directory =
+            std::env::temp_dir().join(format!("blobwars-database-{}", std::process::id()));
+
+        let database = GameDatabase::new(directory.join("games.sqlite3"));
+        let jump = Some(Move {
+            from: (0, 0),
+            to: (0, 1),
+        });
+
+        let old = game(1, Outcome::Win(Player::Blue), vec![jump]);
+        let new = game(2, Outcome::Win(Player::Red), vec![None]);
+
+        pretty_assertions::assert_eq!(database.insert(&old).unwrap(), 1);
+        pretty_assertions::assert_eq!(database.insert(&new).unwrap(), 2);
+
+        pretty_assertions::assert_eq!(database.games().unwrap(), vec![new.clone(), old.clone()]);
+        pretty_assertions::assert_eq!(database.with_opening(&[]).unwrap().len(), 2);
+        pretty_assertions::assert_eq!(database.with_opening(&[jump]).unwrap(), vec![old.clone()]);
+        pretty_assertions::assert_eq!(database.with_opening(&[jump, None]).unwrap(), vec![]);
+        pretty_assertions::assert_eq!(database.with_opening(&[None]).unwrap(), vec![new]);
+        pretty_assertions::assert_eq!(database.with_player("Remote").unwrap(), vec![]);
+        pretty_assertions::assert_eq!(database.with_player("Human").unwrap().len(), 2);
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
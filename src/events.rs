@@ -0,0 +1,485 @@
+//! The implementation of [`EventLoop`]
+//!
+//! Every source of events runs as a separate [tokio] task feeding a single channel consumed by the main loop, so that no source blocks the others:
+//! the terminal, the clock, the search of the computer opponent and the connection to the remote player.
+
+use crate::{
+    ai::{SearchResult, Thinking},
+    net::{Connection, NetMessage},
+};
+
+use ratatui::crossterm::event::{self, Event};
+
+use tokio::{
+    runtime::{self, Runtime},
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    time,
+};
+
+use std::{
+    io,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// An event received by the [event loop](EventLoop)
+#[derive(Debug)]
+pub enum LoopEvent {
+    /// An [event](Event) read from the terminal
+    Terminal(Event),
+
+    /// A tick of the [fixed timestep](Ticker), carrying its period
+    Tick(Duration),
+
+    /// A [result](TaskEvent) of a task of the game in progress
+    Task(TaskEvent),
+
+    /// A task has failed and stopped
+    Error(io::Error),
+}
+
+/// A result of a task of the game in progress, [delivered](crate::widgets::board::BoardState::deliver) to its board
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TaskEvent {
+    /// A [search](Thinking) of the computer opponent is over
+    Searched(Thinking, SearchResult),
+
+    /// A [message](NetMessage) has been received from the remote player through a [connection](Connection)
+    Received(Connection, NetMessage),
+
+    /// A lost [connection](Connection) to the remote player has been restored
+    Restored(Connection),
+}
+
+/// A clock producing ticks at a fixed rate
+///
+/// The ticks keep their period whatever the rate of the other events: the missed ticks are caught up, up to [a limit](Self::MAX_CATCH_UP) beyond which they are dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Ticker {
+    period: Duration,
+    next: Instant,
+}
+
+impl Ticker {
+    /// The default number of ticks per second
+    pub const RATE: u32 = 30;
+
+    /// The maximum number of ticks caught up at once
+    pub const MAX_CATCH_UP: u32 = 5;
+
+    /// Create a new [`Ticker`] whose first tick is due after one period
+    ///
+    /// # Parameter
+    ///
+    /// - `period` - The duration between two ticks
+    pub fn new(period: Duration) -> Self {
+        Self {
+            period,
+            next: Instant::now() + period,
+        }
+    }
+
+    /// Get the duration between two ticks
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Get the time left before the next tick
+    pub fn timeout(&self) -> Duration {
+        self.next.saturating_duration_since(Instant::now())
+    }
+
+    /// Consume the ticks due at a given instant
+    ///
+    /// # Parameter
+    ///
+    /// - `now` - The current instant
+    ///
+    /// # Return
+    ///
+    /// The number of ticks to run
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::events::Ticker;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let period = Duration::from_millis(10);
+    /// let mut ticker = Ticker::new(period);
+    /// let now = Instant::now() + 3 * period;
+    ///
+    /// assert_eq!(ticker.advance(now), 3);
+    /// assert_eq!(ticker.advance(now), 0);
+    /// ```
+    pub fn advance(&mut self, now: Instant) -> u32 {
+        let mut ticks = 0;
+
+        while self.next <= now {
+            self.next += self.period;
+            ticks += 1;
+        }
+
+        if ticks > Self::MAX_CATCH_UP {
+            tracing::warn!("{} ticks are dropped", ticks - Self::MAX_CATCH_UP);
+            self.next = now + self.period;
+            ticks = Self::MAX_CATCH_UP;
+        }
+
+        ticks
+    }
+}
+
+impl Default for Ticker {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(1) / Self::RATE)
+    }
+}
+
+/// A flag raised when the process is asked to terminate
+///
+/// The flag is checked by the main loop, so that the game in progress is saved and the terminal is restored before exiting.
+#[derive(Clone, Debug, Default)]
+pub struct Interruption(Arc<AtomicBool>);
+
+impl Interruption {
+    /// Raise the flag on `SIGINT`, `SIGTERM` and `SIGHUP`
+    ///
+    /// The flag is never raised on the platforms without signals.
+    pub fn register() -> io::Result<Self> {
+        let interruption = Self::default();
+
+        #[cfg(unix)]
+        for signal in [
+            signal_hook::consts::SIGINT,
+            signal_hook::consts::SIGTERM,
+            signal_hook::consts::SIGHUP,
+        ] {
+            signal_hook::flag::register(signal, interruption.0.clone())?;
+        }
+
+        Ok(interruption)
+    }
+
+    /// Raise the flag by hand
+    pub fn raise(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Check if the flag has been raised
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::events::Interruption;
+    ///
+    /// let interruption = Interruption::default();
+    /// assert!(!interruption.is_raised());
+    ///
+    /// interruption.clone().raise();
+    /// assert!(interruption.is_raised());
+    /// ```
+    pub fn is_raised(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A flag raised when the user asks to suspend the process
+///
+/// The flag is checked by the main loop, so that the terminal is restored before the process is stopped and set up again once it is continued.
+#[derive(Clone, Debug, Default)]
+pub struct Suspension(Arc<AtomicBool>);
+
+impl Suspension {
+    /// Raise the flag on `SIGTSTP`
+    ///
+    /// The flag is never raised by a signal on the platforms without job control.
+    pub fn register() -> io::Result<Self> {
+        let suspension = Self::default();
+
+        #[cfg(unix)]
+        signal_hook::flag::register(signal_hook::consts::SIGTSTP, suspension.0.clone())?;
+
+        Ok(suspension)
+    }
+
+    /// Raise the flag by hand
+    pub fn raise(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Lower the flag
+    ///
+    /// # Return
+    ///
+    /// The returned value is `true` if the flag was raised; otherwise, it returns `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::events::Suspension;
+    ///
+    /// let suspension = Suspension::default();
+    /// suspension.raise();
+    ///
+    /// assert!(suspension.take());
+    /// assert!(!suspension.take());
+    /// ```
+    pub fn take(&self) -> bool {
+        self.0.swap(false, Ordering::Relaxed)
+    }
+
+    /// Stop the process until it is continued
+    ///
+    /// The terminal is expected to be restored beforehand; nothing is done on the platforms without job control.
+    pub fn suspend(&self) -> io::Result<()> {
+        #[cfg(unix)]
+        signal_hook::low_level::emulate_default_handler(signal_hook::consts::SIGTSTP)?;
+
+        Ok(())
+    }
+}
+
+/// A loop gathering the [events](LoopEvent) of several [tokio] tasks in a single channel
+///
+/// The search of the computer opponent and the connection to the remote player are [watched](Self::watch) as they come and go with the games.
+#[derive(Debug)]
+pub struct EventLoop {
+    runtime: Option<Runtime>,
+    sender: UnboundedSender<LoopEvent>,
+    receiver: UnboundedReceiver<LoopEvent>,
+    thinking: Option<Thinking>,
+    connection: Option<(Connection, Arc<AtomicBool>)>,
+}
+
+impl EventLoop {
+    /// The longest duration for which a watched connection is waited for at a time, before checking if it is still watched
+    const RECV_PERIOD: Duration = Duration::from_millis(100);
+
+    /// Create a new [`EventLoop`] without any task
+    pub fn new() -> io::Result<Self> {
+        let runtime = runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("blobwars-events")
+            .enable_time()
+            .build()?;
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        Ok(Self {
+            runtime: Some(runtime),
+            sender,
+            receiver,
+            thinking: None,
+            connection: None,
+        })
+    }
+
+    /// Read the [events](Event) of the terminal in a dedicated task
+    pub fn with_terminal(self) -> Self {
+        let sender = self.sender.clone();
+
+        self.runtime().spawn_blocking(move || {
+            loop {
+                let event = match event::read() {
+                    Ok(event) => LoopEvent::Terminal(event),
+
+                    Err(error) => {
+                        let _ = sender.send(LoopEvent::Error(error));
+                        break;
+                    }
+                };
+
+                if sender.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        self
+    }
+
+    /// Send the [ticks](LoopEvent::Tick) of a [ticker](Ticker) in a dedicated task
+    ///
+    /// # Parameter
+    ///
+    /// - `ticker` - The [ticker](Ticker) giving the rate of the ticks
+    pub fn with_ticker(self, mut ticker: Ticker) -> Self {
+        let sender = self.sender.clone();
+
+        self.runtime().spawn(async move {
+            loop {
+                time::sleep(ticker.timeout()).await;
+
+                for _ in 0..ticker.advance(Instant::now()) {
+                    if sender.send(LoopEvent::Tick(ticker.period())).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        self
+    }
+
+    /// Watch the search of the computer opponent and the connection to the remote player of the game in progress
+    ///
+    /// A task is started for each new search, [sending](TaskEvent::Searched) its result once over, and for each new connection, [sending](TaskEvent::Received) its messages until it is no longer watched.
+    ///
+    /// # Parameters
+    ///
+    /// - `thinking` - The current [search](Thinking), if any
+    /// - `connection` - The current [connection](Connection), if any
+    pub fn watch(&mut self, thinking: Option<&Thinking>, connection: Option<&Connection>) {
+        if self.thinking.as_ref() != thinking {
+            self.thinking = thinking.cloned();
+
+            if let Some(thinking) = thinking.cloned() {
+                let sender = self.sender.clone();
+
+                self.runtime().spawn_blocking(move || {
+                    if let Some(result) = thinking.wait() {
+                        let event = TaskEvent::Searched(thinking, result);
+                        let _ = sender.send(LoopEvent::Task(event));
+                    }
+                });
+            }
+        }
+
+        if self.connection.as_ref().map(|(watched, _)| watched) != connection {
+            if let Some((_, watched)) = self.connection.take() {
+                watched.store(false, Ordering::Relaxed);
+            }
+
+            if let Some(connection) = connection.cloned() {
+                let watched = Arc::new(AtomicBool::new(true));
+                let sender = self.sender.clone();
+
+                self.connection = Some((connection.clone(), watched.clone()));
+                self.runtime()
+                    .spawn_blocking(move || receive(&connection, &watched, &sender));
+            }
+        }
+    }
+
+    fn runtime(&self) -> &Runtime {
+        self.runtime
+            .as_ref()
+            .expect("The runtime is only taken once dropped")
+    }
+}
+
+impl Iterator for EventLoop {
+    type Item = LoopEvent;
+
+    /// Wait for the next [event](LoopEvent)
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.blocking_recv()
+    }
+}
+
+impl Drop for EventLoop {
+    /// Stop the tasks without waiting for them, since the terminal is read until the next event
+    fn drop(&mut self) {
+        if let Some((_, watched)) = &self.connection {
+            watched.store(false, Ordering::Relaxed);
+        }
+
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+/// Send the messages received through a connection while it is watched
+fn receive(connection: &Connection, watched: &AtomicBool, sender: &UnboundedSender<LoopEvent>) {
+    while watched.load(Ordering::Relaxed) {
+        let event = if connection.take_restored() {
+            TaskEvent::Restored(connection.clone())
+        } else {
+            match connection.recv_timeout(EventLoop::RECV_PERIOD) {
+                Ok(Some(message)) => TaskEvent::Received(connection.clone(), message),
+                Ok(None) => continue,
+                Err(_) => break,
+            }
+        };
+
+        if sender.send(LoopEvent::Task(event)).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        ai::{Ai, Personality},
+        game::Player,
+    };
+
+    use std::{net::TcpListener, thread};
+
+    #[test]
+    fn ticker() {
+        let period = Duration::from_millis(1);
+        let mut events = EventLoop::new().unwrap().with_ticker(Ticker::new(period));
+
+        for _ in 0..3 {
+            assert!(matches!(events.next(), Some(LoopEvent::Tick(elapsed)) if elapsed == period));
+        }
+    }
+
+    #[test]
+    fn watch() {
+        let mut events = EventLoop::new().unwrap();
+
+        let board = "R.B".parse().unwrap();
+        let thinking = Ai::with_seed(Personality::Aggressive, 0).think(board, Player::Red);
+        events.watch(Some(&thinking), None);
+
+        assert!(matches!(
+            events.next(),
+            Some(LoopEvent::Task(TaskEvent::Searched(searched, (_, Some(_))))) if searched == thinking
+        ));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let accepted = thread::spawn(move || Connection::accept(&listener).unwrap());
+        let joined = Connection::join(address).unwrap();
+        let accepted = accepted.join().unwrap();
+
+        events.watch(None, Some(&joined));
+        accepted
+            .send(&NetMessage::Chat("hello".to_owned()))
+            .unwrap();
+
+        let received = loop {
+            match events.next() {
+                Some(LoopEvent::Task(TaskEvent::Received(connection, message))) => {
+                    break (connection == joined, message);
+                }
+
+                Some(_) => (),
+                None => panic!("the event loop has stopped"),
+            }
+        };
+
+        pretty_assertions::assert_eq!(received, (true, NetMessage::Chat("hello".to_owned())));
+    }
+
+    #[test]
+    fn catch_up() {
+        let period = Duration::from_millis(10);
+        let mut ticker = Ticker::new(period);
+        let now = Instant::now() + 100 * period;
+
+        pretty_assertions::assert_eq!(ticker.advance(now), Ticker::MAX_CATCH_UP);
+        pretty_assertions::assert_eq!(ticker.advance(now), 0);
+        pretty_assertions::assert_eq!(ticker.advance(now + period), 1);
+    }
+}
@@ -0,0 +1,616 @@
+//! The C interface of the game engine
+//!
+//! This module exposes the [rules](crate::game) and the [computer opponent](crate::ai) through a C ABI so that other languages and user interfaces can reuse them.
+//! The matching header is `include/blobwars.h`, generated by `cbindgen --config cbindgen.toml --output include/blobwars.h`.
+//! The shared library `libblobwars.so` is built by the `blobwars-ffi` package of the workspace, with `cargo build --release --package blobwars-ffi`.
+//!
+//! The boards are handed over as opaque pointers which must be released with [`blobwars_board_free()`].
+//! The players, the personalities and the difficulty levels are passed as integers holding the values of [`BlobwarsPlayer`], [`BlobwarsPersonality`] and [`BlobwarsDifficulty`]; the functions receiving them check them and return a [status](BlobwarsStatus).
+
+use crate::{
+    ai::{Ai, Difficulty, Personality},
+    game::{Board, CellState, Move, Player},
+    rng::Rng,
+};
+
+use std::{
+    ffi::{CStr, c_char, c_int},
+    ptr,
+};
+
+/// The status returned by the functions of this interface
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlobwarsStatus {
+    /// The call has succeeded
+    Ok = 0,
+
+    /// The move is illegal and has not been played
+    IllegalMove = 1,
+
+    /// No move can be chosen since the player has to pass
+    Pass = 2,
+
+    /// The player is not a value of [`BlobwarsPlayer`]
+    InvalidPlayer = -1,
+
+    /// The personality is not a value of [`BlobwarsPersonality`]
+    InvalidPersonality = -2,
+
+    /// The difficulty level is not a value of [`BlobwarsDifficulty`]
+    InvalidDifficulty = -3,
+
+    /// The number of cells of the board overflows `size_t`
+    InvalidSize = -4,
+}
+
+/// A [player](Player)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlobwarsPlayer {
+    /// The [blue player](Player::Blue)
+    Blue = 0,
+
+    /// The [red player](Player::Red)
+    Red = 1,
+}
+
+impl BlobwarsPlayer {
+    /// Read a player passed as an integer
+    fn read(value: c_int) -> Result<Player, BlobwarsStatus> {
+        match value {
+            0 => Ok(Player::Blue),
+            1 => Ok(Player::Red),
+            _ => Err(BlobwarsStatus::InvalidPlayer),
+        }
+    }
+}
+
+/// The state of a [cell](CellState)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlobwarsCell {
+    /// The cell is [free](CellState::Free)
+    Free = 0,
+
+    /// The cell is [restricted](CellState::Restricted), or out of the board
+    Restricted = 1,
+
+    /// The cell is occupied by the [blue player](Player::Blue)
+    Blue = 2,
+
+    /// The cell is occupied by the [red player](Player::Red)
+    Red = 3,
+}
+
+impl From<CellState> for BlobwarsCell {
+    fn from(value: CellState) -> Self {
+        match value {
+            CellState::Free => Self::Free,
+            CellState::Restricted => Self::Restricted,
+            CellState::Player(Player::Blue) => Self::Blue,
+            CellState::Player(Player::Red) => Self::Red,
+        }
+    }
+}
+
+/// A [personality](Personality) of the computer opponent
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlobwarsPersonality {
+    /// See [`Personality::Aggressive`]
+    Aggressive = 0,
+
+    /// See [`Personality::Territorial`]
+    Territorial = 1,
+
+    /// See [`Personality::Defensive`]
+    Defensive = 2,
+
+    /// See [`Personality::Chaotic`]
+    Chaotic = 3,
+}
+
+impl BlobwarsPersonality {
+    /// Read a personality passed as an integer
+    fn read(value: c_int) -> Result<Personality, BlobwarsStatus> {
+        match value {
+            0 => Ok(Personality::Aggressive),
+            1 => Ok(Personality::Territorial),
+            2 => Ok(Personality::Defensive),
+            3 => Ok(Personality::Chaotic),
+            _ => Err(BlobwarsStatus::InvalidPersonality),
+        }
+    }
+}
+
+/// A [difficulty level](Difficulty) of the computer opponent
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlobwarsDifficulty {
+    /// See [`Difficulty::Easy`]
+    Easy = 0,
+
+    /// See [`Difficulty::Normal`]
+    Normal = 1,
+
+    /// See [`Difficulty::Hard`]
+    Hard = 2,
+}
+
+impl BlobwarsDifficulty {
+    /// Read a difficulty level passed as an integer
+    fn read(value: c_int) -> Result<Difficulty, BlobwarsStatus> {
+        match value {
+            0 => Ok(Difficulty::Easy),
+            1 => Ok(Difficulty::Normal),
+            2 => Ok(Difficulty::Hard),
+            _ => Err(BlobwarsStatus::InvalidDifficulty),
+        }
+    }
+}
+
+/// A [move](Move) of a _blob_
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BlobwarsMove {
+    /// The row of the departure point
+    pub from_row: usize,
+
+    /// The column of the departure point
+    pub from_column: usize,
+
+    /// The row of the destination point
+    pub to_row: usize,
+
+    /// The column of the destination point
+    pub to_column: usize,
+}
+
+impl From<Move> for BlobwarsMove {
+    fn from(value: Move) -> Self {
+        Self {
+            from_row: value.from.0,
+            from_column: value.from.1,
+            to_row: value.to.0,
+            to_column: value.to.1,
+        }
+    }
+}
+
+impl From<BlobwarsMove> for Move {
+    fn from(value: BlobwarsMove) -> Self {
+        Self {
+            from: (value.from_row, value.from_column),
+            to: (value.to_row, value.to_column),
+        }
+    }
+}
+
+/// An opaque [board](Board)
+pub struct BlobwarsBoard(Board);
+
+fn into_raw(board: Board) -> *mut BlobwarsBoard {
+    Box::into_raw(Box::new(BlobwarsBoard(board)))
+}
+
+/// Generate a new board
+///
+/// # Parameters
+///
+/// - `height` - The number of rows
+/// - `width` - The number of columns
+/// - `density` - The percentage of restricted cells
+/// - `seed` - The seed of the random generation
+/// - `board` - Where the board, to be released with [`blobwars_board_free()`], is written
+///
+/// # Return
+///
+/// [`BlobwarsStatus::Ok`], or [`BlobwarsStatus::InvalidSize`] and nothing is written
+///
+/// # Safety
+///
+/// `board` must point to a writable pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blobwars_board_generate(
+    height: usize,
+    width: usize,
+    density: u8,
+    seed: u64,
+    board: *mut *mut BlobwarsBoard,
+) -> BlobwarsStatus {
+    if height.checked_mul(width).is_none() {
+        return BlobwarsStatus::InvalidSize;
+    }
+
+    let generated = Board::generate(height, width, density, &mut Rng::new(seed));
+
+    // SAFETY: the caller guarantees that `board` is writable.
+    unsafe { board.write(into_raw(generated)) };
+    BlobwarsStatus::Ok
+}
+
+/// Parse a board
+///
+/// The rows are separated by slashes and the cells are written `B`, `R`, `#` and `.`, as in `B.#/..R`.
+///
+/// # Parameter
+///
+/// - `text` - The board as a null-terminated string
+///
+/// # Return
+///
+/// The board, to be released with [`blobwars_board_free()`], or a null pointer if `text` is malformed
+///
+/// # Safety
+///
+/// `text` must be either null or a valid null-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blobwars_board_parse(text: *const c_char) -> *mut BlobwarsBoard {
+    if text.is_null() {
+        return ptr::null_mut();
+    }
+
+    // SAFETY: the caller guarantees that `text` is a valid null-terminated string.
+    let text = unsafe { CStr::from_ptr(text) };
+
+    text.to_str()
+        .ok()
+        .and_then(|text| text.parse().ok())
+        .map_or(ptr::null_mut(), into_raw)
+}
+
+/// Release a board
+///
+/// # Parameter
+///
+/// - `board` - The board; nothing is done if it is null
+///
+/// # Safety
+///
+/// `board` must be either null or a board returned by this library which has not been released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blobwars_board_free(board: *mut BlobwarsBoard) {
+    if !board.is_null() {
+        // SAFETY: the caller guarantees that `board` comes from `Box::into_raw()` and is released only once.
+        drop(unsafe { Box::from_raw(board) });
+    }
+}
+
+/// Get the number of rows of a board
+///
+/// # Safety
+///
+/// `board` must be a valid board returned by this library.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blobwars_board_height(board: *const BlobwarsBoard) -> usize {
+    // SAFETY: the caller guarantees that `board` is valid.
+    unsafe { &(*board).0 }.height()
+}
+
+/// Get the number of columns of a board
+///
+/// # Safety
+///
+/// `board` must be a valid board returned by this library.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blobwars_board_width(board: *const BlobwarsBoard) -> usize {
+    // SAFETY: the caller guarantees that `board` is valid.
+    unsafe { &(*board).0 }.width()
+}
+
+/// Get the state of a cell
+///
+/// The cells out of the board are reported as restricted.
+///
+/// # Safety
+///
+/// `board` must be a valid board returned by this library.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blobwars_board_get(
+    board: *const BlobwarsBoard,
+    row: usize,
+    column: usize,
+) -> BlobwarsCell {
+    // SAFETY: the caller guarantees that `board` is valid.
+    let board = unsafe { &(*board).0 };
+
+    board
+        .get(row, column)
+        .map_or(BlobwarsCell::Restricted, BlobwarsCell::from)
+}
+
+/// Get the number of _blobs_ of a player
+///
+/// # Parameters
+///
+/// - `board` - The board
+/// - `player` - The player, as a [`BlobwarsPlayer`]
+/// - `score` - Where the number of _blobs_ is written
+///
+/// # Return
+///
+/// [`BlobwarsStatus::Ok`], or [`BlobwarsStatus::InvalidPlayer`] and nothing is written
+///
+/// # Safety
+///
+/// `board` must be a valid board returned by this library and `score` must point to a writable `size_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blobwars_board_score(
+    board: *const BlobwarsBoard,
+    player: c_int,
+    score: *mut usize,
+) -> BlobwarsStatus {
+    let player = match BlobwarsPlayer::read(player) {
+        Ok(player) => player,
+        Err(status) => return status,
+    };
+
+    // SAFETY: the caller guarantees that `board` is valid and that `score` is writable.
+    unsafe { score.write((*board).0.score().get(player)) };
+    BlobwarsStatus::Ok
+}
+
+/// Play a move
+///
+/// # Parameters
+///
+/// - `board` - The board
+/// - `player` - The player moving one of their _blobs_, as a [`BlobwarsPlayer`]
+/// - `chosen` - The move
+///
+/// # Return
+///
+/// [`BlobwarsStatus::Ok`] if the move has been played; otherwise, [`BlobwarsStatus::IllegalMove`] or [`BlobwarsStatus::InvalidPlayer`] and the board is left unchanged.
+///
+/// # Safety
+///
+/// `board` must be a valid board returned by this library.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blobwars_board_play(
+    board: *mut BlobwarsBoard,
+    player: c_int,
+    chosen: BlobwarsMove,
+) -> BlobwarsStatus {
+    let player = match BlobwarsPlayer::read(player) {
+        Ok(player) => player,
+        Err(status) => return status,
+    };
+
+    // SAFETY: the caller guarantees that `board` is valid.
+    let board = unsafe { &mut (*board).0 };
+    let Move { from, to } = chosen.into();
+
+    if board.get(from.0, from.1) == Some(CellState::Player(player)) && board.jump(from, to) {
+        BlobwarsStatus::Ok
+    } else {
+        BlobwarsStatus::IllegalMove
+    }
+}
+
+/// List the legal moves of a player
+///
+/// # Parameters
+///
+/// - `board` - The board
+/// - `player` - The player, as a [`BlobwarsPlayer`]
+/// - `moves` - The array the moves are written to; it may be null if `capacity` is zero
+/// - `capacity` - The length of `moves`
+/// - `count` - Where the number of legal moves is written; it may exceed `capacity`, only the first `capacity` moves being written
+///
+/// # Return
+///
+/// [`BlobwarsStatus::Ok`], or [`BlobwarsStatus::InvalidPlayer`] and nothing is written
+///
+/// # Safety
+///
+/// `board` must be a valid board returned by this library, `moves` must point to at least `capacity` writable moves and `count` must point to a writable `size_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blobwars_board_moves(
+    board: *const BlobwarsBoard,
+    player: c_int,
+    moves: *mut BlobwarsMove,
+    capacity: usize,
+    count: *mut usize,
+) -> BlobwarsStatus {
+    let player = match BlobwarsPlayer::read(player) {
+        Ok(player) => player,
+        Err(status) => return status,
+    };
+
+    // SAFETY: the caller guarantees that `board` is valid.
+    let board = unsafe { &(*board).0 };
+    let mut legal = 0;
+
+    for chosen in board.moves(player) {
+        if legal < capacity {
+            // SAFETY: the caller guarantees that `moves` holds at least `capacity` moves.
+            unsafe { moves.add(legal).write(chosen.into()) };
+        }
+
+        legal += 1;
+    }
+
+    // SAFETY: the caller guarantees that `count` is writable.
+    unsafe { count.write(legal) };
+    BlobwarsStatus::Ok
+}
+
+/// Choose the move of the computer opponent
+///
+/// # Parameters
+///
+/// - `board` - The board
+/// - `player` - The player controlled by the computer opponent, as a [`BlobwarsPlayer`]
+/// - `personality` - The personality of the computer opponent, as a [`BlobwarsPersonality`]
+/// - `difficulty` - The difficulty level of the computer opponent, as a [`BlobwarsDifficulty`]
+/// - `seed` - The seed used to pick among the best moves
+/// - `chosen` - Where the chosen move is written
+///
+/// # Return
+///
+/// [`BlobwarsStatus::Ok`] if a move has been chosen, [`BlobwarsStatus::Pass`] if the player has to pass, or the status reporting an invalid argument; the move is only written in the first case.
+///
+/// # Safety
+///
+/// `board` must be a valid board returned by this library and `chosen` must point to a writable move.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn blobwars_best_move(
+    board: *const BlobwarsBoard,
+    player: c_int,
+    personality: c_int,
+    difficulty: c_int,
+    seed: u64,
+    chosen: *mut BlobwarsMove,
+) -> BlobwarsStatus {
+    let arguments = BlobwarsPlayer::read(player).and_then(|player| {
+        Ok((
+            player,
+            BlobwarsPersonality::read(personality)?,
+            BlobwarsDifficulty::read(difficulty)?,
+        ))
+    });
+
+    let (player, personality, difficulty) = match arguments {
+        Ok(arguments) => arguments,
+        Err(status) => return status,
+    };
+
+    // SAFETY: the caller guarantees that `board` is valid.
+    let board = unsafe { &(*board).0 };
+
+    let best = Ai::with_seed(personality, seed)
+        .with_difficulty(difficulty)
+        .choose(board, player);
+
+    if let Some(best) = best {
+        // SAFETY: the caller guarantees that `chosen` is writable.
+        unsafe { chosen.write(best.into()) };
+        BlobwarsStatus::Ok
+    } else {
+        BlobwarsStatus::Pass
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn play() {
+        let board = unsafe { blobwars_board_parse(c"R.B".as_ptr()) };
+        assert!(!board.is_null());
+
+        let (blue, red) = (BlobwarsPlayer::Blue as c_int, BlobwarsPlayer::Red as c_int);
+
+        let mut moves = [BlobwarsMove::default(); 4];
+        let mut count = 0;
+
+        pretty_assertions::assert_eq!(
+            unsafe { blobwars_board_moves(board, red, moves.as_mut_ptr(), 4, &mut count) },
+            BlobwarsStatus::Ok
+        );
+        pretty_assertions::assert_eq!(count, 1);
+
+        let mut chosen = BlobwarsMove::default();
+
+        pretty_assertions::assert_eq!(
+            unsafe {
+                blobwars_best_move(
+                    board,
+                    red,
+                    BlobwarsPersonality::Aggressive as c_int,
+                    BlobwarsDifficulty::Hard as c_int,
+                    0,
+                    &mut chosen,
+                )
+            },
+            BlobwarsStatus::Ok
+        );
+        pretty_assertions::assert_eq!(chosen, moves[0]);
+
+        pretty_assertions::assert_eq!(
+            unsafe { blobwars_board_play(board, blue, chosen) },
+            BlobwarsStatus::IllegalMove
+        );
+        pretty_assertions::assert_eq!(
+            unsafe { blobwars_board_play(board, red, chosen) },
+            BlobwarsStatus::Ok
+        );
+
+        pretty_assertions::assert_eq!(
+            unsafe { blobwars_board_get(board, 0, 2) },
+            BlobwarsCell::Red
+        );
+
+        let mut score = 0;
+
+        pretty_assertions::assert_eq!(
+            unsafe { blobwars_board_score(board, red, &mut score) },
+            BlobwarsStatus::Ok
+        );
+        pretty_assertions::assert_eq!(score, 3);
+
+        pretty_assertions::assert_eq!(
+            unsafe { blobwars_best_move(board, red, 0, 0, 0, &mut chosen) },
+            BlobwarsStatus::Pass
+        );
+
+        unsafe { blobwars_board_free(board) };
+        assert!(unsafe { blobwars_board_parse(c"R?B".as_ptr()) }.is_null());
+    }
+
+    #[test]
+    fn generate() {
+        let mut board = ptr::null_mut();
+
+        pretty_assertions::assert_eq!(
+            unsafe { blobwars_board_generate(usize::MAX, 2, 0, 0, &mut board) },
+            BlobwarsStatus::InvalidSize
+        );
+        assert!(board.is_null());
+
+        pretty_assertions::assert_eq!(
+            unsafe { blobwars_board_generate(3, 4, 0, 0, &mut board) },
+            BlobwarsStatus::Ok
+        );
+        pretty_assertions::assert_eq!(
+            unsafe { (blobwars_board_height(board), blobwars_board_width(board)) },
+            (3, 4)
+        );
+
+        unsafe { blobwars_board_free(board) };
+    }
+
+    #[test]
+    fn invalid() {
+        let board = unsafe { blobwars_board_parse(c"R.B".as_ptr()) };
+        let (mut chosen, mut count) = (BlobwarsMove::default(), 7);
+
+        pretty_assertions::assert_eq!(
+            unsafe { blobwars_board_moves(board, 2, ptr::null_mut(), 0, &mut count) },
+            BlobwarsStatus::InvalidPlayer
+        );
+        pretty_assertions::assert_eq!(count, 7);
+
+        pretty_assertions::assert_eq!(
+            unsafe { blobwars_board_score(board, -1, &mut count) },
+            BlobwarsStatus::InvalidPlayer
+        );
+        pretty_assertions::assert_eq!(
+            unsafe { blobwars_board_play(board, 42, chosen) },
+            BlobwarsStatus::InvalidPlayer
+        );
+        pretty_assertions::assert_eq!(
+            unsafe { blobwars_best_move(board, 1, 4, 0, 0, &mut chosen) },
+            BlobwarsStatus::InvalidPersonality
+        );
+        pretty_assertions::assert_eq!(
+            unsafe { blobwars_best_move(board, 1, 0, 3, 0, &mut chosen) },
+            BlobwarsStatus::InvalidDifficulty
+        );
+        pretty_assertions::assert_eq!(chosen, BlobwarsMove::default());
+
+        unsafe { blobwars_board_free(board) };
+    }
+}
@@ -1,11 +1,22 @@
 //! The implementation of [`Board`] and its associated types
 
-use ratatui::{style::Color, text::Span};
-use ratatui_macros::span;
+use crate::rng::Rng;
 
-use std::{
-    fmt::{Display, Formatter},
-    ops::Neg,
+use smallvec::{SmallVec, smallvec};
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+
+use core::{
+    cmp::Ordering,
+    fmt::{Debug, Display, Formatter},
+    ops::{Index as Indexing, IndexMut, Neg, RangeInclusive},
+    str::FromStr,
 };
 
 /// An enumeration reprensenting the state of a cell
@@ -74,12 +85,41 @@ impl CellState {
     }
 }
 
-impl From<CellState> for Color {
-    fn from(value: CellState) -> Self {
-        match value {
-            CellState::Player(player) => player.into(),
-            CellState::Free => Self::default(),
-            CellState::Restricted => Self::Rgb(0xff, 0xa5, 0x00),
+/// A power-up placed on a free cell
+///
+/// A power-up grants a one-shot effect to the player whose _blob_ lands on its cell, and disappears once captured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PowerUp {
+    /// The _blobs_ of the opponent up to two cells away are captured, instead of the adjacent ones only
+    DoubleSpread,
+
+    /// The _blob_ is shielded: the next capture or destruction of its cell by the opponent fails and consumes the shield
+    Shield,
+
+    /// The adjacent _blobs_ of the opponent are captured as usual and the ones two cells away are destroyed
+    Bomb,
+}
+
+impl PowerUp {
+    /// All the power-ups
+    pub const ALL: [Self; 3] = [Self::DoubleSpread, Self::Shield, Self::Bomb];
+
+    /// Get the symbol of the power-up in the [notation of the maps](Board#impl-Display-for-Board)
+    pub fn symbol(self) -> char {
+        match self {
+            Self::DoubleSpread => '+',
+            Self::Shield => '@',
+            Self::Bomb => '*',
+        }
+    }
+}
+
+impl Display for PowerUp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::DoubleSpread => write!(f, "Double spread"),
+            Self::Shield => write!(f, "Shield"),
+            Self::Bomb => write!(f, "Bomb"),
         }
     }
 }
@@ -94,17 +134,8 @@ pub enum Player {
     Red,
 }
 
-impl From<Player> for Color {
-    fn from(value: Player) -> Self {
-        match value {
-            Player::Blue => Color::Blue,
-            Player::Red => Color::Red,
-        }
-    }
-}
-
 impl Display for Player {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Blue => write!(f, "Blue"),
             Self::Red => write!(f, "Red"),
@@ -112,9 +143,25 @@ impl Display for Player {
     }
 }
 
-impl From<Player> for Span<'static> {
-    fn from(value: Player) -> Self {
-        span!(Color::from(value); value)
+impl FromStr for Player {
+    type Err = &'static str;
+
+    /// Parse a player from its name
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::Player;
+    ///
+    /// assert_eq!("Red".parse(), Ok(Player::Red));
+    /// assert!("Green".parse::<Player>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Blue" => Ok(Self::Blue),
+            "Red" => Ok(Self::Red),
+            _ => Err("The name is not recognized as a valid player"),
+        }
     }
 }
 
@@ -133,6 +180,85 @@ impl Neg for Player {
 /// An index of the [`Board`]
 pub type Index = (usize, usize);
 
+/// Write a column in algebraic notation
+///
+/// The columns are written as letters, the ones beyond the 26th with several letters as in spreadsheets (`z`, `aa`, `ab`, …).
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::game::display_column;
+///
+/// assert_eq!(display_column(0), "a");
+/// assert_eq!(display_column(25), "z");
+/// assert_eq!(display_column(26), "aa");
+/// assert_eq!(display_column(29), "ad");
+/// ```
+pub fn display_column(column: usize) -> String {
+    let mut letters = Vec::new();
+    let mut rest = column + 1;
+
+    while rest > 0 {
+        rest -= 1;
+        letters.push((b'a' + (rest % 26) as u8) as char);
+        rest /= 26;
+    }
+
+    letters.iter().rev().collect()
+}
+
+/// Write a [position](Index) in algebraic notation
+///
+/// The column is written as [letters](display_column) and the row as a number starting from 1.
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::game::display_index;
+///
+/// assert_eq!(display_index((0, 0)), "a1");
+/// assert_eq!(display_index((9, 2)), "c10");
+/// assert_eq!(display_index((0, 29)), "ad1");
+/// ```
+pub fn display_index((row, column): Index) -> String {
+    format!("{}{}", display_column(column), row + 1)
+}
+
+/// Read a [position](Index) written in algebraic notation
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::game::parse_index;
+///
+/// assert_eq!(parse_index("c10"), Ok((9, 2)));
+/// assert_eq!(parse_index("ad1"), Ok((0, 29)));
+/// assert!(parse_index("10c").is_err());
+/// ```
+pub fn parse_index(s: &str) -> Result<Index, &'static str> {
+    let split = s
+        .find(|c: char| !c.is_ascii_lowercase())
+        .filter(|&split| split > 0)
+        .ok_or("A position is malformed")?;
+
+    let column = s[..split]
+        .bytes()
+        .try_fold(0usize, |column, letter| {
+            column
+                .checked_mul(26)?
+                .checked_add(usize::from(letter - b'a') + 1)
+        })
+        .ok_or("A position is malformed")?;
+
+    let row = s[split..]
+        .parse::<usize>()
+        .ok()
+        .filter(|&row| row > 0)
+        .ok_or("A position is malformed")?;
+
+    Ok((row - 1, column - 1))
+}
+
 /// The score of a [game](Board)
 #[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
 pub struct Score {
@@ -141,6 +267,12 @@ pub struct Score {
 
     /// The score of the [blue player](Player::Blue)
     pub blue: usize,
+
+    /// The points scored by the [red player](Player::Red) on the [hill](Board::with_hill)
+    pub red_points: usize,
+
+    /// The points scored by the [blue player](Player::Blue) on the [hill](Board::with_hill)
+    pub blue_points: usize,
 }
 
 impl Score {
@@ -152,9 +284,9 @@ impl Score {
     /// use blobwars::game::{Player, Score};
     ///
     /// let mut score = Score::default();
-    /// assert_eq!(score, Score { blue: 0, red: 0 });
+    /// assert_eq!(score, Score { blue: 0, red: 0, ..Score::default() });
     /// *score.get_mut(Player::Blue) += 1;
-    /// assert_eq!(score, Score { blue: 1, red: 0 });
+    /// assert_eq!(score, Score { blue: 1, red: 0, ..Score::default() });
     /// ```
     pub fn get_mut(&mut self, player: Player) -> &mut usize {
         match player {
@@ -162,15 +294,525 @@ impl Score {
             Player::Red => &mut self.red,
         }
     }
+
+    /// Get the score of the corresponding player
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{Player, Score};
+    ///
+    /// let score = Score { blue: 2, red: 3, ..Score::default() };
+    /// assert_eq!(score.get(Player::Blue), 2);
+    /// assert_eq!(score.get(Player::Red), 3);
+    /// ```
+    pub fn get(self, player: Player) -> usize {
+        match player {
+            Player::Blue => self.blue,
+            Player::Red => self.red,
+        }
+    }
+
+    /// Get the points scored on the [hill](Board::with_hill) by the corresponding player
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{Player, Score};
+    ///
+    /// let score = Score { blue_points: 4, ..Score::default() };
+    /// assert_eq!(score.points(Player::Blue), 4);
+    /// assert_eq!(score.points(Player::Red), 0);
+    /// ```
+    pub fn points(self, player: Player) -> usize {
+        match player {
+            Player::Blue => self.blue_points,
+            Player::Red => self.red_points,
+        }
+    }
+
+    /// Get the points scored on the [hill](Board::with_hill) by the corresponding player
+    pub fn points_mut(&mut self, player: Player) -> &mut usize {
+        match player {
+            Player::Blue => &mut self.blue_points,
+            Player::Red => &mut self.red_points,
+        }
+    }
+}
+
+/// The outcome of a finished [game](Board)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// The player has won
+    Win(Player),
+
+    /// Both players have the same score
+    Draw,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Win(player) => write!(f, "{player} wins"),
+            Self::Draw => write!(f, "Draw"),
+        }
+    }
+}
+
+/// A move of a _blob_ from one [position](Index) to another
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Move {
+    /// The departure [position](Index)
+    pub from: Index,
+
+    /// The destination [position](Index)
+    pub to: Index,
+}
+
+/// The topology of a [board](Board)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Topology {
+    /// The board is bounded by its edges
+    #[default]
+    Flat,
+
+    /// The opposite edges of the board are glued together, so that the _blobs_ may move and capture across them
+    Torus,
+}
+
+impl Topology {
+    /// All the topologies
+    pub const ALL: [Self; 2] = [Self::Flat, Self::Torus];
+}
+
+impl Display for Topology {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Flat => write!(f, "Flat"),
+            Self::Torus => write!(f, "Torus"),
+        }
+    }
+}
+
+impl FromStr for Topology {
+    type Err = &'static str;
+
+    /// Parse a topology from its name
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::Topology;
+    ///
+    /// assert_eq!("torus".parse(), Ok(Topology::Torus));
+    /// assert!("sphere".parse::<Topology>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|topology| topology.to_string().eq_ignore_ascii_case(s))
+            .ok_or("The topology is unknown")
+    }
+}
+
+/// The rules a [game](Board) is played with
+///
+/// The default rules are the usual ones: the _blobs_ spread onto the adjacent cells or jump two cells away,
+/// capture the adjacent _blobs_ of the opponent on a flat board and the player with the most _blobs_ wins.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RuleSet {
+    /// The maximum distance of a jump
+    ///
+    /// The _blobs_ are cloned onto the adjacent cells and jump onto the farther ones.
+    pub jump_distance: usize,
+
+    /// The radius of the square centered on the destination of a move in which the _blobs_ of the opponent are captured
+    pub conversion_radius: usize,
+
+    /// The [topology](Topology) of the board
+    pub topology: Topology,
+
+    /// Whether the game is played in _misère_, the player with the fewest _blobs_ at the end winning
+    pub misere: bool,
+
+    /// The number of moves after which the game is over, the passes not being counted
+    pub move_limit: Option<usize>,
+
+    /// The number of [points](Score::points) a player must score on the [hill](Board::with_hill) to win
+    pub goal: Option<usize>,
+}
+
+impl RuleSet {
+    /// The allowed jump distances
+    pub const JUMP_DISTANCES: RangeInclusive<usize> = 2..=4;
+
+    /// The allowed conversion radii
+    pub const CONVERSION_RADII: RangeInclusive<usize> = 1..=3;
+
+    /// The move limits offered by the settings
+    pub const MOVE_LIMITS: [Option<usize>; 4] = [None, Some(50), Some(100), Some(200)];
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), &'static str> {
+        let parse_limit =
+            |value: &str, error| value.parse().ok().filter(|&limit| limit > 0).ok_or(error);
+
+        match key {
+            "jump" => {
+                self.jump_distance = value
+                    .parse()
+                    .ok()
+                    .filter(|distance| Self::JUMP_DISTANCES.contains(distance))
+                    .ok_or("The jump distance is invalid")?
+            }
+
+            "radius" => {
+                self.conversion_radius = value
+                    .parse()
+                    .ok()
+                    .filter(|radius| Self::CONVERSION_RADII.contains(radius))
+                    .ok_or("The conversion radius is invalid")?
+            }
+
+            "topology" => self.topology = value.parse()?,
+            "misere" => self.misere = value.parse().map_err(|_| "The misère flag is invalid")?,
+            "limit" => self.move_limit = Some(parse_limit(value, "The move limit is invalid")?),
+            "goal" => self.goal = Some(parse_limit(value, "The goal is invalid")?),
+            _ => return Err("The rules contain an unknown option"),
+        }
+
+        Ok(())
+    }
+
+    fn code(self) -> u64 {
+        [
+            self.jump_distance as u64,
+            self.conversion_radius as u64,
+            self.topology as u64,
+            self.misere as u64,
+            self.move_limit.map_or(0, |limit| limit as u64 + 1),
+            self.goal.map_or(0, |goal| goal as u64 + 1),
+        ]
+        .into_iter()
+        .fold(0, |code, field| code.wrapping_mul(0x100_0000_01b3) ^ field)
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self {
+            jump_distance: 2,
+            conversion_radius: 1,
+            topology: Topology::Flat,
+            misere: false,
+            move_limit: None,
+            goal: None,
+        }
+    }
+}
+
+impl Display for RuleSet {
+    /// Write the rules differing from the [default ones](RuleSet::default) as options separated by spaces
+    ///
+    /// The options are `jump=` for the jump distance, `radius=` for the conversion radius, `topology=`, `limit=` for the move limit,
+    /// `goal=` and `misere=true`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let default = Self::default();
+        let mut options = Vec::new();
+
+        if self.jump_distance != default.jump_distance {
+            options.push(format!("jump={}", self.jump_distance));
+        }
+
+        if self.conversion_radius != default.conversion_radius {
+            options.push(format!("radius={}", self.conversion_radius));
+        }
+
+        if self.topology != default.topology {
+            options.push(format!("topology={}", self.topology).to_lowercase());
+        }
+
+        if let Some(limit) = self.move_limit {
+            options.push(format!("limit={limit}"));
+        }
+
+        if let Some(goal) = self.goal {
+            options.push(format!("goal={goal}"));
+        }
+
+        if self.misere {
+            options.push("misere=true".into());
+        }
+
+        write!(f, "{}", options.join(" "))
+    }
+}
+
+impl FromStr for RuleSet {
+    type Err = &'static str;
+
+    /// Parse rules written as [displayed](RuleSet#impl-Display-for-RuleSet)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{RuleSet, Topology};
+    ///
+    /// let rules: RuleSet = "jump=3 topology=torus misere=true".parse().unwrap();
+    ///
+    /// assert_eq!(rules.jump_distance, 3);
+    /// assert_eq!(rules.topology, Topology::Torus);
+    /// assert!(rules.misere);
+    /// assert_eq!(rules.to_string(), "jump=3 topology=torus misere=true");
+    /// assert!("jump=1".parse::<RuleSet>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = Self::default();
+
+        for option in s.split_whitespace() {
+            let (key, value) = option
+                .split_once('=')
+                .ok_or("The rules contain an unknown option")?;
+
+            rules.set(key, value)?;
+        }
+
+        Ok(rules)
+    }
+}
+
+/// The neighborhoods of the cells of a board, computed once per geometry and shared by the copies of the board
+///
+/// The neighborhoods of all the cells for a given radius are stored one after the other, the cells being numbered row by row.
+#[derive(Clone, Debug, Default)]
+struct Geometry {
+    height: usize,
+    width: usize,
+    topology: Topology,
+    radius: usize,
+
+    /// The neighborhoods for each radius from `1` to `radius`
+    tables: Vec<Neighborhoods>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Neighborhoods {
+    /// The start of the neighborhood of each cell in `cells`, followed by the total length
+    starts: Vec<usize>,
+    cells: Vec<usize>,
+}
+
+impl Geometry {
+    /// Compute the neighborhoods needed by the moves and the captures under the given [rules](RuleSet)
+    fn new(height: usize, width: usize, rules: RuleSet) -> Self {
+        let radius = Self::radius(rules);
+        let torus = rules.topology == Topology::Torus;
+
+        let tables = (1..=radius)
+            .map(|radius| {
+                let mut table = Neighborhoods {
+                    starts: Vec::with_capacity(height * width + 1),
+                    cells: Vec::new(),
+                };
+
+                table.starts.push(0);
+
+                for i in 0..height {
+                    for j in 0..width {
+                        table.cells.extend(
+                            area((i, j), radius, height, width, torus).map(|(k, l)| k * width + l),
+                        );
+
+                        table.starts.push(table.cells.len());
+                    }
+                }
+
+                table
+            })
+            .collect();
+
+        Self {
+            height,
+            width,
+            topology: rules.topology,
+            radius,
+            tables,
+        }
+    }
+
+    /// Get the largest radius needed: the jump distance or the radius of the captures boosted by a [power-up](PowerUp)
+    fn radius(rules: RuleSet) -> usize {
+        rules.jump_distance.max(rules.conversion_radius + 1)
+    }
+
+    /// Check if the neighborhoods match a board and its [rules](RuleSet)
+    fn fits(&self, height: usize, width: usize, rules: RuleSet) -> bool {
+        self.height == height
+            && self.width == width
+            && self.topology == rules.topology
+            && self.radius == Self::radius(rules)
+    }
+
+    /// Get the cells of the neighborhood of a cell, including the cell itself
+    ///
+    /// If the radius has not been computed, `None` is returned.
+    fn neighborhood(&self, cell: usize, radius: usize) -> Option<&[usize]> {
+        let table = self.tables.get(radius.checked_sub(1)?)?;
+        Some(&table.cells[table.starts[cell]..table.starts[cell + 1]])
+    }
+}
+
+/// The neighborhoods only depend on the geometry they are computed for.
+impl PartialEq for Geometry {
+    fn eq(&self, other: &Self) -> bool {
+        (self.height, self.width, self.topology, self.radius)
+            == (other.height, other.width, other.topology, other.radius)
+    }
+}
+
+impl Eq for Geometry {}
+
+/// The number of [moves](Move) a [`MoveList`] holds without allocating
+pub(crate) const INLINE_MOVES: usize = 64;
+
+/// A list of [moves](Move), stored inline unless the position has more legal moves than most positions
+pub(crate) type MoveList = SmallVec<[Move; INLINE_MOVES]>;
+
+/// The cells of a board, numbered row by row
+///
+/// The cells are stored in chunks shared by the copies of the board: a chunk is only copied when one of its cells is written,
+/// so that copying a board costs a reference count per chunk.
+#[derive(Clone, Default)]
+struct Cells {
+    chunks: Vec<Arc<[CellState; Cells::CHUNK]>>,
+    len: usize,
+}
+
+impl Cells {
+    /// The number of cells in a chunk
+    const CHUNK: usize = 16;
+
+    fn new(cells: Vec<CellState>) -> Self {
+        let chunks = cells
+            .chunks(Self::CHUNK)
+            .map(|cells| {
+                let mut chunk = [CellState::Free; Self::CHUNK];
+                chunk[..cells.len()].copy_from_slice(cells);
+                Arc::new(chunk)
+            })
+            .collect();
+
+        Self {
+            chunks,
+            len: cells.len(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> impl Iterator<Item = CellState> {
+        self.chunks
+            .iter()
+            .flat_map(|chunk| chunk.iter().copied())
+            .take(self.len)
+    }
+}
+
+impl Indexing<usize> for Cells {
+    type Output = CellState;
+
+    fn index(&self, cell: usize) -> &CellState {
+        &self.chunks[cell / Self::CHUNK][cell % Self::CHUNK]
+    }
+}
+
+impl IndexMut<usize> for Cells {
+    fn index_mut(&mut self, cell: usize) -> &mut CellState {
+        &mut Arc::make_mut(&mut self.chunks[cell / Self::CHUNK])[cell % Self::CHUNK]
+    }
+}
+
+impl Debug for Cells {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// The cells beyond the end of the last chunk are always free.
+impl PartialEq for Cells {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .chunks
+                .iter()
+                .zip(&other.chunks)
+                .all(|(chunk, other)| Arc::ptr_eq(chunk, other) || chunk == other)
+    }
+}
+
+impl Eq for Cells {}
+
+/// The changes made by the [moves](Board::make_move) played on a [board](Board), to [undo](Board::unmake_move) them
+///
+/// The buffers are kept when the moves are undone, so that a search playing and undoing moves does not allocate once they have grown.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct History {
+    frames: Vec<Frame>,
+    cells: Vec<(usize, CellState)>,
+    shields: Vec<(Index, bool)>,
+}
+
+impl History {
+    /// Get the number of moves which can be undone
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Check if there is no move to undo
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Forget the recorded moves, keeping the buffers
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.cells.clear();
+        self.shields.clear();
+    }
+}
+
+/// The changes made by a single move
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Frame {
+    /// The number of changed cells recorded before the move
+    cells: usize,
+
+    /// The number of shield changes recorded before the move, a change being `true` when a shield is added
+    shields: usize,
+
+    /// The [power-up](PowerUp) consumed by the move
+    power_up: Option<(Index, PowerUp)>,
+
+    score: Score,
+    played: usize,
 }
 
 /// A game board
+///
+/// Copying a board is cheap: its cells are shared with the copy until they are written, a few at a time.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Board {
-    board: Vec<CellState>,
+    board: Cells,
     score: Score,
     height: usize,
     width: usize,
+    power_ups: Vec<(Index, PowerUp)>,
+    shields: Vec<Index>,
+    hill: Vec<Index>,
+    rules: RuleSet,
+    played: usize,
+    geometry: Arc<Geometry>,
 }
 
 impl Board {
@@ -184,10 +826,16 @@ impl Board {
         let board = vec![CellState::Free; height * width];
 
         Self {
-            board,
+            board: Cells::new(board),
             score: Score::default(),
             height,
             width,
+            power_ups: Vec::new(),
+            shields: Vec::new(),
+            hill: Vec::new(),
+            rules: RuleSet::default(),
+            played: 0,
+            geometry: Arc::new(Geometry::new(height, width, RuleSet::default())),
         }
     }
 
@@ -220,18 +868,359 @@ impl Board {
         });
 
         Self {
-            board,
+            board: Cells::new(board),
             score,
             height,
             width,
+            power_ups: Vec::new(),
+            shields: Vec::new(),
+            hill: Vec::new(),
+            rules: RuleSet::default(),
+            played: 0,
+            geometry: Arc::new(Geometry::new(height, width, RuleSet::default())),
         }
     }
 
+    /// Place [power-ups](PowerUp) on the board
+    ///
+    /// The power-ups placed outside the board or on cells that are not free are ignored.
+    ///
+    /// # Parameter
+    ///
+    /// - `power_ups` - The [positions](Index) of the power-ups
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{Board, PowerUp};
+    ///
+    /// let board: Board = "R..B".parse().unwrap();
+    /// let board = board.with_power_ups([((0, 1), PowerUp::Bomb), ((0, 3), PowerUp::Shield)]);
+    ///
+    /// assert_eq!(board.power_up(0, 1), Some(PowerUp::Bomb));
+    /// assert_eq!(board.power_up(0, 3), None);
+    /// assert_eq!(board.to_string(), "R*.B");
+    /// ```
+    pub fn with_power_ups(mut self, power_ups: impl IntoIterator<Item = (Index, PowerUp)>) -> Self {
+        for (index @ (i, j), power_up) in power_ups {
+            if self.get(i, j) == Some(CellState::Free) {
+                match self
+                    .power_ups
+                    .binary_search_by_key(&index, |&(index, _)| index)
+                {
+                    Ok(position) => self.power_ups[position].1 = power_up,
+                    Err(position) => self.power_ups.insert(position, (index, power_up)),
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Get the [power-up](PowerUp) placed on a cell
+    ///
+    /// # Parameters
+    ///
+    /// - `row` - The row index
+    /// - `column` - The column index
+    pub fn power_up(&self, row: usize, column: usize) -> Option<PowerUp> {
+        self.power_ups
+            .binary_search_by_key(&(row, column), |&(index, _)| index)
+            .ok()
+            .map(|position| self.power_ups[position].1)
+    }
+
+    /// Get an [iterator](Iterator) over the [power-ups](PowerUp) left on the board
+    pub fn power_ups(&self) -> impl Iterator<Item = (Index, PowerUp)> {
+        self.power_ups.iter().copied()
+    }
+
+    /// Check if the _blob_ of a cell is protected by a [shield](PowerUp::Shield)
+    ///
+    /// # Parameters
+    ///
+    /// - `row` - The row index
+    /// - `column` - The column index
+    pub fn is_shielded(&self, row: usize, column: usize) -> bool {
+        self.shields.binary_search(&(row, column)).is_ok()
+    }
+
+    /// Mark the scoring cells of the _king of the hill_ variant
+    ///
+    /// After each of their moves, a player scores a [point](Score::points) per scoring cell occupied by one of their _blobs_.
+    /// The cells outside the board are ignored.
+    ///
+    /// # Parameter
+    ///
+    /// - `hill` - The [positions](Index) of the scoring cells
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{Board, Player};
+    ///
+    /// let board: Board = "R..B".parse().unwrap();
+    /// let mut board = board.with_hill([(0, 1), (0, 2)]);
+    ///
+    /// board.jump((0, 0), (0, 1));
+    /// assert_eq!(board.score().points(Player::Red), 1);
+    /// assert_eq!(board.to_string(), "RR.B hill=b1;c1 points=0;1");
+    /// ```
+    pub fn with_hill(mut self, hill: impl IntoIterator<Item = Index>) -> Self {
+        let hill = hill
+            .into_iter()
+            .filter(|&(i, j)| self.contains(i, j))
+            .collect::<Vec<_>>();
+
+        self.hill.extend(hill);
+        self.hill.sort_unstable();
+        self.hill.dedup();
+        self
+    }
+
+    /// Check if a cell belongs to the [hill](Self::with_hill)
+    ///
+    /// # Parameters
+    ///
+    /// - `row` - The row index
+    /// - `column` - The column index
+    pub fn is_hill(&self, row: usize, column: usize) -> bool {
+        self.hill.binary_search(&(row, column)).is_ok()
+    }
+
+    /// Get an [iterator](Iterator) over the scoring cells of the [hill](Self::with_hill)
+    pub fn hill(&self) -> impl Iterator<Item = Index> {
+        self.hill.iter().copied()
+    }
+
+    /// Set the [rules](RuleSet) the game is played with
+    ///
+    /// # Parameter
+    ///
+    /// - `rules` - The new rules
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{Board, Outcome, Player, RuleSet};
+    ///
+    /// let board: Board = "RRB".parse().unwrap();
+    /// assert_eq!(board.outcome(), Some(Outcome::Win(Player::Red)));
+    ///
+    /// let board = board.with_rules(RuleSet { misere: true, ..RuleSet::default() });
+    /// assert_eq!(board.outcome(), Some(Outcome::Win(Player::Blue)));
+    /// assert_eq!(board.to_string(), "RRB misere=true");
+    /// ```
+    pub fn with_rules(self, rules: RuleSet) -> Self {
+        let geometry = if self.geometry.fits(self.height, self.width, rules) {
+            self.geometry
+        } else {
+            Arc::new(Geometry::new(self.height, self.width, rules))
+        };
+
+        Self {
+            rules,
+            geometry,
+            ..self
+        }
+    }
+
+    /// Get the [rules](RuleSet) the game is played with
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
+
+    /// Get the number of moves played so far
+    ///
+    /// The moves are only counted when the [rules](RuleSet::move_limit) limit them.
+    pub fn played(&self) -> usize {
+        self.played
+    }
+
+    /// Generate a random [`Board`]
+    ///
+    /// The [red player](Player::Red) starts in the top-left corner and the [blue player](Player::Blue) in the bottom-right one.
+    /// The restricted cells are placed symmetrically with respect to the center of the grid so that no player is advantaged.
+    ///
+    /// # Parameters
+    ///
+    /// - `height` - The height of the grid
+    /// - `width` - The width of the grid
+    /// - `density` - The percentage of restricted cells
+    /// - `rng` - The [random number generator](Rng) used to place the restricted cells
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Player::*},
+    ///     rng::Rng,
+    /// };
+    ///
+    /// let board = Board::generate(5, 6, 100, &mut Rng::new(42));
+    ///
+    /// assert_eq!(board.get(0, 0), Some(Player(Red)));
+    /// assert_eq!(board.get(4, 5), Some(Player(Blue)));
+    /// assert_eq!(board.iter().filter(|cell| cell.is_restricted()).count(), 28);
+    /// ```
+    pub fn generate(height: usize, width: usize, density: u8, rng: &mut Rng) -> Self {
+        let len = height * width;
+        let mut board = vec![CellState::Free; len];
+
+        for index in 0..len.div_ceil(2) {
+            if rng.next_f64() * 100. < density as f64 {
+                board[index] = CellState::Restricted;
+                board[len - 1 - index] = CellState::Restricted;
+            }
+        }
+
+        if len > 0 {
+            board[0] = CellState::Player(Player::Red);
+            board[len - 1] = CellState::Player(Player::Blue);
+        }
+
+        Self::new(height, width, board)
+    }
+
     /// Get the current [score](Score)
     pub fn score(&self) -> Score {
         self.score
     }
 
+    /// Get the _Zobrist_ hash of the board
+    ///
+    /// The hash is the exclusive or of a fixed random key per occupied or restricted cell, so that it can be updated incrementally and identifies a position in a bug report.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{Board, CellState::*, Player::*};
+    ///
+    /// let mut board = Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+    /// let before = board.zobrist();
+    ///
+    /// board.jump((0, 0), (0, 1));
+    /// assert_ne!(board.zobrist(), before);
+    /// assert_eq!(board.zobrist(), Board::new(1, 3, vec![Player(Red); 3]).zobrist());
+    /// ```
+    pub fn zobrist(&self) -> u64 {
+        self.board
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                let code = match cell {
+                    CellState::Free => return 0,
+                    CellState::Restricted => 1,
+                    CellState::Player(Player::Blue) => 2,
+                    CellState::Player(Player::Red) => 3,
+                };
+
+                zobrist_key(4 * index as u64 + code)
+            })
+            .chain(self.power_ups.iter().map(|&((i, j), power_up)| {
+                let code = match power_up {
+                    PowerUp::DoubleSpread => 0,
+                    PowerUp::Shield => 1,
+                    PowerUp::Bomb => 2,
+                };
+
+                zobrist_key(4 * (self.len() + i * self.width + j) as u64 + code)
+            }))
+            .chain(
+                self.shields
+                    .iter()
+                    .map(|&(i, j)| zobrist_key(4 * (self.len() + i * self.width + j) as u64 + 3)),
+            )
+            .chain(
+                self.hill.iter().map(|&(i, j)| {
+                    zobrist_key(8 * self.len() as u64 + (i * self.width + j) as u64)
+                }),
+            )
+            .chain(
+                [(self.score.blue_points, 0), (self.score.red_points, 1)]
+                    .into_iter()
+                    .filter(|&(points, _)| points > 0)
+                    .map(|(points, code)| zobrist_key(u64::MAX - 2 * points as u64 - code)),
+            )
+            .chain(
+                (self.rules != RuleSet::default())
+                    .then(|| zobrist_key((u64::MAX / 2) ^ self.rules.code())),
+            )
+            .chain((self.played > 0).then(|| zobrist_key(u64::MAX / 4 - self.played as u64)))
+            .fold(0, |hash, key| hash ^ key)
+    }
+
+    /// Get the number of free cells
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{Board, CellState::*, Player::*};
+    ///
+    /// let board = Board::new(1, 4, vec![Player(Red), Free, Restricted, Free]);
+    /// assert_eq!(board.free_cells(), 2);
+    /// ```
+    pub fn free_cells(&self) -> usize {
+        self.iter().filter(|cell| cell.is_free()).count()
+    }
+
+    /// Get the [outcome](Outcome) of the game
+    ///
+    /// The game is over when a player has no _blob_ anymore, when no player can play or when the [move limit](RuleSet::move_limit) is reached.
+    /// With a [goal](RuleSet::goal), it is also won by the first player who scores the goal on the [hill](Self::with_hill).
+    /// In [_misère_](RuleSet::misere), the player with the fewest _blobs_ wins.
+    ///
+    /// # Return
+    ///
+    /// If the game is not over, `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{Board, CellState::*, Outcome, Player::*};
+    ///
+    /// let board = Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+    /// assert_eq!(board.outcome(), None);
+    ///
+    /// let board = Board::new(1, 3, vec![Player(Red), Player(Red), Player(Blue)]);
+    /// assert_eq!(board.outcome(), Some(Outcome::Win(Red)));
+    ///
+    /// let board = Board::new(1, 3, vec![Player(Red), Free, Free]);
+    /// assert_eq!(board.outcome(), Some(Outcome::Win(Red)));
+    /// ```
+    pub fn outcome(&self) -> Option<Outcome> {
+        let Score { red, blue, .. } = self.score;
+
+        if let Some(goal) = self.rules.goal
+            && let Some(player) = [Player::Blue, Player::Red]
+                .into_iter()
+                .find(|&player| self.score.points(player) >= goal)
+        {
+            return Some(Outcome::Win(player));
+        }
+
+        let ordering = if self.rules.misere {
+            blue.cmp(&red)
+        } else {
+            red.cmp(&blue)
+        };
+
+        let limited = self
+            .rules
+            .move_limit
+            .is_some_and(|limit| self.played >= limit);
+
+        (red == 0
+            || blue == 0
+            || limited
+            || (!self.can_play(Player::Red) && !self.can_play(Player::Blue)))
+        .then_some(match ordering {
+            Ordering::Greater => Outcome::Win(Player::Red),
+            Ordering::Less => Outcome::Win(Player::Blue),
+            Ordering::Equal => Outcome::Draw,
+        })
+    }
+
     /// Get the height of the underlying grid
     pub fn height(&self) -> usize {
         self.height
@@ -268,11 +1257,13 @@ impl Board {
     /// assert_eq!(board.len(), 0);
     /// ```
     pub fn is_empty(&self) -> bool {
-        self.board.is_empty()
+        self.board.len() == 0
     }
 
     /// Get the distance between two positions
     ///
+    /// On a [torus](Topology::Torus), the distance is measured across the edges too.
+    ///
     /// # Parameters
     ///
     /// - `from` - The departure [position](Index)
@@ -280,7 +1271,7 @@ impl Board {
     ///
     /// # Return
     ///
-    /// This function returns the jump distance if the positions and the jump distance are valid; otherwise, `None` is returned.
+    /// This function returns the jump distance if the positions and the [jump distance](RuleSet::jump_distance) are valid; otherwise, `None` is returned.
     ///
     /// # Example
     ///
@@ -296,8 +1287,24 @@ impl Board {
     /// ```
     pub fn jump_distance(&self, from: Index, to: Index) -> Option<usize> {
         (self.contains(from.0, from.1) && self.contains(to.0, to.1))
-            .then(|| from.0.abs_diff(to.0).max(from.1.abs_diff(to.1)))
-            .filter(|distance| (1..=2).contains(distance))
+            .then(|| self.distance(from, to))
+            .filter(|distance| (1..=self.rules.jump_distance).contains(distance))
+    }
+
+    fn distance(&self, from: Index, to: Index) -> usize {
+        let torus = self.rules.topology == Topology::Torus;
+
+        let axis = |a: usize, b: usize, length: usize| {
+            let distance = a.abs_diff(b);
+
+            if torus {
+                distance.min(length - distance)
+            } else {
+                distance
+            }
+        };
+
+        axis(from.0, to.0, self.height).max(axis(from.1, to.1, self.width))
     }
 
     /// Check if the given index is valid
@@ -344,76 +1351,326 @@ impl Board {
     /// ```
     pub fn get(&self, row: usize, column: usize) -> Option<CellState> {
         if self.contains(row, column) {
-            self.board.get(row * self.width + column).copied()
+            Some(self.board[row * self.width + column])
         } else {
             None
         }
     }
 
-    fn get_mut(&mut self, row: usize, column: usize) -> Option<&mut CellState> {
-        if self.contains(row, column) {
-            self.board.get_mut(row * self.width + column)
+    /// Get an [iterator](Iterator) over the cells
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blobwars::game::{Board, CellState};
+    ///
+    /// let board = Board::free(2, 2);
+    /// let mut iterator = board.iter();
+    ///
+    /// assert_eq!(iterator.next(), Some(CellState::Free));
+    /// assert_eq!(iterator.next(), Some(CellState::Free));
+    /// assert_eq!(iterator.next(), Some(CellState::Free));
+    /// assert_eq!(iterator.next(), Some(CellState::Free));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = CellState> {
+        self.board.iter()
+    }
+
+    /// Get an [iterator](Iterator) over the neighborhood of a cell
+    ///
+    /// The neighborhood is the square of the given radius centered on the cell (which is included), wrapping around the edges on a [torus](Topology::Torus).
+    ///
+    /// # Parameters
+    ///
+    /// - `row` - The row index
+    /// - `column` - The column index
+    /// - `radius` - The radius of the neighborhood
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::Board;
+    ///
+    /// let board = Board::free(5, 5);
+    ///
+    /// assert_eq!(board.neighbors(2, 2, 1).count(), 9);
+    /// assert_eq!(board.neighbors(1, 0, 1).count(), 6);
+    /// assert_eq!(board.neighbors(0, 0, 2).count(), 9);
+    /// assert_eq!(board.neighbors(5, 0, 1).count(), 0);
+    /// ```
+    pub fn neighbors(
+        &self,
+        row: usize,
+        column: usize,
+        radius: usize,
+    ) -> impl Iterator<Item = CellState> {
+        let (table, computed) = if self.contains(row, column) {
+            match self
+                .geometry
+                .neighborhood(row * self.width + column, radius)
+            {
+                Some(table) => (table, Vec::new()),
+                None => {
+                    let torus = self.rules.topology == Topology::Torus;
+
+                    let computed = area((row, column), radius, self.height, self.width, torus)
+                        .map(|(i, j)| i * self.width + j)
+                        .collect();
+
+                    (&[][..], computed)
+                }
+            }
         } else {
-            None
-        }
+            (&[][..], Vec::new())
+        };
+
+        table
+            .iter()
+            .copied()
+            .chain(computed)
+            .map(|cell| self.board[cell])
     }
 
-    /// Get an [iterator](Iterator) over the cells
+    /// Get an [iterator](Iterator) over the legal [moves](Move) of a player
+    ///
+    /// # Parameter
+    ///
+    /// - `player` - The [player](Player) whose moves are generated
     ///
     /// # Example
     ///
+    /// ```rust
+    /// use blobwars::game::{Board, CellState::*, Move, Player::*};
+    ///
+    /// #[rustfmt::skip]
+    /// let board = vec![
+    ///     Player(Red), Free,       Free,
+    ///     Restricted,  Restricted, Free,
+    ///     Free,        Free,       Player(Blue),
+    /// ];
+    ///
+    /// let board = Board::new(3, 3, board);
+    /// let mut moves = board.moves(Red);
+    ///
+    /// assert_eq!(moves.next(), Some(Move { from: (0, 0), to: (0, 1) }));
+    /// assert_eq!(moves.next(), Some(Move { from: (0, 0), to: (0, 2) }));
+    /// assert_eq!(moves.next(), Some(Move { from: (0, 0), to: (1, 2) }));
+    /// assert_eq!(moves.next(), Some(Move { from: (0, 0), to: (2, 0) }));
+    /// assert_eq!(moves.next(), Some(Move { from: (0, 0), to: (2, 1) }));
+    /// assert_eq!(moves.next(), None);
     /// ```
-    /// use blobwars::game::{Board, CellState};
+    pub fn moves(&self, player: Player) -> impl Iterator<Item = Move> {
+        (0..self.height)
+            .flat_map(move |i| (0..self.width).map(move |j| (i, j)))
+            .filter(move |&(i, j)| self.get(i, j) == Some(CellState::Player(player)))
+            .flat_map(move |from| self.moves_from(from))
+    }
+
+    /// Get an [iterator](Iterator) over the legal [moves](Move) starting from a position
+    ///
+    /// # Parameter
+    ///
+    /// - `from` - The departure [position](Index)
+    ///
+    /// # Return
+    ///
+    /// If `from` is not occupied by a player, the returned iterator is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{Board, CellState::*, Move, Player::*};
+    ///
+    /// #[rustfmt::skip]
+    /// let board = vec![
+    ///     Player(Red), Restricted, Free,
+    ///     Restricted,  Restricted, Free,
+    ///     Restricted,  Restricted, Player(Blue),
+    /// ];
+    ///
+    /// let board = Board::new(3, 3, board);
+    ///
+    /// assert_eq!(board.moves_from((0, 0)).count(), 2);
+    /// assert_eq!(board.moves_from((2, 2)).count(), 2);
+    /// assert_eq!(board.moves_from((0, 2)).count(), 0);
+    /// ```
+    pub fn moves_from(&self, from @ (i, j): Index) -> impl Iterator<Item = Move> {
+        let destinations = self
+            .get(i, j)
+            .is_some_and(CellState::is_playable)
+            .then(|| {
+                self.geometry
+                    .neighborhood(i * self.width + j, self.rules.jump_distance)
+            })
+            .flatten()
+            .unwrap_or_default();
+
+        destinations
+            .iter()
+            .filter(|&&cell| self.board[cell].is_free())
+            .map(move |&cell| Move {
+                from,
+                to: (cell / self.width, cell % self.width),
+            })
+    }
+
+    /// Check if a player has at least one legal [move](Move)
+    ///
+    /// # Parameter
+    ///
+    /// - `player` - The [player](Player) to check
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{Board, CellState::*, Player::*};
+    ///
+    /// let board = Board::new(1, 2, vec![Player(Red), Player(Blue)]);
+    ///
+    /// assert!(!board.can_play(Red));
+    /// assert!(!board.can_play(Blue));
+    /// ```
+    pub fn can_play(&self, player: Player) -> bool {
+        self.moves(player).next().is_some()
+    }
+
+    /// Count the positions reached after a given number of plies
+    ///
+    /// This is the usual _perft_ function used to check and to benchmark the move generation.
+    /// The clones of several _blobs_ onto the same cell lead to the same position and are counted once; a player without any legal [move](Move) passes unless the game is over.
+    ///
+    /// # Parameters
+    ///
+    /// - `player` - The [player](Player) to move
+    /// - `depth` - The number of plies
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{Board, Player::*};
     ///
-    /// let board = Board::free(2, 2);
-    /// let mut iterator = board.iter();
+    /// let board: Board = "B.R".parse().unwrap();
     ///
-    /// assert_eq!(iterator.next(), Some(CellState::Free));
-    /// assert_eq!(iterator.next(), Some(CellState::Free));
-    /// assert_eq!(iterator.next(), Some(CellState::Free));
-    /// assert_eq!(iterator.next(), Some(CellState::Free));
-    /// assert_eq!(iterator.next(), None);
+    /// assert_eq!(board.perft(Blue, 0), 1);
+    /// assert_eq!(board.perft(Blue, 1), 1);
     /// ```
-    pub fn iter(&self) -> impl Iterator<Item = CellState> {
-        self.board.iter().copied()
+    pub fn perft(&self, player: Player, depth: usize) -> u64 {
+        let mut board = self.clone();
+        board.perft_with(player, depth, &mut History::default())
     }
 
-    fn neighbors_mut(
-        &mut self,
-        row: usize,
-        column: usize,
-        radius: usize,
-    ) -> impl Iterator<Item = &mut CellState> {
-        self.contains(row, column)
-            .then(|| {
-                self.board
-                    .chunks_mut(self.width)
-                    .skip(row.saturating_sub(radius))
-                    .take(1 + radius + row.saturating_sub(radius).min(radius))
-                    .flat_map(move |row| {
-                        row.iter_mut()
-                            .skip(column.saturating_sub(radius))
-                            .take(1 + radius + column.saturating_sub(radius).min(radius))
-                    })
-            })
-            .into_iter()
-            .flatten()
+    fn perft_with(&mut self, player: Player, depth: usize, history: &mut History) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        if self.outcome().is_some() {
+            return 0;
+        }
+
+        let candidates = self.moves(player).collect::<MoveList>();
+        let mut cloned: SmallVec<[bool; 256]> = smallvec![false; self.len()];
+        let mut count = None;
+
+        for candidate in candidates {
+            let (i, j) = candidate.to;
+
+            if self.jump_distance(candidate.from, candidate.to) == Some(1) {
+                if cloned[i * self.width + j] {
+                    continue;
+                }
+
+                cloned[i * self.width + j] = true;
+            }
+
+            self.make_move(candidate, history);
+            *count.get_or_insert(0) += self.perft_with(-player, depth - 1, history);
+            self.unmake_move(history);
+        }
+
+        count.unwrap_or_else(|| self.perft_with(-player, depth - 1, history))
     }
 
-    fn blob(&mut self, row: usize, column: usize) -> bool {
+    fn blob(&mut self, row: usize, column: usize, mut history: Option<&mut History>) -> bool {
         if let Some(new_state @ CellState::Player(new_player)) = self.get(row, column) {
-            let mut score = self.score;
-
-            for state in self
-                .neighbors_mut(row, column, 1)
-                .filter(|&&mut current| current.is_opponent_of(new_state))
+            let power_up = self
+                .power_ups
+                .binary_search_by_key(&(row, column), |&(index, _)| index)
+                .ok()
+                .map(|position| self.power_ups.remove(position).1);
+
+            if let Some(history) = history.as_deref_mut()
+                && let Some(frame) = history.frames.last_mut()
             {
-                *score.get_mut(new_player) += 1;
+                frame.power_up = power_up.map(|power_up| ((row, column), power_up));
+            }
+
+            let radius = self.rules.conversion_radius
+                + usize::from(matches!(
+                    power_up,
+                    Some(PowerUp::DoubleSpread | PowerUp::Bomb)
+                ));
+
+            let center = row * self.width + column;
+
+            let Self {
+                board,
+                score,
+                shields,
+                geometry,
+                width,
+                ..
+            } = self;
+
+            let cells = geometry.neighborhood(center, radius).unwrap_or_default();
+
+            // Only the cells beyond the usual radius are destroyed by a bomb
+            let spared = geometry
+                .neighborhood(center, radius.saturating_sub(1))
+                .unwrap_or_default();
+
+            for &cell in cells {
+                if !board[cell].is_opponent_of(new_state) {
+                    continue;
+                }
+
+                let index = (cell / *width, cell % *width);
+
+                if let Ok(shield) = shields.binary_search(&index) {
+                    shields.remove(shield);
+
+                    if let Some(history) = history.as_deref_mut() {
+                        history.shields.push((index, false));
+                    }
+
+                    continue;
+                }
+
+                if let Some(history) = history.as_deref_mut() {
+                    history.cells.push((cell, board[cell]));
+                }
+
                 *score.get_mut(-new_player) -= 1;
-                *state = new_state;
+
+                if power_up == Some(PowerUp::Bomb) && !spared.contains(&cell) {
+                    board[cell] = CellState::Free;
+                } else {
+                    *score.get_mut(new_player) += 1;
+                    board[cell] = new_state;
+                }
+            }
+
+            if power_up == Some(PowerUp::Shield)
+                && let Err(position) = self.shields.binary_search(&(row, column))
+            {
+                self.shields.insert(position, (row, column));
+
+                if let Some(history) = history {
+                    history.shields.push(((row, column), true));
+                }
             }
 
-            self.score = score;
             true
         } else {
             false
@@ -463,22 +1720,145 @@ impl Board {
     /// assert_eq!(tested, expected);
     /// ```
     pub fn jump(&mut self, from: Index, to: Index) -> bool {
+        self.play(from, to, None)
+    }
+
+    /// Play a [move](Move) which can be [undone](Self::unmake_move)
+    ///
+    /// The changes are recorded in `history`, whose buffers are reused so that no allocation is made once they have grown.
+    ///
+    /// # Parameters
+    ///
+    /// - `chosen` - The played [move](Move)
+    /// - `history` - The [history](History) recording the changes
+    ///
+    /// # Return
+    ///
+    /// If the move is legal, `true` is returned; otherwise, `false` is returned and nothing is recorded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{Board, History, Move};
+    ///
+    /// let mut board: Board = "R.B/...".parse().unwrap();
+    /// let mut history = History::default();
+    ///
+    /// assert!(board.make_move(Move { from: (0, 0), to: (0, 1) }, &mut history));
+    /// assert_eq!(board.to_string(), "RRR/...");
+    ///
+    /// assert!(board.unmake_move(&mut history));
+    /// assert_eq!(board.to_string(), "R.B/...");
+    /// assert!(!board.unmake_move(&mut history));
+    /// ```
+    pub fn make_move(&mut self, chosen: Move, history: &mut History) -> bool {
+        self.play(chosen.from, chosen.to, Some(history))
+    }
+
+    /// Undo the last [move](Move) [made](Self::make_move) with `history`
+    ///
+    /// # Parameter
+    ///
+    /// - `history` - The [history](History) the move has been recorded in
+    ///
+    /// # Return
+    ///
+    /// If there is no move to undo, `false` is returned.
+    pub fn unmake_move(&mut self, history: &mut History) -> bool {
+        let Some(frame) = history.frames.pop() else {
+            return false;
+        };
+
+        for (cell, state) in history.cells.drain(frame.cells..).rev() {
+            self.board[cell] = state;
+        }
+
+        for (index, added) in history.shields.drain(frame.shields..).rev() {
+            match self.shields.binary_search(&index) {
+                Ok(position) if added => {
+                    self.shields.remove(position);
+                }
+
+                Err(position) if !added => self.shields.insert(position, index),
+                _ => (),
+            }
+        }
+
+        if let Some((index, power_up)) = frame.power_up
+            && let Err(position) = self
+                .power_ups
+                .binary_search_by_key(&index, |&(index, _)| index)
+        {
+            self.power_ups.insert(position, (index, power_up));
+        }
+
+        self.score = frame.score;
+        self.played = frame.played;
+        true
+    }
+
+    fn play(&mut self, from: Index, to: Index, mut history: Option<&mut History>) -> bool {
         if let Some(distance) = self.jump_distance(from, to)
             && let Some(player_state @ CellState::Player(player)) = self.get(from.0, from.1)
-            && let Some(dest_state) = self
-                .get_mut(to.0, to.1)
-                .filter(|dest_state| dest_state.is_free())
+            && self.get(to.0, to.1) == Some(CellState::Free)
         {
-            *dest_state = player_state;
+            let (source, destination) = (from.0 * self.width + from.1, to.0 * self.width + to.1);
+
+            if let Some(history) = history.as_deref_mut() {
+                history.frames.push(Frame {
+                    cells: history.cells.len(),
+                    shields: history.shields.len(),
+                    power_up: None,
+                    score: self.score,
+                    played: self.played,
+                });
+
+                history.cells.push((destination, CellState::Free));
+            }
+
+            self.board[destination] = player_state;
+
+            if self.rules.move_limit.is_some() {
+                self.played += 1;
+            }
 
-            if distance == 2 {
-                // It cannot panic because it has been checked just before that `from` is a valid index.
-                *self.get_mut(from.0, from.1).unwrap() = CellState::Free;
+            if distance > 1 {
+                self.board[source] = CellState::Free;
+
+                if let Some(history) = history.as_deref_mut() {
+                    history.cells.push((source, player_state));
+                }
+
+                // A shield follows its blob
+                if let Ok(position) = self.shields.binary_search(&from) {
+                    self.shields.remove(position);
+
+                    if let Some(history) = history.as_deref_mut() {
+                        history.shields.push((from, false));
+                    }
+
+                    if let Err(position) = self.shields.binary_search(&to) {
+                        self.shields.insert(position, to);
+
+                        if let Some(history) = history.as_deref_mut() {
+                            history.shields.push((to, true));
+                        }
+                    }
+                }
             } else {
                 *self.score.get_mut(player) += 1;
             }
 
-            self.blob(to.0, to.1)
+            self.blob(to.0, to.1, history);
+
+            let occupied = self
+                .hill
+                .iter()
+                .filter(|&&(i, j)| self.board[i * self.width + j] == player_state)
+                .count();
+
+            *self.score.points_mut(player) += occupied;
+            true
         } else {
             false
         }
@@ -506,10 +1886,225 @@ impl IntoIterator for Board {
     /// assert_eq!(iterator.next(), None);
     /// ```
     fn into_iter(self) -> Self::IntoIter {
-        self.board.into_iter()
+        self.board.iter().collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl Display for Board {
+    /// Write the board row by row, the rows being separated by `/`
+    ///
+    /// A free cell is written `.`, a restricted one `#` and the _blobs_ `B` and `R`, or `b` and `r` when they are [shielded](PowerUp::Shield).
+    /// A free cell holding a [power-up](PowerUp) is written with the [symbol](PowerUp::symbol) of the power-up.
+    ///
+    /// The [hill](Self::with_hill) follows the cells as `hill=` and its positions in algebraic notation separated by `;`,
+    /// then the [rules](RuleSet#impl-Display-for-RuleSet) differing from the default ones, the number of moves [played](Self::played), if any, as `played=`
+    /// and the [points](Score::points) scored so far, if any, as `points=` blue and red ones separated by `;`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for i in 0..self.height {
+            if i > 0 {
+                write!(f, "/")?;
+            }
+
+            for j in 0..self.width {
+                let symbol = match self.board[i * self.width + j] {
+                    CellState::Free => self.power_up(i, j).map_or('.', PowerUp::symbol),
+                    CellState::Restricted => '#',
+                    CellState::Player(Player::Blue) if self.is_shielded(i, j) => 'b',
+                    CellState::Player(Player::Red) if self.is_shielded(i, j) => 'r',
+                    CellState::Player(Player::Blue) => 'B',
+                    CellState::Player(Player::Red) => 'R',
+                };
+
+                write!(f, "{symbol}")?;
+            }
+        }
+
+        if !self.hill.is_empty() {
+            let hill = self
+                .hill
+                .iter()
+                .map(|&index| display_index(index))
+                .collect::<Vec<_>>();
+
+            write!(f, " hill={}", hill.join(";"))?;
+        }
+
+        if self.rules != RuleSet::default() {
+            write!(f, " {}", self.rules)?;
+        }
+
+        if self.played > 0 {
+            write!(f, " played={}", self.played)?;
+        }
+
+        let Score {
+            blue_points,
+            red_points,
+            ..
+        } = self.score;
+
+        if blue_points > 0 || red_points > 0 {
+            write!(f, " points={blue_points};{red_points}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Board {
+    type Err = &'static str;
+
+    /// Parse a board written row by row
+    ///
+    /// The rows are separated either by `/` or by line breaks; blank lines are ignored.
+    /// The cells are written as [displayed](Board#impl-Display-for-Board), including the [power-ups](PowerUp) and the shielded _blobs_,
+    /// and may be followed by the [hill](Self::with_hill), the [rules](RuleSet), the number of moves played and the points.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::game::{Board, CellState::*, Player::*};
+    ///
+    /// let board: Board = "R.\n#B".parse().unwrap();
+    ///
+    /// assert_eq!(board, Board::new(2, 2, vec![Player(Red), Free, Restricted, Player(Blue)]));
+    /// assert_eq!(board.to_string(), "R./#B");
+    /// assert!("R./#".parse::<Board>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (options, rows): (Vec<_>, Vec<_>) = s
+            .split_whitespace()
+            .flat_map(|token| token.split('/'))
+            .filter(|row| !row.is_empty())
+            .partition(|token| token.contains('='));
+
+        let width = rows.first().map_or(0, |row| row.chars().count());
+
+        if width == 0 || rows.iter().any(|row| row.chars().count() != width) {
+            return Err("The rows of the board do not have the same length");
+        }
+
+        let symbols = rows.iter().flat_map(|row| row.chars()).collect::<Vec<_>>();
+
+        let cells = symbols
+            .iter()
+            .map(|&c| match c {
+                '.' => Ok(CellState::Free),
+                '#' => Ok(CellState::Restricted),
+                'B' | 'b' => Ok(CellState::Player(Player::Blue)),
+                'R' | 'r' => Ok(CellState::Player(Player::Red)),
+                _ if PowerUp::ALL.iter().any(|power_up| power_up.symbol() == c) => {
+                    Ok(CellState::Free)
+                }
+                _ => Err("The board contains an unknown cell"),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let index = |position: usize| (position / width, position % width);
+
+        let power_ups = symbols.iter().enumerate().filter_map(|(position, &c)| {
+            PowerUp::ALL
+                .into_iter()
+                .find(|power_up| power_up.symbol() == c)
+                .map(|power_up| (index(position), power_up))
+        });
+
+        let mut board = Self::new(rows.len(), width, cells).with_power_ups(power_ups);
+
+        board.shields = symbols
+            .iter()
+            .enumerate()
+            .filter(|&(_, &c)| c == 'b' || c == 'r')
+            .map(|(position, _)| index(position))
+            .collect();
+
+        for option in options {
+            let (key, value) = option.split_once('=').unwrap_or_default();
+
+            match key {
+                "hill" => {
+                    let hill = value
+                        .split(';')
+                        .map(parse_index)
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    board = board.with_hill(hill);
+                }
+
+                "played" => {
+                    board.played = value
+                        .parse()
+                        .map_err(|_| "The number of moves played is invalid")?
+                }
+
+                "points" => {
+                    let (blue, red) = value
+                        .split_once(';')
+                        .and_then(|(blue, red)| Some((blue.parse().ok()?, red.parse().ok()?)))
+                        .ok_or("The points are invalid")?;
+
+                    board.score.blue_points = blue;
+                    board.score.red_points = red;
+                }
+
+                _ => board.rules.set(key, value)?,
+            }
+        }
+
+        let rules = board.rules;
+        Ok(board.with_rules(rules))
     }
 }
 
+/// Get an [iterator](Iterator) over the positions of the square of the given radius centered on a cell
+///
+/// On a torus, the square wraps around the edges and each position is yielded once.
+fn area(
+    (row, column): Index,
+    radius: usize,
+    height: usize,
+    width: usize,
+    torus: bool,
+) -> impl Iterator<Item = Index> {
+    let rows = span(row, radius, height, torus);
+    let columns = span(column, radius, width, torus);
+
+    rows.flat_map(move |i| columns.clone().map(move |j| (i, j)))
+}
+
+/// Get the indexes of a line of cells centered on `center`, wrapping around the ends on a torus
+fn span(
+    center: usize,
+    radius: usize,
+    length: usize,
+    torus: bool,
+) -> impl Iterator<Item = usize> + Clone {
+    let length = length.max(1);
+
+    let (start, count) = if torus {
+        (
+            (center % length + length - radius % length) % length,
+            (2 * radius + 1).min(length),
+        )
+    } else {
+        let start = center.saturating_sub(radius);
+        (
+            start,
+            (center + radius + 1).min(length).saturating_sub(start),
+        )
+    };
+
+    (0..count).map(move |k| (start + k) % length)
+}
+
+/// Get the _Zobrist_ key of a cell state, scrambling its code with _SplitMix64_
+fn zobrist_key(code: u64) -> u64 {
+    let mut key = code.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    key = (key ^ (key >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    key = (key ^ (key >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    key ^ (key >> 31)
+}
+
 #[cfg(test)]
 mod tests {
     use super::{CellState::*, Player::*, *};
@@ -521,14 +2116,59 @@ mod tests {
             let board = vec![Free; 25];
             let mut tested = Board::new(5, 5, board);
 
-            for state in tested.neighbors_mut(row, column, 1) {
-                *state = Restricted;
+            let cells = tested
+                .contains(row, column)
+                .then(|| {
+                    tested
+                        .geometry
+                        .neighborhood(row * 5 + column, 1)
+                        .map(<[_]>::to_vec)
+                })
+                .flatten();
+
+            for cell in cells.into_iter().flatten() {
+                tested.board[cell] = Restricted;
             }
 
             let expected = Board::new(5, 5, expected);
             pretty_assertions::assert_eq!(tested, expected);
         }
 
+        #[test]
+        fn tables() {
+            for topology in Topology::ALL {
+                let rules = RuleSet {
+                    jump_distance: 3,
+                    topology,
+                    ..RuleSet::default()
+                };
+
+                let board = Board::free(4, 7).with_rules(rules);
+
+                for (i, j) in area((0, 0), 7, 4, 7, false) {
+                    for radius in 0..=4 {
+                        let expected = area((i, j), radius, 4, 7, topology == Topology::Torus)
+                            .map(|(k, l)| k * 7 + l)
+                            .collect::<Vec<_>>();
+
+                        let tested = board.geometry.neighborhood(i * 7 + j, radius);
+
+                        if (1..=3).contains(&radius) {
+                            pretty_assertions::assert_eq!(tested, Some(&expected[..]));
+                        } else {
+                            pretty_assertions::assert_eq!(tested, None);
+                        }
+
+                        // The radii beyond the tables are computed on demand
+                        pretty_assertions::assert_eq!(
+                            board.neighbors(i, j, radius).count(),
+                            expected.len()
+                        );
+                    }
+                }
+            }
+        }
+
         #[test]
         fn mid() {
             #[rustfmt::skip]
@@ -655,6 +2295,34 @@ mod tests {
             check(4, 4, expected);
         }
 
+        #[test]
+        fn second_row() {
+            #[rustfmt::skip]
+            let expected = vec![
+                Free, Restricted, Restricted, Restricted, Free,
+                Free, Restricted, Restricted, Restricted, Free,
+                Free, Restricted, Restricted, Restricted, Free,
+                Free, Free,       Free,       Free,       Free,
+                Free, Free,       Free,       Free,       Free,
+            ];
+
+            check(1, 2, expected);
+        }
+
+        #[test]
+        fn second_column() {
+            #[rustfmt::skip]
+            let expected = vec![
+                Free,       Free,       Free,       Free, Free,
+                Restricted, Restricted, Restricted, Free, Free,
+                Restricted, Restricted, Restricted, Free, Free,
+                Restricted, Restricted, Restricted, Free, Free,
+                Free,       Free,       Free,       Free, Free,
+            ];
+
+            check(2, 1, expected);
+        }
+
         #[test]
         fn out_of_bound_column() {
             #[rustfmt::skip]
@@ -814,4 +2482,281 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn snapshot() {
+        let board = Board::free(8, 8);
+        let mut tested = board.clone();
+
+        let shared = |tested: &Board| {
+            board
+                .board
+                .chunks
+                .iter()
+                .zip(&tested.board.chunks)
+                .filter(|(chunk, other)| Arc::ptr_eq(chunk, other))
+                .count()
+        };
+
+        pretty_assertions::assert_eq!(shared(&tested), 4);
+
+        // Only the chunk of the written cell is copied
+        tested.board[20] = Player(Red);
+        pretty_assertions::assert_eq!(shared(&tested), 3);
+        pretty_assertions::assert_eq!(board.get(2, 4), Some(Free));
+        pretty_assertions::assert_eq!(tested.get(2, 4), Some(Player(Red)));
+        assert_ne!(board, tested);
+
+        tested.board[20] = Free;
+        pretty_assertions::assert_eq!(board, tested);
+    }
+
+    #[test]
+    fn make_move() {
+        let mut rng = Rng::new(7);
+
+        for notation in [
+            "r..+...B/...@..../.#..*.#./b......R",
+            "R..+...B/...@.*../.#....#./B......R hill=c2;d2 jump=3 radius=2 topology=torus limit=30",
+        ] {
+            let mut board: Board = notation.parse().unwrap();
+            let mut history = History::default();
+            let mut snapshots = Vec::new();
+            let mut player = Blue;
+
+            for _ in 0..40 {
+                let candidates = board.moves(player).collect::<Vec<_>>();
+                player = -player;
+
+                if candidates.is_empty() {
+                    continue;
+                }
+
+                let chosen = candidates[rng.next_u64() as usize % candidates.len()];
+
+                let mut expected = board.clone();
+                assert!(expected.jump(chosen.from, chosen.to));
+
+                snapshots.push(board.clone());
+                assert!(board.make_move(chosen, &mut history));
+                pretty_assertions::assert_eq!(board.to_string(), expected.to_string());
+                pretty_assertions::assert_eq!(board, expected);
+            }
+
+            pretty_assertions::assert_eq!(history.len(), snapshots.len());
+
+            while let Some(snapshot) = snapshots.pop() {
+                assert!(board.unmake_move(&mut history));
+                pretty_assertions::assert_eq!(board.to_string(), snapshot.to_string());
+                pretty_assertions::assert_eq!(board, snapshot);
+            }
+
+            assert!(history.is_empty());
+            pretty_assertions::assert_eq!(board.to_string(), notation);
+        }
+    }
+
+    #[test]
+    fn perft() {
+        let board: Board = "B.....R/......./......./......./......./......./R.....B"
+            .parse()
+            .unwrap();
+
+        let tested = (1..=3)
+            .map(|depth| board.perft(Blue, depth))
+            .collect::<Vec<_>>();
+
+        pretty_assertions::assert_eq!(tested, vec![16, 256, 6460]);
+    }
+
+    mod rules {
+        use super::*;
+
+        #[test]
+        fn torus() {
+            let board: Board = "R.../..../..../...B topology=torus".parse().unwrap();
+
+            pretty_assertions::assert_eq!(board.jump_distance((0, 0), (3, 3)), Some(1));
+            pretty_assertions::assert_eq!(board.jump_distance((0, 0), (2, 1)), Some(2));
+            pretty_assertions::assert_eq!(board.moves_from((0, 0)).count(), 14);
+            pretty_assertions::assert_eq!(board.neighbors(0, 0, 1).count(), 9);
+
+            // The blob spreads across the corner and captures the opposite one
+            let mut tested = board.clone();
+            assert!(tested.jump((0, 0), (0, 3)));
+            pretty_assertions::assert_eq!(tested.to_string(), "R..R/..../..../...R topology=torus");
+
+            // The flat board keeps its edges
+            let flat = board.with_rules(RuleSet::default());
+            pretty_assertions::assert_eq!(flat.jump_distance((0, 0), (3, 3)), None);
+            pretty_assertions::assert_eq!(flat.moves_from((0, 0)).count(), 8);
+        }
+
+        #[test]
+        fn distances() {
+            let board: Board = "R...B/...../B.... jump=3 radius=2".parse().unwrap();
+
+            pretty_assertions::assert_eq!(board.jump_distance((0, 0), (0, 3)), Some(3));
+            pretty_assertions::assert_eq!(board.jump_distance((0, 0), (0, 4)), None);
+
+            // The jump captures both blue blobs, two cells away
+            let mut tested = board.clone();
+            assert!(tested.jump((0, 0), (1, 2)));
+            pretty_assertions::assert_eq!(tested.to_string(), "....R/..R../R.... jump=3 radius=2");
+        }
+
+        #[test]
+        fn move_limit() {
+            let mut board: Board = "R.../..../...B limit=2".parse().unwrap();
+
+            assert!(board.jump((0, 0), (0, 1)));
+            pretty_assertions::assert_eq!(board.outcome(), None);
+            pretty_assertions::assert_eq!(board.to_string(), "RR../..../...B limit=2 played=1");
+
+            assert!(board.jump((2, 3), (1, 3)));
+            pretty_assertions::assert_eq!(board.played(), 2);
+            pretty_assertions::assert_eq!(board.outcome(), Some(Outcome::Draw));
+
+            // The moves are not counted without a limit
+            let mut unlimited = board.with_rules(RuleSet::default());
+            assert!(unlimited.jump((0, 1), (0, 2)));
+            pretty_assertions::assert_eq!(unlimited.played(), 2);
+            pretty_assertions::assert_eq!(unlimited.outcome(), None);
+        }
+
+        #[test]
+        fn notation() {
+            assert!("R..B jump=9".parse::<Board>().is_err());
+            assert!("R..B radius=0".parse::<Board>().is_err());
+            assert!("R..B limit=0".parse::<Board>().is_err());
+            assert!("R..B topology=sphere".parse::<Board>().is_err());
+            assert!("R..B rules=none".parse::<Board>().is_err());
+
+            let board: Board = "R..B goal=3 radius=2 misere=true limit=9 jump=3 topology=Torus"
+                .parse()
+                .unwrap();
+
+            pretty_assertions::assert_eq!(
+                board.to_string(),
+                "R..B jump=3 radius=2 topology=torus limit=9 goal=3 misere=true"
+            );
+            pretty_assertions::assert_eq!(board.to_string().parse(), Ok(board));
+        }
+    }
+
+    mod hill {
+        use super::*;
+
+        #[test]
+        fn notation() {
+            let board: Board = "R../.../..B hill=b2;a2 goal=5 points=1;3".parse().unwrap();
+
+            assert!(board.is_hill(1, 0) && board.is_hill(1, 1));
+            assert!(!board.is_hill(0, 0));
+            pretty_assertions::assert_eq!(board.rules().goal, Some(5));
+            pretty_assertions::assert_eq!(board.score().points(Blue), 1);
+            pretty_assertions::assert_eq!(board.score().points(Red), 3);
+            pretty_assertions::assert_eq!(
+                board.to_string(),
+                "R../.../..B hill=a2;b2 goal=5 points=1;3"
+            );
+
+            // The hill and the goal may follow a map written on several lines
+            let map: Board = "R..\n...\n..B\nhill=b2;a2 goal=5 points=1;3"
+                .parse()
+                .unwrap();
+            pretty_assertions::assert_eq!(map, board);
+
+            assert!(
+                "R..B hill=z9"
+                    .parse::<Board>()
+                    .map(|board| board.is_hill(8, 25))
+                    == Ok(false)
+            );
+            assert!("R..B hill=b".parse::<Board>().is_err());
+            assert!("R..B goal=x".parse::<Board>().is_err());
+            assert!("R..B size=4".parse::<Board>().is_err());
+        }
+
+        #[test]
+        fn points() {
+            let mut board = "R..../...../....B"
+                .parse::<Board>()
+                .unwrap()
+                .with_hill([(1, 1), (1, 2)]);
+
+            board.jump((0, 0), (1, 1));
+            pretty_assertions::assert_eq!(board.score().points(Red), 1);
+
+            // Only the player who moves scores
+            board.jump((2, 4), (2, 3));
+            pretty_assertions::assert_eq!(board.score().points(Red), 1);
+            pretty_assertions::assert_eq!(board.score().points(Blue), 0);
+
+            board.jump((1, 1), (1, 2));
+            pretty_assertions::assert_eq!(board.score().points(Red), 3);
+        }
+
+        #[test]
+        fn goal() {
+            let mut board: Board = "R..../...../....B hill=b2 goal=2".parse().unwrap();
+
+            board.jump((0, 0), (1, 1));
+            pretty_assertions::assert_eq!(board.outcome(), None);
+
+            board.jump((2, 4), (2, 3));
+            board.jump((1, 1), (0, 1));
+            pretty_assertions::assert_eq!(board.outcome(), Some(Outcome::Win(Red)));
+        }
+    }
+
+    mod power_ups {
+        use super::*;
+
+        fn check(tested: &str, from: Index, to: Index, expected: &str) {
+            let mut board: Board = tested.parse().unwrap();
+            assert!(board.jump(from, to));
+            pretty_assertions::assert_eq!(board.to_string(), expected);
+
+            // The score follows the effects of the power-ups
+            let expected: Board = expected.parse().unwrap();
+            pretty_assertions::assert_eq!(board.score(), expected.score());
+        }
+
+        #[test]
+        fn notation() {
+            let board: Board = "R+@*/b..r".parse().unwrap();
+
+            pretty_assertions::assert_eq!(board.power_up(0, 1), Some(PowerUp::DoubleSpread));
+            pretty_assertions::assert_eq!(board.power_up(0, 2), Some(PowerUp::Shield));
+            pretty_assertions::assert_eq!(board.power_up(0, 3), Some(PowerUp::Bomb));
+            assert!(board.is_shielded(1, 0) && board.is_shielded(1, 3));
+            assert!(!board.is_shielded(0, 0));
+            pretty_assertions::assert_eq!(board.to_string(), "R+@*/b..r");
+            assert_ne!(
+                board.zobrist(),
+                "R.../B..R".parse::<Board>().unwrap().zobrist()
+            );
+        }
+
+        #[test]
+        fn double_spread() {
+            check("R+.B/...B", (0, 0), (0, 1), "RR.R/...R");
+        }
+
+        #[test]
+        fn bomb() {
+            check("R*BB/...B", (0, 0), (0, 1), "RRR./....");
+        }
+
+        #[test]
+        fn shield() {
+            check("R@../...B", (0, 0), (0, 1), "Rr../...B");
+
+            // The shield follows its blob and is consumed by the next capture
+            check("r.../.B..", (0, 0), (0, 2), "..r./.R..");
+            check("..r./.B..", (1, 1), (1, 2), "..R./.BB.");
+            check("..R./.BB.", (1, 2), (1, 3), "..B./.BBB");
+        }
+    }
 }
@@ -0,0 +1,974 @@
+//! The implementation of [`Graphics`]
+//!
+//! Some terminals can display images sent through escape sequences, either with the [kitty graphics protocol](https://sw.kovidgoyal.net/kitty/graphics-protocol/) or in the older _sixel_ format.
+//! When the [`Images` render style](RenderStyle::Images) is chosen, the [`Board` widget](crate::widgets::board::Board) leaves its cells blank
+//! and the _blobs_ are drawn over them as an [image](Image) composed from the [state of the board](BoardState).
+//! If no [protocol](Protocol) is detected, the render style falls back to text.
+
+use crate::{
+    game::{CellState, PowerUp},
+    widgets::{Theme, board::BoardState},
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use png::{BitDepth, ColorType};
+use ratatui::{buffer::Buffer, crossterm::terminal, layout::Rect, style::Color};
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    env,
+    fmt::{Display, Formatter, Write},
+    hash::{Hash, Hasher},
+    io::{self, ErrorKind},
+    str::FromStr,
+    time::Duration,
+};
+
+#[cfg(doc)]
+use crate::widgets::board::RenderStyle;
+
+/// The maximal size of a chunk of data sent with the kitty graphics protocol
+const KITTY_CHUNK: usize = 4096;
+
+/// The identifier of the image of the board with the kitty graphics protocol
+const KITTY_IMAGE: u32 = 1;
+
+/// The number of levels of each channel of the palette of a _sixel_ image
+const SIXEL_LEVELS: u16 = 6;
+
+/// The number of subsamples along each axis used to smooth the edges of the shapes
+const SUBSAMPLES: u32 = 4;
+
+/// The radius of the disc of a _blob_, as a fraction of the half size of its cell
+pub(crate) const DISC: f32 = 0.85;
+
+/// The radius of the dot of a free cell, as a fraction of the half size of its cell
+pub(crate) const DOT: f32 = 0.12;
+
+/// The half side of the square of a restricted cell, as a fraction of the half size of its cell
+pub(crate) const SQUARE: f32 = 0.6;
+
+/// The inner half side of the frame of a scoring cell, as a fraction of the half size of its cell
+pub(crate) const FRAME: f32 = 0.92;
+
+/// The color of the frame of a scoring cell
+pub(crate) const HILL: [u8; 3] = [0xff, 0xd5, 0x4f];
+
+/// A protocol used to display images in a terminal
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    /// The [kitty graphics protocol](https://sw.kovidgoyal.net/kitty/graphics-protocol/), also implemented by WezTerm, Ghostty and Konsole
+    Kitty,
+
+    /// The _sixel_ format, implemented by foot, mlterm, Contour and xterm (`xterm -ti vt340`)
+    Sixel,
+}
+
+impl Protocol {
+    /// Detect the protocol supported by the terminal
+    ///
+    /// The protocol is [guessed](Self::guess) from the environment variables.
+    /// `None` is returned if the terminal does not report the size of its cells in pixels, since the images could not be fitted to the board.
+    pub fn detect() -> Option<Self> {
+        cell_pixels()?;
+        Self::guess(|name| env::var(name).ok())
+    }
+
+    /// Guess the protocol supported by the terminal from its environment variables
+    ///
+    /// The `BLOBWARS_GRAPHICS` variable forces the protocol (`kitty`, `sixel` or `none`);
+    /// otherwise the terminal is identified by `TERM`, `TERM_PROGRAM` and `KITTY_WINDOW_ID`.
+    ///
+    /// # Parameter
+    ///
+    /// - `var` - A function giving the value of an environment variable
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::graphics::Protocol;
+    ///
+    /// let guess = |term: &'static str| Protocol::guess(move |name| (name == "TERM").then(|| term.to_string()));
+    ///
+    /// assert_eq!(guess("xterm-kitty"), Some(Protocol::Kitty));
+    /// assert_eq!(guess("foot"), Some(Protocol::Sixel));
+    /// assert_eq!(guess("xterm-256color"), None);
+    /// ```
+    pub fn guess(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        if let Some(forced) = var("BLOBWARS_GRAPHICS") {
+            return forced.parse().ok();
+        }
+
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+
+        if var("KITTY_WINDOW_ID").is_some()
+            || term.contains("kitty")
+            || term.contains("ghostty")
+            || ["WezTerm", "ghostty"].contains(&program.as_str())
+        {
+            Some(Self::Kitty)
+        } else if ["foot", "mlterm", "contour", "yaft"]
+            .iter()
+            .any(|name| term.starts_with(name))
+        {
+            Some(Self::Sixel)
+        } else {
+            None
+        }
+    }
+
+    /// Encode an image to be displayed over an area of the terminal
+    ///
+    /// The cursor is saved before the image is displayed and restored afterwards.
+    ///
+    /// # Parameters
+    ///
+    /// - `image` - The image to encode
+    /// - `area` - The cells of the terminal covered by the image
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::graphics::{Image, Protocol};
+    /// use ratatui::layout::Rect;
+    ///
+    /// let image = Image::new(2, 2);
+    /// let sequence = Protocol::Kitty.encode(&image, Rect::new(0, 0, 1, 1));
+    ///
+    /// assert!(sequence.contains("\x1b_Ga=T,f=100"));
+    /// ```
+    pub fn encode(self, image: &Image, area: Rect) -> String {
+        let mut sequence = format!("\x1b7\x1b[{};{}H", area.y + 1, area.x + 1);
+
+        match self {
+            Self::Kitty => {
+                sequence.push_str(&self.erasure().unwrap_or_default());
+                kitty(&mut sequence, image, area);
+            }
+
+            Self::Sixel => sixel(&mut sequence, image),
+        }
+
+        sequence.push_str("\x1b8");
+        sequence
+    }
+
+    /// Get the escape sequence erasing the displayed image
+    ///
+    /// `None` is returned if the image cannot be erased by itself; the whole terminal has to be cleared instead.
+    pub fn erasure(self) -> Option<String> {
+        match self {
+            Self::Kitty => Some(format!("\x1b_Ga=d,d=I,i={KITTY_IMAGE},q=2\x1b\\")),
+            Self::Sixel => None,
+        }
+    }
+}
+
+impl Display for Protocol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Kitty => write!(f, "kitty"),
+            Self::Sixel => write!(f, "sixel"),
+        }
+    }
+}
+
+impl FromStr for Protocol {
+    type Err = &'static str;
+
+    /// Parse a protocol
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::graphics::Protocol;
+    ///
+    /// assert_eq!("Sixel".parse(), Ok(Protocol::Sixel));
+    /// assert!("none".parse::<Protocol>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Self::Kitty, Self::Sixel]
+            .into_iter()
+            .find(|protocol| protocol.to_string().eq_ignore_ascii_case(s))
+            .ok_or("Unknown graphics protocol")
+    }
+}
+
+/// An image with RGBA pixels
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<[u8; 4]>,
+}
+
+impl Image {
+    /// Create a new transparent [`Image`]
+    ///
+    /// # Parameters
+    ///
+    /// - `width` - The width of the image in pixels
+    /// - `height` - The height of the image in pixels
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0; 4]; (width * height) as usize],
+        }
+    }
+
+    /// Compose the image of a board
+    ///
+    /// The image covers the cells of the last rendering of the [`Board` widget](crate::widgets::board::Board).
+    /// The _blobs_ are drawn as shaded discs, the restricted cells as squares and the free cells as dots;
+    /// the departure and destination points are circled.
+    ///
+    /// # Parameters
+    ///
+    /// - `state` - The state of the rendered board
+    /// - `theme` - The [theme](Theme) giving the colors of the cells
+    /// - `cell` - The size of a cell of the terminal in pixels
+    ///
+    /// # Return
+    ///
+    /// The area of the terminal covered by the image with the image itself, or `None` if nothing has been rendered yet.
+    pub fn compose(state: &BoardState, theme: &Theme, cell: (u32, u32)) -> Option<(Rect, Self)> {
+        let area = state
+            .cells()
+            .iter()
+            .map(|&(_, area)| area)
+            .reduce(|union, area| union.union(area))?;
+
+        let (cell_width, cell_height) = cell;
+        let mut image = Self::new(
+            u32::from(area.width) * cell_width,
+            u32::from(area.height) * cell_height,
+        );
+
+        let ring = rgb(theme.important.fg.unwrap_or(Color::White));
+
+        for &(index, rect) in state.cells() {
+            let Some(content) = state.displayed(index) else {
+                continue;
+            };
+
+            let bounds = (
+                u32::from(rect.x - area.x) * cell_width,
+                u32::from(rect.y - area.y) * cell_height,
+                u32::from(rect.width) * cell_width,
+                u32::from(rect.height) * cell_height,
+            );
+
+            if state.board().is_hill(index.0, index.1) {
+                image.paint_hill(bounds);
+            }
+
+            image.paint_cell(bounds, content, theme);
+
+            if content.is_free()
+                && let Some(power_up) = state.board().power_up(index.0, index.1)
+            {
+                image.paint_power_up(bounds, power_up);
+            }
+
+            if state.from() == Some(index) || state.to() == Some(index) {
+                let radius = bounds.2.min(bounds.3) as f32 / 2.;
+                let (inner, outer) = (0.88 * radius, 0.98 * radius);
+
+                image.paint(
+                    bounds,
+                    |x, y| (inner * inner..=outer * outer).contains(&(x * x + y * y)),
+                    |_, _| ring,
+                );
+            }
+        }
+
+        Some((area, image))
+    }
+
+    /// Get the width of the image in pixels
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the height of the image in pixels
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the pixel at the given position
+    ///
+    /// # Parameters
+    ///
+    /// - `x` - The column of the pixel
+    /// - `y` - The row of the pixel
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        (x < self.width && y < self.height).then(|| self.pixels[(y * self.width + x) as usize])
+    }
+
+    /// Encode the image in the PNG format
+    ///
+    /// # Return
+    ///
+    /// The image, or an error if it is empty
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::graphics::Image;
+    ///
+    /// assert!(Image::new(4, 4).png().unwrap().starts_with(b"\x89PNG\r\n\x1a\n"));
+    /// assert!(Image::new(0, 4).png().is_err());
+    /// ```
+    pub fn png(&self) -> io::Result<Vec<u8>> {
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, self.width, self.height);
+        encoder.set_color(ColorType::Rgba);
+        encoder.set_depth(BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.rgba())?;
+        writer.finish()?;
+
+        Ok(png)
+    }
+
+    /// Get the channels of the pixels, row by row
+    fn rgba(&self) -> Vec<u8> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+
+    /// Paint the content of a cell of the board
+    ///
+    /// The _blobs_ are drawn as shaded discs, the restricted cells as squares and the free cells as dots.
+    ///
+    /// # Parameters
+    ///
+    /// - `bounds` - The column, the row, the width and the height of the cell in pixels
+    /// - `content` - The content of the cell
+    /// - `theme` - The [theme](Theme) giving the colors of the cells
+    pub(crate) fn paint_cell(
+        &mut self,
+        bounds: (u32, u32, u32, u32),
+        content: CellState,
+        theme: &Theme,
+    ) {
+        let radius = bounds.2.min(bounds.3) as f32 / 2.;
+        let color = cell_rgb(content, theme);
+
+        match content {
+            CellState::Player(_) => {
+                let disc = DISC * radius;
+
+                self.paint(
+                    bounds,
+                    |x, y| x * x + y * y <= disc * disc,
+                    |x, y| shade(color, x / disc, y / disc),
+                );
+            }
+
+            CellState::Free => {
+                let dot = DOT * radius;
+                self.paint(bounds, |x, y| x * x + y * y <= dot * dot, |_, _| color);
+            }
+
+            CellState::Restricted => {
+                let side = SQUARE * radius;
+                self.paint(bounds, |x, y| x.abs().max(y.abs()) <= side, |_, _| color);
+            }
+        }
+    }
+
+    /// Paint a [power-up](PowerUp) as a diamond centered in a cell
+    ///
+    /// # Parameters
+    ///
+    /// - `bounds` - The column, the row, the width and the height of the cell in pixels
+    /// - `power_up` - The power-up to paint
+    pub(crate) fn paint_power_up(&mut self, bounds: (u32, u32, u32, u32), power_up: PowerUp) {
+        let side = SQUARE * bounds.2.min(bounds.3) as f32 / 2.;
+        let color = rgb(power_up.into());
+
+        self.paint(bounds, |x, y| x.abs() + y.abs() <= side, |_, _| color);
+    }
+
+    /// Paint the frame of a scoring cell of the [hill](crate::game::Board::with_hill)
+    ///
+    /// # Parameter
+    ///
+    /// - `bounds` - The column, the row, the width and the height of the cell in pixels
+    pub(crate) fn paint_hill(&mut self, bounds: (u32, u32, u32, u32)) {
+        let inner = FRAME * bounds.2.min(bounds.3) as f32 / 2.;
+        self.paint(bounds, |x, y| x.abs().max(y.abs()) >= inner, |_, _| HILL);
+    }
+
+    /// Fill an area of the image with an opaque color
+    ///
+    /// # Parameters
+    ///
+    /// - `bounds` - The column, the row, the width and the height of the area in pixels
+    /// - `color` - The color of the area
+    pub(crate) fn fill(&mut self, bounds: (u32, u32, u32, u32), color: [u8; 3]) {
+        self.paint(bounds, |_, _| true, |_, _| color);
+    }
+
+    /// Paint a shape over an area of the image
+    ///
+    /// The edges of the shape are smoothed by subsampling each pixel.
+    ///
+    /// # Parameters
+    ///
+    /// - `bounds` - The column, the row, the width and the height of the area in pixels
+    /// - `inside` - Check if a point, relative to the center of the area, is inside the shape
+    /// - `color` - Get the color of the shape at a point relative to the center of the area
+    fn paint(
+        &mut self,
+        bounds: (u32, u32, u32, u32),
+        inside: impl Fn(f32, f32) -> bool,
+        color: impl Fn(f32, f32) -> [u8; 3],
+    ) {
+        let (left, top, width, height) = bounds;
+        let center = (width as f32 / 2., height as f32 / 2.);
+        let step = 1. / SUBSAMPLES as f32;
+
+        for y in top..(top + height).min(self.height) {
+            for x in left..(left + width).min(self.width) {
+                let (px, py) = ((x - left) as f32 - center.0, (y - top) as f32 - center.1);
+
+                let covered = (0..SUBSAMPLES * SUBSAMPLES)
+                    .filter(|sample| {
+                        let sx = px + (0.5 + (sample % SUBSAMPLES) as f32) * step;
+                        let sy = py + (0.5 + (sample / SUBSAMPLES) as f32) * step;
+                        inside(sx, sy)
+                    })
+                    .count();
+
+                if covered == 0 {
+                    continue;
+                }
+
+                let alpha = covered as f32 / (SUBSAMPLES * SUBSAMPLES) as f32;
+                let source = color(px + 0.5, py + 0.5);
+                let pixel = &mut self.pixels[(y * self.width + x) as usize];
+                let below = f32::from(pixel[3]) / 255. * (1. - alpha);
+                let total = alpha + below;
+
+                for channel in 0..3 {
+                    let blended = (f32::from(source[channel]) * alpha
+                        + f32::from(pixel[channel]) * below)
+                        / total;
+
+                    pixel[channel] = blended.round() as u8;
+                }
+
+                pixel[3] = (total * 255.).round() as u8;
+            }
+        }
+    }
+}
+
+/// The images displayed by the application
+///
+/// The image of the board is only sent again to the terminal when it changes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Graphics {
+    protocol: Protocol,
+    shown: Option<u64>,
+}
+
+impl Graphics {
+    /// Create a new [`Graphics`]
+    ///
+    /// # Parameter
+    ///
+    /// - `protocol` - The [protocol](Protocol) supported by the terminal
+    pub fn new(protocol: Protocol) -> Self {
+        Self {
+            protocol,
+            shown: None,
+        }
+    }
+
+    /// Get the [protocol](Protocol) used to display the images
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    /// Check if an image is displayed
+    pub fn is_shown(&self) -> bool {
+        self.shown.is_some()
+    }
+
+    /// Display the image of a board
+    ///
+    /// # Parameters
+    ///
+    /// - `state` - The state of the rendered board
+    /// - `buffer` - The buffer of the rendered frame
+    /// - `theme` - The [theme](Theme) giving the colors of the cells
+    /// - `cell` - The size of a cell of the terminal in pixels
+    ///
+    /// # Return
+    ///
+    /// The escape sequence displaying the image, or `None` if the displayed image is already up to date.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, Player},
+    ///     graphics::{Graphics, Protocol},
+    ///     widgets::{Theme, board::{Board as BoardWidget, BoardState, RenderStyle}},
+    /// };
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+    ///
+    /// let board: Board = "B...R/...../..#../...../R...B".parse().unwrap();
+    /// let mut state = BoardState::new(board, Player::Blue);
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let mut buffer = Buffer::empty(area);
+    ///
+    /// BoardWidget::default()
+    ///     .render_style(RenderStyle::Images)
+    ///     .render(area, &mut buffer, &mut state);
+    ///
+    /// let mut graphics = Graphics::new(Protocol::Kitty);
+    /// assert!(graphics.show(&state, &buffer, &Theme::default(), (8, 16)).is_some());
+    /// assert!(graphics.show(&state, &buffer, &Theme::default(), (8, 16)).is_none());
+    /// ```
+    pub fn show(
+        &mut self,
+        state: &BoardState,
+        buffer: &Buffer,
+        theme: &Theme,
+        cell: (u32, u32),
+    ) -> Option<String> {
+        let mut hasher = DefaultHasher::new();
+        cell.hash(&mut hasher);
+        buffer.area.hash(&mut hasher);
+        theme.hash(&mut hasher);
+        state.from().hash(&mut hasher);
+        state.to().hash(&mut hasher);
+
+        for &(index, area) in state.cells() {
+            (index, area, state.displayed(index)).hash(&mut hasher);
+
+            // A sixel image is erased wherever the text below it is redrawn
+            if self.protocol == Protocol::Sixel {
+                for position in area.positions() {
+                    buffer.cell(position).hash(&mut hasher);
+                }
+            }
+        }
+
+        let key = hasher.finish();
+
+        if self.shown == Some(key) {
+            return None;
+        }
+
+        let (area, image) = Image::compose(state, theme, cell)?;
+        self.shown = Some(key);
+
+        Some(self.protocol.encode(&image, area))
+    }
+
+    /// Forget the displayed image
+    ///
+    /// # Return
+    ///
+    /// Whether an image was displayed; it then has to be [erased](Protocol::erasure).
+    pub fn hide(&mut self) -> bool {
+        self.shown.take().is_some()
+    }
+}
+
+/// Encode a sequence of images as an animated PNG
+///
+/// Every frame covers the whole canvas and has the size of the first one; the animation loops forever.
+///
+/// # Parameter
+///
+/// - `frames` - The images with the time each one is shown
+///
+/// # Return
+///
+/// The animation, or an error if there is no frame or if the frames do not have the same size
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::graphics::{Image, apng};
+/// use std::time::Duration;
+///
+/// let frames = vec![(Image::new(2, 2), Duration::from_secs(1)); 3];
+/// let png = apng(&frames).unwrap();
+///
+/// assert_eq!(png.windows(4).filter(|kind| kind == b"fcTL").count(), 3);
+/// ```
+pub fn apng(frames: &[(Image, Duration)]) -> io::Result<Vec<u8>> {
+    let (first, _) = frames.first().ok_or(io::Error::new(
+        ErrorKind::InvalidInput,
+        "An animation has at least one frame",
+    ))?;
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, first.width, first.height);
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+    encoder.set_animated(frames.len() as u32, 0)?;
+
+    let mut writer = encoder.write_header()?;
+
+    for (image, delay) in frames {
+        writer.set_frame_delay(delay.as_millis().min(u16::MAX.into()) as u16, 1000)?;
+        writer.write_image_data(&image.rgba())?;
+    }
+
+    writer.finish()?;
+    Ok(png)
+}
+
+/// Get the size of a cell of the terminal in pixels
+///
+/// `None` is returned if the terminal does not report its size in pixels.
+pub fn cell_pixels() -> Option<(u32, u32)> {
+    let size = terminal::window_size().ok()?;
+
+    (size.columns > 0 && size.rows > 0 && size.width > 0 && size.height > 0)
+        .then(|| {
+            (
+                u32::from(size.width / size.columns),
+                u32::from(size.height / size.rows),
+            )
+        })
+        .filter(|&(width, height)| width > 0 && height > 0)
+}
+
+/// Convert a color to its RGB channels
+///
+/// The named and indexed colors are converted with the default palette of xterm.
+pub(crate) fn rgb(color: Color) -> [u8; 3] {
+    const NAMED: [[u8; 3]; 16] = [
+        [0x00, 0x00, 0x00],
+        [0xcd, 0x00, 0x00],
+        [0x00, 0xcd, 0x00],
+        [0xcd, 0xcd, 0x00],
+        [0x00, 0x00, 0xee],
+        [0xcd, 0x00, 0xcd],
+        [0x00, 0xcd, 0xcd],
+        [0xe5, 0xe5, 0xe5],
+        [0x7f, 0x7f, 0x7f],
+        [0xff, 0x00, 0x00],
+        [0x00, 0xff, 0x00],
+        [0xff, 0xff, 0x00],
+        [0x5c, 0x5c, 0xff],
+        [0xff, 0x00, 0xff],
+        [0x00, 0xff, 0xff],
+        [0xff, 0xff, 0xff],
+    ];
+
+    let index = match color {
+        Color::Rgb(r, g, b) => return [r, g, b],
+        Color::Reset | Color::White => 15,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::Indexed(index) => index,
+    };
+
+    match index {
+        0..16 => NAMED[index as usize],
+        16..232 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + 40 * value };
+            let index = index - 16;
+            [level(index / 36), level(index / 6 % 6), level(index % 6)]
+        }
+        _ => [8 + 10 * (index - 232); 3],
+    }
+}
+
+/// Get the color of the content of a cell
+pub(crate) fn cell_rgb(content: CellState, theme: &Theme) -> [u8; 3] {
+    match content {
+        CellState::Free => rgb(theme.tertiary.fg.unwrap_or(Color::DarkGray)),
+        _ => rgb(theme.cell_color(content)),
+    }
+}
+
+/// Shade a color as lit from the upper left corner
+///
+/// # Parameters
+///
+/// - `color` - The base color
+/// - `x` - The column of the point relative to the center of the disc, as a fraction of its radius
+/// - `y` - The row of the point relative to the center of the disc, as a fraction of its radius
+pub(crate) fn shade(color: [u8; 3], x: f32, y: f32) -> [u8; 3] {
+    let distance = ((x + 0.35).powi(2) + (y + 0.35).powi(2)).sqrt();
+    let light = 0.6 + 0.5 * (1. - distance / 1.6).clamp(0., 1.);
+    let highlight = (1. - distance / 0.45).clamp(0., 1.).powi(2) * 0.6;
+
+    color.map(|channel| {
+        let lit = (f32::from(channel) * light).min(255.);
+        (lit + (255. - lit) * highlight).round() as u8
+    })
+}
+
+/// Write an image with the kitty graphics protocol
+///
+/// The image is sent as [PNG](Image::png) split in chunks and scaled to the given area; nothing is sent if the image is empty.
+fn kitty(sequence: &mut String, image: &Image, area: Rect) {
+    let Ok(png) = image.png() else {
+        return;
+    };
+
+    let encoded = STANDARD.encode(png);
+    let chunks = encoded.as_bytes().chunks(KITTY_CHUNK).collect::<Vec<_>>();
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+
+        if i == 0 {
+            let _ = write!(
+                sequence,
+                "\x1b_Ga=T,f=100,i={KITTY_IMAGE},q=2,C=1,c={},r={},m={more};{chunk}\x1b\\",
+                area.width, area.height,
+            );
+        } else {
+            let _ = write!(sequence, "\x1b_Gm={more};{chunk}\x1b\\");
+        }
+    }
+}
+
+/// Write an image in the _sixel_ format
+///
+/// The colors are quantized to a palette of 216 colors and the mostly transparent pixels are left untouched.
+fn sixel(sequence: &mut String, image: &Image) {
+    let levels = SIXEL_LEVELS - 1;
+    let mut palette = Vec::new();
+
+    let registers = image
+        .pixels
+        .iter()
+        .map(|pixel| {
+            (pixel[3] >= 0x80).then(|| {
+                let color = [0, 1, 2].map(|i| (u16::from(pixel[i]) * levels + 127) / 255);
+
+                palette
+                    .iter()
+                    .position(|&known| known == color)
+                    .unwrap_or_else(|| {
+                        palette.push(color);
+                        palette.len() - 1
+                    })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let _ = write!(
+        sequence,
+        "\x1bP0;1;0q\"1;1;{};{}",
+        image.width, image.height
+    );
+
+    for (register, color) in palette.iter().enumerate() {
+        let [r, g, b] = color.map(|channel| channel * 100 / levels);
+        let _ = write!(sequence, "#{register};2;{r};{g};{b}");
+    }
+
+    for band in (0..image.height).step_by(6) {
+        let rows = band..(band + 6).min(image.height);
+
+        for register in 0..palette.len() {
+            let sixels = (0..image.width)
+                .map(|x| {
+                    rows.clone()
+                        .filter(|&y| registers[(y * image.width + x) as usize] == Some(register))
+                        .fold(0, |bits, y| bits | 1 << (y - band))
+                })
+                .collect::<Vec<u8>>();
+
+            if sixels.iter().all(|&bits| bits == 0) {
+                continue;
+            }
+
+            let _ = write!(sequence, "#{register}");
+
+            for run in sixels.chunk_by(|a, b| a == b) {
+                let symbol = char::from(0x3f + run[0]);
+
+                if run.len() > 3 {
+                    let _ = write!(sequence, "!{}{symbol}", run.len());
+                } else {
+                    sequence.extend(run.iter().map(|_| symbol));
+                }
+            }
+
+            sequence.push('$');
+        }
+
+        sequence.push('-');
+    }
+
+    sequence.push_str("\x1b\\");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::{Board, Player},
+        rng::Rng,
+        widgets::board::{Board as BoardWidget, RenderStyle},
+    };
+    use pretty_assertions::assert_eq;
+    use ratatui::widgets::StatefulWidget;
+
+    #[test]
+    fn guess() {
+        let guess = |vars: &[(&str, &str)]| {
+            Protocol::guess(|name| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            })
+        };
+
+        assert_eq!(guess(&[("TERM", "xterm-kitty")]), Some(Protocol::Kitty));
+        assert_eq!(guess(&[("KITTY_WINDOW_ID", "1")]), Some(Protocol::Kitty));
+        assert_eq!(guess(&[("TERM_PROGRAM", "WezTerm")]), Some(Protocol::Kitty));
+        assert_eq!(guess(&[("TERM", "foot-extra")]), Some(Protocol::Sixel));
+        assert_eq!(guess(&[("TERM", "linux")]), None);
+        assert_eq!(guess(&[]), None);
+
+        assert_eq!(
+            guess(&[("TERM", "xterm-kitty"), ("BLOBWARS_GRAPHICS", "none")]),
+            None
+        );
+
+        assert_eq!(
+            guess(&[("TERM", "xterm"), ("BLOBWARS_GRAPHICS", "sixel")]),
+            Some(Protocol::Sixel)
+        );
+    }
+
+    #[test]
+    fn rgb() {
+        assert_eq!(super::rgb(Color::Rgb(1, 2, 3)), [1, 2, 3]);
+        assert_eq!(super::rgb(Color::Red), [0xcd, 0, 0]);
+        assert_eq!(super::rgb(Color::Indexed(16)), [0, 0, 0]);
+        assert_eq!(super::rgb(Color::Indexed(231)), [255, 255, 255]);
+        assert_eq!(super::rgb(Color::Indexed(196)), [255, 0, 0]);
+        assert_eq!(super::rgb(Color::Indexed(232)), [8, 8, 8]);
+    }
+
+    #[test]
+    fn png() {
+        let mut image = Image::new(2, 1);
+        image.pixels = vec![[1, 2, 3, 4], [5, 6, 7, 8]];
+
+        let png = image.png().unwrap();
+        let mut reader = png::Decoder::new(io::Cursor::new(png)).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+        let frame = reader.next_frame(&mut pixels).unwrap();
+
+        assert_eq!((frame.width, frame.height), (2, 1));
+        assert_eq!(
+            (frame.color_type, frame.bit_depth),
+            (ColorType::Rgba, BitDepth::Eight)
+        );
+        assert_eq!(pixels, [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn kitty() {
+        let mut image = Image::new(40, 40);
+        image.pixels.fill([1, 2, 3, 4]);
+
+        let sequence = Protocol::Kitty.encode(&image, Rect::new(2, 3, 5, 2));
+
+        // A plain image is compressed in a single chunk starting with the PNG signature
+        assert!(sequence.starts_with("\x1b7\x1b[4;3H\x1b_Ga=d,d=I,i=1,q=2\x1b\\"));
+        assert!(sequence.contains("\x1b_Ga=T,f=100,i=1,q=2,C=1,c=5,r=2,m=0;iVBORw0KGgo"));
+        assert!(sequence.ends_with("\x1b\\\x1b8"));
+
+        // Noise barely compresses and is sent in 3 chunks
+        let mut rng = Rng::new(0);
+
+        for pixel in &mut image.pixels {
+            *pixel = (rng.next_u64() as u32).to_be_bytes();
+        }
+
+        let sequence = Protocol::Kitty.encode(&image, Rect::new(2, 3, 5, 2));
+
+        assert!(sequence.contains("\x1b_Ga=T,f=100,i=1,q=2,C=1,c=5,r=2,m=1;"));
+        assert_eq!(sequence.matches("\x1b_Gm=1;").count(), 1);
+        assert_eq!(sequence.matches("\x1b_Gm=0;").count(), 1);
+
+        let empty = Protocol::Kitty.encode(&Image::new(0, 0), Rect::new(0, 0, 1, 1));
+        assert!(!empty.contains("a=T"));
+    }
+
+    #[test]
+    fn sixel() {
+        let mut image = Image::new(5, 7);
+
+        for y in 0..7 {
+            image.pixels[y * 5] = [255, 0, 0, 255];
+            image.pixels[y * 5 + 4] = [0, 0, 255, 255];
+        }
+
+        image.pixels[2] = [0, 255, 0, 0x40];
+
+        let sequence = Protocol::Sixel.encode(&image, Rect::new(0, 0, 1, 1));
+
+        assert_eq!(
+            sequence,
+            "\x1b7\x1b[1;1H\x1bP0;1;0q\"1;1;5;7#0;2;100;0;0#1;2;0;0;100\
+             #0~!4?$#1!4?~$-#0@!4?$#1!4?@$-\x1b\\\x1b8"
+        );
+    }
+
+    #[test]
+    fn compose() {
+        let board: Board = "B.#/..R".parse().unwrap();
+        let mut state = BoardState::new(board, Player::Blue);
+
+        assert!(Image::compose(&state, &Theme::default(), (8, 16)).is_none());
+
+        let area = Rect::new(0, 0, 5, 2);
+        let mut buffer = Buffer::empty(area);
+
+        BoardWidget::default()
+            .render_style(RenderStyle::Images)
+            .render(area, &mut buffer, &mut state);
+
+        let (covered, image) = Image::compose(&state, &Theme::default(), (8, 16)).unwrap();
+
+        assert_eq!(covered, area);
+        assert_eq!((image.width(), image.height()), (40, 32));
+
+        // The blue blob is opaque at its center and transparent in its corners
+        assert_eq!(image.pixel(4, 8).map(|pixel| pixel[3]), Some(255));
+        assert_eq!(image.pixel(0, 0).map(|pixel| pixel[3]), Some(0));
+        assert_eq!(image.pixel(40, 0), None);
+
+        // The free cell only has a small dot and the restricted cell a square
+        assert_eq!(image.pixel(17, 2).map(|pixel| pixel[3]), Some(0));
+        assert_eq!(image.pixel(36, 8).map(|pixel| pixel[3]), Some(255));
+        assert_eq!(image.pixel(33, 2).map(|pixel| pixel[3]), Some(0));
+    }
+}
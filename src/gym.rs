@@ -0,0 +1,378 @@
+//! The implementation of [`Environment`]
+//!
+//! An environment exposes the [rules](crate::game) in the style of the _Gym_ environments, so that agents can be trained by reinforcement learning:
+//! [`Environment::reset()`] starts a new game and [`Environment::step()`] plays an action of the player to move.
+//!
+//! The environment is played by both sides, the agent being asked for a move of whichever player is to move, as for self-play.
+//! The actions are numbered from the departure and destination points of the [moves](Move) and the last one stands for a pass.
+
+use crate::{
+    game::{Board, CellState, Index, Move, Outcome, Player},
+    rng::Rng,
+};
+
+use alloc::{vec, vec::Vec};
+
+/// An observation of the board
+///
+/// It is a tensor of shape `(CHANNELS, height, width)` flattened in row-major order.
+pub type Observation = Vec<f32>;
+
+/// A reinforcement learning environment playing games on generated boards
+#[derive(Clone, Debug, PartialEq)]
+pub struct Environment {
+    height: usize,
+    width: usize,
+    density: u8,
+    max_plies: Option<usize>,
+    rng: Rng,
+    board: Board,
+    player: Player,
+    plies: usize,
+}
+
+impl Environment {
+    /// The number of channels of an [observation](Observation)
+    ///
+    /// The channels hold, in this order, the _blobs_ of the player to move, those of the opponent and the restricted cells; each cell is either `0` or `1`.
+    pub const CHANNELS: usize = 3;
+
+    /// Create a new [`Environment`]
+    ///
+    /// The boards are generated without restricted cells from the seed `0`; the game is not started until the environment is [reset](Self::reset).
+    ///
+    /// # Parameters
+    ///
+    /// - `height` - The height of the generated boards
+    /// - `width` - The width of the generated boards
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            height,
+            width,
+            density: 0,
+            max_plies: None,
+            rng: Rng::new(0),
+            board: Board::free(height, width),
+            player: Player::Blue,
+            plies: 0,
+        }
+    }
+
+    /// Set the percentage of restricted cells of the generated boards
+    pub fn with_density(self, density: u8) -> Self {
+        Self { density, ..self }
+    }
+
+    /// Set the seed the boards are generated from
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self {
+            rng: Rng::new(seed),
+            ..self
+        }
+    }
+
+    /// Set the number of plies after which a game is stopped
+    ///
+    /// A stopped game is done without any reward.
+    pub fn with_max_plies(self, max_plies: usize) -> Self {
+        Self {
+            max_plies: Some(max_plies),
+            ..self
+        }
+    }
+
+    /// Start a new game on a newly generated board
+    ///
+    /// # Return
+    ///
+    /// The [observation](Observation) of the initial position.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::gym::Environment;
+    ///
+    /// let mut environment = Environment::new(5, 5);
+    /// let observation = environment.reset();
+    ///
+    /// assert_eq!(observation.len(), Environment::CHANNELS * 5 * 5);
+    /// assert_eq!(observation.iter().sum::<f32>(), 2.);
+    /// ```
+    pub fn reset(&mut self) -> Observation {
+        self.board = Board::generate(self.height, self.width, self.density, &mut self.rng);
+        self.player = Player::Blue;
+        self.plies = 0;
+        self.observation()
+    }
+
+    /// Play an action of the player to move
+    ///
+    /// # Parameter
+    ///
+    /// - `action` - The [action](Self::action) to play
+    ///
+    /// # Return
+    ///
+    /// The [observation](Observation) of the new position from the point of view of the next player to move,
+    /// the reward of the player who played the action and whether the game is done.
+    /// The reward is `1` for a win, `-1` for a loss and `0` otherwise.
+    /// An error is returned if the action is illegal or if the game is already done.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::gym::Environment;
+    ///
+    /// let mut environment = Environment::new(5, 5);
+    /// environment.reset();
+    ///
+    /// let action = environment.legal_actions()[0];
+    /// let (_, reward, done) = environment.step(action).unwrap();
+    ///
+    /// assert_eq!((reward, done), (0., false));
+    /// assert!(environment.step(environment.action_count()).is_err());
+    /// ```
+    pub fn step(&mut self, action: usize) -> Result<(Observation, f32, bool), &'static str> {
+        if self.is_done() {
+            return Err("The game is over");
+        }
+
+        if !self.legal_actions().contains(&action) {
+            return Err("The action is illegal");
+        }
+
+        if let Some(Some(chosen)) = self.decode(action) {
+            self.board.jump(chosen.from, chosen.to);
+        }
+
+        let mover = self.player;
+        self.player = -self.player;
+        self.plies += 1;
+
+        let reward = match self.board.outcome() {
+            Some(Outcome::Win(winner)) if winner == mover => 1.,
+            Some(Outcome::Win(_)) => -1.,
+            _ => 0.,
+        };
+
+        Ok((self.observation(), reward, self.is_done()))
+    }
+
+    /// Check if the game is done, either because it is over or because it has been stopped
+    pub fn is_done(&self) -> bool {
+        self.board.outcome().is_some() || self.max_plies.is_some_and(|max| self.plies >= max)
+    }
+
+    /// Get the [observation](Observation) of the position from the point of view of the player to move
+    pub fn observation(&self) -> Observation {
+        let len = self.board.len();
+        let mut observation = vec![0.; Self::CHANNELS * len];
+
+        for (index, cell) in self.board.iter().enumerate() {
+            let channel = match cell {
+                CellState::Player(player) if player == self.player => 0,
+                CellState::Player(_) => 1,
+                CellState::Restricted => 2,
+                CellState::Free => continue,
+            };
+
+            observation[channel * len + index] = 1.;
+        }
+
+        observation
+    }
+
+    /// Get the number of actions
+    ///
+    /// There is an action per departure and destination points, and a last one for a pass.
+    pub fn action_count(&self) -> usize {
+        self.board.len() * self.board.len() + 1
+    }
+
+    /// Get the action of a ply
+    ///
+    /// # Parameter
+    ///
+    /// - `ply` - The [move](Move), or `None` for a pass
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{game::Move, gym::Environment};
+    ///
+    /// let environment = Environment::new(5, 5);
+    /// let ply = Some(Move { from: (0, 0), to: (1, 1) });
+    ///
+    /// assert_eq!(environment.action(ply), 6);
+    /// assert_eq!(environment.decode(6), Some(ply));
+    /// assert_eq!(environment.action(None), environment.action_count() - 1);
+    /// ```
+    pub fn action(&self, ply: Option<Move>) -> usize {
+        match ply {
+            Some(chosen) => self.cell(chosen.from) * self.board.len() + self.cell(chosen.to),
+            None => self.action_count() - 1,
+        }
+    }
+
+    /// Get the ply of an action
+    ///
+    /// # Parameter
+    ///
+    /// - `action` - The action
+    ///
+    /// # Return
+    ///
+    /// The ply, being `None` for a pass, or `None` if the action does not exist.
+    pub fn decode(&self, action: usize) -> Option<Option<Move>> {
+        let len = self.board.len();
+
+        if action + 1 == self.action_count() {
+            Some(None)
+        } else if action < len * len {
+            Some(Some(Move {
+                from: self.index(action / len),
+                to: self.index(action % len),
+            }))
+        } else {
+            None
+        }
+    }
+
+    /// Get the legal actions of the player to move
+    ///
+    /// The pass is legal only if the player has no legal [move](Move).
+    pub fn legal_actions(&self) -> Vec<usize> {
+        let mut actions = self
+            .board
+            .moves(self.player)
+            .map(|chosen| self.action(Some(chosen)))
+            .collect::<Vec<_>>();
+
+        if actions.is_empty() {
+            actions.push(self.action(None));
+        }
+
+        actions
+    }
+
+    /// Get the mask of the legal actions of the player to move
+    ///
+    /// The mask holds a flag per action, as needed to mask the output of a policy.
+    pub fn action_mask(&self) -> Vec<bool> {
+        let mut mask = vec![false; self.action_count()];
+
+        for action in self.legal_actions() {
+            mask[action] = true;
+        }
+
+        mask
+    }
+
+    /// Get the current [board](Board)
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Get the [player](Player) to move
+    pub fn current_player(&self) -> Player {
+        self.player
+    }
+
+    fn cell(&self, (row, column): Index) -> usize {
+        row * self.board.width() + column
+    }
+
+    fn index(&self, cell: usize) -> Index {
+        let width = self.board.width().max(1);
+        (cell / width, cell % width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn observation() {
+        let mut environment = Environment::new(2, 3);
+        environment.board = "B.#/..R".parse().unwrap();
+
+        #[rustfmt::skip]
+        let expected = vec![
+            1., 0., 0., 0., 0., 0.,
+            0., 0., 0., 0., 0., 1.,
+            0., 0., 1., 0., 0., 0.,
+        ];
+
+        assert_eq!(environment.observation(), expected);
+
+        #[rustfmt::skip]
+        let expected = vec![
+            0., 0., 0., 0., 0., 1.,
+            1., 0., 0., 0., 0., 0.,
+            0., 0., 1., 0., 0., 0.,
+        ];
+
+        environment.player = Player::Red;
+        assert_eq!(environment.observation(), expected);
+    }
+
+    #[test]
+    fn episode() {
+        let mut environment = Environment::new(4, 4).with_seed(7).with_max_plies(200);
+        environment.reset();
+
+        let mut steps = 0;
+        let mut last = (0., false);
+
+        while !environment.is_done() {
+            let actions = environment.legal_actions();
+            assert!(
+                actions
+                    .iter()
+                    .all(|&action| environment.action_mask()[action])
+            );
+
+            let (observation, reward, done) = environment.step(actions[0]).unwrap();
+            assert_eq!(observation.len(), Environment::CHANNELS * 16);
+
+            last = (reward, done);
+            steps += 1;
+        }
+
+        assert!(last.1);
+        assert!(steps <= 200);
+        assert!(environment.step(0).is_err());
+
+        if let Some(Outcome::Win(winner)) = environment.board().outcome() {
+            assert_eq!(
+                last.0,
+                if winner == -environment.current_player() {
+                    1.
+                } else {
+                    -1.
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn pass() {
+        let mut environment = Environment::new(3, 5);
+        environment.board = "B##../###../###.R".parse().unwrap();
+
+        let pass = environment.action(None);
+        assert_eq!(environment.legal_actions(), [pass]);
+        assert!(environment.step(0).is_err());
+
+        assert_eq!(
+            environment
+                .step(pass)
+                .map(|(_, reward, done)| (reward, done)),
+            Ok((0., false))
+        );
+        assert_eq!(environment.current_player(), Player::Red);
+        assert!(!environment.legal_actions().contains(&pass));
+    }
+}
@@ -0,0 +1,241 @@
+//! The implementation of [`HeadlessGame`]
+//!
+//! A headless game is played on plain text streams, without any terminal user interface, so that it can be scripted or played over a dumb terminal.
+
+use crate::{
+    ai::Ai,
+    game::{Board, CellState, Move, Outcome, Player, display_column},
+    record::{GameRecord, display_ply, parse_ply},
+};
+
+use std::io::{self, BufRead, Write};
+
+/// A game played on plain text streams
+///
+/// The board is written as text after each ply and the plies are read in algebraic notation (`a1b3` or `pass`), one per line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeadlessGame {
+    board: Board,
+    current_player: Player,
+    opponent: Option<(Player, Ai)>,
+    free_pass: bool,
+    record: GameRecord,
+}
+
+impl HeadlessGame {
+    /// Create a new [`HeadlessGame`] between two human players
+    ///
+    /// # Parameters
+    ///
+    /// - `board` - The [board](Board) to play on
+    /// - `first` - The [player](Player) who plays the first ply
+    pub fn new(board: Board, first: Player) -> Self {
+        Self {
+            record: GameRecord::new(board.clone(), first),
+            board,
+            current_player: first,
+            opponent: None,
+            free_pass: false,
+        }
+    }
+
+    /// Play against a computer opponent playing a given player
+    ///
+    /// # Parameters
+    ///
+    /// - `player` - The [player](Player) played by the opponent
+    /// - `opponent` - The [computer opponent](Ai)
+    pub fn with_opponent(self, player: Player, opponent: Ai) -> Self {
+        Self {
+            opponent: Some((player, opponent)),
+            ..self
+        }
+    }
+
+    /// Allow the players to pass their turn at will
+    pub fn with_free_pass(self, free_pass: bool) -> Self {
+        Self { free_pass, ..self }
+    }
+
+    /// Get the [record](GameRecord) of the game
+    pub fn record(&self) -> &GameRecord {
+        &self.record
+    }
+
+    /// Play the game until its end or the end of the input
+    ///
+    /// An illegal or malformed ply is reported and asked again; `quit` stops the game.
+    ///
+    /// # Parameters
+    ///
+    /// - `input` - The stream the plies are read from
+    /// - `output` - The stream the board and the plies are written to
+    ///
+    /// # Return
+    ///
+    /// The [outcome](Outcome) of the game if it is finished
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, Outcome, Player},
+    ///     headless::HeadlessGame,
+    /// };
+    ///
+    /// let mut game = HeadlessGame::new("R.B".parse().unwrap(), Player::Red);
+    /// let mut output = Vec::new();
+    ///
+    /// let outcome = game.play("c1\na1b1\n".as_bytes(), &mut output).unwrap();
+    ///
+    /// assert_eq!(outcome, Some(Outcome::Win(Player::Red)));
+    /// assert!(String::from_utf8(output).unwrap().contains("A move is malformed"));
+    /// ```
+    pub fn play<R, W>(&mut self, input: R, mut output: W) -> io::Result<Option<Outcome>>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        let mut lines = input.lines();
+
+        loop {
+            write_board(&self.board, &mut output)?;
+
+            if let Some(outcome) = self.board.outcome() {
+                let score = self.board.score();
+                writeln!(output, "{outcome} ({} - {})", score.blue, score.red)?;
+                return Ok(Some(outcome));
+            }
+
+            if !self.board.can_play(self.current_player) {
+                writeln!(output, "{} must pass", self.current_player)?;
+                self.end_turn(None);
+                continue;
+            }
+
+            if let Some((player, ai)) = &mut self.opponent
+                && *player == self.current_player
+            {
+                let chosen = ai.choose(&self.board, *player);
+
+                if let Some(chosen) = chosen {
+                    self.board.jump(chosen.from, chosen.to);
+                }
+
+                writeln!(
+                    output,
+                    "{} plays {}",
+                    self.current_player,
+                    display_ply(chosen)
+                )?;
+                self.end_turn(chosen);
+                continue;
+            }
+
+            loop {
+                write!(output, "{} to move> ", self.current_player)?;
+                output.flush()?;
+
+                let Some(line) = lines.next().transpose()? else {
+                    writeln!(output)?;
+                    return Ok(None);
+                };
+
+                let line = line.trim();
+
+                if line == "quit" {
+                    return Ok(None);
+                }
+
+                match self.check(line) {
+                    Ok(chosen) => {
+                        self.end_turn(chosen);
+                        break;
+                    }
+
+                    Err(error) => writeln!(output, "{error}")?,
+                }
+            }
+        }
+    }
+
+    fn check(&mut self, ply: &str) -> Result<Option<Move>, &'static str> {
+        let chosen = parse_ply(ply)?;
+
+        match chosen {
+            Some(chosen) => {
+                if self.board.get(chosen.from.0, chosen.from.1)
+                    != Some(CellState::Player(self.current_player))
+                {
+                    return Err("The departure point is not one of your blobs");
+                }
+
+                if !self.board.jump(chosen.from, chosen.to) {
+                    return Err("A move is illegal");
+                }
+            }
+
+            None if !self.free_pass => return Err("You cannot pass while having a legal move"),
+            None => (),
+        }
+
+        Ok(chosen)
+    }
+
+    fn end_turn(&mut self, chosen: Option<Move>) {
+        self.record.plies.push(chosen);
+        self.current_player = -self.current_player;
+    }
+}
+
+fn write_board(board: &Board, output: &mut impl Write) -> io::Result<()> {
+    let header = (0..board.width())
+        .map(display_column)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    writeln!(output, "    {header}")?;
+
+    for (i, row) in board.to_string().split('/').enumerate() {
+        let cells = row.chars().map(String::from).collect::<Vec<_>>().join(" ");
+        writeln!(output, "{:>3} {cells}", i + 1)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::Personality;
+
+    #[test]
+    fn against_computer() {
+        let mut game = HeadlessGame::new("B..../....R".parse().unwrap(), Player::Blue)
+            .with_opponent(Player::Red, Ai::with_seed(Personality::Aggressive, 0));
+
+        let mut output = Vec::new();
+        let outcome = game.play("a1b1\n".as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        pretty_assertions::assert_eq!(outcome, None);
+        pretty_assertions::assert_eq!(game.record().plies.len(), 2);
+        assert!(output.starts_with("    a b c d e\n  1 B . . . .\n  2 . . . . R\n"));
+        assert!(output.contains("Red plays "));
+    }
+
+    #[test]
+    fn illegal() {
+        let mut game = HeadlessGame::new("B...R".parse().unwrap(), Player::Blue);
+        let mut output = Vec::new();
+
+        game.play("a1e1\npass\nquit\n".as_bytes(), &mut output)
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains("A move is illegal"));
+        assert!(output.contains("You cannot pass while having a legal move"));
+        assert!(game.record().plies.is_empty());
+    }
+}
@@ -0,0 +1,446 @@
+//! The implementation of [`Hooks`]
+//!
+//! The hooks are functions of a [Rhai](https://rhai.rs) script, so that users can script custom bots, annotations or house rules without recompiling the game.
+//! The script is sandboxed: it cannot reach the file system, the processes nor other scripts, its resources are bounded,
+//! and it only works on copies of the boards, through the [API](Hooks#board-api) registered below.
+
+use crate::{
+    config::Config,
+    game::{Board, CellState, Move, Outcome, Player, RuleSet},
+    record::{display_ply, parse_ply},
+};
+
+use rhai::{
+    AST, Array, Dynamic, Engine, EvalAltResult, FuncArgs, INT, ParseError, Scope,
+    module_resolvers::DummyModuleResolver,
+};
+
+use std::{
+    fmt::{Debug, Formatter},
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// An event of the game triggering a [hook](Hooks)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HookEvent<'a> {
+    /// A ply has been played
+    Move {
+        /// The [player](Player) who has played
+        player: Player,
+
+        /// The [move](Move) played, or `None` for a pass
+        ply: Option<Move>,
+
+        /// The [board](Board) after the ply
+        board: &'a Board,
+    },
+
+    /// The game is over
+    GameOver {
+        /// The [outcome](Outcome) of the game
+        outcome: Outcome,
+
+        /// The final [board](Board)
+        board: &'a Board,
+    },
+}
+
+/// The script hooked on the game [events](HookEvent)
+///
+/// The script is read from a [file](Self::path) and may define the following functions, all of them being optional:
+///
+/// - `on_move(board, player, ply)` is called after each ply, the ply being written as in the [records](crate::record::GameRecord) (`a1b3` or `pass`);
+/// - `on_game_over(board, outcome)` is called at the end of a game;
+/// - `choose_move(board, player)` returns the ply the computer opponent plays, which falls back to its own choice if the ply is not legal;
+/// - `rules(rules)` returns the [rules](RuleSet) of the new games, written as in the map files (`jump=3 misere=true`), from the configured ones.
+///
+/// When `on_move` or `on_game_over` returns a string, it is shown to the players as an annotation.
+///
+/// # Board API
+///
+/// The boards given to the script are copies of the played ones; the players are written `"Blue"` and `"Red"`.
+///
+/// - `board.height` and `board.width` are the size of the board;
+/// - `board.rules` is the rules of the board;
+/// - `board.cell(row, column)` is `"Blue"`, `"Red"`, `"free"` or `"restricted"`, or `()` outside the board;
+/// - `board.score(player)` is the number of _blobs_ of a player;
+/// - `board.moves(player)` is the array of the moves of a player;
+/// - `board.can_play(player)` checks if a player has any move;
+/// - `board.play(ply)` plays a move on the copy and checks if it is legal.
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::{
+///     game::{Board, Move, Player},
+///     hooks::Hooks,
+/// };
+///
+/// let hooks: Hooks = r#"
+///     fn choose_move(board, player) {
+///         board.moves(player)[0]
+///     }
+/// "#
+/// .parse()
+/// .unwrap();
+///
+/// let board: Board = "R.B".parse().unwrap();
+/// assert_eq!(hooks.choose_move(&board, Player::Red), Some(Move { from: (0, 0), to: (0, 1) }));
+/// ```
+#[derive(Clone, Default)]
+pub struct Hooks {
+    script: Option<Arc<Script>>,
+}
+
+struct Script {
+    engine: Engine,
+    ast: AST,
+    source: String,
+}
+
+impl Hooks {
+    /// The maximum number of operations run by a single call of the script
+    pub const MAX_OPERATIONS: u64 = 1_000_000;
+
+    /// Get the path of the script
+    ///
+    /// The file is located next to the [configuration file](Config::path).
+    pub fn path() -> Option<PathBuf> {
+        Config::path().map(|path| path.with_file_name("hooks.rhai"))
+    }
+
+    /// Load the script
+    ///
+    /// If the file does not exist or does not compile, no hook is run.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| {
+                Self::read(&path)
+                    .inspect_err(|error| {
+                        if error.kind() != ErrorKind::NotFound {
+                            tracing::warn!("The hooks cannot be loaded: {error}");
+                        }
+                    })
+                    .ok()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Read the script from a given file
+    ///
+    /// # Parameter
+    ///
+    /// - `path` - The path of the file
+    pub fn read(path: &Path) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|error: ParseError| io::Error::new(ErrorKind::InvalidData, error.to_string()))
+    }
+
+    /// Check if no hook is set
+    pub fn is_empty(&self) -> bool {
+        self.script.is_none()
+    }
+
+    /// Run the hook of an [event](HookEvent), if any
+    ///
+    /// # Parameter
+    ///
+    /// - `event` - The [event](HookEvent) triggering the hook
+    ///
+    /// # Return
+    ///
+    /// The annotation returned by the hook, if any
+    pub fn run(&self, event: HookEvent) -> Result<Option<String>, Box<EvalAltResult>> {
+        let result = match event {
+            HookEvent::Move { player, ply, board } => self.call(
+                "on_move",
+                (board.clone(), player.to_string(), display_ply(ply)),
+            ),
+
+            HookEvent::GameOver { outcome, board } => {
+                self.call("on_game_over", (board.clone(), outcome.to_string()))
+            }
+        }?;
+
+        Ok(result.and_then(|value| value.try_cast::<String>()))
+    }
+
+    /// Get the ply chosen by the script for a given player, if any
+    ///
+    /// The ply is only returned if it is a legal move; the failures of the script are logged.
+    ///
+    /// # Parameters
+    ///
+    /// - `board` - The current [board](Board)
+    /// - `player` - The [player](Player) to move
+    pub fn choose_move(&self, board: &Board, player: Player) -> Option<Move> {
+        let chosen = self
+            .call("choose_move", (board.clone(), player.to_string()))
+            .inspect_err(|error| tracing::warn!("The script cannot choose a move: {error}"))
+            .ok()??;
+
+        let chosen = chosen
+            .into_string()
+            .ok()
+            .and_then(|ply| parse_ply(&ply).ok().flatten())
+            .filter(|chosen| board.moves(player).any(|legal| legal == *chosen));
+
+        if chosen.is_none() {
+            tracing::warn!("The script has chosen an illegal move");
+        }
+
+        chosen
+    }
+
+    /// Get the rules of a new game
+    ///
+    /// The given rules are kept if the script does not define them or if the ones it returns are malformed.
+    ///
+    /// # Parameter
+    ///
+    /// - `rules` - The configured [rules](RuleSet)
+    pub fn rules(&self, rules: RuleSet) -> RuleSet {
+        let result = self
+            .call("rules", (rules.to_string(),))
+            .map_err(|error| error.to_string())
+            .and_then(|value| {
+                value.map_or(Ok(rules), |value| {
+                    value
+                        .into_string()
+                        .map_err(str::to_owned)?
+                        .parse()
+                        .map_err(str::to_owned)
+                })
+            });
+
+        result
+            .inspect_err(|error| tracing::warn!("The rules of the script are ignored: {error}"))
+            .unwrap_or(rules)
+    }
+
+    /// Call a function of the script if it is defined
+    fn call(&self, name: &str, args: impl FuncArgs) -> Result<Option<Dynamic>, Box<EvalAltResult>> {
+        let Some(script) = &self.script else {
+            return Ok(None);
+        };
+
+        if !script
+            .ast
+            .iter_functions()
+            .any(|function| function.name == name)
+        {
+            return Ok(None);
+        }
+
+        script
+            .engine
+            .call_fn(&mut Scope::new(), &script.ast, name, args)
+            .map(Some)
+    }
+}
+
+impl Debug for Hooks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hooks")
+            .field("script", &self.script.as_ref().map(|script| &script.source))
+            .finish()
+    }
+}
+
+impl PartialEq for Hooks {
+    fn eq(&self, other: &Self) -> bool {
+        self.script.as_ref().map(|script| &script.source)
+            == other.script.as_ref().map(|script| &script.source)
+    }
+}
+
+impl Eq for Hooks {}
+
+impl FromStr for Hooks {
+    type Err = ParseError;
+
+    /// Compile a script
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let engine = sandbox();
+        let ast = engine.compile(s)?;
+
+        Ok(Self {
+            script: Some(Arc::new(Script {
+                engine,
+                ast,
+                source: s.to_owned(),
+            })),
+        })
+    }
+}
+
+/// Create the engine running the scripts, without any access to the system and with bounded resources
+fn sandbox() -> Engine {
+    let mut engine = Engine::new();
+
+    engine
+        .set_max_operations(Hooks::MAX_OPERATIONS)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(1 << 16)
+        .set_max_array_size(1 << 16)
+        .set_max_map_size(1 << 12)
+        .set_module_resolver(DummyModuleResolver::new())
+        .disable_symbol("eval")
+        .on_print(|text| tracing::info!("The script prints: {text}"))
+        .on_debug(|text, _, position| tracing::debug!("The script at {position}: {text}"));
+
+    engine
+        .register_type_with_name::<Board>("Board")
+        .register_get("height", |board: &mut Board| board.height() as INT)
+        .register_get("width", |board: &mut Board| board.width() as INT)
+        .register_get("rules", |board: &mut Board| board.rules().to_string())
+        .register_fn("cell", cell)
+        .register_fn("score", score)
+        .register_fn("moves", moves)
+        .register_fn("can_play", can_play)
+        .register_fn("play", play)
+        .register_fn("to_string", |board: &mut Board| board.to_string())
+        .register_fn("to_debug", |board: &mut Board| board.to_string());
+
+    engine
+}
+
+fn cell(board: &mut Board, row: INT, column: INT) -> Dynamic {
+    let (Ok(row), Ok(column)) = (usize::try_from(row), usize::try_from(column)) else {
+        return Dynamic::UNIT;
+    };
+
+    match board.get(row, column) {
+        Some(CellState::Player(player)) => player.to_string().into(),
+        Some(CellState::Free) => "free".into(),
+        Some(CellState::Restricted) => "restricted".into(),
+        None => Dynamic::UNIT,
+    }
+}
+
+fn score(board: &mut Board, player: &str) -> Result<INT, Box<EvalAltResult>> {
+    Ok(board.score().get(parse_player(player)?) as INT)
+}
+
+fn moves(board: &mut Board, player: &str) -> Result<Array, Box<EvalAltResult>> {
+    Ok(board
+        .moves(parse_player(player)?)
+        .map(|legal| display_ply(Some(legal)).into())
+        .collect())
+}
+
+fn can_play(board: &mut Board, player: &str) -> Result<bool, Box<EvalAltResult>> {
+    Ok(board.can_play(parse_player(player)?))
+}
+
+fn play(board: &mut Board, ply: &str) -> Result<bool, Box<EvalAltResult>> {
+    Ok(match parse_ply(ply).map_err(|error| error.to_string())? {
+        Some(chosen) => board.jump(chosen.from, chosen.to),
+        None => false,
+    })
+}
+
+fn parse_player(player: &str) -> Result<Player, Box<EvalAltResult>> {
+    player.parse().map_err(|error: &str| error.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotate() {
+        let hooks: Hooks = r#"
+            fn on_move(board, player, ply) {
+                if board.score(player) > board.score("Blue") {
+                    `${player} leads after ${ply}`
+                }
+            }
+        "#
+        .parse()
+        .unwrap();
+
+        let board: Board = "RR.B".parse().unwrap();
+
+        let annotation = hooks.run(HookEvent::Move {
+            player: Player::Red,
+            ply: Some(Move {
+                from: (0, 0),
+                to: (0, 1),
+            }),
+            board: &board,
+        });
+
+        pretty_assertions::assert_eq!(annotation.unwrap(), Some("Red leads after a1b1".to_owned()));
+
+        let annotation = hooks.run(HookEvent::GameOver {
+            outcome: Outcome::Draw,
+            board: &board,
+        });
+
+        pretty_assertions::assert_eq!(annotation.unwrap(), None);
+    }
+
+    #[test]
+    fn choose_move() {
+        let board: Board = "R..B".parse().unwrap();
+
+        let hooks: Hooks = r#"
+            fn choose_move(board, player) {
+                let moves = board.moves(player);
+                let copy = board;
+                copy.play(moves[moves.len() - 1]);
+                if copy.cell(0, 2) == player { moves[moves.len() - 1] } else { "pass" }
+            }
+        "#
+        .parse()
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            hooks.choose_move(&board, Player::Red),
+            Some(Move {
+                from: (0, 0),
+                to: (0, 2),
+            })
+        );
+
+        let illegal: Hooks = r#"fn choose_move(board, player) { "a1d1" }"#.parse().unwrap();
+        pretty_assertions::assert_eq!(illegal.choose_move(&board, Player::Red), None);
+    }
+
+    #[test]
+    fn rules() {
+        let hooks: Hooks = r#"fn rules(rules) { rules + " misere=true" }"#.parse().unwrap();
+        let rules = hooks.rules(RuleSet::default());
+
+        assert!(rules.misere);
+        pretty_assertions::assert_eq!(Hooks::default().rules(rules), rules);
+    }
+
+    #[test]
+    fn sandbox() {
+        let board: Board = "R..B".parse().unwrap();
+
+        let hooks: Hooks = "fn choose_move(board, player) { loop {} }".parse().unwrap();
+        pretty_assertions::assert_eq!(hooks.choose_move(&board, Player::Red), None);
+
+        let hooks: Hooks = r#"fn on_game_over(board, outcome) { import "hooks" as hooks; }"#
+            .parse()
+            .unwrap();
+
+        let event = HookEvent::GameOver {
+            outcome: Outcome::Draw,
+            board: &board,
+        };
+
+        assert!(hooks.run(event).is_err());
+
+        assert!(r#"fn on_game_over(board, outcome) { eval("1") }"#.parse::<Hooks>().is_err());
+    }
+}
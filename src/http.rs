@@ -0,0 +1,713 @@
+//! The implementation of [`HttpServer`], built on [`axum`]
+//!
+//! An HTTP server hosts games through a small REST interface, so that a web user interface can be built on top of it:
+//!
+//! - `GET /games` lists the identifiers of the games;
+//! - `POST /games` creates a game on a board generated from the [configuration](Config) and answers its state along with the [tokens](Token) of its seats, with the `201 Created` status;
+//! - `GET /games/{id}` answers the state of a game;
+//! - `POST /games/{id}/moves` plays a ply for the player to move and answers the new state of the game.
+//!
+//! A ply is sent as `{"ply":"a1b3"}` or `{"ply":"pass"}`, with the token of the seat of the player to move as `Authorization: Bearer <token>`.
+//! A request without a token is answered with the `401 Unauthorized` status, a token unknown to the game with `403 Forbidden` and the token of the other seat with `409 Conflict`.
+//! The plies are validated by a [referee](Referee), exactly as for the network games; an illegal ply is answered with the `422 Unprocessable Content` status.
+//!
+//! The answers are written in JSON; a state looks like
+//! `{"id":1,"board":"B.#/..R","turn":"Blue","score":{"blue":1,"red":1},"outcome":null,"plies":[]}`,
+//! a created game adds `"tokens":{"blue":"…","red":"…"}` and an error looks like `{"error":"The game does not exist"}`.
+//!
+//! # Limits
+//!
+//! - The request line and the headers of a request hold at most [`HttpServer::MAX_HEAD`] bytes and [`HttpServer::MAX_HEADERS`] headers; a larger request is answered with the `431 Request Header Fields Too Large` status.
+//! - The body of a request holds at most [`HttpServer::MAX_BODY`] bytes; a larger one is answered with the `413 Content Too Large` status.
+//! - At most [`HttpServer::MAX_CONNECTIONS`] connections are served at once, the next ones waiting in the backlog of the listener.
+//!   Each connection serves a single request and is closed after [`HttpServer::TIMEOUT`] at most.
+//! - At most [`HttpServer::MAX_GAMES`] games are hosted: the games where no ply has been played for [`HttpServer::IDLE`] are removed when a game is created, and the creation is answered with the `503 Service Unavailable` status when there is still no room.
+//!   The games being played are never removed.
+
+use crate::{
+    config::Config,
+    game::{Board, Outcome, Player},
+    record::{GameRecord, display_ply, parse_ply},
+    rng::Rng,
+    server::Referee,
+};
+
+use axum::{
+    Json, Router,
+    extract::{DefaultBodyLimit, Path, State, rejection::JsonRejection},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+
+use hyper::server::conn::http1;
+use hyper_util::{rt::TokioIo, service::TowerToHyperService};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use tokio::{runtime, sync::Semaphore, time};
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    io,
+    net::{SocketAddr, TcpListener, ToSocketAddrs},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// The secret identifying the seat of a player in a game hosted by an [HTTP server](HttpServer)
+///
+/// A token is made of 32 hexadecimal digits drawn from the random generator of the operating system, so that it cannot be guessed even when the boards are generated from a known seed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token(String);
+
+impl Token {
+    fn generate() -> io::Result<Self> {
+        let mut bytes = [0; 16];
+
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| io::Error::other("The random generator of the system has failed"))?;
+
+        let mut token = String::new();
+
+        for byte in bytes {
+            let _ = write!(token, "{byte:02x}");
+        }
+
+        Ok(Self(token))
+    }
+
+    /// Check if the token is a given one, in a time not depending on their common prefix
+    fn is(&self, other: &str) -> bool {
+        let (this, other) = (self.0.as_bytes(), other.as_bytes());
+
+        this.len() == other.len()
+            && this
+                .iter()
+                .zip(other)
+                .fold(0, |difference, (a, b)| difference | (a ^ b))
+                == 0
+    }
+}
+
+/// A game hosted by an [HTTP server](HttpServer)
+#[derive(Clone, Debug)]
+struct Game {
+    referee: Referee,
+    record: GameRecord,
+    tokens: (Token, Token),
+    played: Instant,
+}
+
+impl Game {
+    /// Get the seat of a token
+    fn seat(&self, token: &str) -> Option<Player> {
+        if self.tokens.0.is(token) {
+            Some(Player::Blue)
+        } else if self.tokens.1.is(token) {
+            Some(Player::Red)
+        } else {
+            None
+        }
+    }
+}
+
+/// The games hosted by an [HTTP server](HttpServer)
+#[derive(Debug, Default)]
+struct Games {
+    games: BTreeMap<u64, Game>,
+    last: u64,
+}
+
+impl Games {
+    /// Host a new game, removing the idle ones first
+    ///
+    /// # Return
+    ///
+    /// The identifier of the game, or `None` if [`HttpServer::MAX_GAMES`] games are still hosted
+    fn insert(&mut self, game: Game, now: Instant) -> Option<u64> {
+        self.games.retain(|&id, game| {
+            let idle = now.duration_since(game.played) >= HttpServer::IDLE;
+
+            if idle {
+                tracing::info!("The idle game {id} is removed");
+            }
+
+            !idle
+        });
+
+        if self.games.len() >= HttpServer::MAX_GAMES {
+            return None;
+        }
+
+        self.last += 1;
+        self.games.insert(self.last, game);
+        Some(self.last)
+    }
+}
+
+/// The state shared by the requests
+#[derive(Clone, Debug)]
+struct Shared {
+    games: Arc<Mutex<Games>>,
+    rng: Arc<Mutex<Rng>>,
+    config: Config,
+}
+
+/// The state of a game, as answered
+#[derive(Serialize)]
+struct GameState {
+    id: u64,
+    board: String,
+    turn: String,
+    score: Score,
+    outcome: Option<String>,
+    plies: Vec<String>,
+}
+
+impl GameState {
+    fn new(id: u64, game: &Game) -> Self {
+        let board = game.referee.board();
+        let score = board.score();
+
+        Self {
+            id,
+            board: board.to_string(),
+            turn: game.referee.current_player().to_string(),
+            score: Score {
+                blue: score.blue,
+                red: score.red,
+            },
+            outcome: board.outcome().map(|outcome| match outcome {
+                Outcome::Win(player) => player.to_string(),
+                Outcome::Draw => "Draw".to_owned(),
+            }),
+            plies: game
+                .record
+                .plies
+                .iter()
+                .map(|&ply| display_ply(ply))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Score {
+    blue: usize,
+    red: usize,
+}
+
+/// The answer to the creation of a game
+#[derive(Serialize)]
+struct CreatedGame {
+    #[serde(flatten)]
+    state: GameState,
+    tokens: Tokens,
+}
+
+#[derive(Serialize)]
+struct Tokens {
+    blue: String,
+    red: String,
+}
+
+/// A ply submitted to a game
+#[derive(Deserialize)]
+struct PlyRequest {
+    ply: String,
+}
+
+/// An error answered by the [HTTP server](HttpServer)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Error(StatusCode, &'static str);
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        #[derive(Serialize)]
+        struct Body {
+            error: &'static str,
+        }
+
+        (self.0, Json(Body { error: self.1 })).into_response()
+    }
+}
+
+const NOT_FOUND: Error = Error(StatusCode::NOT_FOUND, "The game does not exist");
+
+/// A server hosting games through HTTP
+#[derive(Debug)]
+pub struct HttpServer {
+    listener: TcpListener,
+    shared: Shared,
+}
+
+impl HttpServer {
+    /// The address listened on by default
+    pub const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+
+    /// The largest request line and headers accepted in a request, in bytes
+    pub const MAX_HEAD: usize = 8192;
+
+    /// The largest number of headers accepted in a request
+    pub const MAX_HEADERS: usize = 32;
+
+    /// The largest body accepted in a request, in bytes
+    pub const MAX_BODY: usize = 1024;
+
+    /// The largest number of connections served at once
+    pub const MAX_CONNECTIONS: usize = 256;
+
+    /// The duration after which a connection is closed, whether its request has been answered or not
+    pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// The largest number of games hosted at once
+    pub const MAX_GAMES: usize = 1024;
+
+    /// The duration after which a game where no ply has been played is removed
+    pub const IDLE: Duration = Duration::from_secs(60 * 60);
+
+    /// Create a new [`HttpServer`] listening on a given address
+    ///
+    /// # Parameter
+    ///
+    /// - `address` - The address listened on
+    pub fn bind<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(address)?,
+            shared: Shared {
+                games: Arc::default(),
+                rng: Arc::new(Mutex::new(Rng::default())),
+                config: Config::default(),
+            },
+        })
+    }
+
+    /// Generate the boards and apply the rules from a given [configuration](Config)
+    ///
+    /// Only the size and the density of the boards and whether a player may pass at will are used.
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.shared.config = config;
+        self
+    }
+
+    /// Generate the boards from a given [random number generator](Rng)
+    ///
+    /// The boards are generated from a random seed by default.
+    pub fn with_rng(mut self, rng: Rng) -> Self {
+        self.shared.rng = Arc::new(Mutex::new(rng));
+        self
+    }
+
+    /// Get the address actually listened on
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Get the [router](Router) answering the requests, to be served by another server
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/games", get(list).post(create))
+            .route("/games/{id}", get(state))
+            .route("/games/{id}/moves", post(play))
+            .method_not_allowed_fallback(async || {
+                Error(StatusCode::METHOD_NOT_ALLOWED, "The method is not allowed")
+            })
+            .fallback(async || Error(StatusCode::NOT_FOUND, "The resource does not exist"))
+            .layer(DefaultBodyLimit::max(Self::MAX_BODY))
+            .with_state(self.shared.clone())
+    }
+
+    /// Serve the clients until the listener fails
+    ///
+    /// The connections are served by a pool of threads, within the [limits](self#limits) of the server.
+    pub fn run(&self) -> io::Result<()> {
+        runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()?
+            .block_on(self.serve())
+    }
+
+    async fn serve(&self) -> io::Result<()> {
+        let listener = self.listener.try_clone()?;
+        listener.set_nonblocking(true)?;
+
+        let listener = tokio::net::TcpListener::from_std(listener)?;
+        let connections = Arc::new(Semaphore::new(Self::MAX_CONNECTIONS));
+        let service = TowerToHyperService::new(self.router());
+
+        loop {
+            let permit = connections
+                .clone()
+                .acquire_owned()
+                .await
+                .map_err(io::Error::other)?;
+
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+
+                Err(error)
+                    if matches!(
+                        error.kind(),
+                        io::ErrorKind::ConnectionAborted | io::ErrorKind::ConnectionReset
+                    ) =>
+                {
+                    tracing::warn!("A client cannot be accepted: {error}");
+                    continue;
+                }
+
+                Err(error) => return Err(error),
+            };
+
+            let connection = http1::Builder::new()
+                .keep_alive(false)
+                .max_buf_size(Self::MAX_HEAD)
+                .max_headers(Self::MAX_HEADERS)
+                .serve_connection(TokioIo::new(stream), service.clone());
+
+            tokio::spawn(async move {
+                match time::timeout(Self::TIMEOUT, connection).await {
+                    Ok(Ok(())) => (),
+                    Ok(Err(error)) => tracing::warn!("A request cannot be answered: {error}"),
+                    Err(_) => tracing::warn!("A client is disconnected after the timeout"),
+                }
+
+                drop(permit);
+            });
+        }
+    }
+}
+
+async fn list(State(shared): State<Shared>) -> Json<Vec<u64>> {
+    Json(lock(&shared.games).games.keys().copied().collect())
+}
+
+async fn create(State(shared): State<Shared>) -> Result<impl IntoResponse, Error> {
+    let config = shared.config;
+    let board = Board::generate(
+        config.height,
+        config.width,
+        config.density,
+        &mut lock(&shared.rng),
+    );
+
+    let tokens = Token::generate()
+        .and_then(|blue| Ok((blue, Token::generate()?)))
+        .map_err(|error| {
+            tracing::warn!("The tokens of a game cannot be generated: {error}");
+            Error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "The game cannot be created",
+            )
+        })?;
+
+    let game = Game {
+        referee: Referee::new(board.clone(), Player::Blue).with_free_pass(config.free_pass),
+        record: GameRecord::new(board, Player::Blue),
+        tokens: tokens.clone(),
+        played: Instant::now(),
+    };
+
+    let state = GameState::new(0, &game);
+    let id = lock(&shared.games)
+        .insert(game, Instant::now())
+        .ok_or(Error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Too many games are hosted",
+        ))?;
+
+    tracing::info!("The game {id} is created");
+
+    let created = CreatedGame {
+        state: GameState { id, ..state },
+        tokens: Tokens {
+            blue: tokens.0.0,
+            red: tokens.1.0,
+        },
+    };
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+async fn state(
+    State(shared): State<Shared>,
+    Path(id): Path<String>,
+) -> Result<Json<GameState>, Error> {
+    let id = id.parse().map_err(|_| NOT_FOUND)?;
+    let games = lock(&shared.games);
+    let game = games.games.get(&id).ok_or(NOT_FOUND)?;
+
+    Ok(Json(GameState::new(id, game)))
+}
+
+async fn play(
+    State(shared): State<Shared>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    request: Result<Json<PlyRequest>, JsonRejection>,
+) -> Result<Json<GameState>, Error> {
+    let id = id.parse().map_err(|_| NOT_FOUND)?;
+
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(Error(
+            StatusCode::UNAUTHORIZED,
+            "The token of the seat is missing",
+        ))?;
+
+    let Json(request) = request.map_err(|rejection| match rejection {
+        JsonRejection::BytesRejection(_) => {
+            Error(StatusCode::PAYLOAD_TOO_LARGE, "The body is too large")
+        }
+
+        _ => Error(StatusCode::BAD_REQUEST, "The body is not a ply"),
+    })?;
+
+    let ply =
+        parse_ply(request.ply.trim()).map_err(|error| Error(StatusCode::BAD_REQUEST, error))?;
+
+    let mut games = lock(&shared.games);
+    let game = games.games.get_mut(&id).ok_or(NOT_FOUND)?;
+
+    let seat = game.seat(token).ok_or(Error(
+        StatusCode::FORBIDDEN,
+        "The token does not belong to the game",
+    ))?;
+
+    if game.referee.board().outcome().is_some() {
+        return Err(Error(StatusCode::UNPROCESSABLE_ENTITY, "The game is over"));
+    }
+
+    if seat != game.referee.current_player() {
+        return Err(Error(
+            StatusCode::CONFLICT,
+            "It is not the turn of the seat",
+        ));
+    }
+
+    if !game.referee.play(seat, ply) {
+        return Err(Error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "The ply is illegal",
+        ));
+    }
+
+    game.record.plies.push(ply);
+    game.played = Instant::now();
+
+    Ok(Json(GameState::new(id, game)))
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use tower::ServiceExt;
+
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+        thread,
+    };
+
+    /// Send a request to a router and get the status and the body of the answer
+    fn request(
+        router: &Router,
+        method: &str,
+        path: &str,
+        token: Option<&str>,
+        body: &str,
+    ) -> (StatusCode, String) {
+        let mut request = axum::http::Request::builder()
+            .method(method)
+            .uri(path)
+            .header(header::CONTENT_TYPE, "application/json");
+
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+
+        let request = request.body(Body::from(body.to_owned())).unwrap();
+
+        runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(async {
+                let response = router.clone().oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+
+                (status, String::from_utf8(body.to_vec()).unwrap())
+            })
+    }
+
+    fn game(board: &str, played: Instant) -> Game {
+        Game {
+            referee: Referee::new(board.parse().unwrap(), Player::Blue),
+            record: GameRecord::new(board.parse().unwrap(), Player::Blue),
+            tokens: (Token("blue".to_owned()), Token("red".to_owned())),
+            played,
+        }
+    }
+
+    #[test]
+    fn play() {
+        let server = HttpServer::bind("127.0.0.1:0").unwrap();
+        lock(&server.shared.games).insert(game("B..R", Instant::now()), Instant::now());
+
+        let router = server.router();
+        let error = |status, error: &str| (status, format!("{{\"error\":\"{error}\"}}"));
+        let moves = "/games/1/moves";
+
+        pretty_assertions::assert_eq!(
+            request(&router, "POST", moves, None, r#"{"ply":"a1b1"}"#),
+            error(StatusCode::UNAUTHORIZED, "The token of the seat is missing")
+        );
+        pretty_assertions::assert_eq!(
+            request(&router, "POST", moves, Some("green"), r#"{"ply":"a1b1"}"#),
+            error(
+                StatusCode::FORBIDDEN,
+                "The token does not belong to the game"
+            )
+        );
+        pretty_assertions::assert_eq!(
+            request(&router, "POST", moves, Some("red"), r#"{"ply":"d1c1"}"#),
+            error(StatusCode::CONFLICT, "It is not the turn of the seat")
+        );
+        pretty_assertions::assert_eq!(
+            request(&router, "POST", moves, Some("blue"), r#"{"ply":"d1c1"}"#),
+            error(StatusCode::UNPROCESSABLE_ENTITY, "The ply is illegal")
+        );
+        pretty_assertions::assert_eq!(
+            request(&router, "POST", moves, Some("blue"), r#"{"ply":"a1"}"#),
+            error(StatusCode::BAD_REQUEST, "A move is malformed")
+        );
+        pretty_assertions::assert_eq!(
+            request(&router, "POST", moves, Some("blue"), "a1b1"),
+            error(StatusCode::BAD_REQUEST, "The body is not a ply")
+        );
+
+        pretty_assertions::assert_eq!(
+            request(&router, "POST", moves, Some("blue"), r#"{"ply":"a1b1"}"#),
+            (
+                StatusCode::OK,
+                r#"{"id":1,"board":"BB.R","turn":"Red","score":{"blue":2,"red":1},"outcome":null,"plies":["a1b1"]}"#
+                    .to_owned()
+            )
+        );
+
+        let long = format!(r#"{{"ply":"{}"}}"#, " ".repeat(HttpServer::MAX_BODY));
+
+        pretty_assertions::assert_eq!(
+            request(&router, "POST", moves, Some("red"), &long),
+            error(StatusCode::PAYLOAD_TOO_LARGE, "The body is too large")
+        );
+        pretty_assertions::assert_eq!(
+            request(&router, "DELETE", "/games/1", None, "").0,
+            StatusCode::METHOD_NOT_ALLOWED
+        );
+        pretty_assertions::assert_eq!(
+            request(&router, "GET", "/games/2", None, ""),
+            error(StatusCode::NOT_FOUND, "The game does not exist")
+        );
+    }
+
+    #[test]
+    fn create() {
+        let seeded = |seed| {
+            let server = HttpServer::bind("127.0.0.1:0")
+                .unwrap()
+                .with_rng(Rng::new(seed));
+            let router = server.router();
+
+            let (status, body) = request(&router, "POST", "/games", None, "");
+            pretty_assertions::assert_eq!(status, StatusCode::CREATED);
+
+            let created = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+            let games = lock(&server.shared.games);
+            let game = &games.games[&1];
+
+            pretty_assertions::assert_eq!(created["tokens"]["blue"], game.tokens.0.0);
+            pretty_assertions::assert_eq!(created["tokens"]["red"], game.tokens.1.0);
+            assert_ne!(game.tokens.0, game.tokens.1);
+
+            (created["board"].clone(), game.tokens.clone())
+        };
+
+        let (first, first_tokens) = seeded(42);
+        let (second, second_tokens) = seeded(42);
+
+        pretty_assertions::assert_eq!(first, second);
+        assert_ne!(first_tokens, second_tokens);
+    }
+
+    #[test]
+    fn eviction() {
+        let mut games = Games::default();
+        let start = Instant::now();
+
+        for _ in 0..HttpServer::MAX_GAMES {
+            assert!(games.insert(game("B..R", start), start).is_some());
+        }
+
+        games.games.get_mut(&1).unwrap().played = start + Duration::from_secs(1);
+        let later = start + Duration::from_secs(2);
+
+        pretty_assertions::assert_eq!(games.insert(game("B..R", later), later), None);
+        pretty_assertions::assert_eq!(games.games.len(), HttpServer::MAX_GAMES);
+
+        let idle = start + HttpServer::IDLE;
+
+        pretty_assertions::assert_eq!(games.insert(game("B..R", idle), idle), Some(1025));
+        pretty_assertions::assert_eq!(
+            games.games.keys().copied().collect::<Vec<_>>(),
+            vec![1, 1025]
+        );
+    }
+
+    #[test]
+    fn serve() {
+        let server = HttpServer::bind("127.0.0.1:0").unwrap();
+        let address = server.local_addr().unwrap();
+
+        thread::spawn(move || server.run());
+
+        let send = |request: &[u8]| {
+            let mut stream = TcpStream::connect(address).unwrap();
+            let _ = stream.write_all(request);
+
+            let mut response = String::new();
+            let _ = stream.read_to_string(&mut response);
+            response
+        };
+
+        let response =
+            send(b"POST /games HTTP/1.1\r\nHost: localhost\r\nContent-Length: 0\r\n\r\n");
+
+        assert!(response.starts_with("HTTP/1.1 201 Created\r\n"));
+        assert!(response.contains("\"outcome\":null,\"plies\":[],\"tokens\":{"));
+
+        let long = format!(
+            "GET /games HTTP/1.1\r\nHost: localhost\r\nX-Padding: {}\r\n\r\n",
+            "a".repeat(HttpServer::MAX_HEAD)
+        );
+
+        assert!(send(long.as_bytes()).starts_with("HTTP/1.1 431 "));
+
+        let many = format!(
+            "GET /games HTTP/1.1\r\nHost: localhost\r\n{}\r\n",
+            "X-Padding: a\r\n".repeat(HttpServer::MAX_HEADERS)
+        );
+
+        assert!(send(many.as_bytes()).starts_with("HTTP/1.1 431 "));
+    }
+}
@@ -0,0 +1,241 @@
+//! The implementation of [`InputMapper`] and its [actions](Action)
+
+use crate::{Command, keymap::Keymap};
+
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind},
+    layout::{Position, Size},
+};
+
+/// An action requested by the user through the terminal
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Perform a [command](Command)
+    Command(Command),
+
+    /// The terminal has been resized to the given [size](Size)
+    Resize(Size),
+
+    /// The terminal has gained (`true`) or lost (`false`) the focus
+    Focus(bool),
+
+    /// Some text has been pasted
+    Paste(String),
+
+    /// A character has been typed while [typing](InputMapper::typing)
+    Type(char),
+
+    /// The user has asked to terminate the application, with `Ctrl+C` or a signal
+    Interrupt,
+
+    /// The user has asked to suspend the application, with `Ctrl+Z` or a signal
+    Suspend,
+}
+
+/// A mapper translating the terminal [events](Event) into [actions](Action)
+///
+/// Key presses are translated according to the [keymap](Keymap), except `Ctrl+C` which always [interrupts](Action::Interrupt) the application and `Ctrl+Z` which always [suspends](Action::Suspend) it.
+/// Left mouse button events are translated into pointer commands, the wheel moves the selector and the right mouse button resets the selection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InputMapper<'a> {
+    /// The [keymap](Keymap) translating the key presses
+    pub keymap: &'a Keymap,
+
+    /// Whether some text is being typed
+    ///
+    /// While typing, the characters are [typed](Action::Type) and `Enter`, `Backspace` and `Esc` always [select](Command::Select), [reset](Command::Reset) and go [back](Command::Back).
+    pub typing: bool,
+}
+
+impl InputMapper<'_> {
+    /// Translate an [event](Event) into an [action](Action)
+    ///
+    /// # Parameter
+    ///
+    /// - `event` - The [event](Event) read from the terminal
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     Command,
+    ///     input::{Action, InputMapper},
+    ///     keymap::Keymap,
+    /// };
+    ///
+    /// use ratatui::{
+    ///     crossterm::event::{Event, KeyCode, KeyEvent},
+    ///     layout::Size,
+    /// };
+    ///
+    /// let keymap = Keymap::default();
+    /// let mapper = InputMapper { keymap: &keymap, typing: false };
+    ///
+    /// assert_eq!(
+    ///     mapper.map(Event::Key(KeyEvent::from(KeyCode::Enter))),
+    ///     Ok(Action::Command(Command::Select))
+    /// );
+    ///
+    /// assert_eq!(mapper.map(Event::Resize(80, 24)), Ok(Action::Resize(Size::new(80, 24))));
+    /// assert_eq!(mapper.map(Event::FocusLost), Ok(Action::Focus(false)));
+    /// ```
+    pub fn map(self, event: Event) -> Result<Action, &'static str> {
+        match event {
+            Event::Key(value)
+                if value.kind == KeyEventKind::Press
+                    && value.code == KeyCode::Char('c')
+                    && value.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                Ok(Action::Interrupt)
+            }
+
+            Event::Key(value)
+                if value.kind == KeyEventKind::Press
+                    && value.code == KeyCode::Char('z')
+                    && value.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                Ok(Action::Suspend)
+            }
+
+            Event::Key(value)
+                if self.typing
+                    && value.kind == KeyEventKind::Press
+                    && !value
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                match value.code {
+                    KeyCode::Char(c) => Ok(Action::Type(c)),
+                    KeyCode::Enter => Ok(Action::Command(Command::Select)),
+                    KeyCode::Backspace => Ok(Action::Command(Command::Reset)),
+                    KeyCode::Esc => Ok(Action::Command(Command::Back)),
+
+                    _ => self
+                        .keymap
+                        .command(&value)
+                        .map(Action::Command)
+                        .ok_or("The key is not recognized as a valid command"),
+                }
+            }
+
+            Event::Key(value) if value.kind == KeyEventKind::Press => self
+                .keymap
+                .command(&value)
+                .map(Action::Command)
+                .ok_or("The key is not recognized as a valid command"),
+
+            Event::Key(_) => Err("Only key presses are valid commands"),
+
+            Event::Mouse(value) => {
+                let position = Position::new(value.column, value.row);
+
+                let command = match value.kind {
+                    MouseEventKind::Down(MouseButton::Left) => Command::Press(position),
+                    MouseEventKind::Drag(MouseButton::Left) => Command::Drag(position),
+                    MouseEventKind::Up(MouseButton::Left) => Command::Release(position),
+                    MouseEventKind::Down(MouseButton::Right) => Command::Reset,
+                    MouseEventKind::ScrollUp => Command::Up,
+                    MouseEventKind::ScrollDown => Command::Down,
+                    _ => return Err("The mouse event is not recognized as a valid command"),
+                };
+
+                Ok(Action::Command(command))
+            }
+
+            Event::Resize(width, height) => Ok(Action::Resize(Size::new(width, height))),
+            Event::FocusGained => Ok(Action::Focus(true)),
+            Event::FocusLost => Ok(Action::Focus(false)),
+            Event::Paste(text) => Ok(Action::Paste(text)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::crossterm::event::{KeyEvent, MouseEvent};
+
+    #[test]
+    fn mouse() {
+        let keymap = Keymap::default();
+        let mapper = InputMapper {
+            keymap: &keymap,
+            typing: false,
+        };
+
+        let event = |kind| {
+            Event::Mouse(MouseEvent {
+                kind,
+                column: 3,
+                row: 4,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+
+        pretty_assertions::assert_eq!(
+            mapper.map(event(MouseEventKind::Down(MouseButton::Left))),
+            Ok(Action::Command(Command::Press(Position::new(3, 4))))
+        );
+
+        pretty_assertions::assert_eq!(
+            mapper.map(event(MouseEventKind::ScrollDown)),
+            Ok(Action::Command(Command::Down))
+        );
+
+        assert!(mapper.map(event(MouseEventKind::Moved)).is_err());
+    }
+
+    #[test]
+    fn typing() {
+        let keymap = Keymap::default();
+        let mapper = InputMapper {
+            keymap: &keymap,
+            typing: true,
+        };
+
+        pretty_assertions::assert_eq!(
+            mapper.map(Event::Key(KeyEvent::from(KeyCode::Char('q')))),
+            Ok(Action::Type('q'))
+        );
+
+        pretty_assertions::assert_eq!(
+            mapper.map(Event::Key(KeyEvent::from(KeyCode::Esc))),
+            Ok(Action::Command(Command::Back))
+        );
+
+        pretty_assertions::assert_eq!(
+            mapper.map(Event::Key(KeyEvent::from(KeyCode::Up))),
+            Ok(Action::Command(Command::Up))
+        );
+    }
+
+    #[test]
+    fn interrupt() {
+        let keymap = Keymap::default();
+        let mapper = InputMapper {
+            keymap: &keymap,
+            typing: false,
+        };
+
+        pretty_assertions::assert_eq!(
+            mapper.map(Event::Key(KeyEvent::new(
+                KeyCode::Char('c'),
+                KeyModifiers::CONTROL
+            ))),
+            Ok(Action::Interrupt)
+        );
+
+        pretty_assertions::assert_eq!(
+            mapper.map(Event::Key(KeyEvent::new(
+                KeyCode::Char('z'),
+                KeyModifiers::CONTROL
+            ))),
+            Ok(Action::Suspend)
+        );
+
+        pretty_assertions::assert_eq!(
+            mapper.map(Event::Key(KeyEvent::from(KeyCode::Char('c')))),
+            Ok(Action::Command(Command::Credits))
+        );
+    }
+}
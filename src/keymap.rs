@@ -0,0 +1,478 @@
+//! The implementation of [`Keymap`] and [`KeyBinding`]
+
+use crate::{
+    Command,
+    config::{Config, read_table, write_table},
+};
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de, ser::SerializeMap};
+use toml::{Table, Value};
+
+use std::{
+    fmt::{Display, Formatter},
+    io::{self, ErrorKind},
+    path::Path,
+    str::FromStr,
+};
+
+/// A key, possibly combined with modifiers, bound to a [command](Command)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    /// The pressed key
+    pub code: KeyCode,
+
+    /// The modifiers held while the key is pressed
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Create a new [`KeyBinding`] without any modifier
+    ///
+    /// # Parameter
+    ///
+    /// - `code` - The pressed key
+    pub const fn new(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    /// Set the modifiers held while the key is pressed
+    pub const fn with_modifiers(self, modifiers: KeyModifiers) -> Self {
+        Self { modifiers, ..self }
+    }
+
+    /// Check if a [key event](KeyEvent) matches this key binding
+    ///
+    /// The `Shift` modifier is ignored for characters since it is already taken into account by the character itself.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        let modifiers = if let KeyCode::Char(_) = event.code {
+            event.modifiers - KeyModifiers::SHIFT
+        } else {
+            event.modifiers
+        };
+
+        self.code == event.code && self.modifiers == modifiers
+    }
+}
+
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (modifier, name) in MODIFIERS {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(',') => write!(f, "Comma"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+
+            code => {
+                let name = NAMED_KEYS
+                    .iter()
+                    .find(|&&(named, _)| named == code)
+                    .map(|(_, name)| *name)
+                    .unwrap_or("?");
+
+                write!(f, "{name}")
+            }
+        }
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = &'static str;
+
+    /// Parse a key binding
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::keymap::KeyBinding;
+    /// use ratatui::crossterm::event::{KeyCode, KeyModifiers};
+    ///
+    /// assert_eq!("Left".parse(), Ok(KeyBinding::new(KeyCode::Left)));
+    ///
+    /// assert_eq!(
+    ///     "Ctrl+q".parse(),
+    ///     Ok(KeyBinding::new(KeyCode::Char('q')).with_modifiers(KeyModifiers::CONTROL))
+    /// );
+    ///
+    /// assert!("Ctrl+Nothing".parse::<KeyBinding>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (prefix, key) = match s.strip_suffix("++") {
+            Some(prefix) => (Some(prefix), "+"),
+            None => match s.rsplit_once('+') {
+                Some((prefix, key)) if !key.is_empty() => (Some(prefix), key),
+                _ => (None, s),
+            },
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+
+        for name in prefix.into_iter().flat_map(|prefix| prefix.split('+')) {
+            let (modifier, _) = MODIFIERS
+                .into_iter()
+                .find(|(_, modifier)| modifier.eq_ignore_ascii_case(name))
+                .ok_or("Unknown modifier")?;
+
+            modifiers |= modifier;
+        }
+
+        let mut chars = key.chars();
+
+        let code = if let (Some(c), None) = (chars.next(), chars.next()) {
+            KeyCode::Char(c)
+        } else if key.eq_ignore_ascii_case("Space") {
+            KeyCode::Char(' ')
+        } else if key.eq_ignore_ascii_case("Comma") {
+            KeyCode::Char(',')
+        } else if let Some(n) = key
+            .strip_prefix(['F', 'f'])
+            .and_then(|n| n.parse().ok())
+            .filter(|n| (1..=12).contains(n))
+        {
+            KeyCode::F(n)
+        } else {
+            NAMED_KEYS
+                .into_iter()
+                .find(|(_, name)| name.eq_ignore_ascii_case(key))
+                .map(|(code, _)| code)
+                .ok_or("Unknown key")?
+        };
+
+        Ok(Self { code, modifiers })
+    }
+}
+
+const MODIFIERS: [(KeyModifiers, &str); 3] = [
+    (KeyModifiers::CONTROL, "Ctrl"),
+    (KeyModifiers::ALT, "Alt"),
+    (KeyModifiers::SHIFT, "Shift"),
+];
+
+const NAMED_KEYS: [(KeyCode, &str); 15] = [
+    (KeyCode::Left, "Left"),
+    (KeyCode::Right, "Right"),
+    (KeyCode::Up, "Up"),
+    (KeyCode::Down, "Down"),
+    (KeyCode::Enter, "Enter"),
+    (KeyCode::Backspace, "Backspace"),
+    (KeyCode::Esc, "Esc"),
+    (KeyCode::Tab, "Tab"),
+    (KeyCode::BackTab, "BackTab"),
+    (KeyCode::Home, "Home"),
+    (KeyCode::End, "End"),
+    (KeyCode::PageUp, "PageUp"),
+    (KeyCode::PageDown, "PageDown"),
+    (KeyCode::Delete, "Delete"),
+    (KeyCode::Insert, "Insert"),
+];
+
+/// A conflict between two [commands](Command) bound to the same [key](KeyBinding)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    /// The key bound twice
+    pub key: KeyBinding,
+
+    /// The command triggered by the key
+    pub command: Command,
+
+    /// The command shadowed by the first one
+    pub shadowed: Command,
+}
+
+/// The mapping from [keys](KeyBinding) to [commands](Command)
+///
+/// The keymap is persisted in the `keys` table of the [configuration file](Config::path), where each command is bound to a comma-separated list of keys.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Keymap {
+    bindings: Vec<(KeyBinding, Command)>,
+}
+
+impl Keymap {
+    /// The name of the table of the configuration file holding the key bindings
+    pub const TABLE: &str = "keys";
+
+    /// Load the keymap from the [configuration file](Config::path)
+    ///
+    /// If there is no configuration directory or the file has no `keys` table, the default keymap is returned.
+    /// A malformed file is reported as an error of kind [`ErrorKind::InvalidData`].
+    pub fn load() -> io::Result<Self> {
+        match Config::path() {
+            Some(path) => Self::read(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Save the keymap to the [configuration file](Config::path)
+    pub fn save(&self) -> io::Result<()> {
+        let path = Config::path().ok_or(io::Error::new(
+            ErrorKind::NotFound,
+            "No configuration directory was found",
+        ))?;
+
+        self.write(&path)
+    }
+
+    /// Read a keymap from the `keys` table of a given configuration file
+    ///
+    /// # Parameter
+    ///
+    /// - `path` - The path of the file
+    pub fn read(path: &Path) -> io::Result<Self> {
+        match read_table(path)?.remove(Self::TABLE) {
+            Some(keys) => keys
+                .try_into()
+                .map_err(|error| io::Error::new(ErrorKind::InvalidData, error)),
+
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Write the keymap to the `keys` table of a given configuration file
+    ///
+    /// The missing parent directories are created and the rest of the file is kept.
+    ///
+    /// # Parameter
+    ///
+    /// - `path` - The path of the file
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut table = read_table(path)?;
+        let keys = Value::try_from(self).map_err(io::Error::other)?;
+
+        table.insert(Self::TABLE.to_owned(), keys);
+        write_table(path, &table)
+    }
+
+    /// Get the key bindings
+    pub fn bindings(&self) -> &[(KeyBinding, Command)] {
+        &self.bindings
+    }
+
+    /// Get the [keys](KeyBinding) bound to a [command](Command)
+    pub fn keys(&self, command: Command) -> impl Iterator<Item = KeyBinding> {
+        self.bindings
+            .iter()
+            .filter(move |&&(_, bound)| bound == command)
+            .map(|&(key, _)| key)
+    }
+
+    /// Bind a [command](Command) to some [keys](KeyBinding)
+    ///
+    /// The previous keys of the command are replaced.
+    pub fn bind(&mut self, command: Command, keys: impl IntoIterator<Item = KeyBinding>) {
+        self.bindings.retain(|&(_, bound)| bound != command);
+        self.bindings
+            .extend(keys.into_iter().map(|key| (key, command)));
+    }
+
+    /// Get the [conflicts](Conflict) of the keymap
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     Command,
+    ///     keymap::{Conflict, KeyBinding, Keymap},
+    /// };
+    ///
+    /// use ratatui::crossterm::event::KeyCode;
+    ///
+    /// let mut keymap = Keymap::default();
+    /// assert!(keymap.conflicts().is_empty());
+    ///
+    /// keymap.bind(Command::Select, [KeyBinding::new(KeyCode::Char('q'))]);
+    ///
+    /// assert_eq!(
+    ///     keymap.conflicts(),
+    ///     vec![Conflict {
+    ///         key: KeyBinding::new(KeyCode::Char('q')),
+    ///         command: Command::Exit,
+    ///         shadowed: Command::Select,
+    ///     }]
+    /// );
+    /// ```
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        self.bindings
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &(key, command))| {
+                self.bindings[i + 1..]
+                    .iter()
+                    .filter(move |&&(other, shadowed)| other == key && shadowed != command)
+                    .map(move |&(_, shadowed)| Conflict {
+                        key,
+                        command,
+                        shadowed,
+                    })
+            })
+            .collect()
+    }
+
+    /// Get the [command](Command) triggered by a [key event](KeyEvent), if any
+    pub fn command(&self, event: &KeyEvent) -> Option<Command> {
+        self.bindings
+            .iter()
+            .find(|(key, _)| key.matches(event))
+            .map(|&(_, command)| command)
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: Command::KEY_BINDINGS.to_vec(),
+        }
+    }
+}
+
+impl Display for Keymap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&toml::to_string(self).map_err(|_| std::fmt::Error)?)
+    }
+}
+
+impl FromStr for Keymap {
+    type Err = toml::de::Error;
+
+    /// Parse a keymap written as the content of the `keys` table
+    ///
+    /// Missing commands keep their default keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     Command,
+    ///     keymap::{KeyBinding, Keymap},
+    /// };
+    ///
+    /// use ratatui::crossterm::event::KeyCode;
+    ///
+    /// let keymap: Keymap = "select = \"Enter, Space\"".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     keymap.keys(Command::Select).collect::<Vec<_>>(),
+    ///     vec![KeyBinding::new(KeyCode::Enter), KeyBinding::new(KeyCode::Char(' '))]
+    /// );
+    ///
+    /// assert_eq!(
+    ///     keymap.keys(Command::Exit).collect::<Vec<_>>(),
+    ///     vec![KeyBinding::new(KeyCode::Char('q'))]
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s)
+    }
+}
+
+impl Serialize for Keymap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(Command::BINDABLE.len()))?;
+
+        for command in Command::BINDABLE {
+            let keys = self
+                .keys(command)
+                .map(|key| key.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            map.serialize_entry(&command.to_string(), &keys)?;
+        }
+
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Keymap {
+    /// Deserialize a keymap, the commands being bound in the order of the table
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut keymap = Self::default();
+
+        for (command, keys) in Table::deserialize(deserializer)? {
+            let keys = keys
+                .as_str()
+                .ok_or_else(|| de::Error::custom("The keys are not a string"))?
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(str::parse)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(de::Error::custom)?;
+
+            keymap.bind(command.parse().map_err(de::Error::custom)?, keys);
+        }
+
+        Ok(keymap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut keymap = Keymap::default();
+
+        keymap.bind(
+            Command::Exit,
+            [
+                KeyBinding::new(KeyCode::Char('c')).with_modifiers(KeyModifiers::CONTROL),
+                KeyBinding::new(KeyCode::Char('+')).with_modifiers(KeyModifiers::ALT),
+                KeyBinding::new(KeyCode::Char(',')),
+            ],
+        );
+
+        let parsed = keymap.to_string().parse::<Keymap>();
+
+        pretty_assertions::assert_eq!(
+            parsed.map(|parsed| parsed.to_string()),
+            Ok(keymap.to_string())
+        );
+    }
+
+    #[test]
+    fn shift() {
+        let keymap = Keymap::default();
+        let event = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT);
+
+        pretty_assertions::assert_eq!(keymap.command(&event), Some(Command::Help));
+    }
+
+    #[test]
+    fn movement() {
+        let keymap = Keymap::default();
+
+        for (keys, command) in [
+            ("ha4", Command::Left),
+            ("js2", Command::Down),
+            ("kw8", Command::Up),
+            ("ld6", Command::Right),
+            (" 5", Command::Select),
+            ("7", Command::UpLeft),
+            ("9", Command::UpRight),
+            ("1", Command::DownLeft),
+            ("3", Command::DownRight),
+        ] {
+            for key in keys.chars() {
+                let event = KeyEvent::new(KeyCode::Char(key), KeyModifiers::NONE);
+                pretty_assertions::assert_eq!(keymap.command(&event), Some(command));
+            }
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        assert!("select".parse::<Keymap>().is_err());
+        assert!("nothing = \"Enter\"".parse::<Keymap>().is_err());
+        assert!("select = \"Hyper+Enter\"".parse::<Keymap>().is_err());
+    }
+}
@@ -1,26 +1,157 @@
 //! TODO
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
+#[cfg(feature = "tui")]
 use ratatui::{
-    crossterm::event::{Event, KeyCode, KeyEventKind},
+    crossterm::event::{KeyCode, KeyModifiers},
+    layout::Position,
     prelude::*,
+    widgets::Clear,
 };
 
-use ratatui_macros::constraints;
+#[cfg(feature = "tui")]
+use ai::{Ai, Personality, Thinking};
+#[cfg(feature = "tui")]
+use config::Config;
+#[cfg(feature = "tui")]
+use database::{ArchivedGame, GameDatabase};
+#[cfg(feature = "tui")]
+use events::TaskEvent;
+#[cfg(feature = "tui")]
+use hooks::{HookEvent, Hooks};
+#[cfg(feature = "tui")]
+use input::Action;
+#[cfg(feature = "tui")]
+use keymap::{KeyBinding, Keymap};
+#[cfg(feature = "tui")]
+use message::{Message, MessageQueue, Severity};
+#[cfg(feature = "tui")]
+use net::{Connection, NetMessage, Role};
+#[cfg(feature = "tui")]
+use pgn::PortableGame;
+#[cfg(feature = "tui")]
+use ratatui_macros::{constraint, constraints, line, span};
+#[cfg(feature = "tui")]
+use record::GameRecord;
+#[cfg(feature = "tui")]
+use report::BugReport;
+#[cfg(feature = "tui")]
+use rng::Rng;
+#[cfg(feature = "tui")]
+use save::SavedGame;
+#[cfg(feature = "tui")]
+use snapshot::Snapshot;
+#[cfg(feature = "tui")]
+use stats::Statistics;
+#[cfg(feature = "tui")]
+use watch::FileWatcher;
+
+#[cfg(feature = "tui")]
+use std::{
+    collections::VecDeque,
+    fmt::{Display, Formatter},
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
+#[cfg(feature = "tui")]
 use widgets::{
-    Credits, Logo, Theme,
-    board::{BoardState, Score},
+    Credits, CreditsState, DebugPanel, Dialog, Help, LayoutPreset, Logo, Stats, Theme, TooSmall,
+    board::{
+        BoardState, Chat, Clock, GameOver, Grid, Inspector, MoveList, RenderStyle, Score,
+        StatusBar, Summary, TurnIndicator,
+    },
+    join::{JoinMenu, JoinState},
+    load::{LoadMenu, LoadState},
+    maps::{MapMenu, MapState},
+    replay::{Replay, ReplayState},
+    settings::{Settings, SettingsState},
 };
 
+#[cfg(feature = "std")]
+pub mod ai;
+#[cfg(feature = "tui")]
+pub mod animation;
+#[cfg(feature = "tui")]
+pub mod cli;
+#[cfg(feature = "tui")]
+pub mod clock;
+#[cfg(feature = "tui")]
+pub mod code;
+#[cfg(feature = "tui")]
+pub mod config;
+#[cfg(feature = "tui")]
+pub mod database;
+#[cfg(feature = "tui")]
+pub mod events;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "core")]
 pub mod game;
+#[cfg(feature = "tui")]
+pub mod graphics;
+#[cfg(feature = "gym")]
+pub mod gym;
+#[cfg(feature = "tui")]
+pub mod headless;
+#[cfg(feature = "tui")]
+pub mod hooks;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "tui")]
+pub mod input;
+#[cfg(feature = "tui")]
+pub mod keymap;
+#[cfg(feature = "tui")]
+pub mod logging;
+#[cfg(feature = "tui")]
+pub mod map;
+#[cfg(feature = "tui")]
+pub mod message;
+#[cfg(feature = "tui")]
+pub mod movie;
+#[cfg(feature = "tui")]
+pub mod net;
+#[cfg(feature = "tui")]
+pub mod pgn;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "tui")]
+pub mod record;
+#[cfg(feature = "tui")]
+pub mod report;
+#[cfg(feature = "core")]
+pub mod rng;
+#[cfg(feature = "tui")]
+pub mod save;
+#[cfg(feature = "tui")]
+pub mod selfplay;
+#[cfg(feature = "tui")]
+pub mod server;
+#[cfg(feature = "tui")]
+pub mod snapshot;
+#[cfg(feature = "tui")]
+pub mod ssh;
+#[cfg(feature = "tui")]
+pub mod stats;
+#[cfg(feature = "tui")]
+pub mod tournament;
+#[cfg(feature = "tui")]
+pub mod watch;
+#[cfg(feature = "tui")]
 pub mod widgets;
 
 /// Commands used to perform actions based on user inputs
+#[cfg(feature = "tui")]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Command {
-    /// Go back
+    /// Go back, or stop the search of the computer opponent while it is thinking
     Back,
 
     /// Exit
@@ -43,148 +174,2788 @@ pub enum Command {
 
     /// Go down
     Down,
+
+    /// Press the pointer at the given [position](Position)
+    Press(Position),
+
+    /// Drag the pointer to the given [position](Position)
+    Drag(Position),
+
+    /// Release the pointer at the given [position](Position)
+    Release(Position),
+
+    /// Open the settings
+    Settings,
+
+    /// Show or hide the help
+    Help,
+
+    /// Answer yes to a question
+    Yes,
+
+    /// Answer no to a question
+    No,
+
+    /// Go to the next page
+    NextPage,
+
+    /// Go up and to the left
+    UpLeft,
+
+    /// Go up and to the right
+    UpRight,
+
+    /// Go down and to the left
+    DownLeft,
+
+    /// Go down and to the right
+    DownRight,
+
+    /// Go to the first column
+    FirstColumn,
+
+    /// Go to the last column
+    LastColumn,
+
+    /// Go to the first row
+    FirstRow,
+
+    /// Go to the last row
+    LastRow,
+
+    /// Go to the top-left corner
+    Origin,
+
+    /// Pass the turn of the current player
+    Pass,
+
+    /// Show or hide the credits
+    Credits,
+
+    /// Show the statistics
+    Statistics,
+
+    /// Replay the last finished game
+    Replay,
+
+    /// Show or hide the cell inspector
+    Inspect,
+
+    /// Move the cursor to the next movable piece of the current player
+    NextPiece,
+
+    /// Move the cursor to the previous movable piece of the current player
+    PreviousPiece,
+
+    /// Save the current game
+    Save,
+
+    /// Export the current game in the [portable format](PortableGame)
+    Export,
+
+    /// Export the current position as an [image](Snapshot)
+    Snapshot,
+
+    /// Copy the [code](code::encode) of the current position to the clipboard
+    Share,
+
+    /// Open the list of the saved games
+    Load,
+
+    /// Open the list of the games hosted on the local network
+    Join,
+
+    /// Open the list of the custom maps
+    Maps,
+
+    /// Resume the last unfinished game
+    Resume,
+
+    /// Undo the last move
+    Undo,
+
+    /// Redo the last undone move
+    Redo,
+
+    /// Resign the current game
+    Resign,
+
+    /// Suggest a move to the current player
+    Hint,
+
+    /// Show or hide the list of the moves
+    ToggleHistory,
+
+    /// Start a new game
+    NewGame,
+
+    /// Restart the current game from its starting position
+    Restart,
+
+    /// Show or hide the debug overlay
+    Debug,
+
+    /// Open another game, keeping the current one in the background
+    OpenGame,
+
+    /// Switch to the next open game
+    NextGame,
+
+    /// Start or stop typing a chat message to the remote player
+    Chat,
 }
 
-impl TryFrom<Event> for Command {
-    type Error = &'static str;
+#[cfg(feature = "tui")]
+impl Command {
+    /// The default key bindings of the commands
+    ///
+    /// Several keys can be bound to the same command.
+    /// These bindings can be overridden by the [keymap](Keymap).
+    /// The digits move the selector like a numeric keypad, `5` selecting the current cell.
+    pub const KEY_BINDINGS: [(KeyBinding, Self); 63] = [
+        (KeyBinding::new(KeyCode::Left), Self::Left),
+        (KeyBinding::new(KeyCode::Char('h')), Self::Left),
+        (KeyBinding::new(KeyCode::Char('a')), Self::Left),
+        (KeyBinding::new(KeyCode::Right), Self::Right),
+        (KeyBinding::new(KeyCode::Char('l')), Self::Right),
+        (KeyBinding::new(KeyCode::Char('d')), Self::Right),
+        (KeyBinding::new(KeyCode::Up), Self::Up),
+        (KeyBinding::new(KeyCode::Char('k')), Self::Up),
+        (KeyBinding::new(KeyCode::Char('w')), Self::Up),
+        (KeyBinding::new(KeyCode::Down), Self::Down),
+        (KeyBinding::new(KeyCode::Char('j')), Self::Down),
+        (KeyBinding::new(KeyCode::Char('s')), Self::Down),
+        (KeyBinding::new(KeyCode::Char('7')), Self::UpLeft),
+        (KeyBinding::new(KeyCode::Char('8')), Self::Up),
+        (KeyBinding::new(KeyCode::Char('9')), Self::UpRight),
+        (KeyBinding::new(KeyCode::Char('4')), Self::Left),
+        (KeyBinding::new(KeyCode::Char('5')), Self::Select),
+        (KeyBinding::new(KeyCode::Char('6')), Self::Right),
+        (KeyBinding::new(KeyCode::Char('1')), Self::DownLeft),
+        (KeyBinding::new(KeyCode::Char('2')), Self::Down),
+        (KeyBinding::new(KeyCode::Char('3')), Self::DownRight),
+        (KeyBinding::new(KeyCode::Home), Self::FirstColumn),
+        (KeyBinding::new(KeyCode::End), Self::LastColumn),
+        (KeyBinding::new(KeyCode::PageUp), Self::FirstRow),
+        (KeyBinding::new(KeyCode::PageDown), Self::LastRow),
+        (
+            KeyBinding::new(KeyCode::Home).with_modifiers(KeyModifiers::CONTROL),
+            Self::Origin,
+        ),
+        (KeyBinding::new(KeyCode::Enter), Self::Select),
+        (KeyBinding::new(KeyCode::Char(' ')), Self::Select),
+        (KeyBinding::new(KeyCode::Backspace), Self::Reset),
+        (KeyBinding::new(KeyCode::Char('p')), Self::Pass),
+        (KeyBinding::new(KeyCode::Tab), Self::NextPiece),
+        (KeyBinding::new(KeyCode::BackTab), Self::PreviousPiece),
+        (KeyBinding::new(KeyCode::F(2)), Self::NextPage),
+        (KeyBinding::new(KeyCode::Char('o')), Self::Settings),
+        (
+            KeyBinding::new(KeyCode::Char('s')).with_modifiers(KeyModifiers::CONTROL),
+            Self::Save,
+        ),
+        (
+            KeyBinding::new(KeyCode::Char('o')).with_modifiers(KeyModifiers::CONTROL),
+            Self::Load,
+        ),
+        (KeyBinding::new(KeyCode::Char('e')), Self::Export),
+        (KeyBinding::new(KeyCode::Char('E')), Self::Snapshot),
+        (KeyBinding::new(KeyCode::Char('C')), Self::Share),
+        (KeyBinding::new(KeyCode::Char('J')), Self::Join),
+        (KeyBinding::new(KeyCode::Char('M')), Self::Maps),
+        (KeyBinding::new(KeyCode::Char('u')), Self::Resume),
+        (KeyBinding::new(KeyCode::Char('z')), Self::Undo),
+        (KeyBinding::new(KeyCode::Char('Z')), Self::Redo),
+        (KeyBinding::new(KeyCode::Char('R')), Self::Resign),
+        (KeyBinding::new(KeyCode::Char('H')), Self::Hint),
+        (KeyBinding::new(KeyCode::Char('m')), Self::ToggleHistory),
+        (
+            KeyBinding::new(KeyCode::Char('n')).with_modifiers(KeyModifiers::CONTROL),
+            Self::NewGame,
+        ),
+        (
+            KeyBinding::new(KeyCode::Char('r')).with_modifiers(KeyModifiers::CONTROL),
+            Self::Restart,
+        ),
+        (
+            KeyBinding::new(KeyCode::Char('t')).with_modifiers(KeyModifiers::CONTROL),
+            Self::OpenGame,
+        ),
+        (
+            KeyBinding::new(KeyCode::Tab).with_modifiers(KeyModifiers::CONTROL),
+            Self::NextGame,
+        ),
+        (KeyBinding::new(KeyCode::Char('t')), Self::Chat),
+        (KeyBinding::new(KeyCode::Char('T')), Self::Statistics),
+        (KeyBinding::new(KeyCode::Char('r')), Self::Replay),
+        (KeyBinding::new(KeyCode::Char('i')), Self::Inspect),
+        (KeyBinding::new(KeyCode::Char('c')), Self::Credits),
+        (KeyBinding::new(KeyCode::Char('?')), Self::Help),
+        (KeyBinding::new(KeyCode::F(1)), Self::Help),
+        (KeyBinding::new(KeyCode::F(12)), Self::Debug),
+        (KeyBinding::new(KeyCode::Char('y')), Self::Yes),
+        (KeyBinding::new(KeyCode::Char('n')), Self::No),
+        (KeyBinding::new(KeyCode::Esc), Self::Back),
+        (KeyBinding::new(KeyCode::Char('q')), Self::Exit),
+    ];
 
-    fn try_from(value: Event) -> Result<Self, Self::Error> {
-        if let Event::Key(value) = value
-            && value.kind == KeyEventKind::Press
-        {
-            match value.code {
-                KeyCode::Backspace => Ok(Self::Reset),
-                KeyCode::Enter => Ok(Self::Select),
-                KeyCode::Left => Ok(Self::Left),
-                KeyCode::Right => Ok(Self::Right),
-                KeyCode::Up => Ok(Self::Up),
-                KeyCode::Down => Ok(Self::Down),
-                KeyCode::Char('q') => Ok(Self::Exit),
-                KeyCode::Esc => Ok(Self::Back),
-                _ => Err("The key is not recognized as a valid command"),
-            }
-        } else {
-            Err("Only key press events are valid commands")
+    /// The commands which can be bound to keys
+    pub const BINDABLE: [Self; 48] = [
+        Self::Left,
+        Self::Right,
+        Self::Up,
+        Self::Down,
+        Self::UpLeft,
+        Self::UpRight,
+        Self::DownLeft,
+        Self::DownRight,
+        Self::FirstColumn,
+        Self::LastColumn,
+        Self::FirstRow,
+        Self::LastRow,
+        Self::Origin,
+        Self::Select,
+        Self::Reset,
+        Self::Pass,
+        Self::Undo,
+        Self::Redo,
+        Self::Hint,
+        Self::Resign,
+        Self::NextPiece,
+        Self::PreviousPiece,
+        Self::NextPage,
+        Self::Settings,
+        Self::Statistics,
+        Self::Replay,
+        Self::Save,
+        Self::Export,
+        Self::Snapshot,
+        Self::Share,
+        Self::Load,
+        Self::Join,
+        Self::Maps,
+        Self::Resume,
+        Self::NewGame,
+        Self::Restart,
+        Self::OpenGame,
+        Self::NextGame,
+        Self::Chat,
+        Self::Inspect,
+        Self::ToggleHistory,
+        Self::Help,
+        Self::Credits,
+        Self::Debug,
+        Self::Yes,
+        Self::No,
+        Self::Back,
+        Self::Exit,
+    ];
+
+    /// Get a short description of the command
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Back => "Go back or stop the search",
+            Self::Exit => "Exit",
+            Self::Reset => "Reset the current selection",
+            Self::Select => "Select the current cell",
+            Self::Left => "Go left",
+            Self::Right => "Go right",
+            Self::Up => "Go up",
+            Self::Down => "Go down",
+            Self::UpLeft => "Go up and left",
+            Self::UpRight => "Go up and right",
+            Self::DownLeft => "Go down and left",
+            Self::DownRight => "Go down and right",
+            Self::FirstColumn => "Go to the first column",
+            Self::LastColumn => "Go to the last column",
+            Self::FirstRow => "Go to the first row",
+            Self::LastRow => "Go to the last row",
+            Self::Origin => "Go to the top-left corner",
+            Self::Press(_) => "Press the pointer",
+            Self::Drag(_) => "Drag the pointer",
+            Self::Release(_) => "Release the pointer",
+            Self::Settings => "Open the settings",
+            Self::Help => "Show or hide the help",
+            Self::Credits => "Show or hide the credits",
+            Self::Statistics => "Show the statistics",
+            Self::Replay => "Replay the last finished game",
+            Self::Inspect => "Show or hide the cell inspector",
+            Self::Yes => "Answer yes",
+            Self::No => "Answer no",
+            Self::NextPage => "Go to the next page",
+            Self::Pass => "Pass your turn",
+            Self::NextPiece => "Go to your next movable piece",
+            Self::PreviousPiece => "Go to your previous movable piece",
+            Self::Save => "Save the current game",
+            Self::Export => "Export the current game",
+            Self::Snapshot => "Export the current position as an image",
+            Self::Share => "Copy the code of the current position",
+            Self::Load => "Load a saved game",
+            Self::Join => "Join a game of the local network",
+            Self::Maps => "Choose the map of the new games",
+            Self::Resume => "Resume the last unfinished game",
+            Self::Undo => "Undo your last move",
+            Self::Redo => "Redo your last undone move",
+            Self::Resign => "Resign the current game",
+            Self::Hint => "Suggest a move",
+            Self::ToggleHistory => "Show or hide the list of the moves",
+            Self::NewGame => "Start a new game",
+            Self::Restart => "Restart the current game",
+            Self::Debug => "Show or hide the debug overlay",
+            Self::OpenGame => "Open another game",
+            Self::NextGame => "Switch to the next open game",
+            Self::Chat => "Chat with the remote player",
+        }
+    }
+}
+
+#[cfg(feature = "tui")]
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Back => write!(f, "back"),
+            Self::Exit => write!(f, "exit"),
+            Self::Reset => write!(f, "reset"),
+            Self::Select => write!(f, "select"),
+            Self::Left => write!(f, "left"),
+            Self::Right => write!(f, "right"),
+            Self::Up => write!(f, "up"),
+            Self::Down => write!(f, "down"),
+            Self::UpLeft => write!(f, "up_left"),
+            Self::UpRight => write!(f, "up_right"),
+            Self::DownLeft => write!(f, "down_left"),
+            Self::DownRight => write!(f, "down_right"),
+            Self::FirstColumn => write!(f, "first_column"),
+            Self::LastColumn => write!(f, "last_column"),
+            Self::FirstRow => write!(f, "first_row"),
+            Self::LastRow => write!(f, "last_row"),
+            Self::Origin => write!(f, "origin"),
+            Self::Press(_) => write!(f, "press"),
+            Self::Drag(_) => write!(f, "drag"),
+            Self::Release(_) => write!(f, "release"),
+            Self::Settings => write!(f, "settings"),
+            Self::Help => write!(f, "help"),
+            Self::Credits => write!(f, "credits"),
+            Self::Statistics => write!(f, "statistics"),
+            Self::Replay => write!(f, "replay"),
+            Self::Inspect => write!(f, "inspect"),
+            Self::Yes => write!(f, "yes"),
+            Self::No => write!(f, "no"),
+            Self::NextPage => write!(f, "next_page"),
+            Self::Pass => write!(f, "pass"),
+            Self::NextPiece => write!(f, "next_piece"),
+            Self::PreviousPiece => write!(f, "previous_piece"),
+            Self::Save => write!(f, "save"),
+            Self::Export => write!(f, "export"),
+            Self::Snapshot => write!(f, "snapshot"),
+            Self::Share => write!(f, "share"),
+            Self::Load => write!(f, "load"),
+            Self::Join => write!(f, "join"),
+            Self::Maps => write!(f, "maps"),
+            Self::Resume => write!(f, "resume"),
+            Self::Undo => write!(f, "undo"),
+            Self::Redo => write!(f, "redo"),
+            Self::Resign => write!(f, "resign"),
+            Self::Hint => write!(f, "hint"),
+            Self::ToggleHistory => write!(f, "toggle_history"),
+            Self::NewGame => write!(f, "new_game"),
+            Self::Restart => write!(f, "restart"),
+            Self::Debug => write!(f, "debug"),
+            Self::OpenGame => write!(f, "open_game"),
+            Self::NextGame => write!(f, "next_game"),
+            Self::Chat => write!(f, "chat"),
         }
     }
 }
 
+#[cfg(feature = "tui")]
+impl FromStr for Command {
+    type Err = &'static str;
+
+    /// Parse the name of a [bindable](Self::BINDABLE) command
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::Command;
+    ///
+    /// assert_eq!("select".parse(), Ok(Command::Select));
+    /// assert!("press".parse::<Command>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::BINDABLE
+            .into_iter()
+            .find(|command| command.to_string().eq_ignore_ascii_case(s))
+            .ok_or("Unknown command")
+    }
+}
+
 /// Trait allowing to manage states with [commands](Command)
+#[cfg(feature = "tui")]
 pub trait CommandManaged {
     /// Handle given [command](Command)
     fn handle_command(&mut self, command: Command);
 }
 
-/// Main state of the application
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
-pub enum ApplicationState {
-    /// The main state of the application with the [`Board` widget](widgets::board::Board)
-    Board(BoardState),
+/// A screen of the application
+#[cfg(feature = "tui")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Screen {
+    /// The main screen of the application with the [`Board` widget](widgets::board::Board)
+    Board(Box<BoardState>),
 
     /// The application has exited
     Exit,
 
-    /// Default state showing the application logo
-    #[default]
-    Logo,
+    /// The screen showing the results of a finished game with the [`GameOver` widget](widgets::board::GameOver)
+    GameOver(Box<BoardState>),
+
+    /// Default screen showing the application logo with the time elapsed since it is shown
+    Logo(Duration),
+
+    /// The screen showing the [`Settings` widget](widgets::settings::Settings)
+    Settings(SettingsState),
+
+    /// The screen showing the [`Stats` widget](widgets::Stats)
+    Statistics,
+
+    /// The screen showing the [`Replay` widget](widgets::replay::Replay)
+    Replay(Box<ReplayState>),
+
+    /// The screen showing the [`LoadMenu` widget](widgets::load::LoadMenu)
+    Load(LoadState),
+
+    /// The screen showing the [`JoinMenu` widget](widgets::join::JoinMenu)
+    Join(JoinState),
+
+    /// The screen showing the [`MapMenu` widget](widgets::maps::MapMenu)
+    Maps(MapState),
 }
 
-impl ApplicationState {
-    /// Check if the application has exited
-    pub fn has_exited(&self) -> bool {
-        *self == Self::Exit
+#[cfg(feature = "tui")]
+impl Screen {
+    /// Get the name of the screen
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::Screen;
+    ///
+    /// assert_eq!(Screen::Statistics.name(), "Statistics");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Board(_) => "Board",
+            Self::Exit => "Exit",
+            Self::GameOver(_) => "GameOver",
+            Self::Logo(_) => "Logo",
+            Self::Settings(_) => "Settings",
+            Self::Statistics => "Statistics",
+            Self::Replay(_) => "Replay",
+            Self::Load(_) => "Load",
+            Self::Join(_) => "Join",
+            Self::Maps(_) => "Maps",
+        }
     }
-}
 
-impl CommandManaged for ApplicationState {
-    fn handle_command(&mut self, command: Command) {
-        match command {
-            Command::Back => match self {
-                Self::Board(_) => *self = Self::Logo,
-                Self::Exit => (),
-                Self::Logo => *self = Self::Exit,
-            },
+    /// Get the minimum size of the terminal needed to show the screen
+    ///
+    /// The size depends on the layout of the screen and, if any, on the size of the shown board.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::Screen;
+    /// use ratatui::layout::Size;
+    ///
+    /// assert_eq!(Screen::Exit.minimum_size(), Size::ZERO);
+    /// ```
+    pub fn minimum_size(&self) -> Size {
+        match self {
+            Self::Board(board_state) => {
+                let board_width = 2 * board_state.width() as u16 - 1;
+                let board_height = board_state.height() as u16;
+
+                Size::new(board_width.max(40), board_height.min(8) + 2)
+            }
 
-            Command::Exit => *self = Self::Exit,
+            Self::Exit => Size::ZERO,
 
-            _ => match self {
-                Self::Board(board_state) => board_state.handle_command(command),
-                Self::Exit => (),
+            Self::GameOver(board_state) => {
+                let board_width = 2 * board_state.width() as u16 - 1;
+                let board_height = board_state.height() as u16;
 
-                Self::Logo => {
-                    if matches!(command, Command::Select) {
-                        use game::CellState::*;
-                        use game::Player::*;
+                Size::new((board_width * 5).div_ceil(3).max(75), board_height.max(18))
+            }
 
-                        #[rustfmt::skip]
-                        let board = vec![
-                            Player(Red),  Free, Free, Free, Free, Free, Free, Free,
-                            Free,         Free, Free, Free, Free, Free, Free, Free,
-                            Free,         Free, Free, Free, Free, Free, Free, Free,
-                            Free,         Free, Free, Free, Free, Free, Free, Free,
-                            Free,         Free, Free, Free, Free, Free, Free, Free,
-                            Free,         Free, Free, Free, Free, Free, Free, Free,
-                            Free,         Free, Free, Free, Free, Free, Free, Free,
-                            Free,         Free, Free, Free, Free, Free, Free, Player(Blue),
-                        ];
+            Self::Logo(_) => Size::new(64, 2),
+            Self::Settings(_) => Size::new(40, 19),
+            Self::Statistics => Size::new(42, 16),
 
-                        let board = game::Board::new(8, 8, board);
-                        let board_state = BoardState::new(board, Blue);
-                        *self = Self::Board(board_state);
-                    }
-                }
-            },
+            Self::Replay(replay_state) => {
+                let board = &replay_state.record().board;
+                let board_width = 2 * board.width() as u16 - 1;
+
+                Size::new(
+                    (board_width * 5).div_ceil(4).max(75),
+                    (board.height() as u16).max(14),
+                )
+            }
+
+            Self::Load(_) | Self::Join(_) => Size::new(40, 10),
+            Self::Maps(_) => Size::new(50, 12),
         }
     }
 }
 
-/// Main widget of the application
+/// Get the size from which the side panel and the credits of the game screen are shown
+#[cfg(feature = "tui")]
+fn comfortable_size(board_state: &BoardState) -> Size {
+    let board_width = 2 * board_state.width() as u16 - 1;
+    let board_height = board_state.height() as u16;
+    let clock_height = if board_state.clock().is_some() { 4 } else { 0 };
+    let panel_height = 3 + clock_height + 6;
+
+    Size::new(
+        (board_width * 5).div_ceil(4).max(80),
+        (board_height.max(panel_height) * 5).div_ceil(3) + 1,
+    )
+}
+
+#[cfg(feature = "tui")]
+impl Default for Screen {
+    fn default() -> Self {
+        Self::Logo(Duration::ZERO)
+    }
+}
+
+/// An overlay shown on top of the current [screen](Screen)
+#[cfg(feature = "tui")]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct Application<'a> {
-    /// The selected symbol
+pub enum Overlay {
+    /// The [`Help` widget](widgets::Help)
+    Help,
+
+    /// The [`Credits` widget](widgets::Credits)
+    Credits,
+
+    /// A [confirmation](Confirmation) dialog
+    Confirm(Confirmation),
+}
+
+/// A request for confirmation before performing a destructive [command](Command)
+#[cfg(feature = "tui")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Confirmation {
+    /// The question asked to the user
+    pub message: &'static str,
+
+    /// The [command](Command) performed once confirmed
+    pub command: Command,
+}
+
+/// Main state of the application
+#[cfg(feature = "tui")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ApplicationState {
+    screen: Screen,
+    history: Vec<Screen>,
+    config: Config,
+    keymap: Keymap,
+    overlay: Option<Overlay>,
+    credits: CreditsState,
+    statistics: Statistics,
+    rating_change: Option<i32>,
+    last_game: Option<GameRecord>,
+    map: Option<game::Board>,
+    rng: Rng,
+    config_path: Option<PathBuf>,
+    autosave: Option<PathBuf>,
+    autosaved: usize,
+    database: Option<GameDatabase>,
+    resumable: Option<SavedGame>,
+    messages: MessageQueue,
+    unfocused: bool,
+    inspector: bool,
+    move_list: bool,
+    debug: bool,
+    sessions: Vec<BoardState>,
+    chat: Option<String>,
+    clipboard: Option<String>,
+    hooks: Hooks,
+    hooked: usize,
+    watcher: FileWatcher,
+    commands: VecDeque<Command>,
+    frame_time: Duration,
+    dirty: bool,
+}
+
+#[cfg(feature = "tui")]
+impl ApplicationState {
+    /// The number of recent commands kept for the [bug reports](BugReport)
+    pub const COMMAND_LOG: usize = 32;
+
+    /// Create a new [`ApplicationState`]
     ///
-    /// This symbol is used to show the selected cell.
-    pub selected_symbol: &'a str,
+    /// # Parameter
+    ///
+    /// - `config` - The [configuration](Config) of the application
+    pub fn new(config: Config) -> Self {
+        Self {
+            screen: Screen::default(),
+            history: Vec::new(),
+            config,
+            keymap: Keymap::default(),
+            overlay: None,
+            credits: CreditsState::default(),
+            statistics: Statistics::default(),
+            rating_change: None,
+            last_game: None,
+            map: None,
+            rng: Rng::default(),
+            config_path: None,
+            autosave: None,
+            autosaved: 0,
+            database: None,
+            resumable: None,
+            messages: MessageQueue::default(),
+            unfocused: false,
+            inspector: false,
+            move_list: false,
+            debug: false,
+            sessions: Vec::new(),
+            chat: None,
+            clipboard: None,
+            hooks: Hooks::default(),
+            hooked: 0,
+            watcher: FileWatcher::default(),
+            commands: VecDeque::new(),
+            frame_time: Duration::ZERO,
+            dirty: true,
+        }
+    }
 
-    /// The unselected symbol
+    /// Use a custom [keymap](Keymap)
     ///
-    /// This symbol is used to show unselected cells.
-    pub unselected_symbol: &'a str,
+    /// # Parameter
+    ///
+    /// - `keymap` - The [keymap](Keymap) translating the key presses into [commands](Command)
+    pub fn with_keymap(self, keymap: Keymap) -> Self {
+        Self { keymap, ..self }
+    }
 
-    /// The [theme](Theme) used to colorize text
-    pub theme: Theme,
-}
+    /// Apply the changes of the configuration file, key bindings included, live
+    ///
+    /// The changes are reported by the operating system and applied on the next [tick](Self::tick); a malformed file is reported and ignored.
+    ///
+    /// # Parameter
+    ///
+    /// - `config` - The path of the [configuration](Config) file
+    pub fn with_hot_reload(self, config: PathBuf) -> Self {
+        Self {
+            watcher: FileWatcher::new([config]),
+            ..self
+        }
+    }
 
-impl Default for Application<'static> {
-    fn default() -> Self {
+    /// Use some previously recorded [statistics](Statistics)
+    ///
+    /// # Parameter
+    ///
+    /// - `statistics` - The [statistics](Statistics) the finished games are added to
+    pub fn with_statistics(self, statistics: Statistics) -> Self {
+        Self { statistics, ..self }
+    }
+
+    /// Use the [record](GameRecord) of a previously finished game
+    ///
+    /// # Parameter
+    ///
+    /// - `last_game` - The [record](GameRecord) of the last finished game, if any
+    pub fn with_last_game(self, last_game: Option<GameRecord>) -> Self {
+        Self { last_game, ..self }
+    }
+
+    /// Save the [configuration](Config) to a given file instead of the [default one](Config::path)
+    ///
+    /// # Parameter
+    ///
+    /// - `config_path` - The path of the configuration file
+    pub fn with_config_path(self, config_path: PathBuf) -> Self {
         Self {
-            selected_symbol: "V",
-            unselected_symbol: "O",
-            theme: Theme::default(),
+            config_path: Some(config_path),
+            ..self
         }
     }
-}
 
-impl StatefulWidget for Application<'_> {
-    type State = ApplicationState;
+    /// Snapshot the game in progress to a given file after every ply
+    ///
+    /// The file is removed once the game is finished.
+    ///
+    /// # Parameter
+    ///
+    /// - `autosave` - The path of the autosave file, usually [`SavedGame::autosave_path()`]
+    pub fn with_autosave(self, autosave: PathBuf) -> Self {
+        Self {
+            autosave: Some(autosave),
+            ..self
+        }
+    }
 
-    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        match state {
-            Self::State::Board(state) => {
-                let [top, bottom] = Layout::vertical(constraints![==60%, ==40%]).areas(area);
-                let [left, right] = Layout::horizontal(constraints![==80%, ==20%]).areas(top);
+    /// Store every finished game in a [database](GameDatabase)
+    ///
+    /// # Parameter
+    ///
+    /// - `database` - The [database](GameDatabase), usually [`GameDatabase::open()`]
+    pub fn with_database(self, database: GameDatabase) -> Self {
+        Self {
+            database: Some(database),
+            ..self
+        }
+    }
 
-                widgets::board::Board::default()
-                    .selected_symbol(self.selected_symbol)
-                    .unselected_symbol(self.unselected_symbol)
-                    .render(left, buf, state);
+    /// Run some [hooks](Hooks) on the game events
+    ///
+    /// # Parameter
+    ///
+    /// - `hooks` - The [hooks](Hooks) to run
+    pub fn with_hooks(self, hooks: Hooks) -> Self {
+        Self { hooks, ..self }
+    }
 
-                Score { theme: self.theme }.render(right, buf, state);
-                Credits { theme: self.theme }.render(bottom, buf);
-            }
+    /// Offer to resume an unfinished game
+    ///
+    /// A confirmation dialog is shown at once; the game can also be resumed later from the logo screen with [`Command::Resume`].
+    ///
+    /// # Parameter
+    ///
+    /// - `game` - The unfinished [game](SavedGame), usually read from the autosave file
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     ApplicationState, Command, CommandManaged, Screen, config::Config, game::Player,
+    ///     record::GameRecord, save::SavedGame,
+    /// };
+    ///
+    /// let game = SavedGame {
+    ///     record: GameRecord::new("B..R".parse().unwrap(), Player::Blue),
+    ///     clock: None,
+    ///     opponent: None,
+    /// };
+    ///
+    /// let mut state = ApplicationState::new(Config::default()).with_resumable(game);
+    /// assert!(state.overlay().is_some());
+    ///
+    /// state.handle_command(Command::Yes);
+    /// assert!(matches!(state.screen(), Screen::Board(_)));
+    /// ```
+    pub fn with_resumable(self, game: SavedGame) -> Self {
+        Self {
+            resumable: Some(game),
+            overlay: Some(Overlay::Confirm(Confirmation {
+                message: "Resume the last game?",
+                command: Command::Resume,
+            })),
+            ..self
+        }
+    }
+
+    /// Play the new games on a given [board](game::Board) instead of generated ones
+    ///
+    /// # Parameter
+    ///
+    /// - `map` - The [board](game::Board) every new game starts with
+    pub fn with_map(self, map: game::Board) -> Self {
+        Self {
+            map: Some(map),
+            ..self
+        }
+    }
 
-            Self::State::Exit => (),
-            Self::State::Logo => Logo { theme: self.theme }.render(area, buf),
+    /// Draw every random choice from a given seed
+    ///
+    /// The boards, the computer opponents and the hints all derive their randomness from this seed, so that a session can be reproduced.
+    ///
+    /// # Parameter
+    ///
+    /// - `seed` - The seed of the [random number generator](Rng) owned by the application
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self {
+            rng: Rng::new(seed),
+            ..self
         }
     }
+
+    /// Resume a saved game
+    ///
+    /// The game starts from the position reached after the last ply of its [record](GameRecord), with its clock and computer opponent.
+    ///
+    /// # Parameter
+    ///
+    /// - `game` - The [saved game](SavedGame) to resume
+    pub fn with_game(mut self, game: SavedGame) -> Self {
+        self.hooked = game.record.plies.len();
+        let board_state = game.into_state_with(&mut self.rng, &self.hooks);
+        let board_state = self.prepare(board_state);
+        self.push_screen(Screen::Board(Box::new(board_state)));
+        self
+    }
+
+    /// Play a game against a remote player
+    ///
+    /// The guest greets the host and waits for the game; the host then generates the game and its identifier, plays Blue and sends the game to the guest.
+    /// A network game has no clock, since both sides could not agree on it.
+    ///
+    /// # Parameters
+    ///
+    /// - `connection` - The [connection](Connection) to the remote player or to a [server](server::Server)
+    /// - `role` - The [role](Role) of this side
+    pub fn with_remote(mut self, connection: Connection, role: Role) -> io::Result<Self> {
+        let board_state = self.remote_game(connection, role)?;
+        self.push_screen(Screen::Board(Box::new(board_state)));
+        Ok(self)
+    }
+
+    /// Set a network game up, as [`with_remote`](Self::with_remote) does, and get its state
+    fn remote_game(&mut self, connection: Connection, role: Role) -> io::Result<BoardState> {
+        let (game, player, free_pass, record) = if let Role::Guest(greeting) = role {
+            connection.send(&greeting)?;
+
+            match connection.recv()? {
+                NetMessage::Start {
+                    game,
+                    player,
+                    free_pass,
+                    record,
+                } => (game, player, free_pass, *record),
+
+                NetMessage::Refused(reason) => return Err(io::Error::other(reason)),
+
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "The host has not sent any game",
+                    ));
+                }
+            }
+        } else {
+            if connection.recv()? != NetMessage::Hello {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "The other player has not said hello",
+                ));
+            }
+
+            let game = format!("{:x}", self.rng.next_u64());
+            let record = GameRecord::new(self.new_board(), game::Player::Blue);
+
+            connection.send(&NetMessage::Start {
+                game: game.clone(),
+                player: game::Player::Red,
+                free_pass: self.config.free_pass,
+                record: Box::new(record.clone()),
+            })?;
+
+            (game, game::Player::Blue, self.config.free_pass, record)
+        };
+
+        self.hooked = record.plies.len();
+        self.autosaved = self.hooked;
+
+        Ok(self
+            .prepare(BoardState::resume(record))
+            .with_free_pass(free_pass)
+            .with_remote(-player, connection, &game))
+    }
+
+    /// Replay a recorded game
+    ///
+    /// # Parameter
+    ///
+    /// - `record` - The [record](GameRecord) of the replayed game
+    pub fn with_replay(mut self, record: GameRecord) -> Self {
+        self.push_screen(Screen::Replay(Box::new(ReplayState::new(record))));
+        self
+    }
+
+    /// Get the current [screen](Screen)
+    pub fn screen(&self) -> &Screen {
+        &self.screen
+    }
+
+    /// Get the number of [screens](Screen) below the current one
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{ApplicationState, Command, CommandManaged, config::Config};
+    ///
+    /// let mut state = ApplicationState::new(Config::default());
+    /// assert_eq!(state.depth(), 0);
+    ///
+    /// state.handle_command(Command::Statistics);
+    /// assert_eq!(state.depth(), 1);
+    ///
+    /// state.handle_command(Command::Back);
+    /// assert_eq!(state.depth(), 0);
+    /// ```
+    pub fn depth(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Get the current [configuration](Config)
+    pub fn config(&self) -> Config {
+        self.config
+    }
+
+    /// Get the recorded [statistics](Statistics)
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Take the text to copy to the clipboard, if any
+    ///
+    /// The text is expected to be written to the clipboard of the terminal by the main loop.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{ApplicationState, Command, CommandManaged, code, config::Config};
+    ///
+    /// let mut state = ApplicationState::new(Config::default()).with_map("B..R".parse().unwrap());
+    /// state.handle_command(Command::Select);
+    /// state.handle_command(Command::Share);
+    ///
+    /// let copied = state.take_clipboard().unwrap();
+    /// assert_eq!(code::decode(&copied).map(|(board, _)| board.to_string()), Ok("B..R".to_owned()));
+    /// assert_eq!(state.take_clipboard(), None);
+    /// ```
+    pub fn take_clipboard(&mut self) -> Option<String> {
+        self.clipboard.take()
+    }
+
+    /// Get the [record](GameRecord) of the last finished game, if any
+    pub fn last_game(&self) -> Option<&GameRecord> {
+        self.last_game.as_ref()
+    }
+
+    /// Get the current [keymap](Keymap)
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Get the [messages](MessageQueue) giving feedback outside of a game
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{ApplicationState, Command, CommandManaged, config::Config};
+    ///
+    /// let mut state = ApplicationState::new(Config::default());
+    /// state.handle_command(Command::Replay);
+    /// assert!(!state.messages().is_empty());
+    /// ```
+    pub fn messages(&self) -> &MessageQueue {
+        &self.messages
+    }
+
+    /// Get the current [overlay](Overlay), if any
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{ApplicationState, Command, CommandManaged, Overlay, config::Config};
+    ///
+    /// let mut state = ApplicationState::new(Config::default());
+    /// state.handle_command(Command::Select);
+    /// state.handle_command(Command::Exit);
+    /// assert!(matches!(state.overlay(), Some(Overlay::Confirm(_))));
+    /// assert!(!state.has_exited());
+    ///
+    /// state.handle_command(Command::Yes);
+    /// assert!(state.has_exited());
+    /// ```
+    pub fn overlay(&self) -> Option<Overlay> {
+        self.overlay
+    }
+
+    /// Check if the state has changed since the last rendering
+    ///
+    /// The flag is cleared each time the [`Application` widget](Application) is rendered.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{Application, ApplicationState, Command, CommandManaged, config::Config};
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+    ///
+    /// let mut state = ApplicationState::new(Config::default());
+    /// assert!(state.needs_redraw());
+    ///
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// Application::default().render(area, &mut Buffer::empty(area), &mut state);
+    /// assert!(!state.needs_redraw());
+    ///
+    /// state.handle_command(Command::Help);
+    /// assert!(state.needs_redraw());
+    /// ```
+    pub fn needs_redraw(&self) -> bool {
+        self.dirty
+    }
+
+    /// Check if a chat message is being typed
+    ///
+    /// While typing, the key presses are expected to be mapped to [typed characters](Action::Type).
+    pub fn is_typing(&self) -> bool {
+        self.chat.is_some()
+            && matches!(&self.screen, Screen::Board(board_state) if board_state.remote_player().is_some())
+    }
+
+    /// Force the next rendering
+    ///
+    /// This method is expected to be called when the terminal is resized.
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Gather a [bug report](BugReport) describing the current state
+    ///
+    /// # Parameter
+    ///
+    /// - `error` - The error which occurred
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{ApplicationState, Command, CommandManaged, config::Config};
+    ///
+    /// let mut state = ApplicationState::new(Config::default()).with_map("B..R".parse().unwrap());
+    /// state.handle_command(Command::Select);
+    ///
+    /// let report = state.bug_report("Broken pipe");
+    ///
+    /// assert_eq!(report.position.as_deref(), Some("B..R"));
+    /// assert_eq!(report.commands, vec![Command::Select]);
+    /// ```
+    pub fn bug_report(&self, error: impl Display) -> BugReport {
+        let board_state = match &self.screen {
+            Screen::Board(board_state) | Screen::GameOver(board_state) => Some(board_state),
+            _ => None,
+        };
+
+        BugReport {
+            error: error.to_string(),
+            config: self.config,
+            record: board_state.map(|board_state| board_state.record().clone()),
+            position: board_state.map(|board_state| board_state.board().to_string()),
+            commands: self.commands.iter().copied().collect(),
+        }
+    }
+
+    /// Check if the application has exited
+    pub fn has_exited(&self) -> bool {
+        self.screen == Screen::Exit
+    }
+
+    /// Get the search of the computer opponent of the game in progress, if it is thinking
+    pub fn thinking(&self) -> Option<&Thinking> {
+        match &self.screen {
+            Screen::Board(board_state) => board_state.thinking(),
+            _ => None,
+        }
+    }
+
+    /// Get the [connection](Connection) to the remote player of the game in progress, if any
+    pub fn connection(&self) -> Option<&Connection> {
+        match &self.screen {
+            Screen::Board(board_state) => board_state.connection(),
+            _ => None,
+        }
+    }
+
+    /// Deliver the [result](TaskEvent) of a task of the game in progress, applied on the next [update](Self::update)
+    ///
+    /// The results meant for a game which is no longer in progress are ignored.
+    pub fn deliver(&mut self, event: TaskEvent) {
+        if let Screen::Board(board_state) = &mut self.screen {
+            board_state.deliver(event);
+        }
+    }
+
+    /// Deliver the results of the tasks of the game in progress received so far, without blocking
+    ///
+    /// This method is expected to be called regularly by the main loop when the tasks are not [watched](events::EventLoop::watch).
+    pub fn poll(&mut self) {
+        if let Screen::Board(board_state) = &mut self.screen {
+            board_state.poll();
+        }
+    }
+
+    /// Update the parts of the state evolving without any user input, applying the [delivered](Self::deliver) results of the tasks
+    ///
+    /// This method is expected to be called regularly by the main loop.
+    pub fn update(&mut self) {
+        if let Screen::Board(board_state) = &mut self.screen {
+            let thinking = board_state.is_thinking();
+            board_state.update();
+            self.dirty |= thinking != board_state.is_thinking();
+
+            let plies = board_state.record().plies.len();
+
+            if let Some(path) = &self.autosave
+                && plies > 0
+                && plies != self.autosaved
+                && board_state.outcome().is_none()
+            {
+                autosave(path, board_state);
+                self.autosaved = plies;
+            }
+
+            for annotation in run_move_hooks(&self.hooks, board_state.record(), self.hooked) {
+                board_state.notify(Message::info(annotation));
+            }
+
+            self.hooked = plies;
+
+            if let Some(outcome) = board_state.outcome()
+                && !board_state.is_animating()
+            {
+                board_state.cancel_thinking();
+
+                let event = HookEvent::GameOver {
+                    outcome,
+                    board: board_state.board(),
+                };
+
+                if let Some(annotation) = run_hook(&self.hooks, event) {
+                    self.messages.push(Message::info(annotation));
+                }
+
+                self.rating_change = self.statistics.add_game(
+                    outcome,
+                    board_state.moves(),
+                    board_state.captures(),
+                    board_state.opponent_player().zip(board_state.difficulty()),
+                );
+
+                self.last_game = Some(board_state.record().clone());
+
+                if let Some(database) = &self.database
+                    && let Some(game) = ArchivedGame::from_state(board_state)
+                    && let Err(error) = database.insert(&game)
+                {
+                    tracing::warn!("The game cannot be stored: {error}");
+                }
+
+                tracing::info!(
+                    "The game is over: {outcome:?} after {} moves",
+                    board_state.moves()
+                );
+
+                if let Some(path) = &self.autosave {
+                    let _ = std::fs::remove_file(path);
+                }
+
+                self.screen = Screen::GameOver(board_state.clone());
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Handle an [action](Action) requested by the user
+    ///
+    /// The [commands](Command) are handled by [`Self::handle_command()`], a resize or a suspension forces the next rendering, a focus change pauses or resumes the clock, a pasted move in algebraic notation is played on the board and a pasted [board code](code::decode) starts a new game from the title screen.
+    ///
+    /// # Parameter
+    ///
+    /// - `action` - The [action](Action) produced by the [input mapper](input::InputMapper)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     ApplicationState, Command, CommandManaged, Screen, config::Config, input::Action,
+    /// };
+    ///
+    /// let mut state = ApplicationState::new(Config::default()).with_map("B..R".parse().unwrap());
+    /// state.handle_command(Command::Select);
+    /// state.handle_action(Action::Paste("a1b1".to_owned()));
+    ///
+    /// assert!(matches!(state.screen(), Screen::Board(board_state) if board_state.moves() == 1));
+    /// ```
+    pub fn handle_action(&mut self, action: Action) {
+        match action {
+            Action::Command(command) => self.handle_command(command),
+            Action::Resize(_) | Action::Suspend => self.invalidate(),
+
+            Action::Focus(focused) => {
+                self.unfocused = !focused;
+                self.dirty = true;
+            }
+
+            Action::Interrupt => self.interrupt(),
+
+            Action::Type(c) => {
+                if self.is_typing()
+                    && let Some(input) = &mut self.chat
+                {
+                    input.push(c);
+                    self.dirty = true;
+                }
+            }
+
+            Action::Paste(text) => match &mut self.screen {
+                Screen::Board(board_state) if self.overlay.is_none() => {
+                    match record::parse_ply(text.trim()) {
+                        Ok(Some(chosen)) => board_state.play_move(chosen),
+
+                        Ok(None) => {
+                            board_state.pass();
+                        }
+
+                        Err(error) => board_state.notify(Message::warning(error)),
+                    }
+
+                    self.dirty = true;
+                }
+
+                Screen::Logo(_) if self.overlay.is_none() => {
+                    match code::decode(&text) {
+                        Ok((board, player)) => {
+                            let board_state = self.game_on(board, player);
+                            self.push_screen(Screen::Board(Box::new(board_state)));
+                        }
+
+                        Err(error) => self.messages.push(Message::warning(format!(
+                            "The board code cannot be read: {error}"
+                        ))),
+                    }
+
+                    self.dirty = true;
+                }
+
+                _ => (),
+            },
+        }
+    }
+
+    /// Exit at once, autosaving the game in progress if any
+    ///
+    /// Unlike [`Command::Exit`], no confirmation is asked: this method is expected to be called when the process is asked to terminate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{ApplicationState, Command, CommandManaged, config::Config};
+    ///
+    /// let mut state = ApplicationState::new(Config::default());
+    /// state.handle_command(Command::Select);
+    /// state.interrupt();
+    ///
+    /// assert!(state.has_exited());
+    /// ```
+    pub fn interrupt(&mut self) {
+        tracing::info!("The application is interrupted");
+
+        if let Screen::Board(board_state) = &mut self.screen {
+            board_state.cancel_thinking();
+
+            if let Some(path) = &self.autosave
+                && !board_state.record().plies.is_empty()
+                && board_state.outcome().is_none()
+            {
+                autosave(path, board_state);
+            }
+        }
+
+        self.overlay = None;
+        self.history.clear();
+        self.screen = Screen::Exit;
+        self.dirty = true;
+    }
+
+    /// Run the clock and the animations of the current game, if any
+    ///
+    /// The clock is paused while an [overlay](Overlay) is shown or while the terminal is unfocused.
+    ///
+    /// # Parameter
+    ///
+    /// - `elapsed` - The time elapsed since the last tick
+    pub fn tick(&mut self, elapsed: Duration) {
+        if !self.watcher.changes().is_empty() {
+            self.reload();
+        }
+
+        if self.overlay == Some(Overlay::Credits) || matches!(self.screen, Screen::Board(_)) {
+            self.dirty |= self.credits.tick(elapsed);
+        }
+
+        if self.overlay.is_some() || self.unfocused {
+            return;
+        }
+
+        let message = self.messages.current().cloned();
+        self.messages.tick(elapsed);
+        self.dirty |= message.as_ref() != self.messages.current();
+
+        match &mut self.screen {
+            Screen::Board(board_state) => {
+                self.dirty |= board_state.is_running();
+                board_state.tick(elapsed);
+            }
+
+            Screen::Replay(replay_state) => self.dirty |= replay_state.tick(elapsed),
+            Screen::Join(join_state) => self.dirty |= join_state.refresh(),
+
+            Screen::Logo(shown) => {
+                let before = Logo::frame(*shown);
+                *shown += elapsed;
+                self.dirty |= before != Logo::frame(*shown);
+            }
+
+            _ => (),
+        }
+    }
+
+    /// Apply the changes of the watched configuration file
+    fn reload(&mut self) {
+        let Some(path) = self.watcher.paths().next().map(Path::to_path_buf) else {
+            return;
+        };
+
+        let result = Config::read(&path).and_then(|config| {
+            let keymap = Keymap::read(&path)?;
+            let changed = config != self.config || keymap != self.keymap;
+
+            self.config = config;
+            self.keymap = keymap;
+            Ok(changed)
+        });
+
+        let message = match result {
+            Ok(false) => return,
+
+            Ok(true) => {
+                tracing::info!("{} is reloaded", path.display());
+                Message::info(format!("{} reloaded", path.display()))
+            }
+
+            Err(error) => {
+                tracing::warn!("{} cannot be reloaded: {error}", path.display());
+                Message::warning(format!("{} cannot be reloaded: {error}", path.display()))
+            }
+        };
+
+        match &mut self.screen {
+            Screen::Board(board_state) => board_state.notify(message),
+            _ => self.messages.push(message),
+        }
+
+        self.invalidate();
+    }
+
+    fn new_game(&mut self) -> BoardState {
+        let board = self.new_board();
+        self.game_on(board, game::Player::Blue)
+    }
+
+    /// Create the [state](BoardState) of the board of a new game starting from a given position
+    fn game_on(&mut self, board: game::Board, first: game::Player) -> BoardState {
+        let board_state = BoardState::new(board, first).with_rng(self.rng.fork());
+        let mut board_state = self.prepare(board_state);
+
+        if let Some(control) = self.config.clock {
+            board_state = board_state.with_clock(clock::Clock::new(control));
+        }
+
+        if let Some(personality) = self.config.opponent {
+            let opponent = Ai::new(personality)
+                .with_rng(self.rng.fork())
+                .with_difficulty(self.config.difficulty)
+                .with_hooks(self.hooks.clone());
+
+            board_state.with_opponent(opponent)
+        } else {
+            board_state
+        }
+    }
+
+    /// Get the [board](game::Board) a new game starts with
+    ///
+    /// The [rules](game::RuleSet) of the configuration apply to the loaded maps too, unless the maps set their own rules.
+    fn new_board(&mut self) -> game::Board {
+        let board = self.map.clone().unwrap_or_else(|| {
+            game::Board::generate(
+                self.config.height,
+                self.config.width,
+                self.config.density,
+                &mut self.rng,
+            )
+        });
+
+        let rules = if board.rules() == game::RuleSet::default() {
+            self.config.rules
+        } else {
+            board.rules()
+        };
+
+        board.with_rules(self.hooks.rules(rules))
+    }
+
+    /// Apply the preferences of the [configuration](Config) which do not change the rules of the game
+    fn prepare(&self, board_state: BoardState) -> BoardState {
+        board_state
+            .with_wrap(self.config.wrap)
+            .with_free_pass(self.config.free_pass)
+            .with_flip(self.config.flip)
+    }
+
+    /// Show a new [screen](Screen) on top of the current one
+    fn push_screen(&mut self, screen: Screen) {
+        self.history
+            .push(std::mem::replace(&mut self.screen, screen));
+    }
+
+    /// Go back to the previous [screen](Screen)
+    ///
+    /// The application exits if there is no previous screen.
+    fn pop_screen(&mut self) {
+        self.screen = self.history.pop().unwrap_or(Screen::Exit);
+    }
+
+    /// Get the number of open games, including the current one
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{ApplicationState, Command, CommandManaged, config::Config};
+    ///
+    /// let mut state = ApplicationState::new(Config::default());
+    /// assert_eq!(state.open_games(), 0);
+    ///
+    /// state.handle_command(Command::Select);
+    /// state.handle_command(Command::OpenGame);
+    /// assert_eq!(state.open_games(), 2);
+    /// ```
+    pub fn open_games(&self) -> usize {
+        self.sessions.len() + usize::from(matches!(self.screen, Screen::Board(_)))
+    }
+
+    /// Show a game, sending the current one to the background if any
+    fn switch_to(&mut self, mut board_state: BoardState) {
+        self.hooked = board_state.record().plies.len();
+        self.autosaved = self.hooked;
+
+        match &mut self.screen {
+            Screen::Board(current) => {
+                let previous = std::mem::replace(&mut **current, board_state);
+                self.sessions.push(previous);
+            }
+
+            Screen::GameOver(_) => self.screen = Screen::Board(Box::new(board_state)),
+
+            _ => {
+                let games = self.open_games() + 1;
+                board_state.notify(Message::info(format!("Game 1 of {games}")));
+                self.push_screen(Screen::Board(Box::new(board_state)));
+                return;
+            }
+        }
+
+        let games = self.open_games();
+
+        if let Screen::Board(current) = &mut self.screen {
+            current.notify(Message::info(format!("Game {games} of {games}")));
+        }
+    }
+
+    /// Show the oldest game of the background
+    fn next_game(&mut self) {
+        if self.sessions.is_empty() {
+            let message = Message::warning("No other open game");
+
+            match &mut self.screen {
+                Screen::Board(board_state) => board_state.notify(message),
+                _ => self.messages.push(message),
+            }
+        } else {
+            let board_state = self.sessions.remove(0);
+            self.switch_to(board_state);
+        }
+    }
+
+    fn confirmation(&self, command: Command) -> Option<&'static str> {
+        match (&self.screen, command) {
+            (Screen::Board(_), Command::Exit) => Some("Quit the current game?"),
+            (Screen::Board(board_state), Command::Back) if board_state.is_thinking() => None,
+            (Screen::Board(_), Command::Back) => Some("Leave the current game?"),
+            (Screen::Board(_), Command::Resign) => Some("Resign the current game?"),
+            (Screen::Board(_), Command::NewGame) => Some("Start a new game?"),
+            (Screen::Board(_), Command::Restart) => Some("Restart the current game?"),
+            _ => None,
+        }
+    }
+
+    fn perform(&mut self, command: Command) {
+        if !self.is_typing() {
+            self.chat = None;
+        } else if let Some(input) = &mut self.chat
+            && let Screen::Board(board_state) = &mut self.screen
+        {
+            match command {
+                Command::Select => {
+                    let text = std::mem::take(input);
+
+                    if !text.trim().is_empty() {
+                        board_state.say(text.trim());
+                    }
+
+                    return;
+                }
+
+                Command::Reset => {
+                    input.pop();
+                    return;
+                }
+
+                Command::Back | Command::Chat => {
+                    self.chat = None;
+                    return;
+                }
+
+                _ => (),
+            }
+        }
+
+        match command {
+            Command::Help => self.overlay = Some(Overlay::Help),
+            Command::Credits => self.overlay = Some(Overlay::Credits),
+            Command::Inspect => self.inspector = !self.inspector,
+            Command::ToggleHistory => self.move_list = !self.move_list,
+            Command::Debug => self.debug = !self.debug,
+
+            Command::Back => match &mut self.screen {
+                // Stopping the search gives the move of the computer opponent back to the players
+                Screen::Board(board_state) if board_state.is_thinking() => {
+                    board_state.cancel_thinking();
+                    board_state.notify(Message::info("The computer stopped thinking"));
+                }
+
+                Screen::Board(board_state) => {
+                    board_state.cancel_thinking();
+                    self.pop_screen();
+                }
+
+                Screen::Exit => (),
+
+                Screen::Settings(settings_state) => {
+                    self.config = settings_state.config();
+                    let result = match &self.config_path {
+                        Some(path) => self.config.write(path),
+                        None => self.config.save(),
+                    };
+
+                    if let Err(error) = result {
+                        tracing::error!("The configuration cannot be saved: {error}");
+                    }
+                    self.pop_screen();
+                }
+
+                _ => self.pop_screen(),
+            },
+
+            Command::Exit => {
+                self.history.clear();
+                self.screen = Screen::Exit;
+            }
+
+            _ => match &mut self.screen {
+                Screen::Board(board_state) => match command {
+                    Command::Settings => {
+                        self.push_screen(Screen::Settings(SettingsState::new(self.config)))
+                    }
+
+                    Command::Save => {
+                        let message = match SavedGame::from(&**board_state).save() {
+                            Ok(path) => {
+                                tracing::info!("The game is saved to {}", path.display());
+                                Message::info(format!("Game saved to {}", path.display()))
+                            }
+
+                            Err(error) => {
+                                tracing::error!("The game cannot be saved: {error}");
+                                Message::warning(format!("The game cannot be saved: {error}"))
+                            }
+                        };
+
+                        board_state.notify(message);
+                    }
+
+                    Command::Export => board_state.notify(export(board_state)),
+
+                    Command::Snapshot => {
+                        board_state.notify(snapshot(board_state.board(), self.config))
+                    }
+
+                    Command::Share => {
+                        match code::encode(board_state.board(), board_state.current_player()) {
+                            Ok(code) => {
+                                board_state.notify(Message::info(format!(
+                                    "Board code copied to the clipboard: {code}"
+                                )));
+
+                                self.clipboard = Some(code);
+                            }
+
+                            Err(error) => board_state.notify(Message::warning(error)),
+                        }
+                    }
+
+                    Command::Resign => {
+                        board_state.resign();
+                    }
+
+                    Command::NewGame => {
+                        board_state.cancel_thinking();
+                        let board_state = self.new_game();
+                        self.screen = Screen::Board(Box::new(board_state));
+                    }
+
+                    Command::Restart => {
+                        if board_state.remote_player().is_some() {
+                            board_state
+                                .notify(Message::warning("A network game cannot be restarted"));
+                        } else {
+                            board_state.cancel_thinking();
+
+                            let record = board_state.record();
+                            let (board, first) = (record.board.clone(), record.first);
+
+                            let board_state = self.game_on(board, first);
+                            self.screen = Screen::Board(Box::new(board_state));
+                        }
+                    }
+
+                    Command::OpenGame => {
+                        let board_state = self.new_game();
+                        self.switch_to(board_state);
+                    }
+
+                    Command::NextGame => self.next_game(),
+
+                    Command::Chat if board_state.remote_player().is_some() => {
+                        self.chat = Some(String::new());
+                    }
+
+                    Command::Chat => board_state.notify(Message::warning(
+                        "The chat is only available in network games",
+                    )),
+
+                    _ => board_state.handle_command(command),
+                },
+
+                Screen::Exit => (),
+
+                Screen::GameOver(board_state) => match command {
+                    Command::Select | Command::NewGame => {
+                        let board_state = self.new_game();
+                        self.screen = Screen::Board(Box::new(board_state));
+                    }
+
+                    Command::Replay => {
+                        let record = board_state.record().clone();
+                        self.push_screen(Screen::Replay(Box::new(ReplayState::new(record))));
+                    }
+
+                    Command::Export => self.messages.push(export(board_state)),
+
+                    Command::Snapshot => self
+                        .messages
+                        .push(snapshot(board_state.board(), self.config)),
+                    Command::NextGame => self.next_game(),
+
+                    _ => (),
+                },
+
+                Screen::Logo(_) => match command {
+                    Command::Left => self.config.opponent = previous_opponent(self.config.opponent),
+                    Command::Right => self.config.opponent = next_opponent(self.config.opponent),
+
+                    Command::Select | Command::NewGame => {
+                        let board_state = self.new_game();
+                        self.push_screen(Screen::Board(Box::new(board_state)));
+                    }
+
+                    Command::Settings => {
+                        self.push_screen(Screen::Settings(SettingsState::new(self.config)))
+                    }
+
+                    Command::Statistics => self.push_screen(Screen::Statistics),
+                    Command::NextGame => self.next_game(),
+                    Command::Load => self.push_screen(Screen::Load(LoadState::scan())),
+                    Command::Maps => self.push_screen(Screen::Maps(MapState::scan())),
+                    Command::Join => self.push_screen(Screen::Join(JoinState::scan())),
+
+                    Command::Resume => {
+                        if let Some(game) = self.resumable.take() {
+                            self.autosaved = game.record.plies.len();
+                            self.hooked = game.record.plies.len();
+                            let board_state = game.into_state_with(&mut self.rng, &self.hooks);
+                            let board_state = self.prepare(board_state);
+                            self.push_screen(Screen::Board(Box::new(board_state)));
+                        } else {
+                            self.messages
+                                .push(Message::warning("No unfinished game to resume"));
+                        }
+                    }
+
+                    Command::Replay => {
+                        if let Some(record) = &self.last_game {
+                            let replay_state = ReplayState::new(record.clone());
+                            self.push_screen(Screen::Replay(Box::new(replay_state)));
+                        } else {
+                            self.messages
+                                .push(Message::warning("No finished game to replay"));
+                        }
+                    }
+                    _ => (),
+                },
+
+                Screen::Settings(settings_state) => settings_state.handle_command(command),
+                Screen::Statistics => (),
+                Screen::Replay(replay_state) => replay_state.handle_command(command),
+
+                Screen::Load(load_state) => match command {
+                    Command::Select => {
+                        if let Some(game) = load_state.selected().map(|save| save.game.clone()) {
+                            self.hooked = game.record.plies.len();
+                            let board_state = game.into_state_with(&mut self.rng, &self.hooks);
+                            let board_state = self.prepare(board_state);
+                            self.screen = Screen::Board(Box::new(board_state));
+                        }
+                    }
+
+                    _ => load_state.handle_command(command),
+                },
+
+                Screen::Join(join_state) => match command {
+                    Command::Select => {
+                        if let Some(game) = join_state.selected().cloned() {
+                            let joined = Connection::join(game.address).and_then(|connection| {
+                                self.remote_game(
+                                    connection,
+                                    Role::Guest(Box::new(NetMessage::Hello)),
+                                )
+                            });
+
+                            match joined {
+                                Ok(board_state) => {
+                                    self.screen = Screen::Board(Box::new(board_state))
+                                }
+
+                                Err(error) => {
+                                    tracing::warn!(
+                                        "The game {} cannot be joined: {error}",
+                                        game.name
+                                    );
+
+                                    self.messages.push(Message::warning(format!(
+                                        "The game {} cannot be joined",
+                                        game.name
+                                    )));
+                                }
+                            }
+                        }
+                    }
+
+                    _ => join_state.handle_command(command),
+                },
+
+                Screen::Maps(map_state) => match command {
+                    Command::Select => {
+                        match map_state
+                            .selected()
+                            .map(|map| (map.name(), map.board.clone()))
+                        {
+                            Some((name, Err(error))) => self.messages.push(Message::warning(
+                                format!("The map {name} is malformed: {error}"),
+                            )),
+
+                            selected => {
+                                self.map = selected.and_then(|(_, board)| board.ok());
+                                let board_state = self.new_game();
+                                self.screen = Screen::Board(Box::new(board_state));
+                            }
+                        }
+                    }
+
+                    _ => map_state.handle_command(command),
+                },
+            },
+        }
+    }
+}
+
+#[cfg(feature = "tui")]
+impl CommandManaged for ApplicationState {
+    fn handle_command(&mut self, command: Command) {
+        let _span =
+            tracing::debug_span!("command", ?command, screen = self.screen.name()).entered();
+        self.dirty = true;
+
+        if self.commands.len() == Self::COMMAND_LOG {
+            self.commands.pop_front();
+        }
+
+        self.commands.push_back(command);
+
+        match self.overlay {
+            Some(Overlay::Help) => match command {
+                Command::Back | Command::Help => self.overlay = None,
+
+                Command::Exit => {
+                    self.overlay = None;
+                    self.handle_command(command);
+                }
+
+                _ => (),
+            },
+
+            Some(Overlay::Credits) => match command {
+                Command::Back | Command::Credits => self.overlay = None,
+
+                Command::Exit => {
+                    self.overlay = None;
+                    self.handle_command(command);
+                }
+
+                _ => self.credits.handle_command(command),
+            },
+
+            Some(Overlay::Confirm(confirmation)) => match command {
+                Command::Yes | Command::Select => {
+                    self.overlay = None;
+                    self.perform(confirmation.command);
+                }
+
+                Command::No | Command::Back => self.overlay = None,
+                _ => (),
+            },
+
+            None => {
+                if let Some(message) = self.confirmation(command) {
+                    self.overlay = Some(Overlay::Confirm(Confirmation { message, command }));
+                } else {
+                    self.perform(command);
+                }
+            }
+        }
+    }
+}
+
+/// Export a game in the [portable format](PortableGame), logging the outcome
+#[cfg(feature = "tui")]
+fn export(board_state: &BoardState) -> Message {
+    match PortableGame::from_state(board_state).export() {
+        Ok(path) => {
+            tracing::info!("The game is exported to {}", path.display());
+            Message::info(format!("Game exported to {}", path.display()))
+        }
+
+        Err(error) => {
+            tracing::error!("The game cannot be exported: {error}");
+            Message::warning(format!("The game cannot be exported: {error}"))
+        }
+    }
+}
+
+/// Export a snapshot of a position in the configured [format](snapshot::SnapshotFormat), logging the outcome
+#[cfg(feature = "tui")]
+fn snapshot(board: &game::Board, config: Config) -> Message {
+    let snapshot = Snapshot::new(board.clone()).with_theme(Application::from(config).theme);
+
+    match snapshot.export(config.snapshot_format) {
+        Ok(path) => {
+            tracing::info!("The position is exported to {}", path.display());
+            Message::info(format!("Position exported to {}", path.display()))
+        }
+
+        Err(error) => {
+            tracing::error!("The position cannot be exported: {error}");
+            Message::warning(format!("The position cannot be exported: {error}"))
+        }
+    }
+}
+
+/// Write the game in progress to the autosave file, logging the failures
+#[cfg(feature = "tui")]
+fn autosave(path: &Path, board_state: &BoardState) {
+    if let Err(error) = SavedGame::from(board_state).write(path) {
+        tracing::error!(
+            "The game cannot be autosaved to {}: {error}",
+            path.display()
+        );
+    }
+}
+
+/// Run the hook of an event, logging the failures
+///
+/// # Return
+///
+/// The annotation returned by the hook, if any
+#[cfg(feature = "tui")]
+fn run_hook(hooks: &Hooks, event: HookEvent) -> Option<String> {
+    hooks
+        .run(event)
+        .inspect_err(|error| tracing::error!("The hook of {event:?} cannot be run: {error}"))
+        .ok()
+        .flatten()
+}
+
+/// Run the hooks of the plies of a record not hooked yet, each one seeing the board as it was after its ply
+///
+/// # Return
+///
+/// The annotations returned by the hooks
+#[cfg(feature = "tui")]
+fn run_move_hooks(hooks: &Hooks, record: &GameRecord, hooked: usize) -> Vec<String> {
+    if hooks.is_empty() || hooked >= record.plies.len() {
+        return Vec::new();
+    }
+
+    let (mut board, mut player) = record.position(hooked);
+    let mut annotations = Vec::new();
+
+    for &ply in &record.plies[hooked..] {
+        if let Some(chosen) = ply {
+            board.jump(chosen.from, chosen.to);
+        }
+
+        annotations.extend(run_hook(
+            hooks,
+            HookEvent::Move {
+                player,
+                ply,
+                board: &board,
+            },
+        ));
+
+        player = -player;
+    }
+
+    annotations
+}
+
+#[cfg(feature = "tui")]
+fn next_opponent(opponent: Option<Personality>) -> Option<Personality> {
+    match opponent {
+        None => Some(Personality::ALL[0]),
+        Some(personality) if personality == Personality::ALL[Personality::ALL.len() - 1] => None,
+        Some(personality) => Some(personality.next()),
+    }
+}
+
+#[cfg(feature = "tui")]
+fn previous_opponent(opponent: Option<Personality>) -> Option<Personality> {
+    match opponent {
+        None => Some(Personality::ALL[Personality::ALL.len() - 1]),
+        Some(personality) if personality == Personality::ALL[0] => None,
+        Some(personality) => Some(personality.previous()),
+    }
+}
+
+/// Main widget of the application
+#[cfg(feature = "tui")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Application<'a> {
+    /// The selected symbol
+    ///
+    /// This symbol is used to show the selected cell.
+    pub selected_symbol: &'a str,
+
+    /// The unselected symbol
+    ///
+    /// This symbol is used to show unselected cells.
+    pub unselected_symbol: &'a str,
+
+    /// The [style](RenderStyle) used to render the cells of the board
+    pub render_style: RenderStyle,
+
+    /// Whether each player has its own symbol on the board
+    pub distinct_symbols: bool,
+
+    /// The [grid](Grid) drawn on the board
+    pub grid: Grid,
+
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+
+    /// The [preset](LayoutPreset) of the layout of the game screen
+    pub layout: LayoutPreset,
+}
+
+#[cfg(feature = "tui")]
+impl From<Config> for Application<'static> {
+    fn from(value: Config) -> Self {
+        Self {
+            selected_symbol: value.symbols.selected,
+            unselected_symbol: value.symbols.unselected,
+            render_style: value.render_style.supported(),
+            distinct_symbols: value.colorblind,
+            grid: value.grid,
+            layout: value.layout,
+
+            theme: if value.colorblind {
+                Theme::from(value.theme).colorblind()
+            } else {
+                value.theme.into()
+            },
+        }
+    }
+}
+
+#[cfg(feature = "tui")]
+impl Default for Application<'static> {
+    fn default() -> Self {
+        Self {
+            selected_symbol: "V",
+            unselected_symbol: "O",
+            render_style: RenderStyle::default(),
+            distinct_symbols: false,
+            grid: Grid::default(),
+            theme: Theme::default(),
+            layout: LayoutPreset::default(),
+        }
+    }
+}
+
+#[cfg(feature = "tui")]
+impl StatefulWidget for Application<'_> {
+    type State = ApplicationState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let started = Instant::now();
+        state.dirty = false;
+
+        let required = state.screen.minimum_size();
+
+        if area.width < required.width || area.height < required.height {
+            TooSmall {
+                required,
+                theme: self.theme,
+            }
+            .render(area, buf);
+
+            return;
+        }
+
+        match &mut state.screen {
+            Screen::Board(board_state) => {
+                let comfortable = comfortable_size(board_state);
+                let compact = self.layout == LayoutPreset::Compact;
+                let show_panel = !compact && area.width >= comfortable.width;
+                let show_credits = show_panel && area.height >= comfortable.height;
+
+                let [top, summary, status, bottom] = Layout::vertical(if show_credits {
+                    constraints![==60%, ==0, ==1, *=1]
+                } else {
+                    constraints![*=1, ==u16::from(!show_panel), ==1, ==0]
+                })
+                .areas(area);
+
+                let chat_height = if state.chat.is_some() { 6 } else { 0 };
+                let [top, chat] = Layout::vertical(constraints![*=1, ==chat_height]).areas(top);
+
+                let [left, right] = Layout::horizontal(if show_panel {
+                    constraints![==80%, ==20%]
+                } else {
+                    constraints![*=1, ==0]
+                })
+                .areas(top);
+
+                // No image is drawn below an overlay
+                let render_style = if state.overlay.is_some() {
+                    self.render_style.textual()
+                } else {
+                    self.render_style
+                };
+
+                widgets::board::Board::default()
+                    .selected_symbol(self.selected_symbol)
+                    .unselected_symbol(self.unselected_symbol)
+                    .render_style(render_style)
+                    .grid(self.grid)
+                    .distinct_symbols(self.distinct_symbols)
+                    .theme(self.theme)
+                    .render(left, buf, board_state);
+
+                let clock_height = if board_state.clock().is_some() { 4 } else { 0 };
+
+                let [turn, clock, score] =
+                    Layout::vertical(constraints![==3, ==clock_height, *=1]).areas(right);
+
+                TurnIndicator {
+                    symbol: self.unselected_symbol,
+                    theme: self.theme,
+                }
+                .render(turn, buf, board_state);
+
+                Clock { theme: self.theme }.render(clock, buf, board_state);
+                if state.inspector {
+                    Inspector { theme: self.theme }.render(score, buf, board_state);
+                } else if state.move_list {
+                    MoveList { theme: self.theme }.render(score, buf, board_state);
+                } else {
+                    Score { theme: self.theme }.render(score, buf, board_state);
+                }
+                Summary { theme: self.theme }.render(summary, buf, board_state);
+                StatusBar { theme: self.theme }.render(status, buf, board_state);
+
+                if let Some(input) = &state.chat {
+                    Chat {
+                        input,
+                        theme: self.theme,
+                    }
+                    .render(chat, buf, board_state);
+                }
+
+                Credits { theme: self.theme }.render(bottom, buf, &mut state.credits);
+            }
+
+            Screen::Exit => (),
+
+            Screen::GameOver(board_state) => {
+                let [left, right] = Layout::horizontal(constraints![==60%, ==40%]).areas(area);
+
+                widgets::board::Board::default()
+                    .selected_symbol(self.selected_symbol)
+                    .unselected_symbol(self.unselected_symbol)
+                    .render_style(self.render_style.textual())
+                    .grid(self.grid)
+                    .distinct_symbols(self.distinct_symbols)
+                    .theme(self.theme)
+                    .render(left, buf, board_state);
+
+                GameOver {
+                    theme: self.theme,
+                    rating: state
+                        .rating_change
+                        .map(|change| (state.statistics.rating(), change)),
+                }
+                .render(right, buf, board_state);
+            }
+
+            Screen::Logo(shown) => {
+                let [logo_area, prompt_area, opponent_area] =
+                    Layout::vertical(constraints![>=0, ==1, ==1]).areas(area);
+
+                Logo {
+                    theme: self.theme,
+                    elapsed: *shown,
+                }
+                .render(logo_area, buf);
+
+                if let Some(message) = state.messages.current() {
+                    let style = match message.severity {
+                        Severity::Info => self.theme.secondary,
+                        Severity::Warning => self.theme.important,
+                    };
+
+                    line![span!(style; message.text)]
+                        .centered()
+                        .render(prompt_area, buf);
+                } else if let (_, Some(highlighted)) = Logo::frame(*shown) {
+                    let key = state
+                        .keymap
+                        .keys(Command::Select)
+                        .next()
+                        .map(|key| key.to_string())
+                        .unwrap_or("Enter".to_owned());
+
+                    let style = if highlighted {
+                        self.theme.important
+                    } else {
+                        self.theme.secondary
+                    };
+
+                    line![span!(style; "Press {key} to start")]
+                        .centered()
+                        .render(prompt_area, buf);
+                }
+
+                let opponent = match state.config.opponent {
+                    Some(personality) => format!("{personality} computer"),
+                    None => "Human".to_owned(),
+                };
+
+                line![
+                    span!(self.theme.secondary; "Opponent (←/→): "),
+                    span!(self.theme.important; opponent),
+                    span!(self.theme.secondary; "    Settings (o)    Statistics (t)    Help (?)"),
+                ]
+                .render(opponent_area, buf);
+            }
+
+            Screen::Settings(settings_state) => Settings {
+                theme: self.theme,
+                keymap: &state.keymap,
+            }
+            .render(area, buf, settings_state),
+
+            Screen::Replay(replay_state) => Replay {
+                board: widgets::board::Board::default()
+                    .selected_symbol(self.selected_symbol)
+                    .unselected_symbol(self.unselected_symbol)
+                    .render_style(self.render_style.textual())
+                    .grid(self.grid)
+                    .distinct_symbols(self.distinct_symbols)
+                    .theme(self.theme),
+                theme: self.theme,
+            }
+            .render(area, buf, replay_state),
+
+            Screen::Statistics => Stats {
+                statistics: &state.statistics,
+                theme: self.theme,
+            }
+            .render(area, buf),
+
+            Screen::Load(load_state) => {
+                LoadMenu { theme: self.theme }.render(area, buf, load_state)
+            }
+
+            Screen::Join(join_state) => {
+                JoinMenu { theme: self.theme }.render(area, buf, join_state)
+            }
+
+            Screen::Maps(map_state) => MapMenu { theme: self.theme }.render(area, buf, map_state),
+        }
+
+        match state.overlay {
+            Some(Overlay::Help) => {
+                let area = widgets::popup_area(area, constraint!(==80%), constraint!(==80%));
+                Clear.render(area, buf);
+
+                Help {
+                    selected_symbol: self.selected_symbol,
+                    unselected_symbol: self.unselected_symbol,
+                    theme: self.theme,
+                    key_bindings: state.keymap.bindings(),
+                }
+                .render(area, buf);
+            }
+
+            Some(Overlay::Credits) => {
+                let area = widgets::popup_area(area, constraint!(==80%), constraint!(==80%));
+                Clear.render(area, buf);
+                Credits { theme: self.theme }.render(area, buf, &mut state.credits);
+            }
+
+            Some(Overlay::Confirm(confirmation)) => {
+                let area = widgets::popup_area(area, constraint!(==50), constraint!(==5));
+                Clear.render(area, buf);
+
+                Dialog {
+                    message: confirmation.message,
+                    theme: self.theme,
+                }
+                .render(area, buf);
+            }
+
+            None => (),
+        }
+
+        if state.debug {
+            let board_state = match &state.screen {
+                Screen::Board(board_state) | Screen::GameOver(board_state) => Some(&**board_state),
+                _ => None,
+            };
+
+            let [_, area] = Layout::horizontal(constraints![*=1, ==36]).areas(area);
+            let [area, _] = Layout::vertical(constraints![==9, *=1]).areas(area);
+            Clear.render(area, buf);
+
+            DebugPanel {
+                frame_time: state.frame_time,
+                last_command: state.commands.back().copied(),
+                screen: state.screen.name(),
+                board_state,
+                theme: self.theme,
+            }
+            .render(area, buf);
+        }
+
+        state.frame_time = started.elapsed();
+    }
+}
+
+#[cfg(all(test, feature = "tui"))]
+mod tests {
+    use super::*;
+
+    fn render(state: &mut ApplicationState) -> Buffer {
+        let area = Rect::new(0, 0, 80, 24);
+        let mut buffer = Buffer::empty(area);
+        Application::default().render(area, &mut buffer, state);
+        buffer
+    }
+
+    #[test]
+    fn frame_change() {
+        let mut state = ApplicationState::new(Config::default());
+        state.tick(Duration::from_secs(60));
+        let first = render(&mut state);
+
+        state.tick(Logo::PULSE * 2);
+        state.update();
+        assert!(!state.needs_redraw());
+        pretty_assertions::assert_eq!(render(&mut state), first);
+
+        state.handle_command(Command::Right);
+        assert!(state.needs_redraw());
+        assert_ne!(render(&mut state), first);
+        assert!(!state.needs_redraw());
+    }
+
+    #[test]
+    fn logo() {
+        let mut state = ApplicationState::new(Config::default());
+        let first = render(&mut state);
+
+        state.tick(Logo::STEP);
+        assert!(state.needs_redraw());
+        assert_ne!(render(&mut state), first);
+
+        state.tick(Duration::from_secs(60));
+        let revealed = render(&mut state);
+
+        state.tick(Logo::PULSE);
+        assert!(state.needs_redraw());
+        assert_ne!(render(&mut state), revealed);
+    }
+
+    #[test]
+    fn credits() {
+        let mut state = ApplicationState::new(Config::default());
+        state.handle_command(Command::Select);
+        render(&mut state);
+
+        state.tick(CreditsState::STEP);
+        assert!(state.needs_redraw());
+        pretty_assertions::assert_eq!(state.credits.offset(), 1);
+
+        state.handle_command(Command::Credits);
+        state.handle_command(Command::Up);
+        assert!(!state.credits.is_auto_scrolling());
+        pretty_assertions::assert_eq!(state.credits.offset(), 0);
+
+        state.handle_command(Command::Back);
+        pretty_assertions::assert_eq!(state.overlay(), None);
+    }
+
+    #[test]
+    fn debug() {
+        let mut state = ApplicationState::new(Config::default());
+        state.handle_command(Command::Select);
+        state.handle_command(Command::Debug);
+
+        let content = render(&mut state)
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+
+        assert!(content.contains("Command: Debug"));
+        assert!(content.contains("Screen: Board"));
+        assert!(content.contains("Zobrist: "));
+
+        state.handle_command(Command::Debug);
+
+        let content = render(&mut state)
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+
+        assert!(!content.contains("Zobrist: "));
+    }
+
+    #[test]
+    fn hot_reload() {
+        let directory =
+            std::env::temp_dir().join(format!("blobwars-reload-{}", std::process::id()));
+        let config = directory.join("config.toml");
+
+        Config::default().write(&config).unwrap();
+
+        let mut state = ApplicationState::new(Config::default()).with_hot_reload(config.clone());
+
+        std::fs::write(&config, "[keys]\nselect = \"Unknown+x\"").unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        state.tick(Duration::ZERO);
+
+        pretty_assertions::assert_eq!(state.keymap(), &Keymap::default());
+        assert!(
+            state
+                .messages()
+                .current()
+                .is_some_and(|message| message.severity == Severity::Warning)
+        );
+
+        let wrapped = Config {
+            wrap: true,
+            ..Config::default()
+        };
+
+        std::fs::write(&config, wrapped.to_string()).unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        state.tick(Duration::ZERO);
+
+        let _ = std::fs::remove_dir_all(directory);
+        pretty_assertions::assert_eq!(state.config(), wrapped);
+    }
+
+    #[test]
+    fn resign() {
+        let mut state = ApplicationState::new(Config::default());
+        state.handle_command(Command::Select);
+        state.handle_command(Command::Resign);
+
+        assert!(matches!(state.overlay(), Some(Overlay::Confirm(_))));
+
+        state.handle_command(Command::Yes);
+        state.update();
+
+        assert!(
+            matches!(state.screen(), Screen::GameOver(board_state) if board_state.outcome().is_some())
+        );
+
+        state.handle_command(Command::NewGame);
+        assert!(
+            matches!(state.screen(), Screen::Board(board_state) if board_state.outcome().is_none())
+        );
+    }
+
+    #[test]
+    fn restart() {
+        let mut state = ApplicationState::new(Config::default());
+        state.handle_command(Command::Select);
+
+        let Screen::Board(board_state) = state.screen() else {
+            panic!("no game started");
+        };
+
+        let board = board_state.board().clone();
+
+        state.handle_command(Command::Restart);
+        assert!(matches!(state.overlay(), Some(Overlay::Confirm(_))));
+
+        state.handle_command(Command::Yes);
+        assert!(
+            matches!(state.screen(), Screen::Board(board_state) if *board_state.board() == board)
+        );
+    }
+
+    #[test]
+    fn tabs() {
+        let mut state = ApplicationState::new(Config::default());
+        state.handle_command(Command::NextGame);
+        pretty_assertions::assert_eq!(state.open_games(), 0);
+
+        state.handle_command(Command::Select);
+        state.handle_command(Command::Hint);
+        state.handle_command(Command::OpenGame);
+        pretty_assertions::assert_eq!(state.open_games(), 2);
+
+        state.handle_command(Command::NextGame);
+        pretty_assertions::assert_eq!(state.open_games(), 2);
+
+        assert!(
+            matches!(state.screen(), Screen::Board(board_state) if board_state.from().is_some())
+        );
+
+        state.handle_command(Command::NextGame);
+        assert!(
+            matches!(state.screen(), Screen::Board(board_state) if board_state.from().is_none())
+        );
+    }
+
+    #[test]
+    fn network() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let guest = std::thread::spawn(move || {
+            ApplicationState::new(Config::default())
+                .with_remote(
+                    Connection::join(address).unwrap(),
+                    Role::Guest(Box::new(NetMessage::Hello)),
+                )
+                .unwrap()
+        });
+
+        let host = ApplicationState::new(Config::default())
+            .with_remote(Connection::accept(&listener).unwrap(), Role::Host)
+            .unwrap();
+
+        let guest = guest.join().unwrap();
+
+        let (Screen::Board(host), Screen::Board(guest)) = (host.screen(), guest.screen()) else {
+            panic!("A network game has not started");
+        };
+
+        pretty_assertions::assert_eq!(host.record(), guest.record());
+        pretty_assertions::assert_eq!(host.remote_player(), Some(game::Player::Red));
+        pretty_assertions::assert_eq!(guest.remote_player(), Some(game::Player::Blue));
+    }
+
+    #[test]
+    fn statistics() {
+        let mut state = ApplicationState::new(Config::default());
+        state.handle_command(Command::Statistics);
+        pretty_assertions::assert_eq!(state.screen(), &Screen::Statistics);
+
+        state.handle_command(Command::Back);
+        assert!(matches!(state.screen(), Screen::Logo(_)));
+    }
+
+    #[test]
+    fn board_code() {
+        let board: game::Board = "B.#/..R".parse().unwrap();
+        let code = code::encode(&board, game::Player::Red).unwrap();
+
+        let mut state = ApplicationState::new(Config::default());
+        state.handle_action(Action::Paste("not a code".to_owned()));
+        assert!(matches!(state.screen(), Screen::Logo(_)));
+
+        state.handle_action(Action::Paste(code));
+
+        assert!(matches!(
+            state.screen(),
+            Screen::Board(board_state)
+                if board_state.board() == &board && board_state.current_player() == game::Player::Red
+        ));
+    }
+
+    #[test]
+    fn too_small() {
+        let mut state = ApplicationState::new(Config::default());
+        state.handle_command(Command::Select);
+
+        let area = Rect::new(0, 0, 36, 8);
+        let mut buffer = Buffer::empty(area);
+        Application::default().render(area, &mut buffer, &mut state);
+
+        let content = buffer
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+
+        assert!(content.contains("Please resize to at least 40×10"));
+        assert!(
+            !render(&mut state)
+                .content()
+                .iter()
+                .any(|cell| cell.symbol() == "×")
+        );
+    }
+
+    #[test]
+    fn compact_layout() {
+        let mut state = ApplicationState::new(Config::default());
+        state.handle_command(Command::Select);
+
+        let mut content = |application: Application, width, height| {
+            let area = Rect::new(0, 0, width, height);
+            let mut buffer = Buffer::empty(area);
+            application.render(area, &mut buffer, &mut state);
+
+            buffer
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect::<String>()
+        };
+
+        let comfortable = content(Application::default(), 80, 24);
+        assert!(comfortable.contains("Score") && !comfortable.contains("to move │"));
+
+        let narrow = content(Application::default(), 50, 24);
+        assert!(!narrow.contains("Score") && narrow.contains("to move │"));
+
+        let compact = Application {
+            layout: LayoutPreset::Compact,
+            ..Application::default()
+        };
+
+        let compact = content(compact, 80, 24);
+        assert!(!compact.contains("Score") && compact.contains("to move │"));
+    }
+
+    #[test]
+    fn navigation() {
+        let mut state = ApplicationState::new(Config::default());
+        state.handle_command(Command::Select);
+        state.handle_command(Command::Settings);
+        pretty_assertions::assert_eq!(state.depth(), 2);
+
+        state.handle_command(Command::Back);
+        assert!(matches!(state.screen(), Screen::Board(_)));
+
+        state.handle_command(Command::Back);
+        state.handle_command(Command::Yes);
+        assert!(matches!(state.screen(), Screen::Logo(_)));
+
+        state.handle_command(Command::Back);
+        assert!(state.has_exited());
+    }
+
+    #[test]
+    fn seeded_map() {
+        let map: game::Board = "R..#/...B".parse().unwrap();
+
+        let mut state = ApplicationState::new(Config::default())
+            .with_seed(1)
+            .with_map(map.clone());
+
+        state.handle_command(Command::Select);
+        assert!(
+            matches!(state.screen(), Screen::Board(board_state) if board_state.record().board == map)
+        );
+
+        let config = Config {
+            opponent: Some(Personality::Chaotic),
+            ..Config::default()
+        };
+
+        let mut first = ApplicationState::new(config).with_seed(42);
+        let mut second = ApplicationState::new(config).with_seed(42);
+        pretty_assertions::assert_eq!(first.new_game(), second.new_game());
+        pretty_assertions::assert_eq!(first.new_game(), second.new_game());
+    }
+
+    #[test]
+    fn resume() {
+        let mut game = SavedGame {
+            record: GameRecord::new("R..B".parse().unwrap(), game::Player::Red),
+            clock: None,
+            opponent: None,
+        };
+
+        game.record.plies.push(None);
+
+        let state = ApplicationState::new(Config::default()).with_game(game);
+
+        assert!(matches!(
+            state.screen(),
+            Screen::Board(board_state) if board_state.current_player() == game::Player::Blue
+        ));
+
+        pretty_assertions::assert_eq!(state.depth(), 1);
+    }
+
+    #[test]
+    fn move_hooks() {
+        let hooks: Hooks = r#"fn on_move(board, player, ply) { `${ply} ${board.score(player)}` }"#
+            .parse()
+            .unwrap();
+
+        let mut record = GameRecord::new("R...B".parse().unwrap(), game::Player::Red);
+
+        record.plies = vec![
+            Some(game::Move {
+                from: (0, 0),
+                to: (0, 1),
+            }),
+            Some(game::Move {
+                from: (0, 4),
+                to: (0, 2),
+            }),
+            None,
+        ];
+
+        pretty_assertions::assert_eq!(
+            run_move_hooks(&hooks, &record, 0),
+            ["a1b1 2", "e1c1 2", "pass 1"]
+        );
+
+        pretty_assertions::assert_eq!(run_move_hooks(&hooks, &record, 2), ["pass 1"]);
+        assert!(run_move_hooks(&Hooks::default(), &record, 0).is_empty());
+    }
+
+    #[test]
+    fn cancel_search() {
+        let game = SavedGame {
+            record: GameRecord::new("R..B".parse().unwrap(), game::Player::Red),
+            clock: None,
+            opponent: Some((
+                game::Player::Red,
+                Personality::Territorial,
+                ai::Difficulty::Easy,
+            )),
+        };
+
+        let mut state = ApplicationState::new(Config::default()).with_game(game);
+        assert!(matches!(state.screen(), Screen::Board(board_state) if board_state.is_thinking()));
+
+        state.handle_command(Command::Back);
+        pretty_assertions::assert_eq!(state.overlay(), None);
+        pretty_assertions::assert_eq!(state.depth(), 1);
+
+        state.update();
+
+        assert!(matches!(
+            state.screen(),
+            Screen::Board(board_state) if !board_state.is_thinking()
+                && board_state.record().plies.is_empty()
+                && board_state.current_player() == game::Player::Red
+        ));
+
+        state.handle_command(Command::Back);
+        assert!(matches!(state.overlay(), Some(Overlay::Confirm(_))));
+    }
+
+    #[test]
+    fn autosave() {
+        let path =
+            std::env::temp_dir().join(format!("blobwars-autosave-{}.toml", std::process::id()));
+
+        let mut game = SavedGame {
+            record: GameRecord::new("B...R".parse().unwrap(), game::Player::Blue),
+            clock: None,
+            opponent: None,
+        };
+
+        game.record.plies.push(Some(game::Move {
+            from: (0, 0),
+            to: (0, 1),
+        }));
+
+        let mut state = ApplicationState::new(Config::default())
+            .with_autosave(path.clone())
+            .with_game(game.clone());
+
+        state.update();
+
+        pretty_assertions::assert_eq!(SavedGame::read(&path).ok(), Some(game));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn running_clock() {
+        let config = Config {
+            clock: Some(clock::TimeControl::PRESETS[0]),
+            ..Config::default()
+        };
+
+        let mut state = ApplicationState::new(config);
+        state.handle_command(Command::Select);
+        render(&mut state);
+
+        state.tick(Duration::from_millis(100));
+        assert!(state.needs_redraw());
+    }
 }
@@ -0,0 +1,125 @@
+//! The implementation of [`FileLogger`]
+//!
+//! The terminal being owned by the user interface, the traces are never written to the standard outputs but appended to a [file](FileLogger::path).
+//! Each event is written along with the spans it happens in, such as the handled command or the search of the computer opponent.
+
+use crate::save::SavedGame;
+use tracing::{Dispatch, level_filters::LevelFilter};
+use tracing_subscriber::fmt::{self, format::FmtSpan};
+
+use std::{
+    env,
+    fs::{self, File, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// A [subscriber](tracing::Subscriber) appending the traces to a file
+#[derive(Debug)]
+pub struct FileLogger {
+    file: Mutex<File>,
+    level: LevelFilter,
+}
+
+impl FileLogger {
+    /// The name of the environment variable holding the default [level](LevelFilter)
+    pub const ENV: &str = "BLOBWARS_LOG";
+
+    /// Get the path of the log file
+    ///
+    /// The file is located next to the [save directory](SavedGame::directory).
+    pub fn path() -> Option<PathBuf> {
+        SavedGame::directory().map(|directory| directory.with_file_name("blobwars.log"))
+    }
+
+    /// Get the [level](LevelFilter) of the traces to write
+    ///
+    /// The level given on the command line takes precedence over the one of the [environment](Self::ENV); only the warnings and the errors are logged by default.
+    ///
+    /// # Parameter
+    ///
+    /// - `level` - The level given on the command line, if any
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::logging::FileLogger;
+    /// use tracing::level_filters::LevelFilter;
+    ///
+    /// assert_eq!(FileLogger::level(Some(LevelFilter::TRACE)), LevelFilter::TRACE);
+    /// ```
+    pub fn level(level: Option<LevelFilter>) -> LevelFilter {
+        level
+            .or_else(|| env::var(Self::ENV).ok()?.parse().ok())
+            .unwrap_or(LevelFilter::WARN)
+    }
+
+    /// Create a new [`FileLogger`]
+    ///
+    /// # Parameters
+    ///
+    /// - `path` - The path of the log file; it is created with its parent directories if needed
+    /// - `level` - The [level](LevelFilter) of the traces to write
+    pub fn new(path: &Path, level: LevelFilter) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(Self {
+            file: Mutex::new(file),
+            level,
+        })
+    }
+
+    /// Get the [dispatcher](Dispatch) writing the traces to the file
+    ///
+    /// The spans are written when they close, along with the time spent in them.
+    pub fn dispatch(self) -> Dispatch {
+        let subscriber = fmt::Subscriber::builder()
+            .with_writer(self.file)
+            .with_max_level(self.level)
+            .with_ansi(false)
+            .with_span_events(FmtSpan::CLOSE)
+            .finish();
+
+        Dispatch::new(subscriber)
+    }
+
+    /// Install the logger as the global one
+    ///
+    /// Nothing is done if the level is [off](LevelFilter::OFF) or if a logger has already been installed.
+    pub fn install(self) {
+        if self.level != LevelFilter::OFF {
+            let _ = tracing::dispatcher::set_global_default(self.dispatch());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write() {
+        let path = env::temp_dir().join(format!("blobwars-{}.log", std::process::id()));
+        let logger = FileLogger::new(&path, LevelFilter::INFO).unwrap();
+
+        tracing::dispatcher::with_default(&logger.dispatch(), || {
+            let _span = tracing::info_span!("game", id = 3).entered();
+            tracing::debug!("hidden");
+            tracing::warn!("shown");
+        });
+
+        let content = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(path);
+
+        let first = content.lines().next().unwrap();
+        assert!(first.contains("WARN"));
+        assert!(first.ends_with("game{id=3}: blobwars::logging::tests: shown"));
+        assert!(content.contains("game{id=3}: blobwars::logging::tests: close"));
+        assert!(!content.contains("hidden"));
+    }
+}
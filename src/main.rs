@@ -1,32 +1,789 @@
-use blobwars::{Application, ApplicationState, Command, CommandManaged};
-use ratatui::{DefaultTerminal, crossterm::event};
-use std::{error::Error, time::Duration};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use blobwars::{
+    Application, ApplicationState, Screen,
+    ai::{Ai, Difficulty},
+    cli::{Options, Task},
+    config::Config,
+    database::{ArchivedGame, GameDatabase},
+    events::{EventLoop, Interruption, LoopEvent, Suspension, Ticker},
+    game::{Board, Player, RuleSet},
+    graphics::{self, Graphics, Protocol},
+    headless::HeadlessGame,
+    hooks::Hooks,
+    input::{Action, InputMapper},
+    keymap::Keymap,
+    logging::FileLogger,
+    movie::{Movie, MovieFormat},
+    net::{
+        Connection, NetMessage, Role,
+        discovery::Announcement,
+        tls::{Certificate, Trust},
+    },
+    pgn::PortableGame,
+    record::GameRecord,
+    rng::Rng,
+    save::SavedGame,
+    selfplay::{self, SelfPlay},
+    snapshot::{Snapshot, SnapshotFormat},
+    stats::Statistics,
+    tournament::{self, Engine, Format, Tournament},
+    widgets::board::RenderStyle,
+};
+use clap::Parser;
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::{self, Write, stdout},
+    net::ToSocketAddrs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    time::Instant,
+};
+use tracing::level_filters::LevelFilter;
+
+use ratatui::{
+    DefaultTerminal,
+    crossterm::{
+        event::{
+            self, DisableBracketedPaste, DisableFocusChange, DisableMouseCapture,
+            EnableBracketedPaste, EnableFocusChange, EnableMouseCapture, Event,
+        },
+        execute,
+        terminal::{self, EnterAlternateScreen},
+    },
+};
 
 fn main() -> Result<(), Box<dyn Error>> {
+    let options = Options::parse();
+
+    match &options.task {
+        Some(Task::WriteConfig) => return write_config(&options),
+
+        Some(Task::Stats) => {
+            print_statistics();
+            return Ok(());
+        }
+
+        Some(Task::Export) => return export(&options),
+        Some(Task::Snapshot) => return snapshot(&options),
+        Some(Task::RenderReplay { file }) => return render_replay(file, &options),
+        Some(Task::Tournament | Task::Selfplay) | None => (),
+    }
+
+    if let Some(path) = &options.import {
+        return import(path);
+    }
+
+    let level = FileLogger::level(options.log_level);
+
+    if level != LevelFilter::OFF
+        && let Some(path) = FileLogger::path()
+    {
+        match FileLogger::new(&path, level) {
+            Ok(logger) => logger.install(),
+            Err(error) => eprintln!("blobwars: the log file cannot be opened: {error}"),
+        }
+    }
+
+    tracing::info!("blobwars {} starts", env!("CARGO_PKG_VERSION"));
+
+    if options.headless {
+        return headless(&options);
+    }
+
+    match options.task {
+        Some(Task::Tournament) => return tournament(&options),
+        Some(Task::Selfplay) => return selfplay(&options),
+        _ => (),
+    }
+
+    if let Some(address) = &options.server
+        && options.create.is_none()
+        && options.enter.is_none()
+    {
+        return lobby(address);
+    }
+
+    let application_state = load(&options).inspect_err(|error| tracing::error!("{error}"))?;
+
     let terminal = ratatui::init();
-    let result = run(terminal);
-    ratatui::restore();
+    enter()?;
+    let result = run(terminal, application_state, options.threaded);
+    leave()?;
+
+    match &result {
+        Ok(()) => tracing::info!("blobwars exits"),
+        Err(error) => tracing::error!("blobwars exits on an error: {error}"),
+    }
+
     result
 }
 
-fn run(mut terminal: DefaultTerminal) -> Result<(), Box<dyn Error>> {
-    let mut application_state = ApplicationState::default();
+/// Enable the terminal features used on top of the raw mode and the alternate screen
+fn enter() -> io::Result<()> {
+    execute!(
+        stdout(),
+        EnableMouseCapture,
+        EnableFocusChange,
+        EnableBracketedPaste
+    )
+}
 
-    while !application_state.has_exited() {
-        terminal.draw(|frame| {
-            frame.render_stateful_widget(
-                Application::default(),
-                frame.area(),
+/// Disable the terminal features and restore the terminal
+fn leave() -> io::Result<()> {
+    execute!(
+        stdout(),
+        DisableMouseCapture,
+        DisableFocusChange,
+        DisableBracketedPaste
+    )?;
+
+    ratatui::restore();
+    Ok(())
+}
+
+/// Restore the terminal, stop the process and set the terminal up again once it is continued
+fn suspend(terminal: &mut DefaultTerminal, suspension: &Suspension) -> Result<(), Box<dyn Error>> {
+    tracing::info!("blobwars is suspended");
+    leave()?;
+    suspension.suspend()?;
+
+    terminal::enable_raw_mode()?;
+    execute!(stdout(), EnterAlternateScreen)?;
+    enter()?;
+    terminal.clear()?;
+
+    tracing::info!("blobwars is resumed");
+    Ok(())
+}
+
+fn print_statistics() {
+    let statistics = Statistics::load();
+    let average = |value: Option<f64>| value.map_or("-".to_owned(), |value| format!("{value:.1}"));
+
+    println!("Games played: {}", statistics.games());
+    println!(
+        "Wins: Blue {}, Red {}, Draws {}",
+        statistics.wins.blue, statistics.wins.red, statistics.draws
+    );
+    println!(
+        "Average game length: {}",
+        average(statistics.average_length())
+    );
+    println!("Longest game: {} moves", statistics.longest);
+    println!(
+        "Captures per game: Blue {}, Red {}",
+        average(statistics.average_captures(Player::Blue)),
+        average(statistics.average_captures(Player::Red))
+    );
+    println!("Rating: {}", statistics.rating());
+
+    for difficulty in Difficulty::ALL {
+        println!(
+            "Against {difficulty}: {} won, rated {}",
+            statistics.record(difficulty),
+            statistics.ai_rating(difficulty)
+        );
+    }
+}
+
+fn export(options: &Options) -> Result<(), Box<dyn Error>> {
+    let game = if let Some(path) = &options.load {
+        let board_state = SavedGame::read(path)?.into_state(&mut Rng::default());
+        PortableGame::from_state(&board_state)
+    } else if let Some(path) = &options.replay {
+        PortableGame::new(read_record(path)?)
+    } else {
+        PortableGame::new(GameRecord::load().ok_or("No finished game was found")?)
+    };
+
+    print!("{game}");
+    Ok(())
+}
+
+fn snapshot(options: &Options) -> Result<(), Box<dyn Error>> {
+    let config = options.apply(match &options.config {
+        Some(path) => Config::read(path)?,
+        None => Config::load()?,
+    });
+
+    let board = if let Some(path) = &options.load {
+        SavedGame::read(path)?
+            .into_state(&mut Rng::default())
+            .board()
+            .clone()
+    } else if let Some(path) = &options.replay {
+        let record = read_record(path)?;
+        record.position(record.plies.len()).0
+    } else if let Some(path) = &options.map {
+        fs::read_to_string(path)?.parse()?
+    } else {
+        let record = GameRecord::load().ok_or("No finished game was found")?;
+        record.position(record.plies.len()).0
+    };
+
+    let snapshot = Snapshot::new(board).with_theme(Application::from(config).theme);
+
+    match &options.output {
+        Some(path) => {
+            let format = SnapshotFormat::from_path(path).unwrap_or_default();
+            fs::write(path, snapshot.encode(format)?)?;
+        }
+
+        None => print!("{}", snapshot.svg()),
+    }
+
+    Ok(())
+}
+
+fn render_replay(path: &Path, options: &Options) -> Result<(), Box<dyn Error>> {
+    let config = options.apply(match &options.config {
+        Some(path) => Config::read(path)?,
+        None => Config::load()?,
+    });
+
+    let movie = Movie::new(read_record(path)?).with_theme(Application::from(config).theme);
+
+    let output = options
+        .output
+        .clone()
+        .unwrap_or_else(|| path.with_extension(MovieFormat::Apng.extension()));
+
+    let format = MovieFormat::from_path(&output).unwrap_or_default();
+    fs::write(&output, movie.encode(format)?)?;
+    println!("The replay has been written to {}", output.display());
+
+    Ok(())
+}
+
+fn import(path: &Path) -> Result<(), Box<dyn Error>> {
+    let game = fs::read_to_string(path)?.parse::<PortableGame>()?;
+    let game = ArchivedGame::from_portable(game).ok_or("Only finished games can be imported")?;
+    let database = GameDatabase::open().ok_or("No data directory was found")?;
+
+    let stored = database.insert(&game)?;
+    println!(
+        "The game has been stored as #{stored} in {}",
+        database.path().display()
+    );
+
+    Ok(())
+}
+
+/// Read a game recorded either as a [record](GameRecord) or as a [portable game](PortableGame)
+fn read_record(path: &Path) -> Result<GameRecord, Box<dyn Error>> {
+    let content = fs::read_to_string(path)?;
+
+    if path.extension().is_some_and(|extension| extension == "pgn")
+        || content.trim_start().starts_with('[')
+    {
+        Ok(content.parse::<PortableGame>()?.record)
+    } else {
+        Ok(content.parse()?)
+    }
+}
+
+fn write_config(options: &Options) -> Result<(), Box<dyn Error>> {
+    let path = options
+        .config
+        .clone()
+        .or_else(Config::path)
+        .ok_or("No configuration directory was found")?;
+
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()).into());
+    }
+
+    Config::default().write(&path)?;
+    Keymap::default().write(&path)?;
+
+    println!(
+        "The default configuration and key bindings have been written to {}",
+        path.display()
+    );
+    Ok(())
+}
+
+fn headless(options: &Options) -> Result<(), Box<dyn Error>> {
+    let config = options.apply(match &options.config {
+        Some(path) => Config::read(path)?,
+        None => Config::load()?,
+    });
+
+    let mut rng = options.seed.map(Rng::new).unwrap_or_default();
+
+    let board = match &options.map {
+        Some(path) => fs::read_to_string(path)?.parse()?,
+        None => Board::generate(config.height, config.width, config.density, &mut rng),
+    };
+
+    let board = if board.rules() == RuleSet::default() {
+        board.with_rules(config.rules)
+    } else {
+        board
+    };
+
+    let mut game = HeadlessGame::new(board, Player::Blue).with_free_pass(config.free_pass);
+
+    if let Some(personality) = config.opponent {
+        let opponent = Ai::new(personality)
+            .with_rng(rng.fork())
+            .with_difficulty(config.difficulty);
+
+        game = game.with_opponent(Player::Red, opponent);
+    }
+
+    if game.play(io::stdin().lock(), stdout().lock())?.is_some() {
+        game.record().save()?;
+    }
+
+    Ok(())
+}
+
+fn tournament(options: &Options) -> Result<(), Box<dyn Error>> {
+    let config = options.apply(match &options.config {
+        Some(path) => Config::read(path)?,
+        None => Config::load()?,
+    });
+
+    let maps = match &options.map {
+        Some(path) => vec![fs::read_to_string(path)?.parse()?],
+
+        None => {
+            let mut rng = Rng::new(options.seed.unwrap_or(1));
+
+            (0..options.rounds.unwrap_or(4))
+                .map(|_| Board::generate(config.height, config.width, config.density, &mut rng))
+                .collect()
+        }
+    };
+
+    let engines = if options.engines.is_empty() {
+        Engine::all()
+    } else {
+        options.engines.clone()
+    };
+
+    let format = if options.gauntlet {
+        Format::Gauntlet
+    } else {
+        Format::RoundRobin
+    };
+
+    let mut tournament = Tournament::new(engines, maps)
+        .with_format(format)
+        .with_seed(options.seed.unwrap_or(1));
+
+    if let Some(threads) = options.threads {
+        tournament = tournament.with_threads(threads);
+    }
+
+    let standings = tournament.run();
+    print!("{}", tournament::to_table(&standings));
+
+    if let Some(path) = &options.output {
+        let content = if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            tournament::to_json(&standings)
+        } else {
+            tournament::to_csv(&standings)
+        };
+
+        fs::write(path, content)?;
+    }
+
+    Ok(())
+}
+
+fn selfplay(options: &Options) -> Result<(), Box<dyn Error>> {
+    let config = options.apply(match &options.config {
+        Some(path) => Config::read(path)?,
+        None => Config::load()?,
+    });
+
+    let games = options.games.unwrap_or(100);
+
+    let maps = match &options.map {
+        Some(path) => vec![fs::read_to_string(path)?.parse()?],
+
+        None => {
+            let mut rng = Rng::new(options.seed.unwrap_or(1));
+
+            (0..games)
+                .map(|_| Board::generate(config.height, config.width, config.density, &mut rng))
+                .collect()
+        }
+    };
+
+    let engine = options.engines.first().copied().unwrap_or_default();
+
+    let mut selfplay = SelfPlay::new(engine, maps)
+        .with_games(games)
+        .with_seed(options.seed.unwrap_or(1));
+
+    if let Some(threads) = options.threads {
+        selfplay = selfplay.with_threads(threads);
+    }
+
+    let samples = selfplay.run();
+
+    match &options.output {
+        Some(path) if path.extension().is_some_and(|extension| extension == "csv") => {
+            fs::write(path, selfplay::to_csv(&samples))?
+        }
+
+        Some(path) => fs::write(path, selfplay::to_binary(&samples))?,
+        None => print!("{}", selfplay::to_csv(&samples)),
+    }
+
+    Ok(())
+}
+
+/// Announce the game hosted on a given address to the local network, named after the user
+fn announce(address: &str) -> Option<Announcement> {
+    let port = address.to_socket_addrs().ok()?.next()?.port();
+
+    let name = env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or("blobwars".to_owned());
+
+    Announcement::start(&name, port)
+        .inspect_err(|error| eprintln!("The game cannot be announced: {error}"))
+        .ok()
+}
+
+fn lobby(address: &str) -> Result<(), Box<dyn Error>> {
+    let connection = Connection::join(address)?;
+    connection.send(&NetMessage::List)?;
+
+    match connection.recv()? {
+        NetMessage::Games(names) if names.is_empty() => println!("No game is open"),
+        NetMessage::Games(names) => names.iter().for_each(|name| println!("{name}")),
+        NetMessage::Refused(reason) => return Err(reason.into()),
+        _ => return Err("The server has not listed its games".into()),
+    }
+
+    Ok(())
+}
+
+fn load(options: &Options) -> Result<ApplicationState, Box<dyn Error>> {
+    let (config, keymap, hooks) = match &options.config {
+        Some(path) => {
+            let hooks = Hooks::read(&path.with_file_name("hooks.rhai")).unwrap_or_default();
+            (Config::read(path)?, Keymap::read(path)?, hooks)
+        }
+
+        None => (Config::load()?, Keymap::load()?, Hooks::load()),
+    };
+
+    let mut application_state = ApplicationState::new(options.apply(config))
+        .with_keymap(keymap)
+        .with_hooks(hooks)
+        .with_statistics(Statistics::load())
+        .with_last_game(GameRecord::load());
+
+    if let Some(path) = &options.config {
+        application_state = application_state.with_config_path(path.clone());
+    }
+
+    if let Some(database) = GameDatabase::open() {
+        application_state = application_state.with_database(database);
+    }
+
+    if let Some(path) = options.config.clone().or_else(Config::path) {
+        application_state = application_state.with_hot_reload(path);
+    }
+
+    if let Some(path) = SavedGame::autosave_path() {
+        if let Ok(game) = SavedGame::read(&path)
+            && options.load.is_none()
+            && options.replay.is_none()
+            && options.host.is_none()
+            && options.join.is_none()
+        {
+            application_state = application_state.with_resumable(game);
+        }
+
+        application_state = application_state.with_autosave(path);
+    }
+
+    if let Some(seed) = options.seed {
+        application_state = application_state.with_seed(seed);
+    }
+
+    if let Some(path) = &options.map {
+        application_state = application_state.with_map(fs::read_to_string(path)?.parse()?);
+    }
+
+    if let Some(path) = &options.load {
+        application_state = application_state.with_game(SavedGame::read(path)?);
+    }
+
+    if let Some(path) = &options.replay {
+        application_state = application_state.with_replay(read_record(path)?);
+    }
+
+    let trust = options.pin.map_or(Trust::Authorities, Trust::Pinned);
+
+    if let Some(address) = &options.host {
+        eprintln!("Waiting for a player to join on {address}");
+
+        let connection = if address.starts_with("ws") {
+            Connection::relay_tls(address, trust)?
+        } else if let (Some(certificate), Some(key)) = (&options.cert, &options.key) {
+            let certificate = Certificate::read(certificate, key)?;
+            eprintln!(
+                "The fingerprint of the certificate is {}",
+                certificate.fingerprint()
+            );
+
+            let _announcement = announce(address);
+            Connection::host_tls(address, certificate)?
+        } else {
+            let _announcement = announce(address);
+            Connection::host(address)?
+        };
+
+        application_state = application_state.with_remote(connection, Role::Host)?;
+    } else if let Some(address) = &options.join {
+        let connection = if address.starts_with("ws") {
+            Connection::relay_tls(address, trust)?
+        } else if options.pin.is_some() {
+            Connection::join_tls(address, trust)?
+        } else {
+            Connection::join(address)?
+        };
+
+        let role = Role::Guest(Box::new(NetMessage::Hello));
+        application_state = application_state.with_remote(connection, role)?;
+    } else if let Some(address) = &options.server {
+        let request = match (&options.create, &options.enter) {
+            (Some(name), _) => NetMessage::Create(name.clone()),
+            (None, Some(name)) => NetMessage::Join(name.clone()),
+            (None, None) => return Err("No game to create or join was given".into()),
+        };
+
+        eprintln!("Waiting for the game to start on {address}");
+
+        let connection = Connection::join(address)?;
+        application_state =
+            application_state.with_remote(connection, Role::Guest(Box::new(request)))?;
+    }
+
+    Ok(application_state)
+}
+
+fn run(
+    mut terminal: DefaultTerminal,
+    mut application_state: ApplicationState,
+    threaded: bool,
+) -> Result<(), Box<dyn Error>> {
+    let interruption = Interruption::register()?;
+    let suspension = Suspension::register()?;
+    let mut graphics = Protocol::detect().map(Graphics::new);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if threaded {
+            run_tasks(
+                &mut terminal,
+                &mut application_state,
+                &mut graphics,
+                &interruption,
+                &suspension,
+            )
+        } else {
+            run_polling(
+                &mut terminal,
                 &mut application_state,
+                &mut graphics,
+                &interruption,
+                &suspension,
             )
+        }
+    }))
+    .unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or("unknown panic".to_owned());
+
+        Err(format!("blobwars panicked: {message}").into())
+    });
+
+    if let Err(error) = result {
+        return Err(match application_state.bug_report(&error).save() {
+            Ok(path) => format!(
+                "{error}\nA bug report has been written to {}",
+                path.display()
+            ),
+
+            Err(report_error) => {
+                tracing::error!("The bug report cannot be written: {report_error}");
+                error.to_string()
+            }
+        }
+        .into());
+    }
+
+    application_state.statistics().save()?;
+
+    if let Some(record) = application_state.last_game() {
+        record.save()?;
+    }
+
+    Ok(())
+}
+
+fn run_polling(
+    terminal: &mut DefaultTerminal,
+    application_state: &mut ApplicationState,
+    graphics: &mut Option<Graphics>,
+    interruption: &Interruption,
+    suspension: &Suspension,
+) -> Result<(), Box<dyn Error>> {
+    let mut ticker = Ticker::default();
+
+    while !application_state.has_exited() {
+        if interruption.is_raised() {
+            application_state.interrupt();
+            break;
+        }
+
+        if suspension.take() {
+            suspend(terminal, suspension)?;
+            application_state.handle_action(Action::Suspend);
+
+            // The image has been erased with the terminal
+            if let Some(graphics) = graphics {
+                graphics.hide();
+            }
+        }
+
+        draw(terminal, application_state, graphics)?;
+
+        if event::poll(ticker.timeout())? {
+            handle_event(application_state, event::read()?, suspension);
+        }
+
+        for _ in 0..ticker.advance(Instant::now()) {
+            application_state.tick(ticker.period());
+            application_state.poll();
+            application_state.update();
+        }
+    }
+
+    Ok(())
+}
+
+fn run_tasks(
+    terminal: &mut DefaultTerminal,
+    application_state: &mut ApplicationState,
+    graphics: &mut Option<Graphics>,
+    interruption: &Interruption,
+    suspension: &Suspension,
+) -> Result<(), Box<dyn Error>> {
+    let mut events = EventLoop::new()?
+        .with_terminal()
+        .with_ticker(Ticker::default());
+
+    while !application_state.has_exited() {
+        if interruption.is_raised() {
+            application_state.interrupt();
+            break;
+        }
+
+        if suspension.take() {
+            suspend(terminal, suspension)?;
+            application_state.handle_action(Action::Suspend);
+
+            // The image has been erased with the terminal
+            if let Some(graphics) = graphics {
+                graphics.hide();
+            }
+        }
+
+        draw(terminal, application_state, graphics)?;
+        events.watch(application_state.thinking(), application_state.connection());
+
+        match events.next().ok_or("The event loop has stopped")? {
+            LoopEvent::Terminal(event) => handle_event(application_state, event, suspension),
+            LoopEvent::Tick(elapsed) => application_state.tick(elapsed),
+            LoopEvent::Task(event) => application_state.deliver(event),
+            LoopEvent::Error(error) => return Err(error.into()),
+        }
+
+        application_state.update();
+    }
+
+    Ok(())
+}
+
+fn draw(
+    terminal: &mut DefaultTerminal,
+    application_state: &mut ApplicationState,
+    graphics: &mut Option<Graphics>,
+) -> Result<(), Box<dyn Error>> {
+    if application_state.needs_redraw() {
+        let application = Application::from(application_state.config());
+        let theme = application.theme;
+
+        let images = application.render_style == RenderStyle::Images
+            && application_state.overlay().is_none()
+            && matches!(application_state.screen(), Screen::Board(_));
+
+        if let Some(graphics) = graphics
+            && !images
+            && graphics.hide()
+        {
+            match graphics.protocol().erasure() {
+                Some(erasure) => write!(stdout(), "{erasure}")?,
+                None => terminal.clear()?,
+            }
+        }
+
+        let frame = terminal.draw(|frame| {
+            frame.render_stateful_widget(application, frame.area(), application_state)
         })?;
 
-        if event::poll(Duration::from_millis(500))?
-            && let Ok(command) = Command::try_from(event::read()?)
+        if let Some(graphics) = graphics
+            && images
+            && let Screen::Board(board_state) = application_state.screen()
+            && let Some(cell) = graphics::cell_pixels()
+            && let Some(sequence) = graphics.show(board_state, frame.buffer, &theme, cell)
         {
-            application_state.handle_command(command);
+            let mut stdout = stdout();
+            write!(stdout, "{sequence}")?;
+            stdout.flush()?;
         }
     }
 
+    if let Some(text) = application_state.take_clipboard() {
+        copy(&text)?;
+    }
+
     Ok(())
 }
+
+/// Copy some text to the clipboard with the OSC 52 escape sequence, understood by most terminals
+fn copy(text: &str) -> io::Result<()> {
+    let mut stdout = stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    stdout.flush()
+}
+
+fn handle_event(application_state: &mut ApplicationState, event: Event, suspension: &Suspension) {
+    let mapper = InputMapper {
+        keymap: application_state.keymap(),
+        typing: application_state.is_typing(),
+    };
+
+    match mapper.map(event) {
+        Ok(Action::Suspend) => suspension.raise(),
+        Ok(action) => application_state.handle_action(action),
+        Err(_) => (),
+    }
+}
@@ -0,0 +1,157 @@
+//! The implementation of [`MapFile`]
+
+use crate::{
+    game::{Board, Player},
+    pgn,
+    save::SavedGame,
+};
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A map found in the [map directory](MapFile::directory)
+///
+/// A map is either written row by row like the [boards](Board), in a file whose extension is `txt` or `map`, or as an Ataxx FEN, in a file whose extension is `fen`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MapFile {
+    /// The path of the file
+    pub path: PathBuf,
+
+    /// The [board](Board) held by the file, or the reason why it is malformed
+    pub board: Result<Board, &'static str>,
+}
+
+impl MapFile {
+    /// Get the directory holding the custom maps
+    ///
+    /// The directory is located next to the [save directory](SavedGame::directory).
+    pub fn directory() -> Option<PathBuf> {
+        SavedGame::directory().map(|directory| directory.with_file_name("maps"))
+    }
+
+    /// Read a map from a file
+    ///
+    /// # Parameter
+    ///
+    /// - `path` - The path of the file
+    pub fn read(path: &Path) -> Self {
+        let fen = path.extension().is_some_and(|extension| extension == "fen");
+
+        let board = fs::read_to_string(path)
+            .map_err(|_| "The file cannot be read")
+            .and_then(|content| parse_map(&content, fen));
+
+        Self {
+            path: path.to_owned(),
+            board,
+        }
+    }
+
+    /// List the maps of the [map directory](Self::directory)
+    ///
+    /// The maps are sorted by name; the malformed ones are listed as well.
+    pub fn list() -> Vec<Self> {
+        Self::directory()
+            .map(|directory| Self::list_in(&directory))
+            .unwrap_or_default()
+    }
+
+    /// List the maps of a given directory
+    ///
+    /// # Parameter
+    ///
+    /// - `directory` - The directory holding the maps
+    pub fn list_in(directory: &Path) -> Vec<Self> {
+        let Ok(entries) = fs::read_dir(directory) else {
+            return Vec::new();
+        };
+
+        let mut maps = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension().is_some_and(|extension| {
+                    extension == "txt" || extension == "map" || extension == "fen"
+                })
+            })
+            .map(|path| Self::read(&path))
+            .collect::<Vec<_>>();
+
+        maps.sort_by_key(Self::name);
+        maps
+    }
+
+    /// Get the name of the map, that is the name of its file without the extension
+    pub fn name(&self) -> String {
+        self.path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// Read a map and check that it can be played on
+///
+/// # Parameters
+///
+/// - `content` - The content of the map file
+/// - `fen` - Whether the map is written as an Ataxx FEN instead of row by row
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::map::parse_map;
+///
+/// assert!(parse_map("B.\n.R", false).is_ok());
+/// assert!(parse_map("x5o/7/7/7/7/7/o5x x", true).is_ok());
+/// assert!(parse_map("B..", false).is_err());
+/// ```
+pub fn parse_map(content: &str, fen: bool) -> Result<Board, &'static str> {
+    let board = if fen {
+        pgn::parse_fen(content)?.0
+    } else {
+        content.parse()?
+    };
+
+    let score = board.score();
+
+    if score.get(Player::Blue) == 0 || score.get(Player::Red) == 0 {
+        return Err("Each player needs at least one blob");
+    }
+
+    Ok(board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list() {
+        let directory = std::env::temp_dir().join(format!("blobwars-maps-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+
+        fs::write(directory.join("cross.txt"), "B.#\n#.R").unwrap();
+        fs::write(directory.join("ataxx.fen"), "x5o/7/7/7/7/7/o5x x").unwrap();
+        fs::write(directory.join("broken.map"), "B?R").unwrap();
+        fs::write(directory.join("notes.md"), "Not a map").unwrap();
+
+        let maps = MapFile::list_in(&directory);
+
+        pretty_assertions::assert_eq!(
+            maps.iter().map(MapFile::name).collect::<Vec<_>>(),
+            ["ataxx", "broken", "cross"]
+        );
+
+        pretty_assertions::assert_eq!(maps[1].board, Err("The board contains an unknown cell"));
+
+        pretty_assertions::assert_eq!(
+            maps[2].board.as_ref().map(ToString::to_string),
+            Ok("B.#/#.R".to_owned())
+        );
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+//! The implementation of [`MessageQueue`]
+
+use std::{collections::VecDeque, time::Duration};
+
+/// The severity of a [`Message`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// A message informing about the game
+    #[default]
+    Info,
+
+    /// A message warning about a failed action
+    Warning,
+}
+
+/// A message giving feedback to the players
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Message {
+    /// The text of the message
+    pub text: String,
+
+    /// The [severity](Severity) of the message
+    pub severity: Severity,
+}
+
+impl Message {
+    /// Create a new [informative](Severity::Info) message
+    pub fn info(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            severity: Severity::Info,
+        }
+    }
+
+    /// Create a new [warning](Severity::Warning) message
+    pub fn warning(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            severity: Severity::Warning,
+        }
+    }
+}
+
+/// A queue of [messages](Message)
+///
+/// Messages are shown one after the other, each one during [`Self::DURATION`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MessageQueue {
+    messages: VecDeque<Message>,
+    shown: Duration,
+}
+
+impl MessageQueue {
+    /// The duration during which a message is shown
+    pub const DURATION: Duration = Duration::from_secs(3);
+
+    /// The maximum number of pending messages
+    ///
+    /// When the queue is full, the oldest messages are dropped.
+    pub const CAPACITY: usize = 4;
+
+    /// Push a [message](Message) at the end of the queue
+    pub fn push(&mut self, message: Message) {
+        if self.messages.len() == Self::CAPACITY {
+            self.messages.pop_front();
+            self.shown = Duration::ZERO;
+        }
+
+        self.messages.push_back(message);
+    }
+
+    /// Get the currently shown [message](Message), if any
+    pub fn current(&self) -> Option<&Message> {
+        self.messages.front()
+    }
+
+    /// Check if the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Remove all the messages
+    pub fn clear(&mut self) {
+        self.messages.clear();
+        self.shown = Duration::ZERO;
+    }
+
+    /// Advance the display of the messages
+    ///
+    /// # Parameter
+    ///
+    /// - `elapsed` - The time elapsed since the last tick
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::message::{Message, MessageQueue};
+    ///
+    /// let mut queue = MessageQueue::default();
+    /// queue.push(Message::info("first"));
+    /// queue.push(Message::info("second"));
+    ///
+    /// assert_eq!(queue.current(), Some(&Message::info("first")));
+    /// queue.tick(MessageQueue::DURATION);
+    /// assert_eq!(queue.current(), Some(&Message::info("second")));
+    /// queue.tick(MessageQueue::DURATION);
+    /// assert_eq!(queue.current(), None);
+    /// ```
+    pub fn tick(&mut self, elapsed: Duration) {
+        if self.messages.is_empty() {
+            return;
+        }
+
+        self.shown += elapsed;
+
+        if self.shown >= Self::DURATION {
+            self.messages.pop_front();
+            self.shown = Duration::ZERO;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capacity() {
+        let mut queue = MessageQueue::default();
+
+        for i in 0..=MessageQueue::CAPACITY {
+            queue.push(Message::warning(i.to_string()));
+        }
+
+        pretty_assertions::assert_eq!(queue.current(), Some(&Message::warning("1")));
+    }
+
+    #[test]
+    fn partial_tick() {
+        let mut queue = MessageQueue::default();
+        queue.push(Message::info("shown"));
+        queue.tick(MessageQueue::DURATION / 2);
+
+        pretty_assertions::assert_eq!(queue.current(), Some(&Message::info("shown")));
+    }
+}
@@ -0,0 +1,386 @@
+//! The implementation of [`Movie`]
+//!
+//! A movie is an animation of a recorded game with one frame per ply, so that finished games can be shared outside the terminal.
+//! It is written either as an animated PNG, whose frames are drawn like the [snapshots](Snapshot),
+//! or as an [asciinema](https://asciinema.org) cast replaying the game in a terminal.
+
+use crate::{
+    game::{Board, CellState, Player, PowerUp},
+    graphics::{self, apng},
+    record::{GameRecord, display_ply},
+    snapshot::Snapshot,
+    widgets::Theme,
+};
+
+use ratatui::style::Color;
+use serde::Serialize;
+
+use std::{
+    fmt::{Display, Formatter, Write},
+    io,
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+
+/// The header of an asciinema cast
+#[derive(Serialize)]
+struct CastHeader {
+    version: u8,
+    width: usize,
+    height: usize,
+    title: &'static str,
+}
+
+/// The format of a [movie](Movie)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MovieFormat {
+    /// Animated portable network graphics
+    #[default]
+    Apng,
+
+    /// An [asciinema](https://docs.asciinema.org/manual/asciicast/v2/) recording of a terminal
+    Cast,
+}
+
+impl MovieFormat {
+    /// All the available formats
+    pub const ALL: [Self; 2] = [Self::Apng, Self::Cast];
+
+    /// Get the extension of the files written in this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Apng => "png",
+            Self::Cast => "cast",
+        }
+    }
+
+    /// Guess the format of a file from its extension
+    ///
+    /// Both `png` and `apng` are recognized as animated PNG.
+    ///
+    /// # Parameter
+    ///
+    /// - `path` - The path of the file
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::movie::MovieFormat;
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(MovieFormat::from_path(Path::new("game.apng")), Some(MovieFormat::Apng));
+    /// assert_eq!(MovieFormat::from_path(Path::new("game.cast")), Some(MovieFormat::Cast));
+    /// assert_eq!(MovieFormat::from_path(Path::new("game.gif")), None);
+    /// ```
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+
+        if extension.eq_ignore_ascii_case("apng") {
+            return Some(Self::Apng);
+        }
+
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extension().eq_ignore_ascii_case(extension))
+    }
+}
+
+impl Display for MovieFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Apng => write!(f, "APNG"),
+            Self::Cast => write!(f, "cast"),
+        }
+    }
+}
+
+impl FromStr for MovieFormat {
+    type Err = &'static str;
+
+    /// Parse a movie format
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::movie::MovieFormat;
+    ///
+    /// assert_eq!("apng".parse(), Ok(MovieFormat::Apng));
+    /// assert!("gif".parse::<MovieFormat>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.to_string().eq_ignore_ascii_case(s))
+            .ok_or("Unknown movie format")
+    }
+}
+
+/// An animation of a [recorded game](GameRecord)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Movie {
+    record: GameRecord,
+    theme: Theme,
+    cell_size: u32,
+    delay: Duration,
+}
+
+impl Movie {
+    /// The default time each ply is shown
+    pub const DELAY: Duration = Duration::from_secs(1);
+
+    /// The time the final position is shown before the animation loops
+    pub const HOLD: Duration = Duration::from_secs(3);
+
+    /// Create a new [`Movie`]
+    ///
+    /// # Parameter
+    ///
+    /// - `record` - The [record](GameRecord) of the game to animate
+    pub fn new(record: GameRecord) -> Self {
+        Self {
+            record,
+            theme: Theme::default(),
+            cell_size: Snapshot::CELL_SIZE,
+            delay: Self::DELAY,
+        }
+    }
+
+    /// Set the [theme](Theme) giving the colors of the cells
+    pub fn with_theme(self, theme: Theme) -> Self {
+        Self { theme, ..self }
+    }
+
+    /// Set the size of a cell of the animated PNG in pixels
+    pub fn with_cell_size(self, cell_size: u32) -> Self {
+        Self { cell_size, ..self }
+    }
+
+    /// Set the time each ply is shown
+    pub fn with_delay(self, delay: Duration) -> Self {
+        Self { delay, ..self }
+    }
+
+    /// Get the frames of the movie
+    ///
+    /// # Return
+    ///
+    /// The position after each ply, starting with the initial one, with the time it is shown.
+    pub fn frames(&self) -> Vec<(Board, Duration)> {
+        let count = self.record.plies.len();
+
+        (0..=count)
+            .map(|ply| {
+                let delay = if ply == count { Self::HOLD } else { self.delay };
+                (self.record.position(ply).0, delay)
+            })
+            .collect()
+    }
+
+    /// Draw the movie as an animated PNG
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{game::{Board, Move, Player}, movie::Movie, record::GameRecord};
+    ///
+    /// let mut record = GameRecord::new("B..R".parse::<Board>().unwrap(), Player::Blue);
+    /// record.plies.push(Some(Move { from: (0, 0), to: (0, 1) }));
+    ///
+    /// assert!(Movie::new(record).apng().unwrap().starts_with(b"\x89PNG"));
+    /// ```
+    pub fn apng(&self) -> io::Result<Vec<u8>> {
+        let frames = self
+            .frames()
+            .into_iter()
+            .map(|(board, delay)| {
+                let snapshot = Snapshot::new(board)
+                    .with_theme(self.theme)
+                    .with_cell_size(self.cell_size);
+
+                (snapshot.image(), delay)
+            })
+            .collect::<Vec<_>>();
+
+        apng(&frames)
+    }
+
+    /// Write the movie as an asciinema cast
+    ///
+    /// Each frame clears the terminal and draws the board with the [circles](crate::widgets::board::RenderStyle::Circles),
+    /// followed by the last ply and the score.
+    pub fn cast(&self) -> io::Result<String> {
+        let board = &self.record.board;
+
+        let header = CastHeader {
+            version: 2,
+            width: (board.width() * 2).max(40),
+            height: board.height() + 2,
+            title: "blobwars",
+        };
+
+        let mut cast = serde_json::to_string(&header)? + "\n";
+
+        let mut time = Duration::ZERO;
+
+        for (ply, (board, delay)) in self.frames().into_iter().enumerate() {
+            let mut screen = String::from("\x1b[2J\x1b[H");
+
+            for i in 0..board.height() {
+                let row = (0..board.width())
+                    .filter_map(|j| {
+                        board
+                            .get(i, j)
+                            .map(|content| (content, board.power_up(i, j)))
+                    })
+                    .map(|(content, power_up)| self.symbol(content, power_up))
+                    .collect::<Vec<_>>();
+
+                let _ = write!(screen, "{}\r\n", row.join(" "));
+            }
+
+            let score = board.score();
+
+            let last = match ply.checked_sub(1) {
+                Some(previous) => format!("{ply}. {}", display_ply(self.record.plies[previous])),
+                None => "Start".to_owned(),
+            };
+
+            let _ = write!(
+                screen,
+                "\r\n{last} · Blue {} – Red {}",
+                score.blue, score.red
+            );
+
+            cast += &serde_json::to_string(&(time.as_secs_f64(), "o", screen))?;
+            cast.push('\n');
+            time += delay;
+        }
+
+        // An empty output keeps the final position shown until the end
+        cast += &serde_json::to_string(&(time.as_secs_f64(), "o", ""))?;
+        cast.push('\n');
+        Ok(cast)
+    }
+
+    /// Encode the movie in a given format
+    ///
+    /// # Parameter
+    ///
+    /// - `format` - The [format](MovieFormat) of the movie
+    pub fn encode(&self, format: MovieFormat) -> io::Result<Vec<u8>> {
+        match format {
+            MovieFormat::Apng => self.apng(),
+            MovieFormat::Cast => self.cast().map(String::into_bytes),
+        }
+    }
+
+    /// Get the colored symbol of a cell in the cast
+    fn symbol(&self, content: CellState, power_up: Option<PowerUp>) -> String {
+        let (symbol, color) = match (content, power_up) {
+            (CellState::Free, Some(PowerUp::DoubleSpread)) => ("⊕", PowerUp::DoubleSpread.into()),
+            (CellState::Free, Some(PowerUp::Shield)) => ("◈", PowerUp::Shield.into()),
+            (CellState::Free, Some(PowerUp::Bomb)) => ("✱", PowerUp::Bomb.into()),
+            (CellState::Player(Player::Blue), _) => ("●", self.theme.blue),
+            (CellState::Player(Player::Red), _) => ("●", self.theme.red),
+            (CellState::Free, None) => ("○", self.theme.tertiary.fg.unwrap_or(Color::DarkGray)),
+            (CellState::Restricted, _) => ("⊘", self.theme.restricted),
+        };
+
+        let [r, g, b] = graphics::rgb(color);
+        format!("\x1b[38;2;{r};{g};{b}m{symbol}\x1b[0m")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Move;
+    use pretty_assertions::assert_eq;
+
+    fn record() -> GameRecord {
+        let mut record = GameRecord::new("B..R".parse().unwrap(), Player::Blue);
+
+        record.plies = vec![
+            Some(Move {
+                from: (0, 0),
+                to: (0, 1),
+            }),
+            None,
+        ];
+
+        record
+    }
+
+    #[test]
+    fn frames() {
+        let frames = Movie::new(record())
+            .with_delay(Duration::from_millis(500))
+            .frames();
+
+        let boards = frames
+            .iter()
+            .map(|(board, _)| board.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(boards, ["B..R", "BB.R", "BB.R"]);
+
+        let delays = frames.iter().map(|&(_, delay)| delay).collect::<Vec<_>>();
+
+        assert_eq!(
+            delays,
+            [
+                Duration::from_millis(500),
+                Duration::from_millis(500),
+                Movie::HOLD
+            ]
+        );
+    }
+
+    #[test]
+    fn apng() {
+        let png = Movie::new(record()).with_cell_size(4).apng().unwrap();
+        let count = |kind: &[u8]| png.windows(4).filter(|window| *window == kind).count();
+
+        assert_eq!(count(b"acTL"), 1);
+        assert_eq!(count(b"fcTL"), 3);
+        assert_eq!(count(b"IDAT"), 1);
+        assert_eq!(count(b"fdAT"), 2);
+
+        // Three frames shown forever
+        let start = png.windows(4).position(|window| window == b"acTL").unwrap() + 4;
+        assert_eq!(png[start..start + 8], [0, 0, 0, 3, 0, 0, 0, 0]);
+
+        let reader = png::Decoder::new(std::io::Cursor::new(png))
+            .read_info()
+            .unwrap();
+        let info = reader.info();
+
+        assert_eq!((info.width, info.height), (16, 4));
+        assert_eq!(
+            info.frame_control().map(|frame| frame.delay_num),
+            Some(1000)
+        );
+    }
+
+    #[test]
+    fn cast() {
+        let cast = Movie::new(record()).cast().unwrap();
+        let lines = cast.lines().collect::<Vec<_>>();
+
+        assert_eq!(
+            lines[0],
+            "{\"version\":2,\"width\":40,\"height\":3,\"title\":\"blobwars\"}"
+        );
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with("[0.0,\"o\",\"\\u001b[2J"));
+        assert!(lines[2].starts_with("[1.0,\"o\","));
+        assert!(lines[2].contains("1. a1b1 · Blue 2 – Red 1"));
+        assert!(lines[3].contains("2. pass · Blue 2 – Red 1"));
+        assert_eq!(lines[4], "[5.0,\"o\",\"\"]");
+
+        let event = serde_json::from_str::<(f64, String, String)>(lines[2]).unwrap();
+        assert!(event.2.starts_with("\x1b[2J\x1b[H"));
+    }
+}
@@ -0,0 +1,1284 @@
+//! The implementation of [`Connection`]
+//!
+//! Two instances of the game play together over TCP: one player [hosts](Connection::host) the game and the other [joins](Connection::join) it.
+//! Across NATs, both players rather connect to a [relay](Connection::relay) through the WebSocket protocol, the messages being the same.
+//! The joining player says [hello](NetMessage::Hello), the host answers with the game in a [start message](NetMessage::Start); then both sides exchange their plies, each of them checking the plies of the other against the rules of the game.
+//!
+//! A dropped connection is restored in the background; both sides then [resume](NetMessage::Resume) the game by exchanging its identifier and their numbers of plies, and the side ahead [replays](NetMessage::Replay) the missing plies.
+//!
+//! # Security
+//!
+//! Both transports may be encrypted with [TLS](tls): a host presenting a [certificate](tls::Certificate) is [joined](Connection::join_tls) by a player pinning its fingerprint, and a relay is reached through a `wss://` URL.
+//! Otherwise, the messages are sent in plain text.
+//!
+//! # Protocol
+//!
+//! Each [message](NetMessage) is a line of text made of a keyword followed by its arguments, separated by spaces; the plies are written in [algebraic notation](crate::record::display_ply) and the boards as in the [records](GameRecord).
+//! A message longer than [`MAX_MESSAGE`] bytes is refused and ends the connection.
+//! On every new or restored connection, both sides first send the [version](PROTOCOL_VERSION) of the protocol they speak, and answer the first version received with their own, in case a relay has dropped theirs.
+//! A side receiving another version, or any message but a ping or a pong before a version, refuses the connection with the reason and closes it.
+//! Unknown messages are ignored, so that new messages do not require a new version; the version changes whenever a message changes in an incompatible way.
+//!
+//! - `version <number>` - the [version](NetMessage::Version) of the protocol
+//! - `hello` - the guest is ready to receive the game
+//! - `start <game> <player> <free pass> <first player> <board> <plies…>` - the game to play, the player being the one of the receiver
+//! - `ply <ply>` - a ply of the sender, `pass` standing for a pass
+//! - `resume <game> <plies>` - the sender resumes the game after a dropped connection, knowing the given number of plies
+//! - `replay <from> <plies…>` - the plies missed by the receiver, following the given number of plies
+//! - `resign` - the sender resigns
+//! - `chat <text>` - a chat message
+//! - `bye` - the sender leaves
+//! - `list`, `games <names…>` - a request of the open games of a [server](crate::server::Server) and its answer
+//! - `create <name>`, `join <name>` - a request to open or join a game on a server
+//! - `refused <reason>` - a request has been refused
+//! - `ping <stamp>`, `pong <stamp>` - a measure of the [latency](Connection::latency)
+
+use crate::{
+    game::{Board, Move, Player},
+    record::{GameRecord, display_ply, parse_ply},
+};
+
+use rustls::{ClientConnection, ServerConnection, StreamOwned};
+use tls::{Certificate, Trust};
+use tungstenite::{Message, WebSocket};
+
+use std::{
+    fmt::{Display, Formatter},
+    io::{self, BufRead, BufReader, ErrorKind, Read, Write},
+    mem,
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    str::FromStr,
+    sync::{
+        Arc, Mutex, Weak,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+pub mod discovery;
+pub mod tls;
+pub mod websocket;
+
+/// The version of the protocol spoken by this release
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The maximum length of a received message, in bytes
+pub const MAX_MESSAGE: usize = 1 << 20;
+
+/// A message exchanged between two instances of the game
+///
+/// Each message is written on its own line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetMessage {
+    /// The version of the protocol spoken by the sender
+    Version(u32),
+
+    /// The game chosen by the host
+    Start {
+        /// The identifier of the game, used to [resume](Self::Resume) it
+        game: String,
+
+        /// The [player](Player) played by the receiver
+        player: Player,
+
+        /// Whether a player may pass at will
+        free_pass: bool,
+
+        /// The [record](GameRecord) of the game to continue
+        record: Box<GameRecord>,
+    },
+
+    /// The sender is ready to receive the game
+    Hello,
+
+    /// A ply of the sender; `None` stands for a pass
+    Ply(Option<Move>),
+
+    /// The connection has been restored and the sender resumes the game
+    Resume {
+        /// The identifier of the game
+        game: String,
+
+        /// The number of plies known to the sender
+        plies: usize,
+    },
+
+    /// The plies missed by the receiver while the connection was lost; `None` stands for a pass
+    Replay {
+        /// The number of plies preceding the first replayed one
+        from: usize,
+
+        /// The replayed plies
+        plies: Vec<Option<Move>>,
+    },
+
+    /// The sender resigns
+    Resign,
+
+    /// A chat message of the sender
+    Chat(String),
+
+    /// The sender leaves the game
+    Bye,
+
+    /// A request of the open games of a [server](crate::server::Server)
+    List,
+
+    /// The names of the open games of a [server](crate::server::Server)
+    Games(Vec<String>),
+
+    /// A request to open a game of the given name on a [server](crate::server::Server)
+    Create(String),
+
+    /// A request to join the open game of the given name on a [server](crate::server::Server)
+    Join(String),
+
+    /// A request has been refused for the given reason
+    Refused(String),
+
+    /// A request for a [pong](Self::Pong), stamped with the time of the sender in milliseconds
+    Ping(u64),
+
+    /// The answer to a [ping](Self::Ping), carrying its stamp
+    Pong(u64),
+}
+
+impl Display for NetMessage {
+    /// Write the message
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{game::Move, net::NetMessage};
+    ///
+    /// let message = NetMessage::Ply(Some(Move { from: (0, 0), to: (1, 2) }));
+    /// assert_eq!(message.to_string(), "ply a1c2");
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Start {
+                game,
+                player,
+                free_pass,
+                record,
+            } => write!(
+                f,
+                "start {game} {player} {free_pass} {} {} {}",
+                record.first,
+                record.board,
+                display_plies(&record.plies)
+            ),
+
+            Self::Version(version) => write!(f, "version {version}"),
+            Self::Hello => write!(f, "hello"),
+            Self::Ply(ply) => write!(f, "ply {}", display_ply(*ply)),
+            Self::Resume { game, plies } => write!(f, "resume {game} {plies}"),
+            Self::Replay { from, plies } => write!(f, "replay {from} {}", display_plies(plies)),
+            Self::Resign => write!(f, "resign"),
+            Self::Chat(text) => write!(f, "chat {text}"),
+            Self::Bye => write!(f, "bye"),
+            Self::List => write!(f, "list"),
+            Self::Games(names) => write!(f, "games {}", names.join(" ")),
+            Self::Create(name) => write!(f, "create {name}"),
+            Self::Join(name) => write!(f, "join {name}"),
+            Self::Refused(reason) => write!(f, "refused {reason}"),
+            Self::Ping(stamp) => write!(f, "ping {stamp}"),
+            Self::Pong(stamp) => write!(f, "pong {stamp}"),
+        }
+    }
+}
+
+impl FromStr for NetMessage {
+    type Err = &'static str;
+
+    /// Parse a message
+    ///
+    /// The plies of a start message are checked against the rules of the game.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{game::Player, net::NetMessage};
+    ///
+    /// let message = "start 2a Red false Blue B..R a1b1".parse::<NetMessage>().unwrap();
+    /// assert!(matches!(message, NetMessage::Start { player: Player::Red, .. }));
+    ///
+    /// assert!("start 2a Red false Blue B..R d1c1".parse::<NetMessage>().is_err());
+    /// assert!("greet".parse::<NetMessage>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+
+        match words.next() {
+            Some("start") => {
+                let game = words
+                    .next()
+                    .ok_or("A start message is truncated")?
+                    .to_owned();
+
+                let player = words
+                    .next()
+                    .ok_or("A start message is truncated")?
+                    .parse()?;
+
+                let free_pass = words
+                    .next()
+                    .and_then(|free_pass| free_pass.parse().ok())
+                    .ok_or("A start message is truncated")?;
+
+                let first = words.next().ok_or("A start message is truncated")?;
+                let mut board = words
+                    .next()
+                    .ok_or("A start message is truncated")?
+                    .to_owned();
+
+                // The options of the board, such as its rules, follow it
+                let mut words = words.peekable();
+
+                while let Some(option) = words.next_if(|word| word.contains('=')) {
+                    board = format!("{board} {option}");
+                }
+
+                let board = board.parse::<Board>()?;
+
+                let plies = words.collect::<Vec<_>>().join(" ");
+
+                let record = format!(
+                    "height = {}\nwidth = {}\nboard = \"{board}\"\nfirst = \"{first}\"\nplies = \"{plies}\"",
+                    board.height(),
+                    board.width()
+                )
+                .parse()?;
+
+                Ok(Self::Start {
+                    game,
+                    player,
+                    free_pass,
+                    record: Box::new(record),
+                })
+            }
+
+            Some("ply") => {
+                let ply = words.next().ok_or("A ply message is truncated")?;
+                Ok(Self::Ply(parse_ply(ply)?))
+            }
+
+            Some("version") => words
+                .next()
+                .and_then(|version| version.parse().ok())
+                .map(Self::Version)
+                .ok_or("A version message is malformed"),
+
+            Some("resume") => {
+                let game = words
+                    .next()
+                    .ok_or("A resume message is truncated")?
+                    .to_owned();
+
+                let plies = words
+                    .next()
+                    .and_then(|plies| plies.parse().ok())
+                    .ok_or("A resume message is malformed")?;
+
+                Ok(Self::Resume { game, plies })
+            }
+
+            Some("replay") => {
+                let from = words
+                    .next()
+                    .and_then(|from| from.parse().ok())
+                    .ok_or("A replay message is malformed")?;
+
+                let plies = words.map(parse_ply).collect::<Result<_, _>>()?;
+                Ok(Self::Replay { from, plies })
+            }
+
+            Some(kind @ ("ping" | "pong")) => {
+                let stamp = words
+                    .next()
+                    .and_then(|stamp| stamp.parse().ok())
+                    .ok_or("A ping message is malformed")?;
+
+                if kind == "ping" {
+                    Ok(Self::Ping(stamp))
+                } else {
+                    Ok(Self::Pong(stamp))
+                }
+            }
+
+            Some(kind @ ("create" | "join")) => {
+                let name = words
+                    .next()
+                    .filter(|_| words.next().is_none())
+                    .ok_or("A game name is not a single word")?
+                    .to_owned();
+
+                if kind == "create" {
+                    Ok(Self::Create(name))
+                } else {
+                    Ok(Self::Join(name))
+                }
+            }
+
+            Some("games") => Ok(Self::Games(words.map(str::to_owned).collect())),
+            Some("refused") => Ok(Self::Refused(words.collect::<Vec<_>>().join(" "))),
+            Some("chat") => Ok(Self::Chat(words.collect::<Vec<_>>().join(" "))),
+            Some("list") => Ok(Self::List),
+            Some("hello") => Ok(Self::Hello),
+            Some("resign") => Ok(Self::Resign),
+            Some("bye") => Ok(Self::Bye),
+            _ => Err("A message is unknown"),
+        }
+    }
+}
+
+/// Write plies in algebraic notation, separated by spaces
+fn display_plies(plies: &[Option<Move>]) -> String {
+    plies
+        .iter()
+        .map(|&ply| display_ply(ply))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The part taken by a side in setting a network game up
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// Wait for the guest to say hello, then generate the game and send it
+    Host,
+
+    /// Send a greeting and wait for the game
+    ///
+    /// The greeting is [hello](NetMessage::Hello) to a host, or a request [creating](NetMessage::Create) or [joining](NetMessage::Join) a game to a [server](crate::server::Server).
+    Guest(Box<NetMessage>),
+}
+
+/// A connection to the other instance of the game
+///
+/// The received [messages](NetMessage) are read in the background; once the connection is closed, a [`NetMessage::Bye`] is received.
+/// Both instances ping each other every [period](Self::PING_PERIOD) to measure the [latency](Self::latency); the pings are answered in the background too.
+/// A connection which ends without a bye or stays silent for [too long](Self::LOST_AFTER) is [lost](Self::is_lost); unless it has been [accepted](Self::accept), it is then restored in the background by listening or connecting again, for [a while](Self::RECONNECT_TIMEOUT).
+/// The connection is closed when its last clone is dropped.
+#[derive(Clone, Debug)]
+pub struct Connection {
+    link: Arc<Link>,
+    peer: SocketAddr,
+}
+
+impl Connection {
+    /// The duration between two pings
+    pub const PING_PERIOD: Duration = Duration::from_secs(2);
+
+    /// The silence after which the connection is considered lost
+    pub const LOST_AFTER: Duration = Duration::from_secs(6);
+
+    /// The duration during which a lost connection is being restored
+    pub const RECONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// The duration between two attempts to restore a lost connection
+    const RETRY_PERIOD: Duration = Duration::from_millis(250);
+
+    /// The longest duration for which the stream is read at a time, the messages to send waiting meanwhile
+    const POLL_PERIOD: Duration = Duration::from_millis(20);
+
+    /// Wait for a player to join on a given address
+    ///
+    /// This call blocks until a player joins.
+    ///
+    /// # Parameter
+    ///
+    /// - `address` - The address listened on, such as `0.0.0.0:4444`
+    pub fn host<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let stream = Stream::tcp(Socket::Plain(listener.accept()?.0))?;
+
+        Self::new(stream, Some(Endpoint::Listener(listener, None)))
+    }
+
+    /// Wait for a player to join on a given listener
+    ///
+    /// This call blocks until a player joins.
+    /// The returned connection is not restored once lost, since the listener is not owned.
+    ///
+    /// # Parameter
+    ///
+    /// - `listener` - The [listener](TcpListener) accepting the player
+    pub fn accept(listener: &TcpListener) -> io::Result<Self> {
+        Self::new(Stream::tcp(Socket::Plain(listener.accept()?.0))?, None)
+    }
+
+    /// Wait for a player to join on a given address over TLS
+    ///
+    /// This call blocks until a player joins.
+    ///
+    /// # Parameters
+    ///
+    /// - `address` - The address listened on, such as `0.0.0.0:4444`
+    /// - `certificate` - The [certificate](Certificate) presented to the player, whose [fingerprint](Certificate::fingerprint) the player is expected to pin
+    pub fn host_tls<A: ToSocketAddrs>(address: A, certificate: Certificate) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let socket = Socket::Server(Box::new(certificate.accept(listener.accept()?.0)?));
+        let endpoint = Endpoint::Listener(listener, Some(certificate));
+
+        Self::new(Stream::tcp(socket)?, Some(endpoint))
+    }
+
+    /// Wait for a player to join on a given listener over TLS
+    ///
+    /// This call blocks until a player joins.
+    /// The returned connection is not restored once lost, since the listener is not owned.
+    ///
+    /// # Parameters
+    ///
+    /// - `listener` - The [listener](TcpListener) accepting the player
+    /// - `certificate` - The [certificate](Certificate) presented to the player
+    pub fn accept_tls(listener: &TcpListener, certificate: &Certificate) -> io::Result<Self> {
+        let socket = Socket::Server(Box::new(certificate.accept(listener.accept()?.0)?));
+        Self::new(Stream::tcp(socket)?, None)
+    }
+
+    /// Join a player hosting a game on a given address
+    ///
+    /// # Parameter
+    ///
+    /// - `address` - The address of the host
+    pub fn join<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        let endpoint = Endpoint::Address(stream.peer_addr()?, None);
+
+        Self::new(Stream::tcp(Socket::Plain(stream))?, Some(endpoint))
+    }
+
+    /// Join a player hosting a game on a given address over TLS
+    ///
+    /// # Parameters
+    ///
+    /// - `address` - The address of the host, such as `example.org:4444`
+    /// - `trust` - How the [certificate](Certificate) of the host is [trusted](Trust), usually by its pinned fingerprint
+    pub fn join_tls(address: &str, trust: Trust) -> io::Result<Self> {
+        let host = address
+            .rsplit_once(':')
+            .map_or(address, |(host, _)| host)
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_owned();
+
+        let stream = TcpStream::connect(address)?;
+        let peer = stream.peer_addr()?;
+        let socket = Socket::Client(Box::new(trust.connect(&host, stream)?));
+        let endpoint = Endpoint::Address(peer, Some((host, trust)));
+
+        Self::new(Stream::tcp(socket)?, Some(endpoint))
+    }
+
+    /// Connect to a relay server through the WebSocket protocol
+    ///
+    /// The relay is expected to forward every text message to the other player of the same URL, so that both players can reach it across NATs.
+    /// Both the host and the other player connect to the relay; the [messages](NetMessage) are the same as over TCP, one per text message.
+    ///
+    /// Over `wss://`, the certificate of the relay must be issued for its name by one of the usual [authorities](Trust::Authorities).
+    ///
+    /// # Parameter
+    ///
+    /// - `url` - The URL of the relay, such as `ws://relay.example.org:8080/room` or `wss://relay.example.org/room`
+    pub fn relay(url: &str) -> io::Result<Self> {
+        Self::relay_tls(url, Trust::Authorities)
+    }
+
+    /// Connect to a relay server through the WebSocket protocol, trusting its certificate in a given way over `wss://`
+    ///
+    /// # Parameters
+    ///
+    /// - `url` - The URL of the relay, such as `wss://relay.example.org/room`
+    /// - `trust` - How the certificate of the relay is [trusted](Trust), such as by its pinned fingerprint
+    pub fn relay_tls(url: &str, trust: Trust) -> io::Result<Self> {
+        let endpoint = Endpoint::Relay(url.to_owned(), trust);
+        Self::new(
+            Stream::websocket(websocket::connect(url, trust)?)?,
+            Some(endpoint),
+        )
+    }
+
+    fn new(stream: Stream, endpoint: Option<Endpoint>) -> io::Result<Self> {
+        let peer = stream.socket().peer_addr()?;
+        let (sender, receiver) = mpsc::channel();
+
+        let link = Arc::new(Link {
+            stream: Mutex::new(stream),
+            writers: AtomicUsize::new(0),
+            endpoint,
+            received: Mutex::new(receiver),
+            latency: AtomicU64::new(u64::MAX),
+            lost: AtomicBool::new(false),
+            restored: AtomicBool::new(false),
+            started: Instant::now(),
+        });
+
+        link.write(&NetMessage::Version(PROTOCOL_VERSION))?;
+
+        let weak = Arc::downgrade(&link);
+
+        thread::spawn(move || {
+            while receive(&weak, &sender, peer) && restore(&weak, peer) {}
+
+            let _ = sender.send(NetMessage::Bye);
+        });
+
+        let weak = Arc::downgrade(&link);
+
+        thread::spawn(move || {
+            while let Some(link) = weak.upgrade() {
+                let _ = link.write(&NetMessage::Ping(link.now()));
+                drop(link);
+                thread::sleep(Self::PING_PERIOD);
+            }
+        });
+
+        tracing::info!("Connected to {peer}");
+
+        Ok(Self { link, peer })
+    }
+
+    /// Get the address of the other instance, or of the relay
+    pub fn peer(&self) -> SocketAddr {
+        self.peer
+    }
+
+    /// Get the round-trip time to the other instance, once measured
+    pub fn latency(&self) -> Option<Duration> {
+        match self.link.latency.load(Ordering::Relaxed) {
+            u64::MAX => None,
+            latency => Some(Duration::from_millis(latency)),
+        }
+    }
+
+    /// Check if the connection is lost, that is if it has ended without a [bye](NetMessage::Bye) and has not been restored yet
+    pub fn is_lost(&self) -> bool {
+        self.link.lost.load(Ordering::Relaxed)
+    }
+
+    /// Check if the connection has been restored since the last call
+    ///
+    /// Once restored, both instances are expected to [resume](NetMessage::Resume) the game, since the plies sent meanwhile may have been lost.
+    pub fn take_restored(&self) -> bool {
+        self.link.restored.swap(false, Ordering::Relaxed)
+    }
+
+    /// Send a [message](NetMessage)
+    ///
+    /// A message cannot be sent while the connection is [lost](Self::is_lost).
+    pub fn send(&self, message: &NetMessage) -> io::Result<()> {
+        self.link.write(message)
+    }
+
+    /// Get the next received [message](NetMessage), if any, without blocking
+    pub fn try_recv(&self) -> Option<NetMessage> {
+        self.link.received.lock().ok()?.try_recv().ok()
+    }
+
+    /// Wait for the next received [message](NetMessage)
+    pub fn recv(&self) -> io::Result<NetMessage> {
+        self.link
+            .received
+            .lock()
+            .map_err(|_| io::Error::other("The connection is poisoned"))?
+            .recv()
+            .map_err(|_| io::Error::new(ErrorKind::ConnectionAborted, "The connection is lost"))
+    }
+
+    /// Wait for the next received [message](NetMessage) for a given duration at most
+    ///
+    /// # Parameter
+    ///
+    /// - `timeout` - The longest duration waited for
+    ///
+    /// # Return
+    ///
+    /// The received message is returned, or `None` if none has been received in time.
+    pub fn recv_timeout(&self, timeout: Duration) -> io::Result<Option<NetMessage>> {
+        match self
+            .link
+            .received
+            .lock()
+            .map_err(|_| io::Error::other("The connection is poisoned"))?
+            .recv_timeout(timeout)
+        {
+            Ok(message) => Ok(Some(message)),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+
+            Err(RecvTimeoutError::Disconnected) => Err(io::Error::new(
+                ErrorKind::ConnectionAborted,
+                "The connection is lost",
+            )),
+        }
+    }
+}
+
+impl PartialEq for Connection {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.link, &other.link)
+    }
+}
+
+impl Eq for Connection {}
+
+/// A socket carrying a connection, encrypted with [TLS](tls) or not
+#[derive(Debug)]
+pub enum Socket {
+    /// A socket in plain text
+    Plain(TcpStream),
+
+    /// The client side of a TLS connection
+    Client(Box<StreamOwned<ClientConnection, TcpStream>>),
+
+    /// The server side of a TLS connection
+    Server(Box<StreamOwned<ServerConnection, TcpStream>>),
+}
+
+impl Socket {
+    /// Get the underlying [TCP stream](TcpStream)
+    pub fn tcp(&self) -> &TcpStream {
+        match self {
+            Self::Plain(stream) => stream,
+            Self::Client(stream) => stream.get_ref(),
+            Self::Server(stream) => stream.get_ref(),
+        }
+    }
+}
+
+impl Read for Socket {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(stream) => stream.read(buf),
+            Self::Client(stream) => stream.read(buf),
+            Self::Server(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Socket {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(stream) => stream.write(buf),
+            Self::Client(stream) => stream.write(buf),
+            Self::Server(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(stream) => stream.flush(),
+            Self::Client(stream) => stream.flush(),
+            Self::Server(stream) => stream.flush(),
+        }
+    }
+}
+
+/// The stream carrying the messages
+#[derive(Debug)]
+enum Stream {
+    /// One message per line over TCP, along with the part of the line read so far
+    Tcp(BufReader<Socket>, Vec<u8>),
+
+    /// One message per text message over WebSocket
+    WebSocket(Box<WebSocket<Socket>>),
+}
+
+impl Stream {
+    fn tcp(socket: Socket) -> io::Result<Self> {
+        socket
+            .tcp()
+            .set_read_timeout(Some(Connection::POLL_PERIOD))?;
+
+        Ok(Self::Tcp(BufReader::new(socket), Vec::new()))
+    }
+
+    fn websocket(socket: WebSocket<Socket>) -> io::Result<Self> {
+        socket
+            .get_ref()
+            .tcp()
+            .set_read_timeout(Some(Connection::POLL_PERIOD))?;
+
+        Ok(Self::WebSocket(Box::new(socket)))
+    }
+
+    /// Get the underlying [TCP stream](TcpStream)
+    fn socket(&self) -> &TcpStream {
+        match self {
+            Self::Tcp(reader, _) => reader.get_ref().tcp(),
+            Self::WebSocket(socket) => socket.get_ref().tcp(),
+        }
+    }
+
+    /// Read the next text, or `None` once the connection is closed
+    ///
+    /// The read times out after a [period](Connection::POLL_PERIOD) without any complete text, what is read so far being kept for the next call.
+    /// A text longer than [`MAX_MESSAGE`] is refused before being read in full.
+    fn read(&mut self) -> io::Result<Option<String>> {
+        match self {
+            Self::Tcp(reader, line) => loop {
+                let available = reader.fill_buf()?;
+
+                if available.is_empty() {
+                    return Ok((!line.is_empty())
+                        .then(|| String::from_utf8_lossy(&mem::take(line)).into_owned()));
+                }
+
+                let (length, complete) = match available.iter().position(|&byte| byte == b'\n') {
+                    Some(end) => (end + 1, true),
+                    None => (available.len(), false),
+                };
+
+                line.extend_from_slice(&available[..length]);
+                reader.consume(length);
+
+                if line.len() > MAX_MESSAGE {
+                    return Err(io::Error::new(
+                        ErrorKind::InvalidData,
+                        "A message is too long",
+                    ));
+                }
+
+                if complete {
+                    return Ok(Some(String::from_utf8_lossy(&mem::take(line)).into_owned()));
+                }
+            },
+
+            Self::WebSocket(socket) => loop {
+                match socket.read() {
+                    Ok(Message::Text(text)) => return Ok(Some(text.as_str().to_owned())),
+                    Ok(Message::Close(_)) => return Ok(None),
+                    Ok(_) => (),
+
+                    Err(
+                        tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed,
+                    ) => {
+                        return Ok(None);
+                    }
+
+                    Err(error) => return Err(websocket::io_error(error)),
+                }
+            },
+        }
+    }
+
+    /// Write a text at once, as a line over TCP or as a text message over WebSocket
+    fn write(&mut self, text: &str) -> io::Result<()> {
+        match self {
+            Self::Tcp(reader, _) => {
+                let socket = reader.get_mut();
+                socket.write_all(&[text.as_bytes(), b"\n"].concat())?;
+                socket.flush()
+            }
+
+            Self::WebSocket(socket) => socket
+                .send(Message::text(text))
+                .map_err(websocket::io_error),
+        }
+    }
+
+    /// Close the stream, letting the other side know over WebSocket
+    fn close(&mut self) {
+        if let Self::WebSocket(socket) = self {
+            let _ = socket.close(None);
+            let _ = socket.flush();
+        }
+
+        let _ = self.socket().shutdown(Shutdown::Both);
+    }
+}
+
+/// Where a lost connection is restored from
+#[derive(Debug)]
+enum Endpoint {
+    /// The listener of the host, waiting for the other player to join again, along with the certificate of the host over TLS
+    Listener(TcpListener, Option<Certificate>),
+
+    /// The address of the host, along with its name and the way its certificate is trusted over TLS
+    Address(SocketAddr, Option<(String, Trust)>),
+
+    /// The URL of the relay, along with the way its certificate is trusted over `wss://`
+    Relay(String, Trust),
+}
+
+impl Endpoint {
+    /// Make one attempt to restore the connection, without waiting for the other player longer than the period between two attempts
+    fn connect(&self) -> io::Result<Stream> {
+        match self {
+            Self::Listener(listener, certificate) => {
+                listener.set_nonblocking(true)?;
+                let accepted = listener.accept();
+                listener.set_nonblocking(false)?;
+
+                let stream = accepted?.0;
+                stream.set_nonblocking(false)?;
+
+                match certificate {
+                    Some(certificate) => {
+                        Stream::tcp(Socket::Server(Box::new(certificate.accept(stream)?)))
+                    }
+
+                    None => Stream::tcp(Socket::Plain(stream)),
+                }
+            }
+
+            Self::Address(address, tls) => {
+                let stream = TcpStream::connect_timeout(address, Connection::RETRY_PERIOD)?;
+
+                match tls {
+                    Some((host, trust)) => {
+                        Stream::tcp(Socket::Client(Box::new(trust.connect(host, stream)?)))
+                    }
+
+                    None => Stream::tcp(Socket::Plain(stream)),
+                }
+            }
+
+            Self::Relay(url, trust) => Stream::websocket(websocket::connect(url, *trust)?),
+        }
+    }
+}
+
+/// Forward the received messages until the stream ends
+///
+/// The stream is read for a [period](Connection::POLL_PERIOD) at a time, and not while a message is waiting to be sent.
+/// The returned value is `true` if the connection is lost, that is if the stream has ended without a bye or stayed silent for [too long](Connection::LOST_AFTER) while the connection is still in use.
+fn receive(link: &Weak<Link>, sender: &Sender<NetMessage>, peer: SocketAddr) -> bool {
+    let mut agreed = false;
+    let mut heard = Instant::now();
+
+    let refuse = |link: &Link, reason: String| {
+        tracing::warn!("The connection to {peer} is refused: {reason}");
+        let _ = link.write(&NetMessage::Refused(reason.clone()));
+        let _ = sender.send(NetMessage::Refused(reason));
+        false
+    };
+
+    while let Some(link) = link.upgrade() {
+        while link.writers.load(Ordering::Acquire) > 0 {
+            thread::yield_now();
+        }
+
+        let read = match link.stream.lock() {
+            Ok(mut stream) => stream.read(),
+            Err(_) => return false,
+        };
+
+        let text = match read {
+            Ok(Some(text)) => text,
+            Ok(None) => break,
+
+            Err(error) if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                if heard.elapsed() > Connection::LOST_AFTER {
+                    break;
+                }
+
+                continue;
+            }
+
+            Err(error) => {
+                tracing::warn!("The stream from {peer} is dropped: {error}");
+                break;
+            }
+        };
+
+        heard = Instant::now();
+
+        for line in text.lines() {
+            match line.parse() {
+                Ok(NetMessage::Version(PROTOCOL_VERSION)) => {
+                    if !agreed {
+                        let _ = link.write(&NetMessage::Version(PROTOCOL_VERSION));
+                    }
+
+                    agreed = true;
+                }
+
+                Ok(NetMessage::Version(version)) => {
+                    return refuse(
+                        &link,
+                        format!(
+                            "The other side speaks version {version} of the protocol instead of version {PROTOCOL_VERSION}"
+                        ),
+                    );
+                }
+
+                Ok(NetMessage::Ping(stamp)) => {
+                    let _ = link.write(&NetMessage::Pong(stamp));
+                }
+
+                Ok(NetMessage::Pong(stamp)) => {
+                    let latency = link.now().saturating_sub(stamp);
+                    link.latency.store(latency, Ordering::Relaxed);
+                }
+
+                Ok(_) if !agreed => {
+                    return refuse(
+                        &link,
+                        "The other side speaks no version of the protocol".to_owned(),
+                    );
+                }
+
+                Ok(NetMessage::Bye) => return false,
+
+                Ok(message) => {
+                    if sender.send(message).is_err() {
+                        return false;
+                    }
+                }
+
+                Err(error) => tracing::warn!("A message from {peer} is ignored: {error}"),
+            }
+        }
+    }
+
+    match link.upgrade() {
+        Some(link) => {
+            tracing::warn!("The connection to {peer} is lost");
+            link.lost.store(true, Ordering::Relaxed);
+
+            if let Ok(mut stream) = link.stream.lock() {
+                stream.close();
+            }
+
+            true
+        }
+
+        None => false,
+    }
+}
+
+/// Restore a lost connection from its [endpoint](Endpoint), if any
+///
+/// The returned value is `true` if the connection is restored.
+fn restore(link: &Weak<Link>, peer: SocketAddr) -> bool {
+    let deadline = Instant::now() + Connection::RECONNECT_TIMEOUT;
+
+    while Instant::now() < deadline {
+        let Some(link) = link.upgrade() else {
+            return false;
+        };
+
+        let Some(endpoint) = &link.endpoint else {
+            return false;
+        };
+
+        match endpoint.connect() {
+            Ok(stream) => {
+                if let Ok(mut current) = link.stream.lock() {
+                    current.close();
+                    *current = stream;
+                }
+
+                tracing::info!("The connection to {peer} is restored");
+                link.lost.store(false, Ordering::Relaxed);
+                let _ = link.write(&NetMessage::Version(PROTOCOL_VERSION));
+                link.restored.store(true, Ordering::Relaxed);
+                return true;
+            }
+
+            Err(error) => tracing::debug!("The connection cannot be restored yet: {error}"),
+        }
+
+        drop(link);
+        thread::sleep(Connection::RETRY_PERIOD);
+    }
+
+    false
+}
+
+#[derive(Debug)]
+struct Link {
+    stream: Mutex<Stream>,
+    writers: AtomicUsize,
+    endpoint: Option<Endpoint>,
+    received: Mutex<Receiver<NetMessage>>,
+    latency: AtomicU64,
+    lost: AtomicBool,
+    restored: AtomicBool,
+    started: Instant,
+}
+
+impl Link {
+    /// Write a message at once, the stream being read meanwhile for a [period](Connection::POLL_PERIOD) at most
+    fn write(&self, message: &NetMessage) -> io::Result<()> {
+        if self.lost.load(Ordering::Relaxed) {
+            return Err(io::Error::new(
+                ErrorKind::NotConnected,
+                "The connection is being restored",
+            ));
+        }
+
+        self.writers.fetch_add(1, Ordering::AcqRel);
+        let stream = self.stream.lock();
+        self.writers.fetch_sub(1, Ordering::AcqRel);
+
+        stream
+            .map_err(|_| io::Error::other("The connection is poisoned"))?
+            .write(&message.to_string())
+    }
+
+    /// Get the number of milliseconds elapsed since the connection, used to stamp the pings
+    fn now(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+}
+
+impl Drop for Link {
+    fn drop(&mut self) {
+        let _ = self.write(&NetMessage::Bye);
+
+        if let Ok(mut stream) = self.stream.lock() {
+            stream.close();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut record = GameRecord::new(
+            "B../.#./..R hill=b2 topology=torus misere=true"
+                .parse()
+                .unwrap(),
+            Player::Blue,
+        );
+        record.plies = vec![Some(Move {
+            from: (0, 0),
+            to: (1, 0),
+        })];
+
+        for message in [
+            NetMessage::Start {
+                game: "2a".to_owned(),
+                player: Player::Red,
+                free_pass: true,
+                record: Box::new(record),
+            },
+            NetMessage::Version(PROTOCOL_VERSION),
+            NetMessage::Hello,
+            NetMessage::Ply(None),
+            NetMessage::Resume {
+                game: "2a".to_owned(),
+                plies: 3,
+            },
+            NetMessage::Replay {
+                from: 2,
+                plies: vec![
+                    None,
+                    Some(Move {
+                        from: (1, 0),
+                        to: (2, 1),
+                    }),
+                ],
+            },
+            NetMessage::Resign,
+            NetMessage::Chat("good game".to_owned()),
+            NetMessage::Bye,
+            NetMessage::List,
+            NetMessage::Games(vec!["friday".to_owned(), "blitz".to_owned()]),
+            NetMessage::Create("friday".to_owned()),
+            NetMessage::Join("friday".to_owned()),
+            NetMessage::Refused("No game is named blitz".to_owned()),
+            NetMessage::Ping(42),
+            NetMessage::Pong(42),
+        ] {
+            pretty_assertions::assert_eq!(message.to_string().parse(), Ok(message));
+        }
+    }
+
+    #[test]
+    fn relay() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}/room", listener.local_addr().unwrap());
+
+        let relay = thread::spawn(move || {
+            let stream = listener.accept().unwrap().0;
+            let mut socket = tungstenite::accept(stream).unwrap();
+
+            socket.send(Message::text("version 1")).unwrap();
+            socket.send(Message::Ping("relay".into())).unwrap();
+            socket.send(Message::text("ply pass")).unwrap();
+
+            while socket.read().unwrap() != Message::Pong("relay".into()) {}
+
+            socket
+                .send(Message::text("x".repeat(MAX_MESSAGE + 1)))
+                .unwrap();
+
+            while socket.read().is_ok() {}
+        });
+
+        let connection = Connection::relay(&url).unwrap();
+        pretty_assertions::assert_eq!(connection.recv().unwrap(), NetMessage::Ply(None));
+
+        relay.join().unwrap();
+        assert!(connection.is_lost());
+    }
+
+    #[test]
+    fn secure_relay() {
+        let certificate = tls::tests::certificate();
+        let trust = Trust::Pinned(certificate.fingerprint());
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "wss://localhost:{}/room",
+            listener.local_addr().unwrap().port()
+        );
+
+        let relay = thread::spawn(move || {
+            let stream = certificate.accept(listener.accept().unwrap().0).unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+
+            socket.send(Message::text("version 1")).unwrap();
+            socket.send(Message::text("ply pass")).unwrap();
+
+            while socket.read().unwrap() != Message::text("ply pass") {}
+        });
+
+        let connection = Connection::relay_tls(&url, trust).unwrap();
+        pretty_assertions::assert_eq!(connection.recv().unwrap(), NetMessage::Ply(None));
+
+        connection.send(&NetMessage::Ply(None)).unwrap();
+        relay.join().unwrap();
+    }
+
+    #[test]
+    fn secure_exchange() {
+        let certificate = tls::tests::certificate();
+        let trust = Trust::Pinned(certificate.fingerprint());
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("localhost:{}", listener.local_addr().unwrap().port());
+
+        let host = thread::spawn(move || {
+            let host = Connection::accept_tls(&listener, &certificate).unwrap();
+            let _ = listener.accept();
+            host
+        });
+
+        let guest = Connection::join_tls(&address, trust).unwrap();
+        assert!(Connection::join_tls(&address, Trust::Authorities).is_err());
+        let host = host.join().unwrap();
+
+        let ply = NetMessage::Ply(Some(Move {
+            from: (0, 0),
+            to: (0, 1),
+        }));
+
+        guest.send(&ply).unwrap();
+        pretty_assertions::assert_eq!(host.recv().unwrap(), ply);
+
+        host.send(&NetMessage::Chat("good game".to_owned()))
+            .unwrap();
+        pretty_assertions::assert_eq!(
+            guest.recv().unwrap(),
+            NetMessage::Chat("good game".to_owned())
+        );
+    }
+
+    #[test]
+    fn long_line() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let connection = thread::spawn(move || Connection::accept(&listener).unwrap());
+        let mut stream = TcpStream::connect(address).unwrap();
+        let connection = connection.join().unwrap();
+
+        stream.write_all(b"version 1\nply pass\n").unwrap();
+        pretty_assertions::assert_eq!(connection.recv().unwrap(), NetMessage::Ply(None));
+
+        let line = [b"chat ".as_slice(), &[b'x'; MAX_MESSAGE], b"\n"].concat();
+        let _ = stream.write_all(&line);
+
+        pretty_assertions::assert_eq!(connection.recv().unwrap(), NetMessage::Bye);
+    }
+
+    #[test]
+    fn incompatible() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        for greeting in ["version 2\n", "hello\n"] {
+            let connection = Connection::join(address).unwrap();
+            let mut stream = listener.accept().unwrap().0;
+            stream.write_all(greeting.as_bytes()).unwrap();
+
+            assert!(matches!(connection.recv().unwrap(), NetMessage::Refused(_)));
+            pretty_assertions::assert_eq!(connection.recv().unwrap(), NetMessage::Bye);
+
+            let refused = BufReader::new(stream)
+                .lines()
+                .map(Result::unwrap)
+                .find(|line| !line.starts_with("version") && !line.starts_with("ping"));
+
+            assert!(refused.unwrap().starts_with("refused"));
+        }
+    }
+
+    #[test]
+    fn exchange() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let host = thread::spawn(move || Connection::accept(&listener).unwrap());
+        let guest = Connection::join(address).unwrap();
+        let host = host.join().unwrap();
+
+        let ply = NetMessage::Ply(Some(Move {
+            from: (0, 0),
+            to: (0, 1),
+        }));
+
+        guest.send(&ply).unwrap();
+        pretty_assertions::assert_eq!(host.recv().unwrap(), ply);
+
+        while guest.latency().is_none() {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        drop(guest);
+        pretty_assertions::assert_eq!(host.recv().unwrap(), NetMessage::Bye);
+        assert!(!host.is_lost());
+    }
+
+    #[test]
+    fn restore() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let host = thread::spawn(move || {
+            let stream = Stream::tcp(Socket::Plain(listener.accept().unwrap().0)).unwrap();
+            Connection::new(stream, Some(Endpoint::Listener(listener, None))).unwrap()
+        });
+
+        let guest = Connection::join(address).unwrap();
+        let host = host.join().unwrap();
+
+        guest
+            .link
+            .stream
+            .lock()
+            .unwrap()
+            .socket()
+            .shutdown(Shutdown::Both)
+            .unwrap();
+
+        for connection in [&guest, &host] {
+            while !connection.take_restored() {
+                thread::sleep(Duration::from_millis(10));
+            }
+        }
+
+        assert!(!guest.is_lost());
+        guest.send(&NetMessage::Ply(None)).unwrap();
+        pretty_assertions::assert_eq!(host.recv().unwrap(), NetMessage::Ply(None));
+    }
+}
@@ -0,0 +1,302 @@
+//! The discovery of the games hosted on the local network
+//!
+//! A host [announces](Announcement) its game as a [DNS-SD service](SERVICE_TYPE) over multicast DNS, until a player joins it.
+//! The other instances [browse](Discovery) the services and list the games currently announced, so that joining one does not require its address.
+//! The name of the service instance is the name of the game, and its `version` property is the version of the [protocol](super::PROTOCOL_VERSION); the games speaking another version are not listed.
+
+use super::PROTOCOL_VERSION;
+use mdns_sd::{Receiver, ServiceDaemon, ServiceEvent, ServiceInfo};
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    io,
+    net::SocketAddr,
+    sync::Arc,
+};
+
+/// The type of the DNS-SD services announcing the games
+pub const SERVICE_TYPE: &str = "_blobwars._tcp.local.";
+
+/// The announcement of a hosted game, lasting until it is dropped
+pub struct Announcement {
+    daemon: ServiceDaemon,
+    fullname: String,
+}
+
+impl Announcement {
+    /// Announce a game to the local network
+    ///
+    /// The game is announced on every address of the host, kept up to date while they change.
+    ///
+    /// # Parameters
+    ///
+    /// - `name` - The name of the game, such as the name of the host
+    /// - `port` - The TCP port the game is hosted on
+    pub fn start(name: &str, port: u16) -> io::Result<Self> {
+        let daemon = ServiceDaemon::new().map_err(io::Error::other)?;
+        let host = format!("{}.local.", host_label(name));
+        let version = PROTOCOL_VERSION.to_string();
+
+        let service = ServiceInfo::new(
+            SERVICE_TYPE,
+            name,
+            &host,
+            "",
+            port,
+            &[("version", version.as_str())][..],
+        )
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?
+        .enable_addr_auto();
+
+        let fullname = service.get_fullname().to_owned();
+        daemon.register(service).map_err(io::Error::other)?;
+
+        Ok(Self { daemon, fullname })
+    }
+}
+
+impl Debug for Announcement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Announcement")
+            .field("fullname", &self.fullname)
+            .finish()
+    }
+}
+
+impl Drop for Announcement {
+    fn drop(&mut self) {
+        if let Ok(status) = self.daemon.unregister(&self.fullname) {
+            let _ = status.recv();
+        }
+
+        let _ = self.daemon.shutdown();
+    }
+}
+
+/// A game announced on the local network
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiscoveredGame {
+    /// The name of the game
+    pub name: String,
+
+    /// The address to [join](super::Connection::join) the game on
+    pub address: SocketAddr,
+
+    fullname: String,
+}
+
+impl DiscoveredGame {
+    /// Read a game from a resolved service speaking the current version of the protocol
+    fn from_service(service: &ServiceInfo) -> Option<Self> {
+        if service
+            .get_property_val_str("version")?
+            .parse::<u32>()
+            .ok()?
+            != PROTOCOL_VERSION
+        {
+            return None;
+        }
+
+        let addresses = service.get_addresses();
+        let ip = addresses
+            .iter()
+            .find(|ip| ip.is_ipv4())
+            .or_else(|| addresses.iter().next())?;
+
+        let fullname = service.get_fullname();
+        let name = fullname
+            .strip_suffix(SERVICE_TYPE)?
+            .strip_suffix('.')?
+            .to_owned();
+
+        Some(Self {
+            name,
+            address: SocketAddr::new(*ip, service.get_port()),
+            fullname: fullname.to_owned(),
+        })
+    }
+}
+
+/// The browser of the games announced on the local network
+#[derive(Clone, Debug)]
+pub struct Discovery {
+    browser: Arc<Browser>,
+    games: Vec<DiscoveredGame>,
+}
+
+impl Discovery {
+    /// Start browsing the announced games
+    pub fn start() -> io::Result<Self> {
+        let daemon = ServiceDaemon::new().map_err(io::Error::other)?;
+        let events = daemon.browse(SERVICE_TYPE).map_err(io::Error::other)?;
+
+        Ok(Self {
+            browser: Arc::new(Browser { daemon, events }),
+            games: Vec::new(),
+        })
+    }
+
+    /// Get the games announced currently, by order of discovery
+    pub fn games(&self) -> &[DiscoveredGame] {
+        &self.games
+    }
+
+    /// Apply the pending announcements and withdrawals of games
+    ///
+    /// This method is expected to be called regularly by the main loop.
+    ///
+    /// # Return
+    ///
+    /// The returned value is `true` if the listed games have changed; otherwise, it returns `false`.
+    pub fn refresh(&mut self) -> bool {
+        let mut changed = false;
+
+        for event in self.browser.events.try_iter() {
+            match event {
+                ServiceEvent::ServiceResolved(service) => {
+                    let Some(game) = DiscoveredGame::from_service(&service) else {
+                        tracing::debug!("The service {} is ignored", service.get_fullname());
+                        continue;
+                    };
+
+                    match self
+                        .games
+                        .iter_mut()
+                        .find(|known| known.fullname == game.fullname)
+                    {
+                        Some(known) => {
+                            changed |= *known != game;
+                            *known = game;
+                        }
+
+                        None => {
+                            tracing::info!(
+                                "The game {} is announced on {}",
+                                game.name,
+                                game.address
+                            );
+                            self.games.push(game);
+                            changed = true;
+                        }
+                    }
+                }
+
+                ServiceEvent::ServiceRemoved(_, fullname) => {
+                    let before = self.games.len();
+                    self.games.retain(|game| game.fullname != fullname);
+                    changed |= before != self.games.len();
+                }
+
+                _ => (),
+            }
+        }
+
+        changed
+    }
+}
+
+impl PartialEq for Discovery {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.browser, &other.browser) && self.games == other.games
+    }
+}
+
+impl Eq for Discovery {}
+
+/// The daemon browsing the services, shut down once dropped
+struct Browser {
+    daemon: ServiceDaemon,
+    events: Receiver<ServiceEvent>,
+}
+
+impl Debug for Browser {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Browser").finish_non_exhaustive()
+    }
+}
+
+impl Drop for Browser {
+    fn drop(&mut self) {
+        let _ = self.daemon.shutdown();
+    }
+}
+
+/// Make a valid DNS label out of the name of a game, to name the host announcing it
+fn host_label(name: &str) -> String {
+    let label = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .take(63)
+        .collect::<String>();
+
+    match label.trim_matches('-') {
+        "" => "blobwars".to_owned(),
+        label => label.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{net::Ipv4Addr, thread, time::Duration};
+
+    #[test]
+    fn service() {
+        let service = |version: &str| {
+            ServiceInfo::new(
+                SERVICE_TYPE,
+                "alice's game",
+                "alice.local.",
+                "192.168.1.2",
+                4444,
+                &[("version", version)][..],
+            )
+            .unwrap()
+        };
+
+        pretty_assertions::assert_eq!(
+            DiscoveredGame::from_service(&service("1")),
+            Some(DiscoveredGame {
+                name: "alice's game".to_owned(),
+                address: SocketAddr::from((Ipv4Addr::new(192, 168, 1, 2), 4444)),
+                fullname: format!("alice's game.{SERVICE_TYPE}"),
+            })
+        );
+
+        pretty_assertions::assert_eq!(DiscoveredGame::from_service(&service("0")), None);
+        pretty_assertions::assert_eq!(host_label("alice's game"), "alice-s-game");
+        pretty_assertions::assert_eq!(host_label("ü"), "blobwars");
+    }
+
+    /// Refresh a discovery until the listed games change, or give up after a few seconds
+    fn wait(discovery: &mut Discovery) -> bool {
+        (0..50).any(|_| {
+            thread::sleep(Duration::from_millis(100));
+            discovery.refresh()
+        })
+    }
+
+    #[test]
+    fn discover() {
+        let mut discovery = Discovery::start().unwrap();
+        let announcement = Announcement::start("bob's game", 4444).unwrap();
+
+        assert!(wait(&mut discovery));
+        let game = discovery
+            .games()
+            .iter()
+            .find(|game| game.name == "bob's game")
+            .unwrap();
+        pretty_assertions::assert_eq!(game.address.port(), 4444);
+
+        drop(announcement);
+
+        assert!(wait(&mut discovery));
+        assert!(
+            discovery
+                .games()
+                .iter()
+                .all(|game| game.name != "bob's game")
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! The encryption of the connections with TLS, built on [`rustls`]
+//!
+//! The side accepting the connection presents a [certificate](Certificate); the side connecting [trusts](Trust) it either because one of the usual authorities has issued it for the name of the server, or because its [fingerprint](Fingerprint) is the one pinned beforehand.
+//! Pinning suits the games hosted by a player, whose certificate is usually self-signed: the host gives the fingerprint of its certificate to the other player along with its address.
+
+use rustls::{
+    CertificateError, ClientConfig, ClientConnection, ConnectionCommon, DigitallySignedStruct,
+    RootCertStore, ServerConfig, ServerConnection, SideData, SignatureScheme, StreamOwned,
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{self, CryptoProvider},
+    pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime, pem::PemObject},
+};
+
+use std::{
+    fmt::{Display, Formatter},
+    io::{self, ErrorKind},
+    net::TcpStream,
+    ops::DerefMut,
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+
+/// The duration after which a silent peer is given up during the handshake
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(6);
+
+/// The SHA-256 digest of a certificate, as shown by `openssl x509 -noout -fingerprint -sha256`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Fingerprint(pub [u8; 32]);
+
+impl Fingerprint {
+    /// Compute the fingerprint of a certificate
+    ///
+    /// # Parameter
+    ///
+    /// - `certificate` - The certificate in the DER format
+    pub fn of(certificate: &[u8]) -> Self {
+        let digest = ring::digest::digest(&ring::digest::SHA256, certificate);
+        let mut fingerprint = [0; 32];
+        fingerprint.copy_from_slice(digest.as_ref());
+
+        Self(fingerprint)
+    }
+}
+
+impl Display for Fingerprint {
+    /// Write the fingerprint as colon-separated pairs of uppercase hexadecimal digits
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, byte) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ":")?;
+            }
+
+            write!(f, "{byte:02X}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = &'static str;
+
+    /// Parse a fingerprint made of 64 hexadecimal digits, optionally separated by colons
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::net::tls::Fingerprint;
+    ///
+    /// let fingerprint = "66:45:fa:4d:70:09:92:f2:94:e3:fb:bb:01:64:79:c9:63:b8:11:36:47:ca:4b:14:64:66:68:6b:42:4c:f0:7c"
+    ///     .parse::<Fingerprint>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(fingerprint.0[..2], [0x66, 0x45]);
+    /// assert_eq!(fingerprint.to_string().replace(':', "").parse(), Ok(fingerprint));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.chars().filter(|&c| c != ':').collect::<Vec<_>>();
+
+        if digits.len() != 64 {
+            return Err("A fingerprint is not made of 64 hexadecimal digits");
+        }
+
+        let mut fingerprint = [0; 32];
+
+        for (byte, pair) in fingerprint.iter_mut().zip(digits.chunks(2)) {
+            let pair = pair.iter().collect::<String>();
+            *byte = u8::from_str_radix(&pair, 16)
+                .map_err(|_| "A fingerprint is not made of hexadecimal digits")?;
+        }
+
+        Ok(Self(fingerprint))
+    }
+}
+
+/// The way the certificate of a server is trusted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Trust {
+    /// The certificate must be issued for the name of the server by one of the usual authorities
+    #[default]
+    Authorities,
+
+    /// The certificate must have the given fingerprint, whoever has issued it and whatever its name
+    Pinned(Fingerprint),
+}
+
+impl Trust {
+    /// Open a TLS connection over a stream and perform the handshake
+    ///
+    /// # Parameters
+    ///
+    /// - `host` - The name or the address of the server
+    /// - `stream` - The stream connected to the server
+    pub fn connect(
+        self,
+        host: &str,
+        mut stream: TcpStream,
+    ) -> io::Result<StreamOwned<ClientConnection, TcpStream>> {
+        let name = ServerName::try_from(host.to_owned())
+            .map_err(|error| io::Error::new(ErrorKind::InvalidInput, error))?;
+
+        let builder = ClientConfig::builder_with_provider(provider())
+            .with_safe_default_protocol_versions()
+            .map_err(io::Error::other)?;
+
+        let config = match self {
+            Self::Authorities => builder
+                .with_root_certificates(RootCertStore {
+                    roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+                })
+                .with_no_client_auth(),
+
+            Self::Pinned(fingerprint) => builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(PinnedVerifier {
+                    fingerprint,
+                    provider: provider(),
+                }))
+                .with_no_client_auth(),
+        };
+
+        let mut connection =
+            ClientConnection::new(Arc::new(config), name).map_err(io::Error::other)?;
+
+        handshake(&mut connection, &mut stream)?;
+        Ok(StreamOwned::new(connection, stream))
+    }
+}
+
+/// The certificate presented by a server, along with its private key
+#[derive(Clone, Debug)]
+pub struct Certificate {
+    config: Arc<ServerConfig>,
+    fingerprint: Fingerprint,
+}
+
+impl Certificate {
+    /// Read a certificate and its private key
+    ///
+    /// # Parameters
+    ///
+    /// - `certificate` - The path of the certificate chain in the PEM format, the certificate of the server coming first
+    /// - `key` - The path of the private key in the PEM format
+    pub fn read(certificate: &Path, key: &Path) -> io::Result<Self> {
+        let chain = CertificateDer::pem_file_iter(certificate)
+            .map_err(invalid)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(invalid)?;
+
+        let fingerprint = chain
+            .first()
+            .map(|certificate| Fingerprint::of(certificate))
+            .ok_or(io::Error::new(
+                ErrorKind::InvalidData,
+                "The certificate file holds no certificate",
+            ))?;
+
+        let key = PrivateKeyDer::from_pem_file(key).map_err(invalid)?;
+
+        let config = ServerConfig::builder_with_provider(provider())
+            .with_safe_default_protocol_versions()
+            .map_err(io::Error::other)?
+            .with_no_client_auth()
+            .with_single_cert(chain, key)
+            .map_err(invalid)?;
+
+        Ok(Self {
+            config: Arc::new(config),
+            fingerprint,
+        })
+    }
+
+    /// Get the fingerprint of the certificate, to be [pinned](Trust::Pinned) by the clients
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.fingerprint
+    }
+
+    /// Accept a TLS connection over a stream and perform the handshake
+    ///
+    /// # Parameter
+    ///
+    /// - `stream` - The stream connected to the client
+    pub fn accept(
+        &self,
+        mut stream: TcpStream,
+    ) -> io::Result<StreamOwned<ServerConnection, TcpStream>> {
+        let mut connection =
+            ServerConnection::new(self.config.clone()).map_err(io::Error::other)?;
+
+        handshake(&mut connection, &mut stream)?;
+        Ok(StreamOwned::new(connection, stream))
+    }
+}
+
+fn invalid<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(ErrorKind::InvalidData, error)
+}
+
+/// A verifier accepting only the certificate of a given fingerprint
+#[derive(Debug)]
+struct PinnedVerifier {
+    fingerprint: Fingerprint,
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for PinnedVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if Fingerprint::of(end_entity) == self.fingerprint {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::ApplicationVerificationFailure,
+            ))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        certificate: &CertificateDer<'_>,
+        signature: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls12_signature(
+            message,
+            certificate,
+            signature,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        certificate: &CertificateDer<'_>,
+        signature: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls13_signature(
+            message,
+            certificate,
+            signature,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+fn provider() -> Arc<CryptoProvider> {
+    Arc::new(crypto::ring::default_provider())
+}
+
+/// Perform the handshake at once, so that the stream can then be read with short timeouts
+fn handshake<C, S>(connection: &mut C, stream: &mut TcpStream) -> io::Result<()>
+where
+    C: DerefMut<Target = ConnectionCommon<S>>,
+    S: SideData,
+{
+    let timeout = stream.read_timeout()?;
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+
+    while connection.is_handshaking() {
+        connection.complete_io(stream)?;
+    }
+
+    stream.set_read_timeout(timeout)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::{net::TcpListener, thread};
+
+    pub(crate) fn certificate() -> Certificate {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/net/testdata");
+        Certificate::read(
+            &directory.join("certificate.pem"),
+            &directory.join("key.pem"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn fingerprint() {
+        pretty_assertions::assert_eq!(
+            certificate().fingerprint().to_string(),
+            "66:45:FA:4D:70:09:92:F2:94:E3:FB:BB:01:64:79:C9:63:B8:11:36:47:CA:4B:14:64:66:68:6B:42:4C:F0:7C"
+        );
+
+        assert!("66:45".parse::<Fingerprint>().is_err());
+        assert!("z".repeat(64).parse::<Fingerprint>().is_err());
+    }
+
+    #[test]
+    fn pinning() {
+        let certificate = certificate();
+        let trusted = Trust::Pinned(certificate.fingerprint());
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let server = thread::spawn(move || {
+            (0..3)
+                .map(|_| certificate.accept(listener.accept().unwrap().0).is_ok())
+                .collect::<Vec<_>>()
+        });
+
+        let stream = TcpStream::connect(address).unwrap();
+        assert!(trusted.connect("localhost", stream).is_ok());
+
+        let untrusted = Trust::Pinned(Fingerprint([0; 32]));
+        let stream = TcpStream::connect(address).unwrap();
+        assert!(untrusted.connect("localhost", stream).is_err());
+
+        let stream = TcpStream::connect(address).unwrap();
+        assert!(Trust::Authorities.connect("localhost", stream).is_err());
+
+        pretty_assertions::assert_eq!(server.join().unwrap(), [true, false, false]);
+    }
+}
@@ -0,0 +1,182 @@
+//! The client side of the WebSocket transport, built on [`tungstenite`]
+//!
+//! The connections are made over plain TCP (`ws://`) or over [TLS](super::tls) (`wss://`).
+//! A frame or a message longer than [`MAX_MESSAGE`] is refused as soon as its header is read, before its payload is.
+
+use super::{MAX_MESSAGE, Socket, tls::Trust};
+use tungstenite::{HandshakeError, WebSocket, http::Uri, protocol::WebSocketConfig};
+
+use std::{
+    io::{self, ErrorKind},
+    net::TcpStream,
+    time::Duration,
+};
+
+/// The duration after which a silent server is given up during the opening handshake
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(6);
+
+/// Get the configuration of the WebSocket connections, bounding the size of the received frames and messages
+pub fn config() -> WebSocketConfig {
+    WebSocketConfig::default()
+        .max_frame_size(Some(MAX_MESSAGE))
+        .max_message_size(Some(MAX_MESSAGE))
+}
+
+/// Connect to a WebSocket server and perform the opening handshake
+///
+/// # Parameters
+///
+/// - `url` - The URL of the server, such as `ws://relay.example.org:8080/room` or `wss://relay.example.org/room`
+/// - `trust` - How the certificate of the server is [trusted](Trust) over `wss://`
+pub fn connect(url: &str, trust: Trust) -> io::Result<WebSocket<Socket>> {
+    let (host, port, secure) = parse_url(url)?;
+
+    let stream = TcpStream::connect((host.as_str(), port))?;
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+
+    let socket = if secure {
+        Socket::Client(Box::new(trust.connect(&host, stream)?))
+    } else {
+        Socket::Plain(stream)
+    };
+
+    match tungstenite::client::client_with_config(url, socket, Some(config())) {
+        Ok((socket, _)) => Ok(socket),
+        Err(HandshakeError::Failure(tungstenite::Error::Http(_))) => Err(io::Error::new(
+            ErrorKind::ConnectionRefused,
+            "The server has refused the WebSocket connection",
+        )),
+        Err(HandshakeError::Failure(error)) => Err(io_error(error)),
+        Err(HandshakeError::Interrupted(_)) => Err(ErrorKind::TimedOut.into()),
+    }
+}
+
+/// Convert an error of [`tungstenite`] into an [I/O error](io::Error)
+///
+/// A closed connection is reported as [aborted](ErrorKind::ConnectionAborted) and a protocol violation, such as a frame too long, as [invalid data](ErrorKind::InvalidData).
+pub fn io_error(error: tungstenite::Error) -> io::Error {
+    match error {
+        tungstenite::Error::Io(error) => error,
+
+        tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed => {
+            ErrorKind::ConnectionAborted.into()
+        }
+
+        error => io::Error::new(ErrorKind::InvalidData, error),
+    }
+}
+
+/// Get the host and the port of a URL, and whether it is secure
+fn parse_url(url: &str) -> io::Result<(String, u16, bool)> {
+    let invalid = |error| io::Error::new(ErrorKind::InvalidInput, error);
+
+    let secure = if url.starts_with("wss://") {
+        true
+    } else if url.starts_with("ws://") {
+        false
+    } else {
+        return Err(invalid("The URL starts with neither `ws://` nor `wss://`"));
+    };
+
+    let uri = url
+        .parse::<Uri>()
+        .map_err(|_| invalid("The URL is malformed"))?;
+
+    let authority = uri
+        .authority()
+        .filter(|authority| !authority.host().is_empty())
+        .ok_or(invalid("The URL has no host"))?;
+
+    let host = authority
+        .host()
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+
+    let port = authority
+        .port_u16()
+        .unwrap_or(if secure { 443 } else { 80 });
+
+    Ok((host.to_owned(), port, secure))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tungstenite::{Message, protocol::Role};
+
+    use std::io::{Cursor, Read, Write};
+
+    #[test]
+    fn url() {
+        pretty_assertions::assert_eq!(
+            parse_url("ws://relay.example.org:8080/room").unwrap(),
+            ("relay.example.org".to_owned(), 8080, false)
+        );
+        pretty_assertions::assert_eq!(
+            parse_url("ws://relay").unwrap(),
+            ("relay".to_owned(), 80, false)
+        );
+        pretty_assertions::assert_eq!(
+            parse_url("wss://relay/room").unwrap(),
+            ("relay".to_owned(), 443, true)
+        );
+        pretty_assertions::assert_eq!(
+            parse_url("ws://[::1]:8080/").unwrap(),
+            ("::1".to_owned(), 8080, false)
+        );
+        assert!(parse_url("http://relay").is_err());
+        assert!(parse_url("ws:///room").is_err());
+    }
+
+    /// A stream whose reads fail once the given number of bytes has been read
+    struct Limited<R>(R, usize);
+
+    impl<R: Read> Read for Limited<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.1 == 0 {
+                return Err(io::Error::other("The payload is being read"));
+            }
+
+            let length = buf.len().min(self.1);
+            let read = self.0.read(&mut buf[..length])?;
+            self.1 -= read;
+            Ok(read)
+        }
+    }
+
+    impl<R> Write for Limited<R> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn long_frame() {
+        let mut frame = vec![0x81, 127];
+        frame.extend_from_slice(&(MAX_MESSAGE as u64 + 1).to_be_bytes());
+        let header = frame.len();
+        frame.resize(header + MAX_MESSAGE + 1, b'.');
+
+        let stream = Limited(Cursor::new(frame), header);
+        let mut socket = WebSocket::from_raw_socket(stream, Role::Client, Some(config()));
+
+        assert!(matches!(
+            socket.read(),
+            Err(tungstenite::Error::Capacity(_))
+        ));
+
+        let message = Message::text("ply a1b2");
+        let mut writer =
+            WebSocket::from_raw_socket(Cursor::new(Vec::new()), Role::Server, Some(config()));
+        writer.send(message.clone()).unwrap();
+
+        let frame = writer.into_inner().into_inner();
+        let mut reader =
+            WebSocket::from_raw_socket(Cursor::new(frame), Role::Client, Some(config()));
+        pretty_assertions::assert_eq!(reader.read().unwrap(), message);
+    }
+}
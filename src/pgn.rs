@@ -0,0 +1,597 @@
+//! The implementation of [`PortableGame`]
+//!
+//! A portable game is written like the Portable Game Notation of chess: some `[Name "value"]` tags followed by the numbered plies in algebraic notation and the result.
+//!
+//! ```text
+//! [Event "Blobwars game"]
+//! [Date "2023.11.14"]
+//! [Blue "Human"]
+//! [Red "Computer (Aggressive, Hard)"]
+//! [Result "0-1"]
+//! [Board "R.B"]
+//! [First "Red"]
+//!
+//! 1. a1b1 0-1
+//! ```
+//!
+//! The result is `1-0` if Blue wins, `0-1` if Red wins, `1/2-1/2` for a draw and `*` for a game in progress.
+//!
+//! The records written by Ataxx programs are [imported](PortableGame::from_str) as well: their initial position is given by a `FEN` tag, where `x` stands for Blue, `o` for Red and `-` for a restricted cell, or is the usual 7x7 one if none is given.
+//! A move to an adjacent cell may be written by its destination only (`b2`) and a pass as `0000`.
+
+use crate::{
+    game::{Board, CellState, Index, Move, Outcome, Player},
+    record::{GameRecord, display_ply, parse_index, parse_ply},
+    save::SavedGame,
+    widgets::board::BoardState,
+};
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const LINE_WIDTH: usize = 80;
+
+const ATAXX_START: &str = "x5o/7/7/7/7/7/o5x x";
+
+/// The reason why a [portable game](PortableGame) cannot be imported
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ImportError {
+    /// The number of the faulty ply, starting from 1, if the plies are at fault
+    pub ply: Option<usize>,
+
+    /// The description of the error
+    pub reason: &'static str,
+}
+
+impl From<&'static str> for ImportError {
+    fn from(value: &'static str) -> Self {
+        Self {
+            ply: None,
+            reason: value,
+        }
+    }
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.ply {
+            Some(ply) => write!(f, "Ply {ply}: {}", self.reason),
+            None => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+/// A [game record](GameRecord) along with its tags and its result, written in a portable format
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PortableGame {
+    /// The tags describing the game, as `(name, value)` pairs, excluding the result, the board and the first player
+    pub tags: Vec<(String, String)>,
+
+    /// The [record](GameRecord) of the game
+    pub record: GameRecord,
+
+    /// The [outcome](Outcome) of the game, if it is over
+    pub outcome: Option<Outcome>,
+}
+
+impl PortableGame {
+    /// Create a new [`PortableGame`] dated today, whose players are unknown
+    ///
+    /// The outcome is the one of the last position of the record.
+    ///
+    /// # Parameter
+    ///
+    /// - `record` - The [record](GameRecord) of the game
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Move, Outcome, Player::*},
+    ///     pgn::PortableGame,
+    ///     record::GameRecord,
+    /// };
+    ///
+    /// let mut record = GameRecord::new(Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]), Red);
+    /// record.plies.push(Some(Move { from: (0, 0), to: (0, 1) }));
+    ///
+    /// let game = PortableGame::new(record);
+    ///
+    /// assert_eq!(game.outcome, Some(Outcome::Win(Red)));
+    /// assert_eq!(game.tag("Blue"), Some("?"));
+    /// assert!(game.to_string().ends_with("1. a1b1 0-1\n"));
+    /// ```
+    pub fn new(record: GameRecord) -> Self {
+        let date = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let outcome = record.position(record.plies.len()).0.outcome();
+
+        Self {
+            tags: vec![
+                ("Event".to_owned(), "Blobwars game".to_owned()),
+                ("Date".to_owned(), display_date(date)),
+                ("Blue".to_owned(), "?".to_owned()),
+                ("Red".to_owned(), "?".to_owned()),
+            ],
+            record,
+            outcome,
+        }
+    }
+
+    /// Create a new [`PortableGame`] from the game of a [board](BoardState), finished or in progress
+    ///
+    /// The players are [named](BoardState::player_name) after their nature.
+    ///
+    /// # Parameter
+    ///
+    /// - `state` - The [state](BoardState) of the board
+    pub fn from_state(state: &BoardState) -> Self {
+        Self {
+            outcome: state.outcome(),
+            ..Self::new(state.record().clone())
+        }
+        .with_tag("Blue", &state.player_name(Player::Blue))
+        .with_tag("Red", &state.player_name(Player::Red))
+    }
+
+    /// Set the value of a tag, adding it if needed
+    ///
+    /// # Parameters
+    ///
+    /// - `name` - The name of the tag
+    /// - `value` - The value of the tag
+    pub fn with_tag(mut self, name: &str, value: &str) -> Self {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, current)) => *current = value.to_owned(),
+            None => self.tags.push((name.to_owned(), value.to_owned())),
+        }
+
+        self
+    }
+
+    /// Get the value of a tag, if any
+    ///
+    /// # Parameter
+    ///
+    /// - `name` - The name of the tag
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Get the date of the game, in seconds since the Unix epoch, if its `Date` tag is complete
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::pgn::PortableGame;
+    ///
+    /// let game: PortableGame = "[Date \"2023.11.14\"]\n\n*".parse().unwrap();
+    /// assert_eq!(game.date(), Some(1_699_920_000));
+    ///
+    /// let game: PortableGame = "[Date \"2023.??.??\"]\n\n*".parse().unwrap();
+    /// assert_eq!(game.date(), None);
+    /// ```
+    pub fn date(&self) -> Option<u64> {
+        let mut parts = self
+            .tag("Date")?
+            .split('.')
+            .map(|part| part.parse::<u64>().ok());
+        let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+
+        ((1..=12).contains(&month) && (1..=31).contains(&day) && year >= 1970)
+            .then(|| days_from_civil(year, month, day) * 86_400)
+    }
+
+    /// Get the directory the games are exported to
+    ///
+    /// The directory is located next to the [save directory](SavedGame::directory).
+    pub fn directory() -> Option<PathBuf> {
+        SavedGame::directory().map(|directory| directory.with_file_name("exports"))
+    }
+
+    /// Export the game to a new file of the [export directory](Self::directory)
+    ///
+    /// # Return
+    ///
+    /// The path of the written file
+    pub fn export(&self) -> io::Result<PathBuf> {
+        let directory = Self::directory().ok_or(io::Error::new(
+            ErrorKind::NotFound,
+            "No data directory was found",
+        ))?;
+
+        fs::create_dir_all(&directory)?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        let path = directory.join(format!("game-{timestamp}.pgn"));
+
+        fs::write(&path, self.to_string())?;
+        Ok(path)
+    }
+
+    /// Get the result written at the end of the game
+    pub fn result(&self) -> &'static str {
+        match self.outcome {
+            Some(Outcome::Win(Player::Blue)) => "1-0",
+            Some(Outcome::Win(Player::Red)) => "0-1",
+            Some(Outcome::Draw) => "1/2-1/2",
+            None => "*",
+        }
+    }
+}
+
+impl Display for PortableGame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (name, value) in &self.tags {
+            writeln!(f, "[{name} \"{value}\"]")?;
+        }
+
+        writeln!(f, "[Result \"{}\"]", self.result())?;
+        writeln!(f, "[Board \"{}\"]", self.record.board)?;
+        writeln!(f, "[First \"{}\"]", self.record.first)?;
+        writeln!(f)?;
+
+        let mut tokens = Vec::new();
+
+        for (i, &ply) in self.record.plies.iter().enumerate() {
+            if i % 2 == 0 {
+                tokens.push(format!("{}.", i / 2 + 1));
+            }
+
+            tokens.push(display_ply(ply));
+        }
+
+        tokens.push(self.result().to_owned());
+
+        let mut width = 0;
+
+        for token in tokens {
+            if width > 0 && width + 1 + token.len() > LINE_WIDTH {
+                writeln!(f)?;
+                width = 0;
+            } else if width > 0 {
+                write!(f, " ")?;
+                width += 1;
+            }
+
+            write!(f, "{token}")?;
+            width += token.len();
+        }
+
+        writeln!(f)
+    }
+}
+
+impl FromStr for PortableGame {
+    type Err = ImportError;
+
+    /// Parse a portable game
+    ///
+    /// Every ply is checked against the rules of the game; the first illegal one is reported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{game::{Outcome, Player}, pgn::{ImportError, PortableGame}};
+    ///
+    /// let game: PortableGame = "[Red \"Alice\"]\n[Board \"R.B\"]\n[First \"Red\"]\n\n1. a1b1 0-1".parse().unwrap();
+    ///
+    /// assert_eq!(game.tag("Red"), Some("Alice"));
+    /// assert_eq!(game.outcome, Some(Outcome::Win(Player::Red)));
+    /// assert_eq!(game.record.plies.len(), 1);
+    ///
+    /// let ataxx: PortableGame = "[FEN \"x5o/7/7/7/7/7/o5x x 0 1\"]\n\n1. b7 b1 2. 0000 *".parse().unwrap();
+    /// assert_eq!(ataxx.record.plies.len(), 3);
+    ///
+    /// let illegal = "[Board \"R.B\"]\n[First \"Red\"]\n\n1. a1b1 a1c1 *".parse::<PortableGame>();
+    /// assert_eq!(illegal.map_err(|error| error.ply), Err(Some(2)));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tags = Vec::new();
+        let (mut board, mut first, mut fen, mut result) = (None, None, None, None);
+        let mut movetext = String::new();
+
+        for line in s.lines().map(str::trim) {
+            if let Some(tag) = line.strip_prefix('[') {
+                let (name, value) = tag
+                    .trim_end_matches(']')
+                    .split_once(' ')
+                    .ok_or("A tag is malformed")?;
+
+                let value = value.trim().trim_matches('"');
+
+                match name {
+                    "Board" => board = Some(value.parse::<Board>()?),
+                    "First" => first = Some(value.parse::<Player>()?),
+                    "FEN" => fen = Some(value.to_owned()),
+                    "Result" => result = Some(value.to_owned()),
+                    _ => tags.push((name.to_owned(), value.to_owned())),
+                }
+            } else if !line.starts_with(';') {
+                movetext.push_str(line);
+                movetext.push(' ');
+            }
+        }
+
+        let (board, first) = match (board, fen) {
+            (Some(board), _) => (board, first.unwrap_or(Player::Blue)),
+            (None, fen) => parse_fen(fen.as_deref().unwrap_or(ATAXX_START))?,
+        };
+
+        let mut record = GameRecord::new(board, first);
+        let (mut board, mut player) = (record.board.clone(), record.first);
+        let mut in_comment = false;
+
+        for token in movetext.split_whitespace() {
+            if in_comment || token.starts_with('{') {
+                in_comment = !token.ends_with('}');
+                continue;
+            }
+
+            if token.ends_with('.') && token.starts_with(|c: char| c.is_ascii_digit()) {
+                continue;
+            }
+
+            if parse_result(token).is_some() {
+                result = Some(token.to_owned());
+                break;
+            }
+
+            let error = |reason| ImportError {
+                ply: Some(record.plies.len() + 1),
+                reason,
+            };
+
+            let ply = parse_ataxx_ply(token, &board, player).map_err(error)?;
+
+            if let Some(Move { from, to }) = ply
+                && (board.get(from.0, from.1) != Some(CellState::Player(player))
+                    || !board.jump(from, to))
+            {
+                return Err(error("The move is illegal"));
+            }
+
+            record.plies.push(ply);
+            player = -player;
+        }
+
+        let outcome = match result {
+            Some(result) => parse_result(&result).ok_or("The result is malformed")?,
+            None => board.outcome(),
+        };
+
+        Ok(Self {
+            tags,
+            record,
+            outcome,
+        })
+    }
+}
+
+/// Read a result written at the end of a game
+///
+/// The outer option is `None` if the token is not a result and the inner one is `None` for a game in progress.
+fn parse_result(token: &str) -> Option<Option<Outcome>> {
+    match token {
+        "1-0" => Some(Some(Outcome::Win(Player::Blue))),
+        "0-1" => Some(Some(Outcome::Win(Player::Red))),
+        "1/2-1/2" => Some(Some(Outcome::Draw)),
+        "*" => Some(None),
+        _ => None,
+    }
+}
+
+/// Read a ply written either in the notation of the [records](GameRecord) or in the one of Ataxx
+fn parse_ataxx_ply(
+    token: &str,
+    board: &Board,
+    player: Player,
+) -> Result<Option<Move>, &'static str> {
+    if token == "0000" {
+        return Ok(None);
+    }
+
+    if let Ok(to) = parse_index(token) {
+        let from = adjacent(to)
+            .find(|&(row, column)| board.get(row, column) == Some(CellState::Player(player)))
+            .ok_or("No blob can reach the destination")?;
+
+        return Ok(Some(Move { from, to }));
+    }
+
+    parse_ply(token)
+}
+
+/// Get the positions adjacent to a given one
+fn adjacent((row, column): Index) -> impl Iterator<Item = Index> {
+    (-1..=1_isize)
+        .flat_map(|dr| (-1..=1_isize).map(move |dc| (dr, dc)))
+        .filter(|&delta| delta != (0, 0))
+        .filter_map(move |(dr, dc)| {
+            Some((row.checked_add_signed(dr)?, column.checked_add_signed(dc)?))
+        })
+}
+
+/// Read the initial position and the first player of an Ataxx FEN
+///
+/// The ranks are written from the top one, which is the last row of the [board](Board); `x` stands for Blue, `o` for Red, `-` for a restricted cell and a digit for as many free cells.
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::{game::Player, pgn::parse_fen};
+///
+/// let (board, first) = parse_fen("x1o/-2 o").unwrap();
+///
+/// assert_eq!(board.to_string(), "#../B.R");
+/// assert_eq!(first, Player::Red);
+/// ```
+pub fn parse_fen(fen: &str) -> Result<(Board, Player), &'static str> {
+    let mut fields = fen.split_whitespace();
+    let ranks = fields.next().ok_or("The FEN is empty")?;
+
+    let mut rows = Vec::new();
+
+    for rank in ranks.split('/').rev() {
+        let mut row = String::new();
+
+        for c in rank.chars() {
+            match c {
+                'x' => row.push('B'),
+                'o' => row.push('R'),
+                '-' => row.push('#'),
+                '1'..='9' => row.push_str(&".".repeat(c as usize - '0' as usize)),
+                _ => return Err("The FEN contains an unknown cell"),
+            }
+        }
+
+        rows.push(row);
+    }
+
+    let board = rows.join("/").parse()?;
+
+    let first = match fields.next() {
+        Some("x") | None => Player::Blue,
+        Some("o") => Player::Red,
+        Some(_) => return Err("The FEN names an unknown player"),
+    };
+
+    Ok((board, first))
+}
+
+/// Count the days from the Unix epoch to a date
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let shifted_month = (month + 9) % 12;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Write a time as a date formatted `YYYY.MM.DD`
+///
+/// # Parameter
+///
+/// - `time` - The time, in seconds since the Unix epoch
+fn display_date(time: u64) -> String {
+    let days = time / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!("{year:04}.{month:02}.{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Board, CellState, Move};
+
+    #[test]
+    fn date() {
+        pretty_assertions::assert_eq!(display_date(0), "1970.01.01");
+        pretty_assertions::assert_eq!(display_date(1_700_000_000), "2023.11.14");
+        pretty_assertions::assert_eq!(display_date(951_782_400), "2000.02.29");
+        pretty_assertions::assert_eq!(days_from_civil(2000, 2, 29) * 86_400, 951_782_400);
+    }
+
+    #[test]
+    fn export() {
+        let mut record = GameRecord::new(
+            Board::new(
+                1,
+                4,
+                vec![
+                    CellState::Player(Player::Blue),
+                    CellState::Free,
+                    CellState::Free,
+                    CellState::Player(Player::Red),
+                ],
+            ),
+            Player::Blue,
+        );
+
+        record.plies = vec![
+            Some(Move {
+                from: (0, 0),
+                to: (0, 1),
+            }),
+            None,
+            Some(Move {
+                from: (0, 1),
+                to: (0, 2),
+            }),
+        ];
+
+        let game = PortableGame {
+            tags: vec![("Event".to_owned(), "Test".to_owned())],
+            record,
+            outcome: None,
+        }
+        .with_tag("Red", "Remote");
+
+        pretty_assertions::assert_eq!(
+            game.to_string(),
+            "[Event \"Test\"]\n[Red \"Remote\"]\n[Result \"*\"]\n[Board \"B..R\"]\n[First \"Blue\"]\n\n\
+             1. a1b1 pass 2. b1c1 *\n"
+        );
+
+        pretty_assertions::assert_eq!(game.to_string().parse(), Ok(game));
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(
+            "[FEN \"x5z/7/7/7/7/7/o5x x\"]"
+                .parse::<PortableGame>()
+                .is_err()
+        );
+        assert!(
+            "[Board \"R.B\"]\n\n1. a1b1 2-0"
+                .parse::<PortableGame>()
+                .is_err()
+        );
+
+        pretty_assertions::assert_eq!(
+            "1. b7 b6 *".parse::<PortableGame>(),
+            Err(ImportError {
+                ply: Some(2),
+                reason: "No blob can reach the destination"
+            })
+        );
+    }
+}
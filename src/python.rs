@@ -0,0 +1,327 @@
+//! The Python module of the game engine, built on [`pyo3`]
+//!
+//! This module exposes the [rules](crate::game) and the [computer opponent](crate::ai) to Python as the `blobwars` module, holding the `Board`, `Move`, `Player` and `Engine` classes.
+//! The extension is built with `cargo rustc --lib --release --no-default-features --features python --crate-type cdylib`, the resulting `libblobwars.so` being renamed `blobwars.so`.
+//!
+//! ```python
+//! from blobwars import Board, Engine, Player
+//!
+//! board = Board("R.B")
+//! engine = Engine("aggressive", "hard", seed=0)
+//!
+//! board.play(Player.Red, engine.choose(board, Player.Red))
+//! assert board.is_over() and board.winner() == Player.Red
+//! ```
+
+use crate::{
+    ai::{Ai, Difficulty, Personality},
+    game::{Board, CellState, Move, Outcome, Player, display_index, parse_index},
+    rng::Rng,
+};
+
+use pyo3::{
+    exceptions::{PyIndexError, PyValueError},
+    prelude::*,
+};
+
+/// A [player](Player)
+#[pyclass(name = "Player", module = "blobwars", eq, eq_int, frozen, hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PyPlayer {
+    /// The [blue player](Player::Blue)
+    Blue,
+
+    /// The [red player](Player::Red)
+    Red,
+}
+
+#[pymethods]
+impl PyPlayer {
+    /// Get the opponent of the player
+    fn opponent(&self) -> Self {
+        (-Player::from(*self)).into()
+    }
+}
+
+impl From<PyPlayer> for Player {
+    fn from(value: PyPlayer) -> Self {
+        match value {
+            PyPlayer::Blue => Self::Blue,
+            PyPlayer::Red => Self::Red,
+        }
+    }
+}
+
+impl From<Player> for PyPlayer {
+    fn from(value: Player) -> Self {
+        match value {
+            Player::Blue => Self::Blue,
+            Player::Red => Self::Red,
+        }
+    }
+}
+
+/// A [move](Move) of a _blob_
+#[pyclass(name = "Move", module = "blobwars", eq, frozen, hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PyMove(Move);
+
+#[pymethods]
+impl PyMove {
+    /// Create a move from its departure and destination points, given as `(row, column)`
+    #[new]
+    fn new(departure: (usize, usize), destination: (usize, usize)) -> Self {
+        Self(Move {
+            from: departure,
+            to: destination,
+        })
+    }
+
+    /// Parse a move written in algebraic notation, such as `a1b3`
+    #[staticmethod]
+    fn parse(text: &str) -> PyResult<Self> {
+        let split = text
+            .char_indices()
+            .skip(1)
+            .find(|&(index, c)| {
+                c.is_ascii_alphabetic() && text[..index].ends_with(|c: char| c.is_ascii_digit())
+            })
+            .map(|(index, _)| index)
+            .ok_or_else(|| PyValueError::new_err("The move is malformed"))?;
+
+        let (from, to) = text.split_at(split);
+
+        Ok(Self(Move {
+            from: parse_index(from).map_err(PyValueError::new_err)?,
+            to: parse_index(to).map_err(PyValueError::new_err)?,
+        }))
+    }
+
+    /// Get the departure point, as `(row, column)`
+    #[getter]
+    fn departure(&self) -> (usize, usize) {
+        self.0.from
+    }
+
+    /// Get the destination point, as `(row, column)`
+    #[getter]
+    fn destination(&self) -> (usize, usize) {
+        self.0.to
+    }
+
+    fn __str__(&self) -> String {
+        format!("{}{}", display_index(self.0.from), display_index(self.0.to))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Move.parse('{}')", self.__str__())
+    }
+}
+
+/// A game [board](Board)
+#[pyclass(name = "Board", module = "blobwars", eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PyBoard(Board);
+
+#[pymethods]
+impl PyBoard {
+    /// Parse a board written as [displayed](Board#impl-Display-for-Board), such as `B.#/..R`
+    #[new]
+    fn new(text: &str) -> PyResult<Self> {
+        text.parse().map(Self).map_err(PyValueError::new_err)
+    }
+
+    /// Generate a board with a given percentage of restricted cells
+    #[staticmethod]
+    fn generate(height: usize, width: usize, density: u8, seed: u64) -> Self {
+        Self(Board::generate(height, width, density, &mut Rng::new(seed)))
+    }
+
+    /// Get the number of rows
+    #[getter]
+    fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    /// Get the number of columns
+    #[getter]
+    fn width(&self) -> usize {
+        self.0.width()
+    }
+
+    /// Get the state of a cell, written `.` when free, `#` when restricted and `B` or `R` when occupied
+    fn cell(&self, row: usize, column: usize) -> PyResult<char> {
+        match self.0.get(row, column) {
+            Some(CellState::Free) => Ok('.'),
+            Some(CellState::Restricted) => Ok('#'),
+            Some(CellState::Player(Player::Blue)) => Ok('B'),
+            Some(CellState::Player(Player::Red)) => Ok('R'),
+            None => Err(PyIndexError::new_err("The cell is out of the board")),
+        }
+    }
+
+    /// Get the number of _blobs_ of a player
+    fn score(&self, player: PyPlayer) -> usize {
+        self.0.score().get(player.into())
+    }
+
+    /// List the legal moves of a player
+    fn moves(&self, player: PyPlayer) -> Vec<PyMove> {
+        self.0.moves(player.into()).map(PyMove).collect()
+    }
+
+    /// Play a move, raising a `ValueError` and leaving the board unchanged if it is illegal
+    fn play(&mut self, player: PyPlayer, chosen: PyMove) -> PyResult<()> {
+        let Move { from, to } = chosen.0;
+
+        if self.0.get(from.0, from.1) == Some(CellState::Player(player.into()))
+            && self.0.jump(from, to)
+        {
+            Ok(())
+        } else {
+            Err(PyValueError::new_err("The move is illegal"))
+        }
+    }
+
+    /// Check if the game is over
+    fn is_over(&self) -> bool {
+        self.0.outcome().is_some()
+    }
+
+    /// Get the winner, or `None` if the game is not over or is a draw
+    fn winner(&self) -> Option<PyPlayer> {
+        match self.0.outcome()? {
+            Outcome::Win(player) => Some(player.into()),
+            Outcome::Draw => None,
+        }
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Board('{}')", self.0)
+    }
+}
+
+/// The [computer opponent](Ai)
+#[pyclass(name = "Engine", module = "blobwars")]
+#[derive(Clone, Debug)]
+pub struct PyEngine(Ai);
+
+#[pymethods]
+impl PyEngine {
+    /// Create a computer opponent from the names of its [personality](Personality) and of its [difficulty](Difficulty)
+    ///
+    /// The moves are picked at random among the best ones, from the given seed if any.
+    #[new]
+    #[pyo3(signature = (personality = "aggressive", difficulty = "normal", seed = None))]
+    fn new(personality: &str, difficulty: &str, seed: Option<u64>) -> PyResult<Self> {
+        let personality = personality
+            .parse::<Personality>()
+            .map_err(PyValueError::new_err)?;
+
+        let difficulty = difficulty
+            .parse::<Difficulty>()
+            .map_err(PyValueError::new_err)?;
+
+        let ai = match seed {
+            Some(seed) => Ai::with_seed(personality, seed),
+            None => Ai::new(personality),
+        };
+
+        Ok(Self(ai.with_difficulty(difficulty)))
+    }
+
+    /// Get the name of the personality
+    #[getter]
+    fn personality(&self) -> String {
+        self.0.personality().to_string()
+    }
+
+    /// Get the name of the difficulty level
+    #[getter]
+    fn difficulty(&self) -> String {
+        self.0.difficulty().to_string()
+    }
+
+    /// Choose the move of a player, or `None` if the player has to pass
+    ///
+    /// The other Python threads keep running during the search.
+    fn choose(&mut self, py: Python<'_>, board: &PyBoard, player: PyPlayer) -> Option<PyMove> {
+        let board = &board.0;
+        let ai = &mut self.0;
+
+        py.detach(|| ai.choose(board, player.into())).map(PyMove)
+    }
+}
+
+/// The `blobwars` Python module
+#[pymodule]
+fn blobwars(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyBoard>()?;
+    module.add_class::<PyEngine>()?;
+    module.add_class::<PyMove>()?;
+    module.add_class::<PyPlayer>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module() {
+        Python::initialize();
+
+        Python::attach(|py| {
+            let module = PyModule::new(py, "blobwars").unwrap();
+            blobwars(&module).unwrap();
+
+            py.import("sys")
+                .and_then(|sys| sys.getattr("modules"))
+                .and_then(|modules| modules.set_item("blobwars", module))
+                .unwrap();
+
+            py.run(
+                cr#"
+from blobwars import Board, Engine, Move, Player
+
+board = Board("R.B")
+assert (board.height, board.width) == (1, 3)
+assert board.moves(Player.Red) == [Move((0, 0), (0, 1))]
+assert str(Move.parse("a1b1")) == "a1b1"
+
+engine = Engine("aggressive", "hard", seed=0)
+chosen = engine.choose(board, Player.Red)
+assert chosen == Move.parse("a1b1")
+
+try:
+    board.play(Player.Blue, chosen)
+    assert False
+except ValueError:
+    pass
+
+board.play(Player.Red, chosen)
+assert board.cell(0, 2) == "R" and board.score(Player.Red) == 3
+assert board.is_over() and board.winner() == Player.Red
+assert engine.choose(board, Player.Red) is None
+assert Player.Red.opponent() == Player.Blue
+
+try:
+    Engine("unknown")
+    assert False
+except ValueError:
+    pass
+"#,
+                None,
+                None,
+            )
+            .unwrap();
+        });
+    }
+}
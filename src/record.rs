@@ -0,0 +1,318 @@
+//! The implementation of [`GameRecord`]
+
+use crate::{
+    config::Config,
+    game::{Board, CellState, Move, Player},
+};
+
+pub use crate::game::{display_column, display_index, parse_index};
+
+use std::{
+    fmt::{Display, Formatter},
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+};
+
+/// The record of a game
+///
+/// A record holds the initial [board](Board) and every ply of the game, a ply being either a [move](Move) or a pass.
+/// It is persisted in a [file](Self::path) using a subset of the TOML format where the moves are written in algebraic notation (`a1b3`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameRecord {
+    /// The [board](Board) before the first ply
+    pub board: Board,
+
+    /// The [player](Player) who plays the first ply
+    pub first: Player,
+
+    /// The plies of the game; `None` stands for a pass
+    pub plies: Vec<Option<Move>>,
+}
+
+impl GameRecord {
+    /// Create a new [`GameRecord`] without any ply
+    ///
+    /// # Parameters
+    ///
+    /// - `board` - The [board](Board) before the first ply
+    /// - `first` - The [player](Player) who plays the first ply
+    pub fn new(board: Board, first: Player) -> Self {
+        Self {
+            board,
+            first,
+            plies: Vec::new(),
+        }
+    }
+
+    /// Get the path of the file holding the last finished game
+    ///
+    /// The file is located next to the [configuration file](Config::path).
+    pub fn path() -> Option<PathBuf> {
+        Config::path().map(|path| path.with_file_name("last_game.toml"))
+    }
+
+    /// Load the last finished game, if any
+    pub fn load() -> Option<Self> {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| content.parse().ok())
+    }
+
+    /// Save the record as the last finished game
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or(io::Error::new(
+            ErrorKind::NotFound,
+            "No configuration directory was found",
+        ))?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, self.to_string())
+    }
+
+    /// Get the [board](Board) and the [player](Player) to move after some plies
+    ///
+    /// # Parameter
+    ///
+    /// - `ply` - The number of plies played; it is clamped to the length of the game
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Move, Player::*},
+    ///     record::GameRecord,
+    /// };
+    ///
+    /// let mut record = GameRecord::new(Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]), Red);
+    /// record.plies.push(Some(Move { from: (0, 0), to: (0, 1) }));
+    ///
+    /// assert_eq!(record.position(0), (record.board.clone(), Red));
+    /// assert_eq!(record.position(1).0.get(0, 1), Some(Player(Red)));
+    /// assert_eq!(record.position(1).1, Blue);
+    /// ```
+    pub fn position(&self, ply: usize) -> (Board, Player) {
+        let mut board = self.board.clone();
+        let mut player = self.first;
+
+        for chosen in self.plies.iter().take(ply).flatten() {
+            board.jump(chosen.from, chosen.to);
+        }
+
+        for _ in 0..ply.min(self.plies.len()) {
+            player = -player;
+        }
+
+        (board, player)
+    }
+}
+
+/// Write a ply in algebraic notation
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::{game::Move, record::display_ply};
+///
+/// assert_eq!(display_ply(Some(Move { from: (0, 0), to: (1, 2) })), "a1c2");
+/// assert_eq!(display_ply(None), "pass");
+/// ```
+pub fn display_ply(ply: Option<Move>) -> String {
+    match ply {
+        Some(Move { from, to }) => format!("{}{}", display_index(from), display_index(to)),
+        None => "pass".to_owned(),
+    }
+}
+
+/// Read a ply written in algebraic notation
+///
+/// A ply is either a [move](Move) written as its departure and destination points (`a1b3`) or `pass`.
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::{game::Move, record::parse_ply};
+///
+/// assert_eq!(parse_ply("a1b3"), Ok(Some(Move { from: (0, 0), to: (2, 1) })));
+/// assert_eq!(parse_ply("pass"), Ok(None));
+/// ```
+pub fn parse_ply(s: &str) -> Result<Option<Move>, &'static str> {
+    if s == "pass" {
+        return Ok(None);
+    }
+
+    // The destination starts with the first letter following a digit
+    let split = s
+        .char_indices()
+        .zip(s.chars().skip(1))
+        .find(|&((_, c), next)| c.is_ascii_digit() && next.is_ascii_lowercase())
+        .map(|((i, _), _)| i + 1)
+        .ok_or("A move is malformed")?;
+
+    Ok(Some(Move {
+        from: parse_index(&s[..split])?,
+        to: parse_index(&s[split..])?,
+    }))
+}
+
+impl Display for GameRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let plies = self
+            .plies
+            .iter()
+            .map(|&ply| display_ply(ply))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        writeln!(f, "height = {}", self.board.height())?;
+        writeln!(f, "width = {}", self.board.width())?;
+        writeln!(f, "board = \"{}\"", self.board)?;
+        writeln!(f, "first = \"{}\"", self.first)?;
+        writeln!(f, "plies = \"{plies}\"")
+    }
+}
+
+impl FromStr for GameRecord {
+    type Err = &'static str;
+
+    /// Parse a game record
+    ///
+    /// Every move is checked against the rules of the game.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::record::GameRecord;
+    ///
+    /// let record = "height = 1\nwidth = 3\nboard = \"R.B\"\nfirst = \"Red\"\nplies = \"a1b1 pass\"";
+    /// assert_eq!(record.parse::<GameRecord>().map(|record| record.plies.len()), Ok(2));
+    ///
+    /// let illegal = "height = 1\nwidth = 3\nboard = \"R.B\"\nfirst = \"Blue\"\nplies = \"a1b1\"";
+    /// assert!(illegal.parse::<GameRecord>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mut height, mut width, mut board, mut first, mut plies) = (0, 0, "", None, "");
+
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or("A line is not a `key = value` pair")?;
+
+            let value = value.trim().trim_matches('"');
+
+            match key.trim() {
+                "height" => height = value.parse().map_err(|_| "The height is invalid")?,
+                "width" => width = value.parse().map_err(|_| "The width is invalid")?,
+                "board" => board = value,
+                "plies" => plies = value,
+
+                "first" => first = Some(value.parse()?),
+
+                _ => (),
+            }
+        }
+
+        let board = board.parse::<Board>()?;
+
+        if board.height() != height || board.width() != width {
+            return Err("The board does not match its size");
+        }
+
+        let mut record = Self::new(board, first.ok_or("The first player is missing")?);
+
+        let (mut board, mut player) = (record.board.clone(), record.first);
+
+        for ply in plies.split_whitespace() {
+            let ply = parse_ply(ply)?;
+
+            if let Some(Move { from, to }) = ply
+                && (board.get(from.0, from.1) != Some(CellState::Player(player))
+                    || !board.jump(from, to))
+            {
+                return Err("A move is illegal");
+            }
+
+            record.plies.push(ply);
+            player = -player;
+        }
+
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::CellState::*;
+    use crate::game::Player::*;
+
+    #[test]
+    fn round_trip() {
+        #[rustfmt::skip]
+        let board = vec![
+            Player(Blue), Free,       Free,
+            Free,         Restricted, Free,
+            Free,         Free,       Player(Red),
+        ];
+
+        let mut record = GameRecord::new(Board::new(3, 3, board), Blue);
+
+        record.plies = vec![
+            Some(Move {
+                from: (0, 0),
+                to: (1, 0),
+            }),
+            None,
+            Some(Move {
+                from: (1, 0),
+                to: (2, 1),
+            }),
+        ];
+
+        pretty_assertions::assert_eq!(record.to_string().parse(), Ok(record));
+    }
+
+    #[test]
+    fn wide_board() {
+        let mut cells = vec![Free; 30];
+        cells[0] = Player(Blue);
+        cells[29] = Player(Red);
+
+        let mut record = GameRecord::new(Board::new(1, 30, cells), Blue);
+
+        record.plies = vec![
+            Some(Move {
+                from: (0, 0),
+                to: (0, 1),
+            }),
+            Some(Move {
+                from: (0, 29),
+                to: (0, 27),
+            }),
+        ];
+
+        pretty_assertions::assert_eq!(display_ply(record.plies[1]), "ad1ab1");
+        pretty_assertions::assert_eq!(record.to_string().parse(), Ok(record));
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(
+            "height = 1\nwidth = 2\nboard = \"R\"\nfirst = \"Red\""
+                .parse::<GameRecord>()
+                .is_err()
+        );
+        assert!(parse_ply("a1").is_err());
+        assert!(parse_ply("aa1").is_err());
+        assert!(parse_index("a0").is_err());
+        assert!(parse_index("1").is_err());
+    }
+}
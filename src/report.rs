@@ -0,0 +1,159 @@
+//! The implementation of [`BugReport`]
+//!
+//! A bug report gathers everything needed to reproduce an error: the version, the configuration, the game in progress and the last commands.
+
+use crate::{Command, config::Config, record::GameRecord, save::SavedGame};
+
+use std::{
+    fmt::{Display, Formatter},
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A bundle describing the state of the application when an error occurred
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BugReport {
+    /// The error which occurred
+    pub error: String,
+
+    /// The [configuration](Config) in use
+    pub config: Config,
+
+    /// The [record](GameRecord) of the game in progress, if any
+    pub record: Option<GameRecord>,
+
+    /// The current position of the game in progress, if any
+    pub position: Option<String>,
+
+    /// The last [commands](Command) handled, the most recent last
+    pub commands: Vec<Command>,
+}
+
+impl BugReport {
+    /// Get the directory where the bug reports are written
+    ///
+    /// The directory is located next to the [save directory](SavedGame::directory).
+    pub fn directory() -> Option<PathBuf> {
+        SavedGame::directory().map(|directory| directory.with_file_name("reports"))
+    }
+
+    /// Write the report to a new file of the [report directory](Self::directory)
+    ///
+    /// # Return
+    ///
+    /// The path of the written file
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let directory = Self::directory().ok_or(io::Error::new(
+            ErrorKind::NotFound,
+            "No data directory was found",
+        ))?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        let path = directory.join(format!("report-{timestamp}.txt"));
+
+        self.write(&path)?;
+        Ok(path)
+    }
+
+    /// Write the report to a given file
+    ///
+    /// The missing parent directories are created.
+    ///
+    /// # Parameter
+    ///
+    /// - `path` - The path of the file
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, self.to_string())
+    }
+}
+
+impl Display for BugReport {
+    /// Write the report
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{Command, config::Config, report::BugReport};
+    ///
+    /// let report = BugReport {
+    ///     error: "Broken pipe".to_owned(),
+    ///     config: Config::default(),
+    ///     record: None,
+    ///     position: Some("R.B".to_owned()),
+    ///     commands: vec![Command::Select, Command::Left],
+    /// };
+    ///
+    /// let report = report.to_string();
+    ///
+    /// assert!(report.contains("error = \"Broken pipe\""));
+    /// assert!(report.contains("position = \"R.B\""));
+    /// assert!(report.contains("commands = \"select left\""));
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let commands = self
+            .commands
+            .iter()
+            .map(Command::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        writeln!(f, "# blobwars bug report")?;
+        writeln!(f, "version = \"{}\"", env!("CARGO_PKG_VERSION"))?;
+        writeln!(f, "error = \"{}\"", self.error.escape_default())?;
+        writeln!(f, "commands = \"{commands}\"")?;
+
+        if let Some(position) = &self.position {
+            writeln!(f, "position = \"{position}\"")?;
+        }
+
+        writeln!(f, "\n[config]\n{}", self.config)?;
+
+        if let Some(record) = &self.record {
+            writeln!(f, "[game]\n{record}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write() {
+        let path = std::env::temp_dir()
+            .join(format!("blobwars-report-{}", std::process::id()))
+            .join("report.txt");
+
+        let report = BugReport {
+            error: "line\nbreak".to_owned(),
+            config: Config::default(),
+            record: Some(
+                "height = 1\nwidth = 3\nboard = \"R.B\"\nfirst = \"Red\"\nplies = \"a1b1\""
+                    .parse()
+                    .unwrap(),
+            ),
+            position: Some("RRB".to_owned()),
+            commands: Vec::new(),
+        };
+
+        report.write(&path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        assert!(content.contains("error = \"line\\nbreak\""));
+        assert!(content.contains("[game]\nheight = 1\n"));
+        assert!(content.contains("plies = \"a1b1\""));
+    }
+}
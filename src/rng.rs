@@ -0,0 +1,59 @@
+//! The implementation of [`Rng`]
+
+#[cfg(feature = "std")]
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+/// A small pseudorandom number generator
+///
+/// This is an implementation of _xorshift64*_; it is not suitable for cryptographic purposes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// Create a new [`Rng`] from a seed
+    pub fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    /// Generate a random [`u64`]
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Generate a random [`f64`] in `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Derive a new [`Rng`] from this one
+    ///
+    /// The derived generator can be handed over to another component while the whole sequence stays reproducible from a single seed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::rng::Rng;
+    ///
+    /// let mut first = Rng::new(42);
+    /// let mut second = Rng::new(42);
+    ///
+    /// assert_eq!(first.fork().next_u64(), second.fork().next_u64());
+    /// ```
+    pub fn fork(&mut self) -> Self {
+        Self::new(self.next_u64())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for Rng {
+    /// Create a new [`Rng`] with a random seed
+    fn default() -> Self {
+        Self::new(RandomState::new().build_hasher().finish())
+    }
+}
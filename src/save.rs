@@ -0,0 +1,391 @@
+//! The implementation of [`SavedGame`]
+
+use crate::{
+    ai::{Ai, Difficulty, Personality},
+    clock::Clock,
+    game::Player,
+    hooks::Hooks,
+    record::GameRecord,
+    rng::Rng,
+    widgets::board::BoardState,
+};
+
+use directories::ProjectDirs;
+use std::{
+    fmt::{Display, Formatter},
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// A game in progress saved to be resumed later
+///
+/// A saved game holds the [record](GameRecord) of the game, from which the position is recomputed, along with the state of the [clock](Clock) and of the computer opponent.
+/// It is persisted in the [save directory](Self::directory) using the same subset of the TOML format as the [records](GameRecord).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SavedGame {
+    /// The [record](GameRecord) of the game
+    pub record: GameRecord,
+
+    /// The [clock](Clock) of the game, if any
+    pub clock: Option<Clock>,
+
+    /// The [player](Player), the [personality](Personality) and the [difficulty](Difficulty) of the computer opponent, if any
+    pub opponent: Option<(Player, Personality, Difficulty)>,
+}
+
+/// A [saved game](SavedGame) found in the [save directory](SavedGame::directory)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SaveFile {
+    /// The path of the file
+    pub path: PathBuf,
+
+    /// The time elapsed since the file was last written, if known
+    pub age: Option<Duration>,
+
+    /// The [saved game](SavedGame) held by the file
+    pub game: SavedGame,
+}
+
+impl SaveFile {
+    /// Get the name of the save, that is the name of its file without the extension
+    pub fn name(&self) -> String {
+        self.path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+impl SavedGame {
+    /// Get the directory holding the saved games
+    ///
+    /// The directory is located in the data directory of the platform, such as `$XDG_DATA_HOME/blobwars/saves` on Linux,
+    /// `~/Library/Application Support/blobwars/saves` on macOS or `%APPDATA%\blobwars\data\saves` on Windows.
+    pub fn directory() -> Option<PathBuf> {
+        ProjectDirs::from("", "", "blobwars")
+            .map(|directories| directories.data_dir().join("saves"))
+    }
+
+    /// Get the path of the file holding the snapshot of the game in progress
+    ///
+    /// The file is located next to the [save directory](Self::directory), so that it is not listed among the saves.
+    pub fn autosave_path() -> Option<PathBuf> {
+        Self::directory().map(|directory| directory.with_file_name("autosave.toml"))
+    }
+
+    /// Read a saved game from a file
+    ///
+    /// # Parameter
+    ///
+    /// - `path` - The path of the file
+    pub fn read(path: &Path) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|error| io::Error::new(ErrorKind::InvalidData, error))
+    }
+
+    /// Write the saved game to a file
+    ///
+    /// # Parameter
+    ///
+    /// - `path` - The path of the file; its parent directories are created if needed
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, self.to_string())
+    }
+
+    /// Save the game to a new file of the [save directory](Self::directory)
+    ///
+    /// # Return
+    ///
+    /// The path of the written file
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let directory = Self::directory().ok_or(io::Error::new(
+            ErrorKind::NotFound,
+            "No data directory was found",
+        ))?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        let path = directory.join(format!("save-{timestamp}.toml"));
+
+        self.write(&path)?;
+        Ok(path)
+    }
+
+    /// List the [saved games](SaveFile) of the [save directory](Self::directory)
+    ///
+    /// The most recent saves come first; the malformed files are skipped.
+    pub fn list() -> Vec<SaveFile> {
+        let Some(entries) = Self::directory().and_then(|directory| fs::read_dir(directory).ok())
+        else {
+            return Vec::new();
+        };
+
+        let mut saves = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "toml")
+            })
+            .filter_map(|path| {
+                let game = Self::read(&path).ok()?;
+
+                let age = fs::metadata(&path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .and_then(|modified| modified.elapsed().ok());
+
+                Some(SaveFile { path, age, game })
+            })
+            .collect::<Vec<_>>();
+
+        saves.sort_by_key(|save| save.age.unwrap_or(Duration::MAX));
+        saves
+    }
+
+    /// Create the [state](BoardState) of the board resuming the saved game
+    ///
+    /// # Parameter
+    ///
+    /// - `rng` - The [random number generator](Rng) the ones of the board and of the computer opponent are derived from
+    pub fn into_state(self, rng: &mut Rng) -> BoardState {
+        self.into_state_with(rng, &Hooks::default())
+    }
+
+    /// Create the [state](BoardState) of the saved game, the computer opponent playing the moves chosen by the [hooks](Hooks) if any
+    ///
+    /// # Parameters
+    ///
+    /// - `rng` - The [random number generator](Rng) the ones of the board and of the computer opponent are derived from
+    /// - `hooks` - The [hooks](Hooks) of the computer opponent
+    pub fn into_state_with(self, rng: &mut Rng, hooks: &Hooks) -> BoardState {
+        let mut state = BoardState::resume(self.record).with_rng(rng.fork());
+
+        if let Some(clock) = self.clock {
+            state = state.with_clock(clock);
+        }
+
+        if let Some((player, personality, difficulty)) = self.opponent {
+            let opponent = Ai::new(personality)
+                .with_rng(rng.fork())
+                .with_difficulty(difficulty)
+                .with_hooks(hooks.clone());
+
+            state = state.with_opponent_as(player, opponent);
+        }
+
+        state
+    }
+}
+
+impl From<&BoardState> for SavedGame {
+    fn from(value: &BoardState) -> Self {
+        Self {
+            record: value.record().clone(),
+            clock: value.clock().copied(),
+            opponent: value
+                .opponent_player()
+                .zip(value.opponent())
+                .zip(value.difficulty())
+                .map(|((player, personality), difficulty)| (player, personality, difficulty)),
+        }
+    }
+}
+
+impl Display for SavedGame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.record)?;
+
+        if let Some(clock) = self.clock {
+            writeln!(f, "clock = \"{}\"", clock.control())?;
+            writeln!(
+                f,
+                "blue_time = {}",
+                clock.remaining(Player::Blue).as_millis()
+            )?;
+            writeln!(f, "red_time = {}", clock.remaining(Player::Red).as_millis())?;
+        }
+
+        if let Some((player, personality, difficulty)) = self.opponent {
+            writeln!(f, "computer = \"{player}\"")?;
+            writeln!(f, "opponent = \"{personality}\"")?;
+            writeln!(f, "difficulty = \"{difficulty}\"")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for SavedGame {
+    type Err = &'static str;
+
+    /// Parse a saved game
+    ///
+    /// The [record](GameRecord) is parsed from the same text, so any record is a valid saved game without clock nor opponent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{ai::Personality, game::Player, save::SavedGame};
+    ///
+    /// let saved = "height = 1\nwidth = 3\nboard = \"R.B\"\nfirst = \"Red\"\nplies = \"a1b1\"\n\
+    ///     computer = \"Blue\"\nopponent = \"Defensive\"\ndifficulty = \"Hard\"";
+    ///
+    /// let saved = saved.parse::<SavedGame>().unwrap();
+    ///
+    /// assert_eq!(saved.record.plies.len(), 1);
+    /// assert_eq!(saved.clock, None);
+    /// assert_eq!(saved.opponent.map(|(player, personality, _)| (player, personality)), Some((Player::Blue, Personality::Defensive)));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let record = s.parse()?;
+
+        let (mut control, mut blue, mut red) = (None, None, None);
+        let (mut computer, mut personality, mut difficulty) = (None, None, None);
+
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or("A line is not a `key = value` pair")?;
+
+            let value = value.trim().trim_matches('"');
+
+            match key.trim() {
+                "clock" => control = Some(value.parse()?),
+                "blue_time" => blue = Some(parse_millis(value)?),
+                "red_time" => red = Some(parse_millis(value)?),
+                "computer" => computer = Some(value.parse()?),
+                "opponent" => personality = Some(value.parse()?),
+                "difficulty" => difficulty = Some(value.parse()?),
+
+                _ => (),
+            }
+        }
+
+        let clock = control.map(|control| {
+            let mut clock = Clock::new(control);
+
+            if let Some(blue) = blue {
+                clock = clock.with_remaining(Player::Blue, blue);
+            }
+
+            if let Some(red) = red {
+                clock = clock.with_remaining(Player::Red, red);
+            }
+
+            clock
+        });
+
+        let opponent = match (computer, personality) {
+            (Some(player), Some(personality)) => {
+                Some((player, personality, difficulty.unwrap_or_default()))
+            }
+
+            (None, None) => None,
+            _ => return Err("The computer opponent is incomplete"),
+        };
+
+        Ok(Self {
+            record,
+            clock,
+            opponent,
+        })
+    }
+}
+
+fn parse_millis(value: &str) -> Result<Duration, &'static str> {
+    value
+        .parse()
+        .map(Duration::from_millis)
+        .map_err(|_| "A remaining time is invalid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        clock::TimeControl,
+        game::{Board, CellState, Move},
+    };
+
+    #[test]
+    fn round_trip() {
+        let mut record = GameRecord::new(
+            Board::new(
+                2,
+                2,
+                vec![
+                    CellState::Player(Player::Blue),
+                    CellState::Free,
+                    CellState::Free,
+                    CellState::Player(Player::Red),
+                ],
+            ),
+            Player::Blue,
+        );
+
+        record.plies.push(Some(Move {
+            from: (0, 0),
+            to: (0, 1),
+        }));
+
+        let saved = SavedGame {
+            record,
+            clock: Some(
+                Clock::new(TimeControl::PerMove(Duration::from_secs(10)))
+                    .with_remaining(Player::Blue, Duration::from_millis(4321)),
+            ),
+            opponent: Some((Player::Red, Personality::Territorial, Difficulty::Easy)),
+        };
+
+        pretty_assertions::assert_eq!(saved.to_string().parse(), Ok(saved));
+    }
+
+    #[test]
+    fn into_state() {
+        let mut record = GameRecord::new(
+            Board::new(
+                1,
+                4,
+                vec![
+                    CellState::Player(Player::Blue),
+                    CellState::Free,
+                    CellState::Free,
+                    CellState::Player(Player::Red),
+                ],
+            ),
+            Player::Blue,
+        );
+
+        record.plies.push(Some(Move {
+            from: (0, 0),
+            to: (0, 1),
+        }));
+
+        let saved = SavedGame {
+            record,
+            clock: None,
+            opponent: None,
+        };
+
+        let state = saved.clone().into_state(&mut Rng::new(1));
+
+        pretty_assertions::assert_eq!(state.current_player(), Player::Red);
+        pretty_assertions::assert_eq!(SavedGame::from(&state), saved);
+    }
+}
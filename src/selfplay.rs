@@ -0,0 +1,362 @@
+//! The implementation of [`SelfPlay`] and of its [samples](Sample)
+//!
+//! Self-play games are played by an [engine](Engine) against itself and every position is written with the chosen ply,
+//! its evaluation and the final result, as data to tune the evaluation or to train neural networks.
+
+use crate::{
+    game::{Board, CellState, Move, Outcome, Player},
+    record::display_ply,
+    tournament::{Engine, Tournament},
+};
+
+use std::{cmp::Ordering, thread};
+
+/// A position of a self-play game
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sample {
+    /// The index of the game
+    pub game: usize,
+
+    /// The index of the ply in the game
+    pub ply: usize,
+
+    /// The position
+    pub board: Board,
+
+    /// The [player](Player) to move
+    pub player: Player,
+
+    /// The chosen ply; `None` stands for a pass
+    pub chosen: Option<Move>,
+
+    /// The [evaluation](crate::ai::Weights::evaluate) of the chosen move by the engine, `0` for a pass
+    pub score: i32,
+
+    /// The result of the game for the player to move: `1` for a win, `0` for a draw and `-1` for a loss
+    pub result: i8,
+}
+
+/// Self-play games of an [engine](Engine)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelfPlay {
+    engine: Engine,
+    maps: Vec<Board>,
+    games: usize,
+    threads: usize,
+    seed: u64,
+}
+
+impl SelfPlay {
+    /// Create a new [`SelfPlay`]
+    ///
+    /// A game is played on each map by default.
+    ///
+    /// # Parameters
+    ///
+    /// - `engine` - The [engine](Engine) playing both sides
+    /// - `maps` - The boards the games are played on, in turn
+    pub fn new(engine: Engine, maps: Vec<Board>) -> Self {
+        Self {
+            engine,
+            games: maps.len(),
+            maps,
+            threads: 1,
+            seed: 1,
+        }
+    }
+
+    /// Set the number of games
+    pub fn with_games(self, games: usize) -> Self {
+        Self { games, ..self }
+    }
+
+    /// Set the number of games played in parallel
+    pub fn with_threads(self, threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+            ..self
+        }
+    }
+
+    /// Set the seed the random choices of the engine are drawn from
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    /// Play the games
+    ///
+    /// A game is stopped after [`Tournament::MAX_PLIES`] plies and then won by the player with the highest score.
+    ///
+    /// # Return
+    ///
+    /// The [samples](Sample) of every position, sorted by game and by ply.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{game::Board, selfplay::SelfPlay, tournament::Engine};
+    ///
+    /// let board: Board = "B..R".parse().unwrap();
+    /// let samples = SelfPlay::new(Engine::default(), vec![board]).with_games(2).run();
+    ///
+    /// assert_eq!(samples.first().map(|sample| sample.game), Some(0));
+    /// assert_eq!(samples.last().map(|sample| sample.game), Some(1));
+    /// ```
+    pub fn run(&self) -> Vec<Sample> {
+        if self.maps.is_empty() {
+            return Vec::new();
+        }
+
+        thread::scope(|scope| {
+            let workers = (0..self.threads)
+                .map(|worker| {
+                    scope.spawn(move || {
+                        (worker..self.games)
+                            .step_by(self.threads)
+                            .flat_map(|game| self.play(game))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let mut samples = workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap_or_default())
+                .collect::<Vec<_>>();
+
+            samples.sort_by_key(|sample| (sample.game, sample.ply));
+            samples
+        })
+    }
+
+    fn play(&self, game: usize) -> Vec<Sample> {
+        let mut board = self.maps[game % self.maps.len()].clone();
+        let mut player = Player::Blue;
+        let mut ai = self.engine.ai(self.seed.wrapping_add(game as u64));
+        let weights = self.engine.personality.weights();
+        let mut samples = Vec::new();
+
+        for ply in 0..Tournament::MAX_PLIES {
+            if board.outcome().is_some() {
+                break;
+            }
+
+            let chosen = ai.choose(&board, player);
+
+            let score = chosen
+                .and_then(|chosen| weights.evaluate(&board, player, chosen))
+                .unwrap_or_default();
+
+            samples.push(Sample {
+                game,
+                ply,
+                board: board.clone(),
+                player,
+                chosen,
+                score,
+                result: 0,
+            });
+
+            if let Some(chosen) = chosen {
+                board.jump(chosen.from, chosen.to);
+            }
+
+            player = -player;
+        }
+
+        let score = board.score();
+
+        let outcome = board.outcome().unwrap_or(match score.blue.cmp(&score.red) {
+            Ordering::Greater => Outcome::Win(Player::Blue),
+            Ordering::Less => Outcome::Win(Player::Red),
+            Ordering::Equal => Outcome::Draw,
+        });
+
+        for sample in &mut samples {
+            sample.result = match outcome {
+                Outcome::Win(winner) if winner == sample.player => 1,
+                Outcome::Win(_) => -1,
+                Outcome::Draw => 0,
+            };
+        }
+
+        samples
+    }
+}
+
+/// Write [samples](Sample) as CSV
+///
+/// The boards are written in the [notation of the maps](Board#impl-Display-for-Board) and the plies in algebraic notation.
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::{game::{Board, Move, Player}, selfplay::{Sample, to_csv}};
+///
+/// let sample = Sample {
+///     game: 0,
+///     ply: 0,
+///     board: "B..R".parse().unwrap(),
+///     player: Player::Blue,
+///     chosen: Some(Move { from: (0, 0), to: (0, 1) }),
+///     score: 3,
+///     result: 1,
+/// };
+///
+/// assert_eq!(to_csv(&[sample]), "game,ply,board,player,move,score,result\n0,0,B..R,Blue,a1b1,3,1\n");
+/// ```
+pub fn to_csv(samples: &[Sample]) -> String {
+    let mut csv = "game,ply,board,player,move,score,result\n".to_owned();
+
+    for sample in samples {
+        csv += &format!(
+            "{},{},{},{},{},{},{}\n",
+            sample.game,
+            sample.ply,
+            sample.board,
+            sample.player,
+            display_ply(sample.chosen),
+            sample.score,
+            sample.result,
+        );
+    }
+
+    csv
+}
+
+/// Write [samples](Sample) in a compact binary format
+///
+/// The data starts with the magic bytes `BWSP` and the version `1`, followed by the samples, each one written as:
+///
+/// - the height and the width of the board, as two bytes;
+/// - the cells row by row, packed four per byte from the least significant bits: `0` for a free cell, `1` for a blue _blob_, `2` for a red one and `3` for a restricted cell;
+/// - the player to move, as a byte: `0` for blue and `1` for red;
+/// - the departure and destination cells of the chosen move, as two little-endian `u16` numbered row by row, both `0xffff` for a pass;
+/// - the score, as a little-endian `i32`;
+/// - the result, as an `i8`.
+pub fn to_binary(samples: &[Sample]) -> Vec<u8> {
+    let mut data = b"BWSP\x01".to_vec();
+
+    for sample in samples {
+        let width = sample.board.width();
+        data.extend([sample.board.height() as u8, width as u8]);
+
+        for cells in sample.board.iter().collect::<Vec<_>>().chunks(4) {
+            let packed = cells.iter().enumerate().fold(0, |packed, (i, cell)| {
+                let code = match cell {
+                    CellState::Free => 0,
+                    CellState::Player(Player::Blue) => 1,
+                    CellState::Player(Player::Red) => 2,
+                    CellState::Restricted => 3,
+                };
+
+                packed | code << (2 * i)
+            });
+
+            data.push(packed);
+        }
+
+        data.push(u8::from(sample.player == Player::Red));
+
+        let (from, to) = sample.chosen.map_or((u16::MAX, u16::MAX), |chosen| {
+            (
+                (chosen.from.0 * width + chosen.from.1) as u16,
+                (chosen.to.0 * width + chosen.to.1) as u16,
+            )
+        });
+
+        data.extend(from.to_le_bytes());
+        data.extend(to.to_le_bytes());
+        data.extend(sample.score.to_le_bytes());
+        data.extend(sample.result.to_le_bytes());
+    }
+
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn run() {
+        let maps = vec!["B...R/...../R...B".parse().unwrap()];
+        let selfplay = SelfPlay::new(Engine::default(), maps).with_games(3);
+
+        let samples = selfplay.run();
+        assert_eq!(samples, selfplay.clone().with_threads(2).run());
+
+        for game in 0..3 {
+            let plies = samples
+                .iter()
+                .filter(|sample| sample.game == game)
+                .collect::<Vec<_>>();
+
+            assert!(!plies.is_empty());
+            assert_eq!(plies[0].board.to_string(), "B...R/...../R...B");
+
+            // Both players get opposite results unless the game is drawn
+            let results = plies
+                .iter()
+                .map(|sample| (sample.player, sample.result))
+                .collect::<Vec<_>>();
+
+            for &(player, result) in &results {
+                for &(other, opposite) in &results {
+                    if player != other {
+                        assert_eq!(result, -opposite);
+                    }
+                }
+            }
+
+            // Each sample leads to the next one
+            for pair in plies.windows(2) {
+                let mut board = pair[0].board.clone();
+
+                if let Some(chosen) = pair[0].chosen {
+                    board.jump(chosen.from, chosen.to);
+                }
+
+                assert_eq!(board, pair[1].board);
+                assert_eq!(pair[1].player, -pair[0].player);
+            }
+        }
+    }
+
+    #[test]
+    fn binary() {
+        let samples = [
+            Sample {
+                game: 0,
+                ply: 0,
+                board: "B.#/..R".parse().unwrap(),
+                player: Player::Red,
+                chosen: Some(Move {
+                    from: (1, 2),
+                    to: (1, 1),
+                }),
+                score: -2,
+                result: -1,
+            },
+            Sample {
+                game: 0,
+                ply: 1,
+                board: "B".parse().unwrap(),
+                player: Player::Blue,
+                chosen: None,
+                score: 0,
+                result: 0,
+            },
+        ];
+
+        #[rustfmt::skip]
+        let expected = [
+            b'B', b'W', b'S', b'P', 1,
+            2, 3, 0b0011_0001, 0b1000, 1, 5, 0, 4, 0, 0xfe, 0xff, 0xff, 0xff, 0xff,
+            1, 1, 0b01, 0, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0, 0,
+        ];
+
+        assert_eq!(to_binary(&samples), expected);
+    }
+}
@@ -0,0 +1,570 @@
+//! The implementation of [`Server`]
+//!
+//! A server hosts several network games at once.
+//! Its clients speak the same [protocol](crate::net::NetMessage) as two instances of the game playing together: they [list](NetMessage::List) the open games, [create](NetMessage::Create) one or [join](NetMessage::Join) one by its name.
+//! Once a game is joined, the server generates its board and [referees](Referee) it, forwarding only the legal plies.
+//! The server can be reached through SSH by forcing the command of an SSH account to `blobwars-ssh`, which only lets the clients [create or join a game](crate::ssh::SshRequest).
+//! Each player is given its own identifier of the game; a player whose connection is lost may connect again and [resume](NetMessage::Resume) the game with it for [a while](Connection::RECONNECT_TIMEOUT).
+
+use crate::{
+    config::Config,
+    game::{Board, CellState, Move, Player},
+    net::{Connection, NetMessage},
+    record::GameRecord,
+    rng::Rng,
+};
+
+use std::{
+    collections::{BTreeMap, btree_map::Entry},
+    io,
+    net::{SocketAddr, TcpListener, ToSocketAddrs},
+    sync::{
+        Arc, Mutex, MutexGuard, PoisonError,
+        mpsc::{self, Receiver, Sender},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+type Lobby = Arc<Mutex<BTreeMap<String, Connection>>>;
+
+/// The running games, by the identifiers given to their players, receiving the new connections of the players resuming them along with their numbers of plies
+type Seats = Arc<Mutex<BTreeMap<String, Sender<(Connection, usize)>>>>;
+
+/// A server hosting several network games
+#[derive(Debug)]
+pub struct Server {
+    listener: TcpListener,
+    config: Config,
+    lobby: Lobby,
+    seats: Seats,
+}
+
+impl Server {
+    /// The address listened on by default
+    pub const DEFAULT_ADDRESS: &str = "0.0.0.0:4444";
+
+    /// The duration waited when no player of a game has sent anything
+    const IDLE: Duration = Duration::from_millis(10);
+
+    /// Create a new [`Server`] listening on a given address
+    ///
+    /// # Parameter
+    ///
+    /// - `address` - The address listened on
+    pub fn bind<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(address)?,
+            config: Config::default(),
+            lobby: Lobby::default(),
+            seats: Seats::default(),
+        })
+    }
+
+    /// Generate the boards and apply the rules from a given [configuration](Config)
+    ///
+    /// Only the size and the density of the boards and whether a player may pass at will are used.
+    pub fn with_config(self, config: Config) -> Self {
+        Self { config, ..self }
+    }
+
+    /// Get the address actually listened on
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serve the clients until the listener fails
+    ///
+    /// Every client is served in its own thread.
+    pub fn run(&self) -> io::Result<()> {
+        loop {
+            let connection = match Connection::accept(&self.listener) {
+                Ok(connection) => connection,
+
+                Err(error)
+                    if matches!(
+                        error.kind(),
+                        io::ErrorKind::ConnectionAborted
+                            | io::ErrorKind::ConnectionReset
+                            | io::ErrorKind::NotConnected
+                    ) =>
+                {
+                    tracing::warn!("A client cannot be accepted: {error}");
+                    continue;
+                }
+
+                Err(error) => return Err(error),
+            };
+
+            let lobby = self.lobby.clone();
+            let seats = self.seats.clone();
+            let config = self.config;
+            let span = tracing::info_span!("client", peer = %connection.peer());
+
+            thread::spawn(move || {
+                let _span = span.entered();
+
+                if let Err(error) = serve(connection, &lobby, &seats, config) {
+                    tracing::warn!("A client has been lost: {error}");
+                }
+            });
+        }
+    }
+}
+
+/// Answer the requests of a client until it creates, joins or resumes a game
+fn serve(connection: Connection, lobby: &Lobby, seats: &Seats, config: Config) -> io::Result<()> {
+    loop {
+        match connection.recv()? {
+            NetMessage::List => {
+                let names = lock(lobby).keys().cloned().collect();
+                connection.send(&NetMessage::Games(names))?;
+            }
+
+            NetMessage::Create(name) => match lock(lobby).entry(name) {
+                Entry::Occupied(entry) => connection.send(&NetMessage::Refused(format!(
+                    "A game named {} is already open",
+                    entry.key()
+                )))?,
+
+                Entry::Vacant(entry) => {
+                    tracing::info!("{} opens the game {}", connection.peer(), entry.key());
+                    entry.insert(connection);
+                    return Ok(());
+                }
+            },
+
+            NetMessage::Join(name) => {
+                let creator = lock(lobby).remove(&name);
+
+                if let Some(creator) = creator {
+                    tracing::info!("{} joins the game {name}", connection.peer());
+                    return referee(config, seats, &name, creator, connection);
+                }
+
+                connection.send(&NetMessage::Refused(format!(
+                    "No open game is named {name}"
+                )))?;
+            }
+
+            NetMessage::Resume { game, plies } => {
+                let seat = lock(seats).get(&game).cloned();
+
+                if let Some(seat) = seat
+                    && seat.send((connection.clone(), plies)).is_ok()
+                {
+                    tracing::info!("{} resumes the game {game}", connection.peer());
+                    return Ok(());
+                }
+
+                connection.send(&NetMessage::Refused(format!(
+                    "No running game is identified by {game}"
+                )))?;
+            }
+
+            NetMessage::Bye => return Ok(()),
+            NetMessage::Refused(reason) => return Err(io::Error::other(reason)),
+
+            _ => connection.send(&NetMessage::Refused(
+                "Create or join a game first".to_owned(),
+            ))?,
+        }
+    }
+}
+
+/// A player of a game refereed by a [server](Server)
+struct Seat {
+    player: Player,
+    connection: Connection,
+    game: String,
+    resumed: Receiver<(Connection, usize)>,
+    lost: Option<Instant>,
+}
+
+impl Seat {
+    /// Send a message, the errors being left to the reader of the connection
+    ///
+    /// A message sent while the connection is lost is not needed since the game is resynchronized once resumed.
+    fn send(&self, message: &NetMessage) {
+        if let Err(error) = self.connection.send(message) {
+            tracing::debug!("A message to {} is dropped: {error}", self.player);
+        }
+    }
+
+    /// Resynchronize the player after a new connection, replaying the missed plies or sending the whole game
+    fn resync(&self, record: &GameRecord, free_pass: bool, known: usize) {
+        let plies = record.plies.len();
+
+        // A player may only be ahead by its own last ply, which was lost on the way
+        if known > plies + 1 {
+            self.send(&NetMessage::Start {
+                game: self.game.clone(),
+                player: self.player,
+                free_pass,
+                record: Box::new(record.clone()),
+            });
+
+            return;
+        }
+
+        self.send(&NetMessage::Resume {
+            game: self.game.clone(),
+            plies,
+        });
+
+        if known < plies {
+            self.send(&NetMessage::Replay {
+                from: known,
+                plies: record.plies[known..].to_vec(),
+            });
+        }
+    }
+}
+
+/// Play a game between two clients, the creator of the game playing first
+#[tracing::instrument(skip_all, fields(game = name))]
+fn referee(
+    config: Config,
+    seats: &Seats,
+    name: &str,
+    blue: Connection,
+    red: Connection,
+) -> io::Result<()> {
+    let mut rng = Rng::default();
+    let board = Board::generate(config.height, config.width, config.density, &mut rng);
+    let mut record = GameRecord::new(board.clone(), Player::Blue);
+    let mut players = Vec::new();
+    let mut senders = Vec::new();
+
+    for (player, connection) in [(Player::Blue, blue), (Player::Red, red)] {
+        let game = format!("{name}-{:x}", rng.next_u64());
+        let (sender, resumed) = mpsc::channel();
+
+        connection.send(&NetMessage::Start {
+            game: game.clone(),
+            player,
+            free_pass: config.free_pass,
+            record: Box::new(record.clone()),
+        })?;
+
+        senders.push((game.clone(), sender));
+
+        players.push(Seat {
+            player,
+            connection,
+            game,
+            resumed,
+            lost: None,
+        });
+    }
+
+    lock(seats).extend(senders);
+
+    let mut referee = Referee::new(board, Player::Blue).with_free_pass(config.free_pass);
+
+    'game: while referee.board().outcome().is_none()
+        || players.iter().any(|seat| seat.lost.is_some())
+    {
+        let mut idle = true;
+
+        for (this, that) in [(0, 1), (1, 0)] {
+            if let Ok((connection, known)) = players[this].resumed.try_recv() {
+                idle = false;
+
+                let seat = &mut players[this];
+                seat.connection = connection;
+                seat.lost = None;
+                seat.resync(&record, config.free_pass, known);
+            }
+
+            if let Some(since) = players[this].lost {
+                if since.elapsed() > Connection::RECONNECT_TIMEOUT {
+                    tracing::info!("{} has not resumed the game {name}", players[this].player);
+                    players[that].send(&NetMessage::Bye);
+                    break 'game;
+                }
+
+                continue;
+            }
+
+            let Some(message) = players[this].connection.try_recv() else {
+                continue;
+            };
+
+            idle = false;
+            let player = players[this].player;
+
+            let (from, plies) = match message {
+                NetMessage::Ply(ply) => (record.plies.len(), vec![ply]),
+                NetMessage::Replay { from, plies } => (from, plies),
+
+                NetMessage::Chat(_) => {
+                    players[that].send(&message);
+                    continue;
+                }
+
+                NetMessage::Bye if players[this].connection.is_lost() => {
+                    tracing::info!("The connection to {player} in the game {name} is lost");
+                    players[this].lost = Some(Instant::now());
+                    continue;
+                }
+
+                NetMessage::Resign | NetMessage::Bye => {
+                    players[that].send(&message);
+                    break 'game;
+                }
+
+                _ => continue,
+            };
+
+            for (index, ply) in (from..).zip(plies) {
+                match record.plies.get(index) {
+                    Some(&known) if known == ply => (),
+
+                    None if referee.play(player, ply) => {
+                        record.plies.push(ply);
+                        players[that].send(&NetMessage::Ply(ply));
+                    }
+
+                    _ => {
+                        players[this].send(&NetMessage::Refused("The ply is illegal".to_owned()));
+                        players[that].send(&NetMessage::Resign);
+                        break 'game;
+                    }
+                }
+            }
+        }
+
+        if idle {
+            thread::sleep(Server::IDLE);
+        }
+    }
+
+    let mut seats = lock(seats);
+
+    for seat in &players {
+        seats.remove(&seat.game);
+    }
+
+    Ok(())
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The authoritative state of a game played on a [server](Server)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Referee {
+    board: Board,
+    current_player: Player,
+    free_pass: bool,
+}
+
+impl Referee {
+    /// Create a new [`Referee`]
+    ///
+    /// # Parameters
+    ///
+    /// - `board` - The [board](Board) before the first ply
+    /// - `first` - The [player](Player) who plays the first ply
+    pub fn new(board: Board, first: Player) -> Self {
+        Self {
+            board,
+            current_player: first,
+            free_pass: false,
+        }
+    }
+
+    /// Allow the players to pass at will
+    pub fn with_free_pass(self, free_pass: bool) -> Self {
+        Self { free_pass, ..self }
+    }
+
+    /// Get the current [board](Board)
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Get the [player](Player) to move
+    pub fn current_player(&self) -> Player {
+        self.current_player
+    }
+
+    /// Play a ply if it is legal
+    ///
+    /// # Parameters
+    ///
+    /// - `player` - The [player](Player) who plays the ply
+    /// - `ply` - The ply; `None` stands for a pass
+    ///
+    /// # Return
+    ///
+    /// The returned value is `true` if the ply has been played; otherwise, it returns `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Move, Player::*},
+    ///     server::Referee,
+    /// };
+    ///
+    /// let mut referee = Referee::new("B..R".parse().unwrap(), Blue);
+    ///
+    /// assert!(!referee.play(Red, Some(Move { from: (0, 3), to: (0, 2) })));
+    /// assert!(!referee.play(Blue, None));
+    /// assert!(referee.play(Blue, Some(Move { from: (0, 0), to: (0, 1) })));
+    /// assert!(referee.play(Red, Some(Move { from: (0, 3), to: (0, 2) })));
+    /// ```
+    pub fn play(&mut self, player: Player, ply: Option<Move>) -> bool {
+        if player != self.current_player {
+            return false;
+        }
+
+        let legal = match ply {
+            Some(Move { from, to }) => {
+                self.board.get(from.0, from.1) == Some(CellState::Player(player))
+                    && self.board.jump(from, to)
+            }
+
+            None => self.free_pass || !self.board.can_play(player),
+        };
+
+        if legal {
+            self.current_player = -player;
+        }
+
+        legal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lobby() {
+        let server = Server::bind("127.0.0.1:0")
+            .unwrap()
+            .with_config(Config::default());
+
+        let address = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+
+        let creator = Connection::join(address).unwrap();
+        creator
+            .send(&NetMessage::Create("friday".to_owned()))
+            .unwrap();
+
+        let joiner = Connection::join(address).unwrap();
+
+        loop {
+            joiner.send(&NetMessage::List).unwrap();
+
+            if joiner.recv().unwrap() == NetMessage::Games(vec!["friday".to_owned()]) {
+                break;
+            }
+        }
+
+        joiner.send(&NetMessage::Join("blitz".to_owned())).unwrap();
+        assert!(matches!(joiner.recv().unwrap(), NetMessage::Refused(_)));
+
+        joiner.send(&NetMessage::Join("friday".to_owned())).unwrap();
+
+        let (NetMessage::Start { player, record, .. }, NetMessage::Start { .. }) =
+            (creator.recv().unwrap(), joiner.recv().unwrap())
+        else {
+            panic!("The game has not started");
+        };
+
+        pretty_assertions::assert_eq!(player, Player::Blue);
+
+        let ply = record.board.moves(Player::Blue).next();
+        creator.send(&NetMessage::Ply(ply)).unwrap();
+        pretty_assertions::assert_eq!(joiner.recv().unwrap(), NetMessage::Ply(ply));
+
+        creator.send(&NetMessage::Ply(None)).unwrap();
+        assert!(matches!(creator.recv().unwrap(), NetMessage::Refused(_)));
+        pretty_assertions::assert_eq!(joiner.recv().unwrap(), NetMessage::Resign);
+    }
+
+    #[test]
+    fn resume() {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let address = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+
+        let creator = Connection::join(address).unwrap();
+        creator
+            .send(&NetMessage::Create("monday".to_owned()))
+            .unwrap();
+
+        let joiner = Connection::join(address).unwrap();
+
+        loop {
+            joiner.send(&NetMessage::Join("monday".to_owned())).unwrap();
+
+            if matches!(joiner.recv().unwrap(), NetMessage::Start { .. }) {
+                break;
+            }
+        }
+
+        let NetMessage::Start { game, record, .. } = creator.recv().unwrap() else {
+            panic!("The game has not started");
+        };
+
+        let ply = record.board.moves(Player::Blue).next();
+        creator.send(&NetMessage::Ply(ply)).unwrap();
+        pretty_assertions::assert_eq!(joiner.recv().unwrap(), NetMessage::Ply(ply));
+
+        let back = Connection::join(address).unwrap();
+        back.send(&NetMessage::Resume {
+            game: game.clone(),
+            plies: 0,
+        })
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            back.recv().unwrap(),
+            NetMessage::Resume {
+                game: game.clone(),
+                plies: 1,
+            }
+        );
+
+        pretty_assertions::assert_eq!(
+            back.recv().unwrap(),
+            NetMessage::Replay {
+                from: 0,
+                plies: vec![ply],
+            }
+        );
+
+        let diverged = Connection::join(address).unwrap();
+        diverged
+            .send(&NetMessage::Resume {
+                game: game.clone(),
+                plies: 5,
+            })
+            .unwrap();
+
+        assert!(matches!(
+            diverged.recv().unwrap(),
+            NetMessage::Start {
+                player: Player::Blue,
+                ..
+            }
+        ));
+
+        let stranger = Connection::join(address).unwrap();
+        stranger
+            .send(&NetMessage::Resume {
+                game: "monday".to_owned(),
+                plies: 0,
+            })
+            .unwrap();
+
+        assert!(matches!(stranger.recv().unwrap(), NetMessage::Refused(_)));
+    }
+}
@@ -0,0 +1,417 @@
+//! The implementation of [`Snapshot`]
+//!
+//! A snapshot is an image of a position, written either as SVG or as PNG so that it can be shared outside the terminal.
+//! Both formats draw the same picture: a checkerboard of tiles with the _blobs_ as shaded discs,
+//! the restricted cells as squares and the free cells as dots, like the [`Images` render style](crate::widgets::board::RenderStyle::Images).
+
+use crate::{
+    game::{Board, CellState, Player},
+    graphics::{self, DISC, DOT, FRAME, HILL, Image, SQUARE},
+    pgn::PortableGame,
+    widgets::Theme,
+};
+
+use std::{
+    fmt::{Display, Formatter, Write},
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The colors of the tiles of the board, alternating as a checkerboard
+const TILES: [[u8; 3]; 2] = [[0x24, 0x24, 0x24], [0x2c, 0x2c, 0x2c]];
+
+/// The format of a [snapshot](Snapshot)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SnapshotFormat {
+    /// Scalable vector graphics
+    #[default]
+    Svg,
+
+    /// Portable network graphics
+    Png,
+}
+
+impl SnapshotFormat {
+    /// All the available formats
+    pub const ALL: [Self; 2] = [Self::Svg, Self::Png];
+
+    /// Get the extension of the files written in this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Png => "png",
+        }
+    }
+
+    /// Guess the format of a file from its extension
+    ///
+    /// # Parameter
+    ///
+    /// - `path` - The path of the file
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::snapshot::SnapshotFormat;
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(SnapshotFormat::from_path(Path::new("position.PNG")), Some(SnapshotFormat::Png));
+    /// assert_eq!(SnapshotFormat::from_path(Path::new("position.txt")), None);
+    /// ```
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extension().eq_ignore_ascii_case(extension))
+    }
+}
+
+impl Display for SnapshotFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Svg => write!(f, "SVG"),
+            Self::Png => write!(f, "PNG"),
+        }
+    }
+}
+
+impl FromStr for SnapshotFormat {
+    type Err = &'static str;
+
+    /// Parse a snapshot format
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::snapshot::SnapshotFormat;
+    ///
+    /// assert_eq!("png".parse(), Ok(SnapshotFormat::Png));
+    /// assert!("gif".parse::<SnapshotFormat>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.to_string().eq_ignore_ascii_case(s))
+            .ok_or("Unknown snapshot format")
+    }
+}
+
+/// An image of a position
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    board: Board,
+    theme: Theme,
+    cell_size: u32,
+}
+
+impl Snapshot {
+    /// The default size of a cell in pixels
+    pub const CELL_SIZE: u32 = 48;
+
+    /// Create a new [`Snapshot`]
+    ///
+    /// # Parameter
+    ///
+    /// - `board` - The position to draw
+    pub fn new(board: Board) -> Self {
+        Self {
+            board,
+            theme: Theme::default(),
+            cell_size: Self::CELL_SIZE,
+        }
+    }
+
+    /// Set the [theme](Theme) giving the colors of the cells
+    pub fn with_theme(self, theme: Theme) -> Self {
+        Self { theme, ..self }
+    }
+
+    /// Set the size of a cell in pixels
+    pub fn with_cell_size(self, cell_size: u32) -> Self {
+        Self {
+            cell_size: cell_size.max(1),
+            ..self
+        }
+    }
+
+    /// Get the width and the height of the image in pixels
+    pub fn size(&self) -> (u32, u32) {
+        (
+            self.board.width() as u32 * self.cell_size,
+            self.board.height() as u32 * self.cell_size,
+        )
+    }
+
+    /// Draw the position as SVG
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{game::Board, snapshot::Snapshot};
+    ///
+    /// let svg = Snapshot::new("B.#/..R".parse::<Board>().unwrap()).svg();
+    ///
+    /// assert!(svg.starts_with("<svg"));
+    /// assert_eq!(svg.matches("url(#blue)").count(), 1);
+    /// ```
+    pub fn svg(&self) -> String {
+        let (width, height) = self.size();
+        let half = self.cell_size as f32 / 2.;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n<defs>\n"
+        );
+
+        for player in [Player::Blue, Player::Red] {
+            let color = graphics::cell_rgb(CellState::Player(player), &self.theme);
+
+            // The stops follow the shading of the images along a radius from the highlight
+            let _ = writeln!(
+                svg,
+                "<radialGradient id=\"{}\" cx=\"0.325\" cy=\"0.325\" r=\"0.8\">{}</radialGradient>",
+                player.to_string().to_lowercase(),
+                [0., 0.45, 1.6]
+                    .map(|distance| format!(
+                        "<stop offset=\"{}\" stop-color=\"{}\"/>",
+                        distance / 1.6,
+                        hex(graphics::shade(color, distance - 0.35, -0.35))
+                    ))
+                    .concat()
+            );
+        }
+
+        svg.push_str("</defs>\n");
+
+        for i in 0..self.board.height() {
+            for j in 0..self.board.width() {
+                let Some(content) = self.board.get(i, j) else {
+                    continue;
+                };
+
+                let (x, y) = (j as u32 * self.cell_size, i as u32 * self.cell_size);
+                let (cx, cy) = (x as f32 + half, y as f32 + half);
+                let color = hex(graphics::cell_rgb(content, &self.theme));
+
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{size}\" height=\"{size}\" fill=\"{}\"/>",
+                    hex(TILES[(i + j) % 2]),
+                    size = self.cell_size,
+                );
+
+                if self.board.is_hill(i, j) {
+                    let width = (1. - FRAME) * half;
+
+                    let _ = writeln!(
+                        svg,
+                        "<rect x=\"{x}\" y=\"{y}\" width=\"{side}\" height=\"{side}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{width}\"/>",
+                        hex(HILL),
+                        x = x as f32 + width / 2.,
+                        y = y as f32 + width / 2.,
+                        side = self.cell_size as f32 - width,
+                    );
+                }
+
+                let power_up = content
+                    .is_free()
+                    .then(|| self.board.power_up(i, j))
+                    .flatten();
+
+                let _ = match (content, power_up) {
+                    (_, Some(power_up)) => {
+                        let side = SQUARE * half;
+
+                        writeln!(
+                            svg,
+                            "<polygon points=\"{cx},{} {},{cy} {cx},{} {},{cy}\" fill=\"{}\"/>",
+                            cy - side,
+                            cx + side,
+                            cy + side,
+                            cx - side,
+                            hex(graphics::rgb(power_up.into())),
+                        )
+                    }
+
+                    (CellState::Player(player), None) => writeln!(
+                        svg,
+                        "<circle cx=\"{cx}\" cy=\"{cy}\" r=\"{}\" fill=\"url(#{})\"/>",
+                        DISC * half,
+                        player.to_string().to_lowercase(),
+                    ),
+
+                    (CellState::Free, None) => writeln!(
+                        svg,
+                        "<circle cx=\"{cx}\" cy=\"{cy}\" r=\"{}\" fill=\"{color}\"/>",
+                        DOT * half,
+                    ),
+
+                    (CellState::Restricted, None) => writeln!(
+                        svg,
+                        "<rect x=\"{x}\" y=\"{y}\" width=\"{side}\" height=\"{side}\" fill=\"{color}\"/>",
+                        x = cx - SQUARE * half,
+                        y = cy - SQUARE * half,
+                        side = 2. * SQUARE * half,
+                    ),
+                };
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Draw the position as an [image](Image)
+    pub fn image(&self) -> Image {
+        let (width, height) = self.size();
+        let mut image = Image::new(width, height);
+
+        for i in 0..self.board.height() {
+            for j in 0..self.board.width() {
+                let Some(content) = self.board.get(i, j) else {
+                    continue;
+                };
+
+                let bounds = (
+                    j as u32 * self.cell_size,
+                    i as u32 * self.cell_size,
+                    self.cell_size,
+                    self.cell_size,
+                );
+
+                image.fill(bounds, TILES[(i + j) % 2]);
+
+                if self.board.is_hill(i, j) {
+                    image.paint_hill(bounds);
+                }
+                image.paint_cell(bounds, content, &self.theme);
+
+                if let Some(power_up) = self.board.power_up(i, j).filter(|_| content.is_free()) {
+                    image.paint_power_up(bounds, power_up);
+                }
+            }
+        }
+
+        image
+    }
+
+    /// Draw the position as PNG
+    pub fn png(&self) -> io::Result<Vec<u8>> {
+        self.image().png()
+    }
+
+    /// Encode the position in a given format
+    ///
+    /// # Parameter
+    ///
+    /// - `format` - The [format](SnapshotFormat) of the image
+    pub fn encode(&self, format: SnapshotFormat) -> io::Result<Vec<u8>> {
+        match format {
+            SnapshotFormat::Svg => Ok(self.svg().into_bytes()),
+            SnapshotFormat::Png => self.png(),
+        }
+    }
+
+    /// Export the snapshot to a new file of the [export directory](PortableGame::directory)
+    ///
+    /// # Parameter
+    ///
+    /// - `format` - The [format](SnapshotFormat) of the image
+    ///
+    /// # Return
+    ///
+    /// The path of the written file
+    pub fn export(&self, format: SnapshotFormat) -> io::Result<PathBuf> {
+        let directory = PortableGame::directory().ok_or(io::Error::new(
+            ErrorKind::NotFound,
+            "No data directory was found",
+        ))?;
+
+        fs::create_dir_all(&directory)?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        let path = directory.join(format!("position-{timestamp}.{}", format.extension()));
+
+        fs::write(&path, self.encode(format)?)?;
+        Ok(path)
+    }
+}
+
+/// Write a color in hexadecimal notation
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn svg() {
+        let board: Board = "B.#/..R".parse().unwrap();
+        let svg = Snapshot::new(board).with_cell_size(10).svg();
+
+        assert!(svg.starts_with(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"20\" viewBox=\"0 0 30 20\">"
+        ));
+
+        assert_eq!(svg.matches("<radialGradient").count(), 2);
+        assert_eq!(svg.matches("<rect").count(), 7);
+        assert_eq!(svg.matches("<circle").count(), 5);
+        assert!(svg.contains("<circle cx=\"5\" cy=\"5\" r=\"4.25\" fill=\"url(#blue)\"/>"));
+        assert!(svg.contains("<circle cx=\"25\" cy=\"15\" r=\"4.25\" fill=\"url(#red)\"/>"));
+        assert!(svg.contains("<rect x=\"22\" y=\"2\" width=\"6\" height=\"6\""));
+        assert!(svg.ends_with("</svg>\n"));
+
+        let board: Board = "B*/.R".parse().unwrap();
+        let svg = Snapshot::new(board).with_cell_size(10).svg();
+
+        assert!(svg.contains("<polygon points=\"15,2 18,5 15,8 12,5\" fill=\"#e91e63\"/>"));
+    }
+
+    #[test]
+    fn image() {
+        let board: Board = "B.#/..R".parse().unwrap();
+        let image = Snapshot::new(board).with_cell_size(10).image();
+
+        assert_eq!((image.width(), image.height()), (30, 20));
+
+        // Every pixel is opaque, the tiles being painted below the cells
+        assert_eq!(image.pixel(0, 0), Some([0x24, 0x24, 0x24, 255]));
+        assert_eq!(image.pixel(10, 0), Some([0x2c, 0x2c, 0x2c, 255]));
+        assert_ne!(image.pixel(5, 5), image.pixel(0, 0));
+    }
+
+    #[test]
+    fn format() {
+        for format in SnapshotFormat::ALL {
+            assert_eq!(format.to_string().parse(), Ok(format));
+            assert_eq!(
+                SnapshotFormat::from_path(Path::new(&format!("a.{}", format.extension()))),
+                Some(format)
+            );
+        }
+
+        let snapshot = Snapshot::new("B/R".parse().unwrap());
+        assert!(
+            snapshot
+                .encode(SnapshotFormat::Svg)
+                .unwrap()
+                .starts_with(b"<svg")
+        );
+        assert!(
+            snapshot
+                .encode(SnapshotFormat::Png)
+                .unwrap()
+                .starts_with(b"\x89PNG")
+        );
+    }
+}
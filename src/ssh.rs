@@ -0,0 +1,141 @@
+//! The implementation of [`SshRequest`]
+//!
+//! The games of a [server](crate::server::Server) are served to SSH clients by an existing SSH daemon, which forces the command of a dedicated account to `blobwars-ssh`; each session then gets its own terminal.
+//! The command asked by the client, given by the daemon in `SSH_ORIGINAL_COMMAND`, is never handed to the game: it is read as a [request](SshRequest) and the game is run with [fixed arguments](SshRequest::args) built from it.
+
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// A request of an SSH client
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SshRequest {
+    /// List the open games, asked by an empty command
+    List,
+
+    /// Open a game and wait for a player, asked by `--create NAME`
+    Create(String),
+
+    /// Join an open game, asked by `--enter NAME`
+    Enter(String),
+}
+
+impl SshRequest {
+    /// The longest name of a game
+    pub const MAX_NAME_LENGTH: usize = 32;
+
+    /// Get the arguments of the game playing the request on a given server
+    ///
+    /// # Parameter
+    ///
+    /// - `server` - The address of the [server](crate::server::Server)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::ssh::SshRequest;
+    ///
+    /// let request: SshRequest = "--enter friday".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     request.args("127.0.0.1:4444"),
+    ///     ["--server", "127.0.0.1:4444", "--enter", "friday"]
+    /// );
+    /// ```
+    pub fn args(&self, server: &str) -> Vec<String> {
+        let mut args = vec!["--server".to_owned(), server.to_owned()];
+
+        match self {
+            Self::List => (),
+            Self::Create(name) => args.extend(["--create".to_owned(), name.clone()]),
+            Self::Enter(name) => args.extend(["--enter".to_owned(), name.clone()]),
+        }
+
+        args
+    }
+}
+
+impl Display for SshRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::List => Ok(()),
+            Self::Create(name) => write!(f, "--create {name}"),
+            Self::Enter(name) => write!(f, "--enter {name}"),
+        }
+    }
+}
+
+impl FromStr for SshRequest {
+    type Err = &'static str;
+
+    /// Parse the command asked by an SSH client
+    ///
+    /// The only accepted commands are an empty one, `--create NAME` and `--enter NAME`, where the name is made of at most [`Self::MAX_NAME_LENGTH`] ASCII letters, digits, `-` and `_`, and does not start with `-`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words = s.split_ascii_whitespace().collect::<Vec<_>>();
+
+        let name = |name: &str| {
+            if name.len() <= Self::MAX_NAME_LENGTH
+                && !name.starts_with('-')
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                Ok(name.to_owned())
+            } else {
+                Err("The name of the game is invalid")
+            }
+        };
+
+        match words[..] {
+            [] => Ok(Self::List),
+            ["--create", game] => name(game).map(Self::Create),
+            ["--enter", game] => name(game).map(Self::Enter),
+            _ => Err("Only --create NAME and --enter NAME are allowed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        pretty_assertions::assert_eq!("".parse(), Ok(SshRequest::List));
+        pretty_assertions::assert_eq!(
+            "--create friday".parse(),
+            Ok(SshRequest::Create("friday".to_owned()))
+        );
+        pretty_assertions::assert_eq!(
+            "  --enter  game_2-b ".parse(),
+            Ok(SshRequest::Enter("game_2-b".to_owned()))
+        );
+
+        for request in [SshRequest::Create("a".to_owned()), SshRequest::List] {
+            pretty_assertions::assert_eq!(request.to_string().parse(), Ok(request));
+        }
+    }
+
+    #[test]
+    fn forbidden() {
+        for command in [
+            "--output /etc/passwd snapshot",
+            "write-config",
+            "--config /tmp/evil.toml",
+            "--host 0.0.0.0:22",
+            "--create friday --host 0.0.0.0:22",
+            "--create --host",
+            "--enter ../friday",
+            "--enter $(id)",
+            "--create ééé",
+            "--create",
+        ] {
+            assert!(command.parse::<SshRequest>().is_err(), "{command}");
+        }
+
+        let long = format!("--create {}", "a".repeat(SshRequest::MAX_NAME_LENGTH + 1));
+        assert!(long.parse::<SshRequest>().is_err());
+    }
+}
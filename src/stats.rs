@@ -0,0 +1,430 @@
+//! The implementation of [`Statistics`]
+
+use crate::{
+    ai::Difficulty,
+    config::Config,
+    game::{Outcome, Player, Score},
+    save::SavedGame,
+};
+
+use std::{
+    fmt::{Display, Formatter},
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+};
+
+/// The results of the games played against a computer opponent of a given [difficulty](Difficulty)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Record {
+    /// The number of games won by the human player
+    pub won: usize,
+
+    /// The number of games played
+    pub played: usize,
+}
+
+impl Record {
+    /// Get the percentage of games won by the human player, if any game has been played
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::stats::Record;
+    ///
+    /// assert_eq!(Record { won: 1, played: 4 }.win_rate(), Some(25.));
+    /// assert_eq!(Record::default().win_rate(), None);
+    /// ```
+    pub fn win_rate(self) -> Option<f64> {
+        (self.played > 0).then(|| self.won as f64 * 100. / self.played as f64)
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.won, self.played)
+    }
+}
+
+impl FromStr for Record {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (won, played) = s.split_once('/').ok_or("A record is malformed")?;
+
+        let record = Self {
+            won: won.trim().parse().map_err(|_| "A record is malformed")?,
+            played: played.trim().parse().map_err(|_| "A record is malformed")?,
+        };
+
+        if record.won <= record.played {
+            Ok(record)
+        } else {
+            Err("A record is malformed")
+        }
+    }
+}
+
+/// The rating of a player who has not played any rated game yet
+pub const INITIAL_RATING: i32 = 1200;
+
+const K_FACTOR: f64 = 32.;
+
+/// Compute the change of an Elo rating after a game
+///
+/// # Parameters
+///
+/// - `rating` - The rating of the player before the game
+/// - `opponent` - The rating of the opponent before the game
+/// - `score` - The score of the player: `1` for a win, `0.5` for a draw and `0` for a loss
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::stats::rating_change;
+///
+/// assert_eq!(rating_change(1200, 1200, 1.), 16);
+/// assert_eq!(rating_change(1200, 1200, 0.5), 0);
+/// assert_eq!(rating_change(1000, 1400, 1.), 29);
+/// ```
+pub fn rating_change(rating: i32, opponent: i32, score: f64) -> i32 {
+    let expected = 1. / (1. + 10_f64.powf(f64::from(opponent - rating) / 400.));
+    (K_FACTOR * (score - expected)).round() as i32
+}
+
+/// The lifetime statistics of the games played
+///
+/// The statistics are persisted in a [file](Self::path) using a subset of the TOML format, and updated each time a game is over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Statistics {
+    /// The number of games won by each color
+    pub wins: Score,
+
+    /// The number of drawn games
+    pub draws: usize,
+
+    /// The total number of moves played
+    pub moves: usize,
+
+    /// The total number of _blobs_ captured by each color
+    pub captures: Score,
+
+    /// The number of moves of the longest game
+    pub longest: usize,
+
+    records: [Record; Difficulty::ALL.len()],
+    rating: i32,
+    ratings: [i32; Difficulty::ALL.len()],
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Self {
+            wins: Score::default(),
+            draws: 0,
+            moves: 0,
+            captures: Score::default(),
+            longest: 0,
+            records: Default::default(),
+            rating: INITIAL_RATING,
+            ratings: Difficulty::ALL.map(initial_rating),
+        }
+    }
+}
+
+impl Statistics {
+    /// Get the path of the statistics file
+    ///
+    /// The file is located in the data directory, next to the [save directory](SavedGame::directory).
+    pub fn path() -> Option<PathBuf> {
+        SavedGame::directory().map(|directory| directory.with_file_name("stats.toml"))
+    }
+
+    /// Load the statistics file
+    ///
+    /// The file formerly located next to the [configuration file](Config::path) is read if the [current one](Self::path) does not exist yet.
+    /// If neither exists or if the file is malformed, empty statistics are returned.
+    pub fn load() -> Self {
+        let legacy = Config::path().map(|path| path.with_file_name("stats.toml"));
+
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .or_else(|| legacy.and_then(|path| fs::read_to_string(path).ok()))
+            .and_then(|content| content.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the statistics file
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or(io::Error::new(
+            ErrorKind::NotFound,
+            "No configuration directory was found",
+        ))?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, self.to_string())
+    }
+
+    /// Get the number of games played
+    pub fn games(&self) -> usize {
+        self.wins.blue + self.wins.red + self.draws
+    }
+
+    /// Get the average number of moves per game, if any game has been played
+    pub fn average_length(&self) -> Option<f64> {
+        let games = self.games();
+        (games > 0).then(|| self.moves as f64 / games as f64)
+    }
+
+    /// Get the average number of _blobs_ captured by a given color per game, if any game has been played
+    ///
+    /// # Parameter
+    ///
+    /// - `player` - The color whose captures are averaged
+    pub fn average_captures(&self, player: Player) -> Option<f64> {
+        let games = self.games();
+        (games > 0).then(|| self.captures.get(player) as f64 / games as f64)
+    }
+
+    /// Get the [record](Record) against the computer opponents of a given [difficulty](Difficulty)
+    pub fn record(&self, difficulty: Difficulty) -> Record {
+        self.records[index(difficulty)]
+    }
+
+    /// Get the Elo rating of the human player
+    pub fn rating(&self) -> i32 {
+        self.rating
+    }
+
+    /// Get the Elo rating of the computer opponents of a given [difficulty](Difficulty)
+    pub fn ai_rating(&self, difficulty: Difficulty) -> i32 {
+        self.ratings[index(difficulty)]
+    }
+
+    /// Take a finished game into account
+    ///
+    /// # Parameters
+    ///
+    /// - `outcome` - The [outcome](Outcome) of the game
+    /// - `moves` - The number of moves played during the game
+    /// - `captures` - The number of _blobs_ captured by each color during the game
+    /// - `opponent` - The [player](Player) and the [difficulty](Difficulty) of the computer opponent, if any
+    ///
+    /// Only the games played against a computer opponent are rated.
+    ///
+    /// # Return
+    ///
+    /// The change of the [rating](Self::rating) of the human player, if the game is rated.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     ai::Difficulty,
+    ///     game::{Outcome, Player, Score},
+    ///     stats::{Record, Statistics},
+    /// };
+    ///
+    /// let mut statistics = Statistics::default();
+    /// let captures = Score { blue: 3, red: 1, ..Score::default() };
+    ///
+    /// let change = statistics.add_game(Outcome::Win(Player::Blue), 10, captures, Some((Player::Red, Difficulty::Hard)));
+    /// assert_eq!(statistics.add_game(Outcome::Draw, 20, captures, None), None);
+    ///
+    /// assert_eq!(statistics.games(), 2);
+    /// assert_eq!(statistics.average_length(), Some(15.));
+    /// assert_eq!(statistics.captures, Score { blue: 6, red: 2, ..Score::default() });
+    /// assert_eq!(statistics.average_captures(Player::Blue), Some(3.));
+    /// assert_eq!(statistics.longest, 20);
+    /// assert_eq!(statistics.record(Difficulty::Hard), Record { won: 1, played: 1 });
+    /// assert_eq!(change, Some(27));
+    /// assert_eq!(statistics.rating(), 1227);
+    /// assert_eq!(statistics.ai_rating(Difficulty::Hard), 1473);
+    /// ```
+    pub fn add_game(
+        &mut self,
+        outcome: Outcome,
+        moves: usize,
+        captures: Score,
+        opponent: Option<(Player, Difficulty)>,
+    ) -> Option<i32> {
+        match outcome {
+            Outcome::Win(player) => *self.wins.get_mut(player) += 1,
+            Outcome::Draw => self.draws += 1,
+        }
+
+        self.moves += moves;
+        self.longest = self.longest.max(moves);
+        self.captures.blue += captures.blue;
+        self.captures.red += captures.red;
+
+        let (player, difficulty) = opponent?;
+        let record = &mut self.records[index(difficulty)];
+        record.played += 1;
+
+        let score = match outcome {
+            Outcome::Win(winner) if winner == player => 0.,
+            Outcome::Win(_) => {
+                record.won += 1;
+                1.
+            }
+            Outcome::Draw => 0.5,
+        };
+
+        let ai_rating = &mut self.ratings[index(difficulty)];
+        let change = rating_change(self.rating, *ai_rating, score);
+        *ai_rating += rating_change(*ai_rating, self.rating, 1. - score);
+        self.rating += change;
+
+        Some(change)
+    }
+}
+
+fn initial_rating(difficulty: Difficulty) -> i32 {
+    match difficulty {
+        Difficulty::Easy => INITIAL_RATING - 300,
+        Difficulty::Normal => INITIAL_RATING,
+        Difficulty::Hard => INITIAL_RATING + 300,
+    }
+}
+
+fn index(difficulty: Difficulty) -> usize {
+    Difficulty::ALL
+        .iter()
+        .position(|&level| level == difficulty)
+        .unwrap_or_default()
+}
+
+impl Display for Statistics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "blue_wins = {}", self.wins.blue)?;
+        writeln!(f, "red_wins = {}", self.wins.red)?;
+        writeln!(f, "draws = {}", self.draws)?;
+        writeln!(f, "moves = {}", self.moves)?;
+        writeln!(f, "longest = {}", self.longest)?;
+        writeln!(f, "blue_captures = {}", self.captures.blue)?;
+        writeln!(f, "red_captures = {}", self.captures.red)?;
+        writeln!(f, "rating = {}", self.rating)?;
+
+        for (difficulty, record) in Difficulty::ALL.into_iter().zip(self.records) {
+            writeln!(
+                f,
+                "{} = \"{record}\"",
+                difficulty.to_string().to_lowercase()
+            )?;
+        }
+
+        for (difficulty, rating) in Difficulty::ALL.into_iter().zip(self.ratings) {
+            writeln!(
+                f,
+                "{}_rating = {rating}",
+                difficulty.to_string().to_lowercase()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Statistics {
+    type Err = &'static str;
+
+    /// Parse some statistics
+    ///
+    /// Missing keys are considered null and unknown keys are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{ai::Difficulty, stats::{Record, Statistics}};
+    ///
+    /// let statistics: Statistics = "draws = 2\nhard = \"1/3\"".parse().unwrap();
+    ///
+    /// assert_eq!(statistics.games(), 2);
+    /// assert_eq!(statistics.record(Difficulty::Hard), Record { won: 1, played: 3 });
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut statistics = Self::default();
+
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or("A line is not a `key = value` pair")?;
+
+            let (key, value) = (key.trim(), value.trim().trim_matches('"'));
+
+            let counter = match key {
+                "blue_wins" => &mut statistics.wins.blue,
+                "red_wins" => &mut statistics.wins.red,
+                "draws" => &mut statistics.draws,
+                "moves" => &mut statistics.moves,
+                "longest" => &mut statistics.longest,
+                "blue_captures" => &mut statistics.captures.blue,
+                "red_captures" => &mut statistics.captures.red,
+
+                "rating" => {
+                    statistics.rating = value.parse().map_err(|_| "A rating is invalid")?;
+                    continue;
+                }
+
+                _ => {
+                    if let Some(difficulty) = key.strip_suffix("_rating")
+                        && let Ok(difficulty) = difficulty.parse()
+                    {
+                        statistics.ratings[index(difficulty)] =
+                            value.parse().map_err(|_| "A rating is invalid")?;
+                    } else if let Ok(difficulty) = key.parse() {
+                        statistics.records[index(difficulty)] = value.parse()?;
+                    }
+
+                    continue;
+                }
+            };
+
+            *counter = value.parse().map_err(|_| "A counter is invalid")?;
+        }
+
+        Ok(statistics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut statistics = Statistics::default();
+        let captures = Score {
+            blue: 4,
+            red: 7,
+            ..Score::default()
+        };
+
+        statistics.add_game(Outcome::Win(Player::Red), 31, captures, None);
+
+        statistics.add_game(
+            Outcome::Win(Player::Red),
+            12,
+            captures,
+            Some((Player::Red, Difficulty::Easy)),
+        );
+
+        pretty_assertions::assert_eq!(statistics.to_string().parse(), Ok(statistics));
+    }
+
+    #[test]
+    fn malformed() {
+        assert!("easy = \"3/2\"".parse::<Statistics>().is_err());
+        assert!("moves = many".parse::<Statistics>().is_err());
+        assert!("hard_rating = strong".parse::<Statistics>().is_err());
+    }
+}
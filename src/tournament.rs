@@ -0,0 +1,523 @@
+//! The implementation of [`Tournament`] and its [results](Standing)
+
+use crate::{
+    ai::{Ai, Difficulty, Personality},
+    game::{Board, Outcome, Player},
+};
+
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    str::FromStr,
+    thread,
+};
+
+/// A computer opponent taking part in a [tournament](Tournament)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Engine {
+    /// The [personality](Personality) of the engine
+    pub personality: Personality,
+
+    /// The [difficulty](Difficulty) of the engine
+    pub difficulty: Difficulty,
+}
+
+impl Engine {
+    /// Get the engines of every [personality](Personality) at the [default difficulty](Difficulty::Normal)
+    pub fn all() -> Vec<Self> {
+        Personality::ALL
+            .into_iter()
+            .map(|personality| Self {
+                personality,
+                difficulty: Difficulty::default(),
+            })
+            .collect()
+    }
+
+    pub(crate) fn ai(self, seed: u64) -> Ai {
+        Ai::with_seed(self.personality, seed).with_difficulty(self.difficulty)
+    }
+}
+
+impl Display for Engine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.personality, self.difficulty)
+    }
+}
+
+impl FromStr for Engine {
+    type Err = &'static str;
+
+    /// Parse an engine written `personality[:difficulty]`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     ai::{Difficulty, Personality},
+    ///     tournament::Engine,
+    /// };
+    ///
+    /// assert_eq!(
+    ///     "defensive:hard".parse(),
+    ///     Ok(Engine { personality: Personality::Defensive, difficulty: Difficulty::Hard })
+    /// );
+    ///
+    /// assert_eq!("chaotic".parse::<Engine>().map(|engine| engine.difficulty), Ok(Difficulty::Normal));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (personality, difficulty) = match s.split_once(':') {
+            Some((personality, difficulty)) => (personality, difficulty.trim().parse()?),
+            None => (s, Difficulty::default()),
+        };
+
+        Ok(Self {
+            personality: personality.trim().parse()?,
+            difficulty,
+        })
+    }
+}
+
+/// The pairing of the engines of a [tournament](Tournament)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Every engine plays against every other one
+    #[default]
+    RoundRobin,
+
+    /// The first engine plays against every other one
+    Gauntlet,
+}
+
+/// A tournament between computer opponents
+///
+/// Each pairing plays every opening map twice, each engine playing each color once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tournament {
+    engines: Vec<Engine>,
+    maps: Vec<Board>,
+    format: Format,
+    threads: usize,
+    seed: u64,
+}
+
+/// The results of an [engine](Engine) in a [tournament](Tournament)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Standing {
+    /// The [engine](Engine)
+    pub engine: Engine,
+
+    /// The number of games won
+    pub wins: usize,
+
+    /// The number of drawn games
+    pub draws: usize,
+
+    /// The number of games lost
+    pub losses: usize,
+}
+
+impl Tournament {
+    /// The maximum number of plies of a game, beyond which the game is decided on the score
+    pub const MAX_PLIES: usize = 1000;
+
+    /// Create a new [`Tournament`]
+    ///
+    /// # Parameters
+    ///
+    /// - `engines` - The [engines](Engine) taking part in the tournament
+    /// - `maps` - The opening [boards](Board) played by each pairing
+    pub fn new(engines: Vec<Engine>, maps: Vec<Board>) -> Self {
+        Self {
+            engines,
+            maps,
+            format: Format::default(),
+            threads: thread::available_parallelism().map_or(1, usize::from),
+            seed: 1,
+        }
+    }
+
+    /// Set the [format](Format) of the tournament
+    pub fn with_format(self, format: Format) -> Self {
+        Self { format, ..self }
+    }
+
+    /// Set the number of games played in parallel
+    pub fn with_threads(self, threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+            ..self
+        }
+    }
+
+    /// Set the seed from which the seeds of the engines are derived
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    /// Get the pairings of the tournament as indices of the engines
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::tournament::{Engine, Format, Tournament};
+    ///
+    /// let tournament = Tournament::new(Engine::all(), Vec::new());
+    /// assert_eq!(tournament.pairings().len(), 6);
+    /// assert_eq!(tournament.with_format(Format::Gauntlet).pairings(), vec![(0, 1), (0, 2), (0, 3)]);
+    /// ```
+    pub fn pairings(&self) -> Vec<(usize, usize)> {
+        let len = self.engines.len();
+
+        match self.format {
+            Format::RoundRobin => (0..len)
+                .flat_map(|i| (i + 1..len).map(move |j| (i, j)))
+                .collect(),
+
+            Format::Gauntlet => (1..len).map(|j| (0, j)).collect(),
+        }
+    }
+
+    /// Play every game of the tournament
+    ///
+    /// # Return
+    ///
+    /// The [standings](Standing) of the engines, the best ones first
+    pub fn run(&self) -> Vec<Standing> {
+        let games = self
+            .pairings()
+            .into_iter()
+            .flat_map(|(i, j)| (0..self.maps.len()).flat_map(move |map| [(i, j, map), (j, i, map)]))
+            .collect::<Vec<_>>();
+
+        let outcomes = thread::scope(|scope| {
+            let workers = (0..self.threads)
+                .map(|worker| {
+                    let games = &games;
+
+                    scope.spawn(move || {
+                        games
+                            .iter()
+                            .enumerate()
+                            .skip(worker)
+                            .step_by(self.threads)
+                            .map(|(index, &(blue, red, map))| {
+                                let seed = self.seed.wrapping_add(2 * index as u64);
+
+                                let outcome = play(
+                                    &self.maps[map],
+                                    self.engines[blue].ai(seed),
+                                    self.engines[red].ai(seed.wrapping_add(1)),
+                                );
+
+                                (blue, red, outcome)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap_or_default())
+                .collect::<Vec<_>>()
+        });
+
+        let mut standings = self
+            .engines
+            .iter()
+            .map(|&engine| Standing {
+                engine,
+                ..Standing::default()
+            })
+            .collect::<Vec<_>>();
+
+        for (blue, red, outcome) in outcomes {
+            match outcome {
+                Outcome::Win(Player::Blue) => {
+                    standings[blue].wins += 1;
+                    standings[red].losses += 1;
+                }
+
+                Outcome::Win(Player::Red) => {
+                    standings[red].wins += 1;
+                    standings[blue].losses += 1;
+                }
+
+                Outcome::Draw => {
+                    standings[blue].draws += 1;
+                    standings[red].draws += 1;
+                }
+            }
+        }
+
+        standings.sort_by(|a, b| b.score().total_cmp(&a.score()));
+        standings
+    }
+}
+
+fn play(board: &Board, mut blue: Ai, mut red: Ai) -> Outcome {
+    let mut board = board.clone();
+    let mut player = Player::Blue;
+
+    for _ in 0..Tournament::MAX_PLIES {
+        if let Some(outcome) = board.outcome() {
+            return outcome;
+        }
+
+        let ai = match player {
+            Player::Blue => &mut blue,
+            Player::Red => &mut red,
+        };
+
+        if let Some(chosen) = ai.choose(&board, player) {
+            board.jump(chosen.from, chosen.to);
+        }
+
+        player = -player;
+    }
+
+    let score = board.score();
+
+    match score.blue.cmp(&score.red) {
+        Ordering::Greater => Outcome::Win(Player::Blue),
+        Ordering::Less => Outcome::Win(Player::Red),
+        Ordering::Equal => Outcome::Draw,
+    }
+}
+
+impl Standing {
+    /// Get the number of games played
+    pub fn games(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Get the score, a win counting for one point and a draw for half a point
+    pub fn score(&self) -> f64 {
+        self.wins as f64 + self.draws as f64 / 2.
+    }
+
+    /// Get the Elo difference with the average opponent and its 95% confidence interval, if any game has been played
+    ///
+    /// The interval is the Wilson score interval of the score, which stays meaningful for small samples and perfect scores.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::tournament::Standing;
+    ///
+    /// let even = Standing { wins: 5, draws: 0, losses: 5, ..Standing::default() };
+    /// let elo = even.elo().unwrap();
+    ///
+    /// assert!(elo.difference.abs() < 1e-9);
+    /// assert!(elo.lower < 0. && elo.upper > 0.);
+    ///
+    /// let perfect = Standing { wins: 4, ..Standing::default() }.elo().unwrap();
+    ///
+    /// assert_eq!(perfect.difference, f64::INFINITY);
+    /// assert_eq!(perfect.upper, f64::INFINITY);
+    /// assert!(perfect.lower.is_finite() && perfect.lower > 0.);
+    /// ```
+    pub fn elo(&self) -> Option<Elo> {
+        const Z: f64 = 1.96;
+
+        let games = self.games() as f64;
+
+        if games == 0. {
+            return None;
+        }
+
+        let p = self.score() / games;
+        let spread = Z * Z / games;
+
+        let center = (p + spread / 2.) / (1. + spread);
+        let half = Z / (1. + spread) * (p * (1. - p) / games + spread / (4. * games)).sqrt();
+
+        Some(Elo {
+            difference: elo(p),
+            lower: elo(center - half),
+            upper: elo(center + half),
+        })
+    }
+}
+
+/// An Elo difference with its 95% confidence interval
+///
+/// The bounds are infinite when the interval of the score reaches a perfect score, and so is the difference when every game has been won or lost.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Elo {
+    /// The estimated difference
+    pub difference: f64,
+
+    /// The lower bound of the interval
+    pub lower: f64,
+
+    /// The upper bound of the interval
+    pub upper: f64,
+}
+
+/// Get the Elo difference matching an expected score
+fn elo(p: f64) -> f64 {
+    if p <= 0. {
+        f64::NEG_INFINITY
+    } else if p >= 1. {
+        f64::INFINITY
+    } else {
+        -400. * (1. / p - 1.).log10()
+    }
+}
+
+/// Write an Elo difference for JSON, which has no infinite numbers
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        format!("{value:.1}")
+    } else {
+        "null".to_owned()
+    }
+}
+
+/// Write [standings](Standing) as a CSV table
+///
+/// # Example
+///
+/// ```rust
+/// use blobwars::tournament::{Standing, to_csv};
+///
+/// let csv = to_csv(&[Standing { wins: 1, ..Standing::default() }]);
+/// assert!(csv.starts_with("engine,games,wins,draws,losses,score,elo,lower,upper\nAggressive:Normal,1,1,0,0,1.0,inf,"));
+/// ```
+pub fn to_csv(standings: &[Standing]) -> String {
+    let mut csv = "engine,games,wins,draws,losses,score,elo,lower,upper\n".to_owned();
+
+    for standing in standings {
+        let elo = standing.elo().unwrap_or_default();
+
+        csv += &format!(
+            "{},{},{},{},{},{:.1},{:.1},{:.1},{:.1}\n",
+            standing.engine,
+            standing.games(),
+            standing.wins,
+            standing.draws,
+            standing.losses,
+            standing.score(),
+            elo.difference,
+            elo.lower,
+            elo.upper,
+        );
+    }
+
+    csv
+}
+
+/// Write [standings](Standing) as a JSON array
+pub fn to_json(standings: &[Standing]) -> String {
+    let entries = standings
+        .iter()
+        .map(|standing| {
+            let elo = standing.elo().unwrap_or_default();
+
+            format!(
+                "  {{\"engine\": \"{}\", \"games\": {}, \"wins\": {}, \"draws\": {}, \"losses\": {}, \"score\": {:.1}, \"elo\": {}, \"lower\": {}, \"upper\": {}}}",
+                standing.engine,
+                standing.games(),
+                standing.wins,
+                standing.draws,
+                standing.losses,
+                standing.score(),
+                json_number(elo.difference),
+                json_number(elo.lower),
+                json_number(elo.upper),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    format!("[\n{entries}\n]\n")
+}
+
+/// Write [standings](Standing) as a table aligned for the terminal
+pub fn to_table(standings: &[Standing]) -> String {
+    let mut table = format!(
+        "{:<20} {:>5} {:>5} {:>5} {:>5} {:>6} {:>22}\n",
+        "Engine", "Games", "Wins", "Draws", "Loss", "Score", "Elo (95%)"
+    );
+
+    for standing in standings {
+        let elo = standing
+            .elo()
+            .map(|elo| {
+                format!(
+                    "{:+.0} ({:+.0}, {:+.0})",
+                    elo.difference, elo.lower, elo.upper
+                )
+            })
+            .unwrap_or_default();
+
+        table += &format!(
+            "{:<20} {:>5} {:>5} {:>5} {:>5} {:>6.1} {elo:>22}\n",
+            standing.engine.to_string(),
+            standing.games(),
+            standing.wins,
+            standing.draws,
+            standing.losses,
+            standing.score(),
+        );
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_robin() {
+        let engines = vec![
+            "aggressive:hard".parse().unwrap(),
+            "chaotic:easy".parse().unwrap(),
+            "defensive".parse().unwrap(),
+        ];
+
+        let maps = vec!["B....../......./......R".parse().unwrap()];
+        let standings = Tournament::new(engines, maps).with_threads(2).run();
+
+        pretty_assertions::assert_eq!(standings.len(), 3);
+
+        for standing in &standings {
+            pretty_assertions::assert_eq!(standing.games(), 4);
+        }
+
+        let (wins, losses) = standings.iter().fold((0, 0), |(wins, losses), standing| {
+            (wins + standing.wins, losses + standing.losses)
+        });
+
+        pretty_assertions::assert_eq!(wins, losses);
+        assert!(standings[0].score() >= standings[2].score());
+    }
+
+    #[test]
+    fn perfect_score() {
+        let standings = [Standing {
+            losses: 3,
+            ..Standing::default()
+        }];
+
+        let table = to_table(&standings);
+        assert!(table.lines().nth(1).unwrap().ends_with("-inf (-inf, +43)"));
+
+        let json = to_json(&standings);
+        assert!(json.contains("\"elo\": null, \"lower\": null, \"upper\": 43.0"));
+    }
+
+    #[test]
+    fn deterministic() {
+        let maps = vec!["B...../....../.....R".parse().unwrap()];
+        let tournament = Tournament::new(Engine::all(), maps).with_seed(7);
+
+        pretty_assertions::assert_eq!(
+            tournament.clone().with_threads(1).run(),
+            tournament.with_threads(3).run()
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! The implementation of [`FileWatcher`]
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    path::{self, Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+};
+
+/// A watcher notified by the operating system of the changes of some files
+///
+/// The directories of the files are watched rather than the files themselves so that a missing file is watched until it is created and a file replaced by an editor is still watched afterwards.
+/// A file whose directory does not exist is not watched.
+#[derive(Default)]
+pub struct FileWatcher {
+    paths: Vec<PathBuf>,
+    events: Option<(RecommendedWatcher, Receiver<notify::Result<Event>>)>,
+}
+
+impl FileWatcher {
+    /// Create a new [`FileWatcher`]
+    ///
+    /// The files are considered unchanged in their current state.
+    ///
+    /// # Parameter
+    ///
+    /// - `paths` - The paths of the watched files
+    pub fn new<I>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = PathBuf>,
+    {
+        let paths = paths
+            .into_iter()
+            .map(|path| resolve(&path).unwrap_or(path))
+            .collect::<Vec<_>>();
+
+        let (sender, receiver) = mpsc::channel();
+
+        let events = match notify::recommended_watcher(sender) {
+            Ok(mut watcher) => {
+                let mut directories = paths
+                    .iter()
+                    .filter_map(|path| path.parent())
+                    .collect::<Vec<_>>();
+                directories.sort();
+                directories.dedup();
+
+                for directory in directories {
+                    if let Err(error) = watcher.watch(directory, RecursiveMode::NonRecursive) {
+                        tracing::warn!("cannot watch `{}`: {error}", directory.display());
+                    }
+                }
+
+                Some((watcher, receiver))
+            }
+
+            Err(error) => {
+                tracing::warn!("cannot watch the files: {error}");
+                None
+            }
+        };
+
+        Self { paths, events }
+    }
+
+    /// Get the paths of the watched files
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.paths.iter().map(PathBuf::as_path)
+    }
+
+    /// Get the files changed since the last call without blocking
+    ///
+    /// # Return
+    ///
+    /// The indices of the changed files, each given once
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::watch::FileWatcher;
+    /// use std::{env, fs, thread, time::Duration};
+    ///
+    /// let path = env::temp_dir().join(format!("blobwars-watch-doc-{}", std::process::id()));
+    /// let _ = fs::remove_file(&path);
+    ///
+    /// let mut watcher = FileWatcher::new([path.clone()]);
+    /// assert!(watcher.changes().is_empty());
+    ///
+    /// fs::write(&path, "theme = \"dark\"").unwrap();
+    /// thread::sleep(Duration::from_millis(100));
+    ///
+    /// assert_eq!(watcher.changes(), vec![0]);
+    /// assert!(watcher.changes().is_empty());
+    ///
+    /// fs::remove_file(path).unwrap();
+    /// ```
+    pub fn changes(&mut self) -> Vec<usize> {
+        let Some((_, receiver)) = &self.events else {
+            return Vec::new();
+        };
+
+        let mut changes = Vec::new();
+
+        for event in receiver.try_iter() {
+            let event = match event {
+                Ok(event) => event,
+
+                Err(error) => {
+                    tracing::warn!("cannot watch the files: {error}");
+                    continue;
+                }
+            };
+
+            if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                continue;
+            }
+
+            for path in &event.paths {
+                if let Some(index) = self.paths.iter().position(|watched| watched == path)
+                    && !changes.contains(&index)
+                {
+                    changes.push(index);
+                }
+            }
+        }
+
+        changes.sort_unstable();
+        changes
+    }
+}
+
+impl Clone for FileWatcher {
+    /// Watch the same files again, the changes not yet [taken](Self::changes) being only given to the original watcher
+    fn clone(&self) -> Self {
+        if self.events.is_some() {
+            Self::new(self.paths.clone())
+        } else {
+            Self {
+                paths: self.paths.clone(),
+                events: None,
+            }
+        }
+    }
+}
+
+impl Debug for FileWatcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileWatcher")
+            .field("paths", &self.paths)
+            .field("watching", &self.events.is_some())
+            .finish()
+    }
+}
+
+impl PartialEq for FileWatcher {
+    fn eq(&self, other: &Self) -> bool {
+        self.paths == other.paths
+    }
+}
+
+impl Eq for FileWatcher {}
+
+/// Resolve the directory of a file the way the operating system reports it in the events
+fn resolve(path: &Path) -> Option<PathBuf> {
+    let path = path::absolute(path).ok()?;
+    let directory = path.parent()?.canonicalize().ok()?;
+
+    Some(directory.join(path.file_name()?))
+}
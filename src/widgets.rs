@@ -1,13 +1,35 @@
 //! A module contaning implementation of the different [widgets](ratatui::widgets::Widget) used by the application
 
+use crate::{
+    Command, CommandManaged,
+    ai::Difficulty,
+    game::{CellState, Player, PowerUp},
+    keymap::KeyBinding,
+    record::display_index,
+    stats::Statistics,
+    widgets::board::BoardState,
+};
+
 use ratatui::{
+    layout::Flex,
     prelude::*,
     widgets::{Block, Paragraph, Wrap},
 };
 
 use ratatui_macros::{line, span, text};
 
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+    time::Duration,
+};
+
 pub mod board;
+pub mod join;
+pub mod load;
+pub mod maps;
+pub mod replay;
+pub mod settings;
 
 /// A theme
 ///
@@ -34,6 +56,15 @@ pub struct Theme {
 
     /// Style used by titles
     pub title: Style,
+
+    /// Color of the [blue player](Player::Blue)
+    pub blue: Color,
+
+    /// Color of the [red player](Player::Red)
+    pub red: Color,
+
+    /// Color of the [restricted cells](CellState::Restricted)
+    pub restricted: Color,
 }
 
 impl Default for Theme {
@@ -46,6 +77,534 @@ impl Default for Theme {
             secondary: Style::default().fg(Color::Rgb(0x7e, 0x89, 0x87)),
             tertiary: Style::default().fg(Color::Rgb(0x4b, 0x4a, 0x67)),
             title: Style::default().bold().italic().underlined(),
+            blue: Player::Blue.into(),
+            red: Player::Red.into(),
+            restricted: CellState::Restricted.into(),
+        }
+    }
+}
+
+impl Theme {
+    /// Create a theme without any color
+    pub fn monochrome() -> Self {
+        Self {
+            emph: Style::default().bold().italic(),
+            important: Style::default().bold(),
+            link: Style::default().underlined(),
+            primary: Style::default(),
+            secondary: Style::default().dim(),
+            tertiary: Style::default().dim().italic(),
+            title: Style::default().bold().italic().underlined(),
+            blue: Player::Blue.into(),
+            red: Player::Red.into(),
+            restricted: CellState::Restricted.into(),
+        }
+    }
+
+    /// Use a palette safe for colorblind people
+    ///
+    /// The colors of the players and of the restricted cells are replaced by colors that can be distinguished with deuteranopia.
+    pub fn colorblind(self) -> Self {
+        Self {
+            blue: Color::Rgb(0x00, 0x72, 0xb2),
+            red: Color::Rgb(0xe6, 0x9f, 0x00),
+            restricted: Color::Rgb(0x99, 0x99, 0x99),
+            ..self
+        }
+    }
+
+    /// Get the color of a [player](Player)
+    pub fn player_color(&self, player: Player) -> Color {
+        match player {
+            Player::Blue => self.blue,
+            Player::Red => self.red,
+        }
+    }
+
+    /// Get the color of a [cell](CellState)
+    pub fn cell_color(&self, cell: CellState) -> Color {
+        match cell {
+            CellState::Player(player) => self.player_color(player),
+            CellState::Free => Color::default(),
+            CellState::Restricted => self.restricted,
+        }
+    }
+
+    /// Get the name of a [player](Player) colorized with its color
+    pub fn player(&self, player: Player) -> Span<'static> {
+        span!(self.player_color(player); player)
+    }
+}
+
+impl From<CellState> for Color {
+    fn from(value: CellState) -> Self {
+        match value {
+            CellState::Player(player) => player.into(),
+            CellState::Free => Self::default(),
+            CellState::Restricted => Self::Rgb(0xff, 0xa5, 0x00),
+        }
+    }
+}
+
+impl From<PowerUp> for Color {
+    fn from(value: PowerUp) -> Self {
+        match value {
+            PowerUp::DoubleSpread => Self::Rgb(0x4c, 0xaf, 0x50),
+            PowerUp::Shield => Self::Rgb(0x26, 0xc6, 0xda),
+            PowerUp::Bomb => Self::Rgb(0xe9, 0x1e, 0x63),
+        }
+    }
+}
+
+impl From<Player> for Color {
+    fn from(value: Player) -> Self {
+        match value {
+            Player::Blue => Color::Blue,
+            Player::Red => Color::Red,
+        }
+    }
+}
+
+impl From<Player> for Span<'static> {
+    fn from(value: Player) -> Self {
+        span!(Color::from(value); value)
+    }
+}
+
+impl From<ThemeName> for Theme {
+    fn from(value: ThemeName) -> Self {
+        match value {
+            ThemeName::Default => Self::default(),
+            ThemeName::Monochrome => Self::monochrome(),
+        }
+    }
+}
+
+/// The name of a [theme](Theme) preset
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ThemeName {
+    /// The [default theme](Theme::default)
+    #[default]
+    Default,
+
+    /// The [monochrome theme](Theme::monochrome)
+    Monochrome,
+}
+
+impl ThemeName {
+    /// All the available theme names
+    pub const ALL: [Self; 2] = [Self::Default, Self::Monochrome];
+}
+
+impl Display for ThemeName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "Default"),
+            Self::Monochrome => write!(f, "Monochrome"),
+        }
+    }
+}
+
+impl FromStr for ThemeName {
+    type Err = &'static str;
+
+    /// Parse a theme name
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::widgets::ThemeName;
+    ///
+    /// assert_eq!("monochrome".parse(), Ok(ThemeName::Monochrome));
+    /// assert!("unknown".parse::<ThemeName>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|name| name.to_string().eq_ignore_ascii_case(s))
+            .ok_or("The name is not recognized as a valid theme")
+    }
+}
+
+/// A preset of the layout of the game screen
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LayoutPreset {
+    /// The side panel and the credits are shown when the terminal is large enough
+    #[default]
+    Comfortable,
+
+    /// Only the board and two status lines are shown
+    Compact,
+}
+
+impl LayoutPreset {
+    /// All the available layout presets
+    pub const ALL: [Self; 2] = [Self::Comfortable, Self::Compact];
+}
+
+impl Display for LayoutPreset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Comfortable => write!(f, "Comfortable"),
+            Self::Compact => write!(f, "Compact"),
+        }
+    }
+}
+
+impl FromStr for LayoutPreset {
+    type Err = &'static str;
+
+    /// Parse a layout preset
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::widgets::LayoutPreset;
+    ///
+    /// assert_eq!("compact".parse(), Ok(LayoutPreset::Compact));
+    /// assert!("cozy".parse::<LayoutPreset>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.to_string().eq_ignore_ascii_case(s))
+            .ok_or("Unknown layout preset")
+    }
+}
+
+/// The symbols used to show the cells of the board
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Symbols {
+    /// The symbol used to show the selected cell
+    pub selected: &'static str,
+
+    /// The symbol used to show unselected cells
+    pub unselected: &'static str,
+}
+
+impl Symbols {
+    /// All the available symbol presets
+    pub const PRESETS: [Self; 4] = [
+        Self {
+            selected: "V",
+            unselected: "O",
+        },
+        Self {
+            selected: "@",
+            unselected: "o",
+        },
+        Self {
+            selected: "X",
+            unselected: "x",
+        },
+        Self {
+            selected: "#",
+            unselected: ".",
+        },
+    ];
+}
+
+impl Default for Symbols {
+    fn default() -> Self {
+        Self::PRESETS[0]
+    }
+}
+
+impl Display for Symbols {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.selected, self.unselected)
+    }
+}
+
+impl FromStr for Symbols {
+    type Err = &'static str;
+
+    /// Parse a symbol preset from its selected and unselected symbols separated by a space
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::widgets::Symbols;
+    ///
+    /// assert_eq!("@ o".parse(), Ok(Symbols::PRESETS[1]));
+    /// assert!("a b".parse::<Symbols>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::PRESETS
+            .into_iter()
+            .find(|symbols| symbols.to_string() == s)
+            .ok_or("The symbols do not correspond to any preset")
+    }
+}
+
+/// Get the area of a popup centered in `area`
+///
+/// # Parameters
+///
+/// - `area` - The area in which the popup is centered
+/// - `width` - The [constraint](Constraint) applied to the width of the popup
+/// - `height` - The [constraint](Constraint) applied to the height of the popup
+pub fn popup_area(area: Rect, width: Constraint, height: Constraint) -> Rect {
+    let [area] = Layout::vertical([height]).flex(Flex::Center).areas(area);
+    let [area] = Layout::horizontal([width]).flex(Flex::Center).areas(area);
+    area
+}
+
+/// Widget asking a yes/no question
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Dialog<'a> {
+    /// The question asked to the user
+    pub message: &'a str,
+
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl Widget for Dialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let text = text![
+            span!(self.theme.important; self.message),
+            line![],
+            line![
+                span!(self.theme.emph; "y"),
+                span!(self.theme.secondary; "es / "),
+                span!(self.theme.emph; "n"),
+                span!(self.theme.secondary; "o"),
+            ],
+        ];
+
+        Paragraph::new(text)
+            .block(Block::bordered().title("Confirmation"))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+}
+
+/// Widget showing some internals of the application
+///
+/// This widget is meant for the development of new screens and for bug reports.
+#[derive(Clone, Copy, Debug)]
+pub struct DebugPanel<'a> {
+    /// The time taken to render the previous frame
+    pub frame_time: Duration,
+
+    /// The last [command](Command) handled, if any
+    pub last_command: Option<Command>,
+
+    /// The [name](crate::Screen::name()) of the current screen
+    pub screen: &'a str,
+
+    /// The state of the current game, if any
+    pub board_state: Option<&'a BoardState>,
+
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl Widget for DebugPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let field = |name, value: String| {
+            line![
+                span!(self.theme.important; "{name}: "),
+                span!(self.theme.secondary; value),
+            ]
+        };
+
+        let mut text = text![
+            field(
+                "Frame",
+                format!("{:.1} ms", self.frame_time.as_secs_f64() * 1000.)
+            ),
+            field(
+                "Command",
+                self.last_command
+                    .map_or("None".to_owned(), |command| format!("{command:?}"))
+            ),
+            field("Screen", self.screen.to_owned()),
+        ];
+
+        if let Some(board_state) = self.board_state {
+            let index = |index: Option<_>| index.map_or("-".to_owned(), display_index);
+
+            text.push_line(field(
+                "Selection",
+                format!(
+                    "{} from {} to {}",
+                    display_index(board_state.selected()),
+                    index(board_state.from()),
+                    index(board_state.to())
+                ),
+            ));
+
+            text.push_line(field("Zobrist", format!("{:016x}", board_state.zobrist())));
+
+            let ai = match (board_state.thinking(), board_state.opponent()) {
+                (Some(thinking), _) => {
+                    let progress = thinking.progress();
+
+                    format!(
+                        "Thinking for {} ms, {} nodes",
+                        thinking.elapsed().as_millis(),
+                        progress.nodes
+                    )
+                }
+
+                (None, Some(personality)) => format!("{personality}, idle"),
+                (None, None) => "None".to_owned(),
+            };
+
+            text.push_line(field("AI", ai));
+        }
+
+        Paragraph::new(text)
+            .block(Block::bordered().title("Debug"))
+            .render(area, buf);
+    }
+}
+
+/// Widget asking to resize a too small terminal
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TooSmall {
+    /// The minimum size needed by the application
+    pub required: Size,
+
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl Widget for TooSmall {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let text = text![
+            span!(self.theme.title; "Terminal too small"),
+            line![],
+            line![
+                "Please resize to at least ",
+                span!(self.theme.important; "{}×{}", self.required.width, self.required.height),
+            ],
+            line![span!(self.theme.secondary; "Current size: {}×{}", area.width, area.height)],
+        ];
+
+        let [area] = Layout::vertical([Constraint::Length(text.height() as u16)])
+            .flex(Flex::Center)
+            .areas(area);
+
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+}
+
+/// The state of the [`Credits` widget](Credits)
+///
+/// The credits are scrolled automatically until the user scrolls them by hand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CreditsState {
+    offset: u16,
+    last: u16,
+    page: u16,
+    auto: bool,
+    elapsed: Duration,
+}
+
+impl Default for CreditsState {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            last: 0,
+            page: 1,
+            auto: true,
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+impl CreditsState {
+    /// The time spent on each line when the credits are scrolled automatically
+    pub const STEP: Duration = Duration::from_millis(1500);
+
+    /// Get the number of lines scrolled
+    pub fn offset(&self) -> u16 {
+        self.offset
+    }
+
+    /// Check if the credits are scrolled automatically
+    pub fn is_auto_scrolling(&self) -> bool {
+        self.auto
+    }
+
+    /// Enable or disable the automatic scrolling
+    pub fn toggle_auto_scroll(&mut self) {
+        self.auto = !self.auto;
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Scroll the credits up by some lines
+    ///
+    /// Scrolling by hand disables the automatic scrolling.
+    ///
+    /// # Parameter
+    ///
+    /// - `lines` - The number of lines to scroll
+    pub fn scroll_up(&mut self, lines: u16) {
+        self.auto = false;
+        self.offset = self.offset.saturating_sub(lines);
+    }
+
+    /// Scroll the credits down by some lines
+    ///
+    /// Scrolling by hand disables the automatic scrolling.
+    ///
+    /// # Parameter
+    ///
+    /// - `lines` - The number of lines to scroll
+    pub fn scroll_down(&mut self, lines: u16) {
+        self.auto = false;
+        self.offset = self.offset.saturating_add(lines).min(self.last);
+    }
+
+    /// Advance the automatic scrolling
+    ///
+    /// Once the end is reached, the credits go back to the top.
+    ///
+    /// # Parameter
+    ///
+    /// - `elapsed` - The time elapsed since the last tick
+    ///
+    /// # Return
+    ///
+    /// The returned value is `true` if the credits have been scrolled; otherwise, it returns `false`.
+    pub fn tick(&mut self, elapsed: Duration) -> bool {
+        if !self.auto || self.last == 0 {
+            return false;
+        }
+
+        self.elapsed += elapsed;
+
+        if self.elapsed < Self::STEP {
+            return false;
+        }
+
+        self.elapsed = Duration::ZERO;
+        self.offset = if self.offset < self.last {
+            self.offset + 1
+        } else {
+            0
+        };
+        true
+    }
+}
+
+impl CommandManaged for CreditsState {
+    fn handle_command(&mut self, command: Command) {
+        match command {
+            Command::Up => self.scroll_up(1),
+            Command::Down => self.scroll_down(1),
+            Command::FirstRow => self.scroll_up(self.page),
+            Command::LastRow => self.scroll_down(self.page),
+            Command::Select => self.toggle_auto_scroll(),
+            _ => (),
         }
     }
 }
@@ -57,8 +616,10 @@ pub struct Credits {
     pub theme: Theme,
 }
 
-impl Widget for Credits {
-    fn render(self, area: Rect, buf: &mut Buffer) {
+impl StatefulWidget for Credits {
+    type State = CreditsState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         let block = Block::bordered().title("Credits");
 
         let text = text![
@@ -103,16 +664,24 @@ impl Widget for Credits {
             line!["under certain conditions; type `show c' for details."],
         ];
 
-        Paragraph::new(text)
+        let inner = block.inner(area);
+        let paragraph = Paragraph::new(text).wrap(Wrap { trim: true });
+        let lines = paragraph.line_count(inner.width) as u16;
+
+        state.page = inner.height.max(1);
+        state.last = lines.saturating_sub(inner.height);
+        state.offset = state.offset.min(state.last);
+
+        paragraph
+            .scroll((state.offset, 0))
             .block(block)
-            .wrap(Wrap { trim: true })
             .render(area, buf);
     }
 }
 
 /// The help widget
 ///
-/// This widget describes how to play to the game.
+/// This widget describes how to play to the game and lists the key bindings.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Help<'a> {
     /// The selected symbol
@@ -127,6 +696,9 @@ pub struct Help<'a> {
 
     /// The [theme](Theme) used to colorize text
     pub theme: Theme,
+
+    /// The listed key bindings
+    pub key_bindings: &'a [(KeyBinding, Command)],
 }
 
 impl<'a> Help<'a> {
@@ -160,6 +732,7 @@ impl Default for Help<'static> {
             selected_symbol: "V",
             unselected_symbol: "O",
             theme: Theme::default(),
+            key_bindings: &Command::KEY_BINDINGS,
         }
     }
 }
@@ -168,7 +741,7 @@ impl Widget for Help<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let block = Block::bordered().title("Help");
 
-        let text = text![
+        let mut text = text![
             span!(self.theme.title; "Goal"),
             line![],
             line![
@@ -190,15 +763,147 @@ impl Widget for Help<'_> {
                     "). ",
                     "Players take turns. ",
                     "On each turn, the current player chooses one of their pieces and moves it. ",
-                    "Any piece can move to an empty adjacent square, including diagonally, duplicating itself and creating a new piece of the same color. ",
+                    "Any piece can move to an empty adjacent square, including diagonally, duplicating itself and creating a new piece of the same color (this move is called a ",
+                ),
+                span!(self.theme.emph; "spread"),
+                concat!(
+                    "). ",
                     "A piece can also move two squares. ",
                     "In this case, there is no duplication and the piece is said to make a ",
                 ),
                 span!(self.theme.emph; "jump"),
                 ". Once it reaches its destination, a piece transforms all of its opponent's neighboring pieces into pieces of its own color."
             ],
+            line![],
+            line![
+                "The cursor is shown with ",
+                span!(self.theme.important; self.selected_symbol),
+                " and the other cells with ",
+                span!(self.theme.important; self.unselected_symbol),
+                ".",
+            ],
+            line![],
+            span!(self.theme.title; "Key bindings"),
+            line![],
+        ];
+
+        let mut commands = Vec::<Command>::new();
+
+        for &(_, command) in self.key_bindings {
+            if !commands.contains(&command) {
+                commands.push(command);
+            }
+        }
+
+        for command in commands {
+            let keys = self
+                .key_bindings
+                .iter()
+                .filter(|&&(_, bound)| bound == command)
+                .map(|(key, _)| key.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            text.push_line(line![
+                span!(self.theme.important; keys),
+                ": ",
+                command.description(),
+            ]);
+        }
+
+        Paragraph::new(text)
+            .block(block)
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+}
+
+/// Widget showing the lifetime [statistics](Statistics)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Stats<'a> {
+    /// The shown [statistics](Statistics)
+    pub statistics: &'a Statistics,
+
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl Widget for Stats<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered().title("Statistics");
+        let statistics = self.statistics;
+
+        let average = statistics
+            .average_length()
+            .map(|average| format!("{average:.1} moves"))
+            .unwrap_or("-".to_owned());
+
+        let [average_blue, average_red] = [Player::Blue, Player::Red].map(|player| {
+            statistics
+                .average_captures(player)
+                .map(|average| format!("{average:.1}"))
+                .unwrap_or("-".to_owned())
+        });
+
+        let mut text = text![
+            line![
+                span!(self.theme.primary; "Games played: "),
+                span!(self.theme.important; statistics.games()),
+            ],
+            line![
+                span!(self.theme.primary; "Wins: "),
+                span!(self.theme.player_color(Player::Blue); "Blue {}", statistics.wins.blue),
+                ", ",
+                span!(self.theme.player_color(Player::Red); "Red {}", statistics.wins.red),
+                ", ",
+                span!(self.theme.secondary; "Draws {}", statistics.draws),
+            ],
+            line![
+                span!(self.theme.primary; "Average game length: "),
+                span!(self.theme.important; average),
+            ],
+            line![
+                span!(self.theme.primary; "Longest game: "),
+                span!(self.theme.important; "{} moves", statistics.longest),
+            ],
+            line![
+                span!(self.theme.primary; "Captured blobs: "),
+                span!(self.theme.player_color(Player::Blue); "Blue {}", statistics.captures.blue),
+                ", ",
+                span!(self.theme.player_color(Player::Red); "Red {}", statistics.captures.red),
+            ],
+            line![
+                span!(self.theme.primary; "Captures per game: "),
+                span!(self.theme.player_color(Player::Blue); "Blue {average_blue}"),
+                ", ",
+                span!(self.theme.player_color(Player::Red); "Red {average_red}"),
+            ],
+            line![
+                span!(self.theme.primary; "Rating: "),
+                span!(self.theme.important; statistics.rating()),
+            ],
+            line![],
+            span!(self.theme.title; "Against the computer:"),
         ];
 
+        for difficulty in Difficulty::ALL {
+            let record = statistics.record(difficulty);
+
+            let rate = record
+                .win_rate()
+                .map(|rate| format!(" ({rate:.0}%)"))
+                .unwrap_or_default();
+
+            text.push_line(line![
+                span!(self.theme.primary; "  {difficulty}: "),
+                span!(self.theme.important; "{record} won{rate}"),
+                span!(self.theme.secondary; ", rated {}", statistics.ai_rating(difficulty)),
+            ]);
+        }
+
+        text.push_line(line![]);
+        text.push_line(span!(self.theme.tertiary; "Esc to go back"));
+
         Paragraph::new(text)
             .block(block)
             .wrap(Wrap { trim: true })
@@ -207,13 +912,55 @@ impl Widget for Help<'_> {
 }
 
 /// A [`Widget`] representing the logo of the application
+///
+/// The logo is revealed column by column, like typed by a typewriter.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Logo {
     /// The [theme](Theme) used to colorize text
     pub theme: Theme,
+
+    /// The time elapsed since the logo is shown
+    pub elapsed: Duration,
 }
 
 impl Logo {
+    /// The time needed to reveal one column of the logo
+    pub const STEP: Duration = Duration::from_millis(15);
+
+    /// The period of the pulsing of the prompt shown once the logo is revealed
+    pub const PULSE: Duration = Duration::from_millis(600);
+
+    /// Get the frame shown after some time
+    ///
+    /// # Parameter
+    ///
+    /// - `elapsed` - The time elapsed since the logo is shown
+    ///
+    /// # Return
+    ///
+    /// The returned value is the number of revealed columns and, once the logo is fully revealed, whether the prompt is highlighted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::widgets::Logo;
+    /// use std::time::Duration;
+    ///
+    /// assert_eq!(Logo::frame(Duration::ZERO), (0, None));
+    /// assert_eq!(Logo::frame(Logo::STEP * 3), (3, None));
+    /// assert_eq!(Logo::frame(Duration::from_secs(60)).1, Some(true));
+    /// ```
+    pub fn frame(elapsed: Duration) -> (u16, Option<bool>) {
+        let revealed = (elapsed.as_millis() / Self::STEP.as_millis()) as u16;
+
+        if revealed < Self::WIDTH {
+            (revealed, None)
+        } else {
+            let pulses = elapsed.as_millis() / Self::PULSE.as_millis();
+            (Self::WIDTH, Some(pulses.is_multiple_of(2)))
+        }
+    }
+
     const B_TEXT: [&'static str; Self::HEIGHT] = [
         r##"     ...     ..     "##,
         r##"  .=*8888x <"?88h.  "##,
@@ -283,6 +1030,8 @@ impl Logo {
 
     const MARGIN_WIDTH: u16 = Self::B_WIDTH;
     const OFFSET_WIDTH: u16 = 2;
+
+    const WIDTH: u16 = Self::MARGIN_WIDTH + Self::W_WIDTH + Self::OFFSET_WIDTH + Self::ARS_WIDTH;
 }
 
 impl Widget for Logo {
@@ -317,5 +1066,13 @@ impl Widget for Logo {
         Text::from_iter(Self::ARS_TEXT)
             .style(self.theme.tertiary)
             .render(ars_area, buf);
+
+        let (revealed, _) = Self::frame(self.elapsed);
+
+        for y in area.top()..area.bottom() {
+            for x in area.left().saturating_add(revealed)..area.right() {
+                buf[(x, y)].reset();
+            }
+        }
     }
 }
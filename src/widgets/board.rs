@@ -2,18 +2,308 @@
 
 use crate::{
     Command, CommandManaged,
-    game::{CellState, Index, Player},
+    ai::{Ai, SearchResult, Thinking},
+    animation::Animation,
+    events::TaskEvent,
+    game::{CellState, Index, Move, Outcome, Player, PowerUp},
+    message::{Message, MessageQueue, Severity},
+    record::{GameRecord, display_index, display_ply},
     widgets::Theme,
 };
 
 use ratatui::{
-    layout::Flex,
+    layout::{Flex, Position},
     prelude::*,
-    widgets::{Block, Paragraph, Wrap},
+    widgets::{Block, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
 };
 
 use ratatui_macros::{constraint, constraints, line, span, text};
-use std::iter::once;
+use std::{
+    collections::VecDeque,
+    env,
+    fmt::{Display, Formatter},
+    iter::once,
+    str::FromStr,
+    time::Duration,
+};
+
+const SPINNER: [&str; 4] = ["|", "/", "-", "\\"];
+
+/// The style used to render the cells of a [`Board`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RenderStyle {
+    /// The [selected](Board::selected_symbol) and [unselected](Board::unselected_symbol) symbols
+    #[default]
+    Ascii,
+
+    /// Unicode circles (`●`, `○`)
+    Circles,
+
+    /// Blocks with a background color
+    Blocks,
+
+    /// Braille patterns filling the whole cell
+    Braille,
+
+    /// Emoji (`🔵`, `🔴`), each one taking two columns
+    Emoji,
+
+    /// Icons of the [Nerd Fonts](https://www.nerdfonts.com)
+    NerdFont,
+
+    /// Images drawn over the board with a [graphics protocol](crate::graphics::Protocol)
+    Images,
+}
+
+impl RenderStyle {
+    /// All the available render styles
+    pub const ALL: [Self; 7] = [
+        Self::Ascii,
+        Self::Circles,
+        Self::Blocks,
+        Self::Braille,
+        Self::Emoji,
+        Self::NerdFont,
+        Self::Images,
+    ];
+
+    /// Check if the render style requires a terminal supporting unicode
+    pub fn is_unicode(self) -> bool {
+        matches!(
+            self,
+            Self::Circles | Self::Braille | Self::Emoji | Self::NerdFont
+        )
+    }
+
+    /// Check if the render style requires glyphs missing from the fonts of the Linux console
+    pub fn is_graphical(self) -> bool {
+        matches!(self, Self::Emoji | Self::NerdFont | Self::Images)
+    }
+
+    /// Get the render style used where no image can be drawn
+    ///
+    /// [`Self::Images`] is replaced by [`Self::Circles`]; the other render styles are kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::widgets::board::RenderStyle;
+    ///
+    /// assert_eq!(RenderStyle::Images.textual(), RenderStyle::Circles);
+    /// assert_eq!(RenderStyle::Braille.textual(), RenderStyle::Braille);
+    /// ```
+    pub fn textual(self) -> Self {
+        match self {
+            Self::Images => Self::Circles,
+            _ => self,
+        }
+    }
+
+    /// Fall back to [`Self::Ascii`] if the terminal does not support the render style
+    ///
+    /// The support of unicode is guessed from the locale environment variables (`LC_ALL`, `LC_CTYPE` and `LANG`).
+    /// The [graphical](Self::is_graphical) render styles are not supported by the Linux console (`TERM=linux`).
+    /// [`Self::Images`] falls back to the [textual](Self::textual) render style if no [graphics protocol](crate::graphics::Protocol::detect) is detected.
+    pub fn supported(self) -> Self {
+        let unicode = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .into_iter()
+            .find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()))
+            .map(|value| value.to_uppercase())
+            .is_some_and(|value| value.contains("UTF-8") || value.contains("UTF8"));
+
+        let console = env::var("TERM").is_ok_and(|term| term == "linux");
+
+        if self.is_graphical() && console {
+            Self::Ascii
+        } else if self == Self::Images && crate::graphics::Protocol::detect().is_none() {
+            self.textual().degrade(unicode)
+        } else {
+            self.degrade(unicode)
+        }
+    }
+
+    /// Fall back to [`Self::Ascii`] if unicode is not supported
+    ///
+    /// # Parameter
+    ///
+    /// - `unicode` - Whether unicode is supported
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::widgets::board::RenderStyle;
+    ///
+    /// assert_eq!(RenderStyle::Circles.degrade(false), RenderStyle::Ascii);
+    /// assert_eq!(RenderStyle::Circles.degrade(true), RenderStyle::Circles);
+    /// assert_eq!(RenderStyle::Blocks.degrade(false), RenderStyle::Blocks);
+    /// ```
+    pub fn degrade(self, unicode: bool) -> Self {
+        if self.is_unicode() && !unicode {
+            Self::Ascii
+        } else {
+            self
+        }
+    }
+}
+
+impl Display for RenderStyle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ascii => write!(f, "ASCII"),
+            Self::Circles => write!(f, "Circles"),
+            Self::Blocks => write!(f, "Blocks"),
+            Self::Braille => write!(f, "Braille"),
+            Self::Emoji => write!(f, "Emoji"),
+            Self::NerdFont => write!(f, "Nerd Font"),
+            Self::Images => write!(f, "Images"),
+        }
+    }
+}
+
+impl FromStr for RenderStyle {
+    type Err = &'static str;
+
+    /// Parse a render style
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::widgets::board::RenderStyle;
+    ///
+    /// assert_eq!("braille".parse(), Ok(RenderStyle::Braille));
+    /// assert!("unknown".parse::<RenderStyle>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|style| style.to_string().eq_ignore_ascii_case(s))
+            .ok_or("Unknown render style")
+    }
+}
+
+/// The grid drawn by a [`Board`] to make its rows and columns easier to track
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Grid {
+    /// No grid is drawn
+    #[default]
+    Off,
+
+    /// Box-drawing lines are drawn between the cells
+    Lines,
+
+    /// Every other cell has a [shaded background](Board::shade_style)
+    Checkerboard,
+
+    /// Both the [lines](Self::Lines) and the [checkerboard](Self::Checkerboard) are drawn
+    Both,
+}
+
+impl Grid {
+    /// All the available grids
+    pub const ALL: [Self; 4] = [Self::Off, Self::Lines, Self::Checkerboard, Self::Both];
+
+    /// Check if lines are drawn between the cells
+    pub fn has_lines(self) -> bool {
+        matches!(self, Self::Lines | Self::Both)
+    }
+
+    /// Check if every other cell has a shaded background
+    pub fn has_checkerboard(self) -> bool {
+        matches!(self, Self::Checkerboard | Self::Both)
+    }
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Off => write!(f, "Off"),
+            Self::Lines => write!(f, "Lines"),
+            Self::Checkerboard => write!(f, "Checkerboard"),
+            Self::Both => write!(f, "Both"),
+        }
+    }
+}
+
+impl FromStr for Grid {
+    type Err = &'static str;
+
+    /// Parse a grid
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::widgets::board::Grid;
+    ///
+    /// assert_eq!("checkerboard".parse(), Ok(Grid::Checkerboard));
+    /// assert!("dots".parse::<Grid>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|grid| grid.to_string().eq_ignore_ascii_case(s))
+            .ok_or("Unknown grid")
+    }
+}
+
+/// The orientation of a [`Board`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Flip {
+    /// The board is never rotated
+    #[default]
+    Off,
+
+    /// The board is always rotated by 180°
+    On,
+
+    /// The board is rotated by 180° when the red player is the one watching it
+    ///
+    /// In a game against the computer, the watching player is the human one; otherwise, it is the player to move.
+    Auto,
+}
+
+impl Flip {
+    /// All the available orientations
+    pub const ALL: [Self; 3] = [Self::Off, Self::On, Self::Auto];
+}
+
+impl Display for Flip {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Off => write!(f, "Off"),
+            Self::On => write!(f, "On"),
+            Self::Auto => write!(f, "Auto"),
+        }
+    }
+}
+
+impl FromStr for Flip {
+    type Err = &'static str;
+
+    /// Parse an orientation
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::widgets::board::Flip;
+    ///
+    /// assert_eq!("auto".parse(), Ok(Flip::Auto));
+    /// assert!("upside down".parse::<Flip>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|flip| flip.to_string().eq_ignore_ascii_case(s))
+            .ok_or("Unknown board orientation")
+    }
+}
+
+/// The remote player of a network game
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Remote {
+    player: Player,
+    connection: crate::net::Connection,
+    game: String,
+}
 
 /// State of the [`Board`] widget
 ///
@@ -26,6 +316,28 @@ pub struct BoardState {
     selected: Index,
     from: Option<Index>,
     to: Option<Index>,
+    opponent: Option<(Player, Ai)>,
+    thinking: Option<Thinking>,
+    remote: Option<Remote>,
+    inbox: VecDeque<TaskEvent>,
+    chat: Vec<(Player, String)>,
+    dragging: bool,
+    cells: Vec<(Index, Rect)>,
+    viewport: Index,
+    moves: usize,
+    captures: crate::game::Score,
+    last_captures: usize,
+    duration: Duration,
+    record: GameRecord,
+    clock: Option<crate::clock::Clock>,
+    animation: Option<Animation>,
+    messages: MessageQueue,
+    wrap: bool,
+    free_pass: bool,
+    flip: Flip,
+    redone: Vec<Option<Move>>,
+    resigned: Option<Player>,
+    rng: crate::rng::Rng,
 }
 
 impl BoardState {
@@ -58,131 +370,518 @@ impl BoardState {
     /// ```
     pub fn new(board: crate::game::Board, current_player: Player) -> Self {
         Self {
+            record: GameRecord::new(board.clone(), current_player),
             board,
             current_player,
             selected: Index::default(),
             from: None,
             to: None,
+            opponent: None,
+            thinking: None,
+            remote: None,
+            inbox: VecDeque::new(),
+            chat: Vec::new(),
+            dragging: false,
+            cells: Vec::new(),
+            viewport: Index::default(),
+            moves: 0,
+            captures: crate::game::Score::default(),
+            last_captures: 0,
+            duration: Duration::ZERO,
+            clock: None,
+            animation: None,
+            messages: MessageQueue::default(),
+            wrap: false,
+            free_pass: false,
+            flip: Flip::Off,
+            redone: Vec::new(),
+            resigned: None,
+            rng: crate::rng::Rng::default(),
         }
     }
 
-    /// Get the height of the current board
+    /// Create a new [`BoardState`] resuming a recorded game
     ///
-    /// This method is just a wrapper around [`Board::height()`](crate::game::Board::height()).
-    pub fn height(&self) -> usize {
-        self.board.height()
+    /// The game continues from the position reached after the last ply of the [record](GameRecord), which keeps growing with the next plies.
+    ///
+    /// # Parameter
+    ///
+    /// - `record` - The [record](GameRecord) of the resumed game
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Move, Player::*, Score},
+    ///     record::GameRecord,
+    ///     widgets::board::BoardState,
+    /// };
+    ///
+    /// let mut record = GameRecord::new(Board::new(1, 4, vec![Player(Red), Free, Player(Blue), Free]), Red);
+    /// record.plies.push(Some(Move { from: (0, 0), to: (0, 1) }));
+    ///
+    /// let state = BoardState::resume(record);
+    ///
+    /// assert_eq!(state.current_player(), Blue);
+    /// assert_eq!(state.moves(), 1);
+    /// assert_eq!(state.captures(), Score { blue: 0, red: 1, ..Score::default() });
+    /// assert_eq!(state.record().plies.len(), 1);
+    /// ```
+    pub fn resume(record: GameRecord) -> Self {
+        let mut state = Self {
+            record: record.clone(),
+            ..Self::new(record.board, record.first)
+        };
+
+        state.replay();
+        state
     }
 
-    /// Get the width of the current board
+    /// Play against a computer opponent
     ///
-    /// This method is just a wrapper around [`Board::width()`](crate::game::Board::width()).
-    pub fn width(&self) -> usize {
-        self.board.width()
+    /// The [opponent](Ai) plays the player who does not start.
+    ///
+    /// # Parameter
+    ///
+    /// - `opponent` - The [computer opponent](Ai)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     ai::{Ai, Personality},
+    ///     game::{Board, CellState::*, Player::*},
+    ///     widgets::board::BoardState,
+    /// };
+    ///
+    /// let board = Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+    /// let state = BoardState::new(board, Blue).with_opponent(Ai::new(Personality::Chaotic));
+    ///
+    /// assert_eq!(state.opponent(), Some(Personality::Chaotic));
+    /// ```
+    pub fn with_opponent(self, opponent: Ai) -> Self {
+        let player = -self.current_player;
+        self.with_opponent_as(player, opponent)
     }
 
-    fn jump<F>(&mut self, jump: F)
-    where
-        F: FnOnce(Index) -> Index,
-    {
-        let selected @ (i, j) = jump(self.selected);
+    /// Play against a computer opponent playing a given player
+    ///
+    /// If the given player is the one to move, the opponent starts thinking at once.
+    ///
+    /// # Parameters
+    ///
+    /// - `player` - The [player](Player) played by the opponent
+    /// - `opponent` - The [computer opponent](Ai)
+    pub fn with_opponent_as(self, player: Player, opponent: Ai) -> Self {
+        let mut state = Self {
+            opponent: Some((player, opponent)),
+            ..self
+        };
 
-        if self.board.contains(i, j) {
-            self.selected = selected;
+        state.play_opponent();
+        state
+    }
+
+    /// Play against a remote player
+    ///
+    /// The plies of the remote player are received from the [connection](crate::net::Connection) and checked against the rules of the game; the plies of the local player are sent to it.
+    /// An illegal ply forfeits the game, as does leaving it.
+    /// Once a lost connection is restored, the game is resumed and the plies missed by either side are replayed.
+    ///
+    /// # Parameters
+    ///
+    /// - `player` - The [player](Player) played by the remote player
+    /// - `connection` - The [connection](crate::net::Connection) to the remote player
+    /// - `game` - The identifier of the game, checked when the game is resumed
+    pub fn with_remote(
+        self,
+        player: Player,
+        connection: crate::net::Connection,
+        game: &str,
+    ) -> Self {
+        Self {
+            remote: Some(Remote {
+                player,
+                connection,
+                game: game.to_owned(),
+            }),
+            ..self
         }
     }
 
-    /// Move the selector to the left
+    /// Use a given [random number generator](crate::rng::Rng) to suggest the [hints](Self::hint)
+    pub fn with_rng(self, rng: crate::rng::Rng) -> Self {
+        Self { rng, ..self }
+    }
+
+    /// Play with a [clock](crate::clock::Clock)
+    ///
+    /// A player who runs out of time loses the game.
+    ///
+    /// # Parameter
+    ///
+    /// - `clock` - The [clock](crate::clock::Clock) to use
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use blobwars::{
-    /// #     game::{Board, CellState::*, Player::*},
-    /// #     widgets::board::BoardState,
-    /// # };
-    /// #
-    /// # #[rustfmt::skip]
-    /// # let board = vec![
-    /// #     Player(Red), Free, Free,       Free, Free,
-    /// #     Free,        Free, Free,       Free, Free,
-    /// #     Free,        Free, Restricted, Free, Free,
-    /// #     Free,        Free, Free,       Free, Free,
-    /// #     Free,        Free, Free,       Free, Player(Blue),
-    /// # ];
-    /// #
-    /// # let board = Board::new(5, 5, board);
-    /// let mut state = BoardState::new(board, Blue); // selected = (0, 0)
-    /// state.right(); // selected = (1, 0)
-    /// state.left(); // Selected = (0, 0)
-    /// state.left(); // Selected = (0, 0)
+    /// use blobwars::{
+    ///     clock::{Clock, TimeControl},
+    ///     game::{Board, CellState::*, Outcome, Player::*},
+    ///     widgets::board::BoardState,
+    /// };
+    ///
+    /// use std::time::Duration;
+    ///
+    /// let board = Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+    /// let clock = Clock::new(TimeControl::Absolute(Duration::from_secs(1)));
+    /// let mut state = BoardState::new(board, Blue).with_clock(clock);
+    ///
+    /// state.tick(Duration::from_secs(2));
+    /// assert_eq!(state.outcome(), Some(Outcome::Win(Red)));
     /// ```
-    #[inline]
-    pub fn left(&mut self) {
-        self.jump(|(i, j)| (i, j.saturating_sub(1)));
+    pub fn with_clock(self, clock: crate::clock::Clock) -> Self {
+        Self {
+            clock: Some(clock),
+            ..self
+        }
     }
 
-    /// Move the selector to the right
+    /// Let the selector wrap around the edges of the board
+    ///
+    /// By default, the selector is clamped to the edges of the board.
+    ///
+    /// # Parameter
+    ///
+    /// - `wrap` - Whether the selector wraps around the edges
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use blobwars::{
-    /// #     game::{Board, CellState::*, Player::*},
-    /// #     widgets::board::BoardState,
-    /// # };
-    /// #
-    /// # #[rustfmt::skip]
-    /// # let board = vec![
-    /// #     Player(Red), Free, Free,       Free, Free,
-    /// #     Free,        Free, Free,       Free, Free,
-    /// #     Free,        Free, Restricted, Free, Free,
-    /// #     Free,        Free, Free,       Free, Free,
-    /// #     Free,        Free, Free,       Free, Player(Blue),
-    /// # ];
-    /// #
-    /// # let board = Board::new(5, 5, board);
-    /// let mut state = BoardState::new(board, Blue); // selected = (0, 0)
-    /// state.right(); // selected = (1, 0)
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Player::*},
+    ///     widgets::board::BoardState,
+    /// };
+    ///
+    /// let board = Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+    /// let mut state = BoardState::new(board, Red).with_wrap(true);
+    ///
+    /// state.left();
+    /// state.select();
+    /// assert_eq!(state.from(), None);
+    /// state.right();
+    /// state.select();
+    /// assert_eq!(state.from(), Some((0, 0)));
     /// ```
-    #[inline]
-    pub fn right(&mut self) {
-        self.jump(|(i, j)| (i, j + 1));
+    pub fn with_wrap(self, wrap: bool) -> Self {
+        Self { wrap, ..self }
     }
 
-    /// Move the selector up
-    /// Move the selector to the left
+    /// Allow the players to pass even when they have a legal move
     ///
-    /// # Example
+    /// # Parameter
     ///
-    /// ```rust
-    /// # use blobwars::{
-    /// #     game::{Board, CellState::*, Player::*},
-    /// #     widgets::board::BoardState,
-    /// # };
-    /// #
-    /// # #[rustfmt::skip]
-    /// # let board = vec![
-    /// #     Player(Red), Free, Free,       Free, Free,
-    /// #     Free,        Free, Free,       Free, Free,
-    /// #     Free,        Free, Restricted, Free, Free,
-    /// #     Free,        Free, Free,       Free, Free,
-    /// #     Free,        Free, Free,       Free, Player(Blue),
-    /// # ];
-    /// #
-    /// # let board = Board::new(5, 5, board);
-    /// let mut state = BoardState::new(board, Blue); // selected = (0, 0)
-    /// state.down(); // selected = (0, 1)
-    /// state.up(); // Selected = (0, 0)
-    /// state.up(); // Selected = (0, 0)
-    /// ```
-    #[inline]
-    pub fn up(&mut self) {
-        self.jump(|(i, j)| (i.saturating_sub(1), j));
+    /// - `free_pass` - Whether passing is always allowed
+    pub fn with_free_pass(self, free_pass: bool) -> Self {
+        Self { free_pass, ..self }
     }
 
-    /// Move the selector down
+    /// Set the orientation of the board
     ///
-    /// # Example
+    /// When the board is rotated, the selector moves according to what is shown: for instance, [`Self::up()`] moves it toward the last row.
+    ///
+    /// # Parameter
+    ///
+    /// - `flip` - The [orientation](Flip) of the board
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, Player::*},
+    ///     widgets::board::{BoardState, Flip},
+    /// };
+    ///
+    /// let state = BoardState::new(Board::free(3, 3), Blue).with_flip(Flip::Auto);
+    /// assert!(!state.is_flipped());
+    ///
+    /// let state = BoardState::new(Board::free(3, 3), Red).with_flip(Flip::Auto);
+    /// assert!(state.is_flipped());
+    /// ```
+    pub fn with_flip(self, flip: Flip) -> Self {
+        Self { flip, ..self }
+    }
+
+    /// Check if the board is currently rotated by 180°
+    pub fn is_flipped(&self) -> bool {
+        match self.flip {
+            Flip::Off => false,
+            Flip::On => true,
+
+            Flip::Auto => {
+                let watching = self
+                    .adversary()
+                    .map_or(self.current_player, |opponent| -opponent);
+
+                watching == Player::Red
+            }
+        }
+    }
+
+    fn orient(&self, (i, j): Index) -> Index {
+        if self.is_flipped() {
+            (self.height() - 1 - i, self.width() - 1 - j)
+        } else {
+            (i, j)
+        }
+    }
+
+    /// Place the selector on a given cell
+    ///
+    /// # Parameter
+    ///
+    /// - `selected` - The [position](Index) of the cell; it is ignored if it is outside of the board
+    pub fn with_selected(self, selected: Index) -> Self {
+        if self.board.contains(selected.0, selected.1) {
+            Self { selected, ..self }
+        } else {
+            self
+        }
+    }
+
+    /// Show an [animation](Animation) on the board
+    ///
+    /// # Parameter
+    ///
+    /// - `animation` - The [animation](Animation) of the last move; it is ignored if it is already finished
+    pub fn with_animation(self, animation: Animation) -> Self {
+        Self {
+            animation: Some(animation).filter(|animation| !animation.is_finished()),
+            ..self
+        }
+    }
+
+    /// Get the selected departure point, if any
+    pub fn from(&self) -> Option<Index> {
+        self.from
+    }
+
+    /// Get the selected destination point, if any
+    pub fn to(&self) -> Option<Index> {
+        self.to
+    }
+
+    /// Get the current [board](crate::game::Board)
+    pub fn board(&self) -> &crate::game::Board {
+        &self.board
+    }
+
+    /// Get the cell under the selector
+    pub fn selected(&self) -> Index {
+        self.selected
+    }
+
+    /// Get the search of the computer opponent, if it is thinking
+    pub fn thinking(&self) -> Option<&Thinking> {
+        self.thinking.as_ref()
+    }
+
+    /// Get the _Zobrist_ hash of the position
+    ///
+    /// The [hash of the board](crate::game::Board::zobrist()) is combined with the player to move.
+    pub fn zobrist(&self) -> u64 {
+        match self.current_player {
+            Player::Blue => self.board.zobrist(),
+            Player::Red => !self.board.zobrist(),
+        }
+    }
+
+    /// Get the [player](Player) played by the computer opponent, if any
+    pub fn opponent_player(&self) -> Option<Player> {
+        self.opponent.as_ref().map(|&(player, _)| player)
+    }
+
+    /// Get the [player](Player) played by the remote player, if any
+    pub fn remote_player(&self) -> Option<Player> {
+        self.remote.as_ref().map(|remote| remote.player)
+    }
+
+    /// Get the chat messages exchanged with the remote player, with their senders, the most recent last
+    pub fn chat(&self) -> &[(Player, String)] {
+        &self.chat
+    }
+
+    /// Send a chat message to the remote player
+    ///
+    /// # Parameter
+    ///
+    /// - `text` - The text of the message
+    ///
+    /// # Return
+    ///
+    /// The returned value is `true` if the message has been sent; otherwise, it returns `false`.
+    pub fn say(&mut self, text: &str) -> bool {
+        let Some(remote) = &self.remote else {
+            self.messages.push(Message::warning(
+                "The chat is only available in network games",
+            ));
+            return false;
+        };
+
+        let sender = -remote.player;
+
+        if let Err(error) = remote
+            .connection
+            .send(&crate::net::NetMessage::Chat(text.to_owned()))
+        {
+            tracing::warn!("A chat message cannot be sent: {error}");
+
+            self.messages.push(Message::warning(
+                "The connection to the remote player is lost",
+            ));
+            return false;
+        }
+
+        self.chat.push((sender, text.to_owned()));
+        true
+    }
+
+    /// Get the [personality](crate::ai::Personality) of the computer opponent, if any
+    pub fn opponent(&self) -> Option<crate::ai::Personality> {
+        self.opponent.as_ref().map(|(_, ai)| ai.personality())
+    }
+
+    /// Get the [difficulty](crate::ai::Difficulty) of the computer opponent, if any
+    pub fn difficulty(&self) -> Option<crate::ai::Difficulty> {
+        self.opponent.as_ref().map(|(_, ai)| ai.difficulty())
+    }
+
+    /// Get the name describing the nature of the player of a given color
+    ///
+    /// The name is `Human`, `Remote` or `Computer` followed by the personality and the difficulty of the computer opponent.
+    ///
+    /// # Parameter
+    ///
+    /// - `player` - The color of the player
+    pub fn player_name(&self, player: Player) -> String {
+        if self.opponent_player() == Some(player) {
+            self.opponent()
+                .zip(self.difficulty())
+                .map(|(personality, difficulty)| format!("Computer ({personality}, {difficulty})"))
+                .unwrap_or("Computer".to_owned())
+        } else if self.remote_player() == Some(player) {
+            "Remote".to_owned()
+        } else {
+            "Human".to_owned()
+        }
+    }
+
+    /// Get the [clock](crate::clock::Clock) of the game, if any
+    pub fn clock(&self) -> Option<&crate::clock::Clock> {
+        self.clock.as_ref()
+    }
+
+    /// Get the [outcome](Outcome) of the game
+    ///
+    /// A player who has [resigned](Self::resign) or run out of time loses; otherwise, this method is just a wrapper around [`Board::outcome()`](crate::game::Board::outcome()).
+    pub fn outcome(&self) -> Option<Outcome> {
+        if let Some(player) = self.resigned {
+            Some(Outcome::Win(-player))
+        } else if let Some(player) = self.clock.as_ref().and_then(crate::clock::Clock::flagged) {
+            Some(Outcome::Win(-player))
+        } else {
+            self.board.outcome()
+        }
+    }
+
+    /// Get the [player](Player) whose turn it is
+    pub fn current_player(&self) -> Player {
+        self.current_player
+    }
+
+    /// Get the current [score](crate::game::Score)
+    ///
+    /// This method is just a wrapper around [`Board::score()`](crate::game::Board::score()).
+    pub fn score(&self) -> crate::game::Score {
+        self.board.score()
+    }
+
+    /// Get the number of moves played since the beginning of the game
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    /// Get the time spent playing the game, as measured by the [ticks](Self::tick) until it is over
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Get the [record](GameRecord) of the game
+    pub fn record(&self) -> &GameRecord {
+        &self.record
+    }
+
+    /// Get the number of _blobs_ captured by each player since the beginning of the game
+    pub fn captures(&self) -> crate::game::Score {
+        self.captures
+    }
+
+    /// Get the number of _blobs_ captured by the last ply
+    ///
+    /// `None` is returned if no ply has been played yet or if the last ply is a pass.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Move, Player::*},
+    ///     widgets::board::BoardState,
+    /// };
+    ///
+    /// let board = Board::new(1, 4, vec![Player(Red), Free, Player(Blue), Free]);
+    /// let mut state = BoardState::new(board, Red);
+    /// assert_eq!(state.last_captures(), None);
+    ///
+    /// state.play_move(Move { from: (0, 0), to: (0, 1) });
+    /// assert_eq!(state.last_captures(), Some(1));
+    /// ```
+    pub fn last_captures(&self) -> Option<usize> {
+        self.record
+            .plies
+            .last()
+            .copied()
+            .flatten()
+            .map(|_| self.last_captures)
+    }
+
+    /// Get the height of the current board
+    ///
+    /// This method is just a wrapper around [`Board::height()`](crate::game::Board::height()).
+    pub fn height(&self) -> usize {
+        self.board.height()
+    }
+
+    /// Get the width of the current board
+    ///
+    /// This method is just a wrapper around [`Board::width()`](crate::game::Board::width()).
+    pub fn width(&self) -> usize {
+        self.board.width()
+    }
+
+    fn jump<F>(&mut self, jump: F)
+    where
+        F: FnOnce(Index) -> Index,
+    {
+        let selected @ (i, j) = jump(self.selected);
+
+        if self.board.contains(i, j) {
+            self.selected = selected;
+        }
+    }
+
+    /// Move the selector to the left
+    ///
+    /// # Example
     ///
     /// ```rust
     /// # use blobwars::{
@@ -201,14 +900,17 @@ impl BoardState {
     /// #
     /// # let board = Board::new(5, 5, board);
     /// let mut state = BoardState::new(board, Blue); // selected = (0, 0)
-    /// state.down(); // selected = (0, 1)
+    /// state.right(); // selected = (1, 0)
+    /// state.left(); // Selected = (0, 0)
+    /// state.left(); // Selected = (0, 0)
     /// ```
     #[inline]
-    pub fn down(&mut self) {
-        self.jump(|(i, j)| (i + 1, j));
+    pub fn left(&mut self) {
+        let (width, wrap, flipped) = (self.width(), self.wrap, self.is_flipped());
+        self.jump(|(i, j)| (i, shift(j, flipped, width, wrap)));
     }
 
-    /// Remove last selected position
+    /// Move the selector to the right
     ///
     /// # Example
     ///
@@ -228,29 +930,48 @@ impl BoardState {
     /// # ];
     /// #
     /// # let board = Board::new(5, 5, board);
-    /// let mut state = BoardState::new(board, Red); // 0 selected & selected = (0, 0)
-    /// state.select(); // 1 selected & selected = (0, 0)
-    /// state.down(); // 1 selected & selected = (0, 1)
-    /// state.select(); // 2 selected & selected = (0, 1)
-    /// state.reset(); // 1 selected & selected = (0, 1)
-    /// state.reset(); // 0 selected & selected = (0, 1)
-    /// state.reset(); // 0 selected & selected = (0, 1)
-    pub fn reset(&mut self) {
-        if self.to.is_some() {
-            self.to = None;
-        } else {
-            self.from = None;
-        }
+    /// let mut state = BoardState::new(board, Blue); // selected = (0, 0)
+    /// state.right(); // selected = (1, 0)
+    /// ```
+    #[inline]
+    pub fn right(&mut self) {
+        let (width, wrap, flipped) = (self.width(), self.wrap, self.is_flipped());
+        self.jump(|(i, j)| (i, shift(j, !flipped, width, wrap)));
     }
 
-    /// Select the cell just below the selector
-    ///
-    /// The first selection corresponds to the departure point; the second one corresponds to the destination point.
-    /// The departure point must be the color of the current player and the destination point must be [free](CellState::Free); if it is not respected, [`Self::select()`] has no effects.
+    /// Move the selector up
+    /// Move the selector to the left
     ///
-    /// # Return
+    /// # Example
     ///
-    /// The returned value is `true` if the selection causes a jump; otherwise, it returns `false`.
+    /// ```rust
+    /// # use blobwars::{
+    /// #     game::{Board, CellState::*, Player::*},
+    /// #     widgets::board::BoardState,
+    /// # };
+    /// #
+    /// # #[rustfmt::skip]
+    /// # let board = vec![
+    /// #     Player(Red), Free, Free,       Free, Free,
+    /// #     Free,        Free, Free,       Free, Free,
+    /// #     Free,        Free, Restricted, Free, Free,
+    /// #     Free,        Free, Free,       Free, Free,
+    /// #     Free,        Free, Free,       Free, Player(Blue),
+    /// # ];
+    /// #
+    /// # let board = Board::new(5, 5, board);
+    /// let mut state = BoardState::new(board, Blue); // selected = (0, 0)
+    /// state.down(); // selected = (0, 1)
+    /// state.up(); // Selected = (0, 0)
+    /// state.up(); // Selected = (0, 0)
+    /// ```
+    #[inline]
+    pub fn up(&mut self) {
+        let (height, wrap, flipped) = (self.height(), self.wrap, self.is_flipped());
+        self.jump(|(i, j)| (shift(i, flipped, height, wrap), j));
+    }
+
+    /// Move the selector down
     ///
     /// # Example
     ///
@@ -270,246 +991,2804 @@ impl BoardState {
     /// # ];
     /// #
     /// # let board = Board::new(5, 5, board);
-    /// let mut state = BoardState::new(board, Red); // 0 selected & selected = (0, 0)
-    /// assert!(!state.select()); // 1 selected & selected = (0, 0)
-    /// state.down(); // 1 selected & selected = (0, 1)
-    /// assert!(!state.select()); // 2 selected & selected = (0, 1)
-    /// assert!(state.select()); // jump & 0 selected & selected = (0, 1)
-    pub fn select(&mut self) -> bool {
-        let current_cell = self.board.get(self.selected.0, self.selected.1);
-
-        if let Some(from) = self.from {
-            if let Some(to) = self.to {
-                self.board.jump(from, to);
+    /// let mut state = BoardState::new(board, Blue); // selected = (0, 0)
+    /// state.down(); // selected = (0, 1)
+    /// ```
+    #[inline]
+    pub fn down(&mut self) {
+        let (height, wrap, flipped) = (self.height(), self.wrap, self.is_flipped());
+        self.jump(|(i, j)| (shift(i, !flipped, height, wrap), j));
+    }
 
-                self.from = None;
-                self.to = None;
+    /// Move the selector up and to the left
+    ///
+    /// A diagonal move is only made when both the row and the column change, so that the selector does not slide along an edge.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{game::{Board, Player::*}, widgets::board::BoardState};
+    ///
+    /// let mut state = BoardState::new(Board::free(4, 6), Blue); // selected = (0, 0)
+    /// state.down_right(); // selected = (1, 1)
+    /// state.up_right(); // selected = (0, 2)
+    /// state.up_left(); // selected = (0, 2)
+    /// state.down_left(); // selected = (1, 1)
+    /// ```
+    #[inline]
+    pub fn up_left(&mut self) {
+        self.diagonal(false, false);
+    }
 
-                return true;
-            } else if current_cell.map(CellState::is_free).unwrap_or_default() {
-                self.to = Some(self.selected);
-            }
-        } else if current_cell
-            .map(|selected| selected == CellState::Player(self.current_player))
-            .unwrap_or_default()
-        {
-            self.from = Some(self.selected);
-        }
+    /// Move the selector up and to the right
+    #[inline]
+    pub fn up_right(&mut self) {
+        self.diagonal(false, true);
+    }
 
-        false
+    /// Move the selector down and to the left
+    #[inline]
+    pub fn down_left(&mut self) {
+        self.diagonal(true, false);
     }
 
-    /// Pass to the next player
-    ///
-    /// The next player is the opponent.
-    pub fn pass_to_next_player(&mut self) {
-        self.current_player = -self.current_player;
+    /// Move the selector down and to the right
+    #[inline]
+    pub fn down_right(&mut self) {
+        self.diagonal(true, true);
     }
-}
 
-impl CommandManaged for BoardState {
-    fn handle_command(&mut self, command: Command) {
-        match command {
-            Command::Reset => self.reset(),
+    fn diagonal(&mut self, down: bool, right: bool) {
+        let (height, width, wrap, flipped) =
+            (self.height(), self.width(), self.wrap, self.is_flipped());
 
-            Command::Select => {
-                if self.select() {
-                    self.pass_to_next_player();
-                }
-            }
+        let (i, j) = self.selected;
 
-            Command::Left => self.left(),
-            Command::Right => self.right(),
-            Command::Up => self.up(),
-            Command::Down => self.down(),
-            _ => (),
+        let selected @ (k, l) = (
+            shift(i, down != flipped, height, wrap),
+            shift(j, right != flipped, width, wrap),
+        );
+
+        if k != i && l != j {
+            self.jump(|_| selected);
         }
     }
-}
 
-/// A board allowing to show a [`Board`](crate::game::Board)
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct Board<'a> {
-    /// The selected symbol
+    /// Move the selector to the first column of its row
+    #[inline]
+    pub fn first_column(&mut self) {
+        self.selected.1 = self.orient((0, 0)).1;
+    }
+
+    /// Move the selector to the last column of its row
+    #[inline]
+    pub fn last_column(&mut self) {
+        self.selected.1 = self.orient((0, self.width() - 1)).1;
+    }
+
+    /// Move the selector to the first row of its column
+    #[inline]
+    pub fn first_row(&mut self) {
+        self.selected.0 = self.orient((0, 0)).0;
+    }
+
+    /// Move the selector to the last row of its column
     ///
-    /// This symbol is used to show the selected cell.
-    pub selected_symbol: &'a str,
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{game::{Board, Player::*}, widgets::board::BoardState};
+    ///
+    /// let mut state = BoardState::new(Board::free(4, 6), Blue); // selected = (0, 0)
+    /// state.last_row(); // selected = (3, 0)
+    /// state.last_column(); // selected = (3, 5)
+    /// state.origin(); // selected = (0, 0)
+    /// ```
+    #[inline]
+    pub fn last_row(&mut self) {
+        self.selected.0 = self.orient((self.height() - 1, 0)).0;
+    }
 
-    /// The unselected symbol
+    /// Move the selector to the top-left corner of the board
+    #[inline]
+    pub fn origin(&mut self) {
+        self.selected = self.orient((0, 0));
+    }
+
+    /// Move the selector to the next _blob_ of the current player having at least one legal move
     ///
-    /// This symbol is used to show unselected cells.
-    pub unselected_symbol: &'a str,
-}
+    /// The cells are browsed row by row, starting just after the selector and wrapping around the board.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Player::*},
+    ///     widgets::board::BoardState,
+    /// };
+    ///
+    /// let board = Board::new(1, 4, vec![Player(Red), Free, Player(Blue), Player(Red)]);
+    /// let mut state = BoardState::new(board, Red); // selected = (0, 0)
+    ///
+    /// state.next_piece(); // selected = (0, 3)
+    /// state.select();
+    /// assert_eq!(state.from(), Some((0, 3)));
+    /// ```
+    pub fn next_piece(&mut self) {
+        self.cycle_pieces(!self.is_flipped());
+    }
 
-impl<'a> Board<'a> {
-    /// Set the [selected symbol](Self::selected_symbol)
-    pub fn selected_symbol(self, selected_symbol: &'a str) -> Self {
-        Self {
-            selected_symbol,
-            ..self
-        }
+    /// Move the selector to the previous _blob_ of the current player having at least one legal move
+    ///
+    /// The cells are browsed row by row, starting just before the selector and wrapping around the board.
+    pub fn previous_piece(&mut self) {
+        self.cycle_pieces(self.is_flipped());
     }
 
-    /// Set the [unselected symbol](Self::unselected_symbol)
-    pub fn unselected_symbol(self, unselected_symbol: &'a str) -> Self {
-        Self {
-            unselected_symbol,
-            ..self
+    fn cycle_pieces(&mut self, forward: bool) {
+        let (width, len) = (self.width(), self.board.len());
+        let start = self.selected.0 * width + self.selected.1;
+
+        let found = (1..=len)
+            .map(|offset| {
+                if forward {
+                    (start + offset) % len
+                } else {
+                    (start + len - offset) % len
+                }
+            })
+            .map(|position| (position / width, position % width))
+            .find(|&index @ (i, j)| {
+                self.board.get(i, j) == Some(CellState::Player(self.current_player))
+                    && self.board.moves_from(index).next().is_some()
+            });
+
+        if let Some(index) = found {
+            self.selected = index;
         }
     }
-}
 
-impl Default for Board<'static> {
-    fn default() -> Self {
-        Self {
-            selected_symbol: "V",
-            unselected_symbol: "O",
+    /// Remove last selected position
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use blobwars::{
+    /// #     game::{Board, CellState::*, Player::*},
+    /// #     widgets::board::BoardState,
+    /// # };
+    /// #
+    /// # #[rustfmt::skip]
+    /// # let board = vec![
+    /// #     Player(Red), Free, Free,       Free, Free,
+    /// #     Free,        Free, Free,       Free, Free,
+    /// #     Free,        Free, Restricted, Free, Free,
+    /// #     Free,        Free, Free,       Free, Free,
+    /// #     Free,        Free, Free,       Free, Player(Blue),
+    /// # ];
+    /// #
+    /// # let board = Board::new(5, 5, board);
+    /// let mut state = BoardState::new(board, Red); // 0 selected & selected = (0, 0)
+    /// state.select(); // 1 selected & selected = (0, 0)
+    /// state.down(); // 1 selected & selected = (0, 1)
+    /// state.select(); // 2 selected & selected = (0, 1)
+    /// state.reset(); // 1 selected & selected = (0, 1)
+    /// state.reset(); // 0 selected & selected = (0, 1)
+    /// state.reset(); // 0 selected & selected = (0, 1)
+    pub fn reset(&mut self) {
+        if self.to.is_some() {
+            self.to = None;
+        } else {
+            self.from = None;
         }
     }
-}
-
-impl StatefulWidget for Board<'_> {
-    type State = BoardState;
-
-    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let [area] = Layout::vertical(constraints![==100%]).areas(area);
-        let [area] = Layout::horizontal(constraints![==100%]).areas(area);
 
-        let constraints = once(constraint!(==1)).cycle().take(state.height());
-        let mut state_iterator = state.board.iter();
-        let rows = Layout::vertical(constraints).flex(Flex::Center).split(area);
+    /// Select the cell just below the selector
+    ///
+    /// The first selection corresponds to the departure point; the second one corresponds to the destination point.
+    /// The departure point must be the color of the current player and the destination point must be [free](CellState::Free) and within [jump distance](crate::game::Board::jump_distance) of the departure point; if it is not respected, [`Self::select()`] has no effects but a warning.
+    /// Once the game is over, nothing can be selected anymore.
+    /// The third selection plays the jump; the selection is cleared even if the jump is refused, so that a new departure point can be selected.
+    ///
+    /// # Return
+    ///
+    /// The returned value is `true` if the selection causes a jump; otherwise, it returns `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use blobwars::{
+    /// #     game::{Board, CellState::*, Player::*},
+    /// #     widgets::board::BoardState,
+    /// # };
+    /// #
+    /// # #[rustfmt::skip]
+    /// # let board = vec![
+    /// #     Player(Red), Free, Free,       Free, Free,
+    /// #     Free,        Free, Free,       Free, Free,
+    /// #     Free,        Free, Restricted, Free, Free,
+    /// #     Free,        Free, Free,       Free, Free,
+    /// #     Free,        Free, Free,       Free, Player(Blue),
+    /// # ];
+    /// #
+    /// # let board = Board::new(5, 5, board);
+    /// let mut state = BoardState::new(board, Red); // 0 selected & selected = (0, 0)
+    /// assert!(!state.select()); // 1 selected & selected = (0, 0)
+    /// state.down(); // 1 selected & selected = (0, 1)
+    /// assert!(!state.select()); // 2 selected & selected = (0, 1)
+    /// assert!(state.select()); // jump & 0 selected & selected = (0, 1)
+    pub fn select(&mut self) -> bool {
+        if self.outcome().is_some() {
+            self.from = None;
+            self.to = None;
 
-        for (i, row) in rows.iter().copied().enumerate() {
-            let constraints = once(constraint!(==1)).cycle().take(state.width());
+            self.messages.push(Message::warning("The game is over"));
+            return false;
+        }
 
-            let column = Layout::horizontal(constraints)
-                .flex(Flex::Center)
-                .spacing(1)
-                .split(row);
+        let current_cell = self.board.get(self.selected.0, self.selected.1);
 
-            for (j, area) in column.iter().copied().enumerate() {
-                if let Some(current) = state_iterator.next() {
-                    let content = if (i, j) == state.selected {
-                        self.selected_symbol
-                    } else {
-                        self.unselected_symbol
-                    };
+        if let Some(from) = self.from {
+            if let Some(to) = self.to {
+                self.from = None;
+                self.to = None;
 
-                    Text::raw(content).fg(current).render(area, buf);
-                }
+                return self.apply(Move { from, to }, self.current_player);
+            } else if !current_cell.map(CellState::is_free).unwrap_or_default() {
+                self.messages
+                    .push(Message::warning("Illegal move: destination occupied"));
+            } else if self.board.jump_distance(from, self.selected).is_none() {
+                self.messages
+                    .push(Message::warning("Illegal move: destination too far"));
+            } else {
+                self.to = Some(self.selected);
             }
+        } else if current_cell
+            .map(|selected| selected == CellState::Player(self.current_player))
+            .unwrap_or_default()
+        {
+            self.from = Some(self.selected);
+        } else {
+            self.messages.push(Message::warning(format!(
+                "Select one of the {} blobs",
+                self.current_player
+            )));
         }
+
+        false
     }
-}
 
-/// The score widget
-pub struct Score {
-    /// The [theme](Theme) used to colorize text
-    pub theme: Theme,
-}
+    /// Pass the turn of the current player
+    ///
+    /// Unless [passing at will](Self::with_free_pass) is allowed, a player may only pass when they have no legal move.
+    ///
+    /// # Return
+    ///
+    /// The returned value is `true` if the turn has been passed; otherwise, it returns `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Player::*},
+    ///     widgets::board::BoardState,
+    /// };
+    ///
+    /// let board = Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+    ///
+    /// let mut state = BoardState::new(board.clone(), Red);
+    /// assert!(!state.pass());
+    /// assert_eq!(state.current_player(), Red);
+    ///
+    /// let mut state = BoardState::new(board, Red).with_free_pass(true);
+    /// assert!(state.pass());
+    /// assert_eq!(state.current_player(), Blue);
+    /// ```
+    pub fn pass(&mut self) -> bool {
+        if !self.can_act() {
+            return false;
+        }
 
-impl StatefulWidget for Score {
-    type State = BoardState;
+        if !self.free_pass && self.board.can_play(self.current_player) {
+            self.messages.push(Message::warning(format!(
+                "{} cannot pass while having a legal move",
+                self.current_player
+            )));
 
-    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let crate::game::Score { red, blue } = state.board.score();
+            return false;
+        }
 
-        let text = text![
-            span!(self.theme.title; "Status:"),
-            line![],
-            line![
-                span!(self.theme.important; "Current player"),
-                ": ",
-                state.current_player,
-            ],
-            line![
-                span!(self.theme.important; "From (selected)"),
-                ": ",
-                display_selected(state.from),
-            ],
-            line![
-                span!(self.theme.important; "To (selected)"),
-                ": ",
-                display_selected(state.to),
-            ],
-            line![],
-            span!(self.theme.title; "Score:"),
-            line![],
-            line![Player::Blue, ": ", blue.to_string()],
-            line![Player::Red, ": ", red.to_string()],
-        ];
+        self.from = None;
+        self.to = None;
 
-        Paragraph::new(text)
-            .block(Block::bordered().title("Score"))
-            .wrap(Wrap { trim: true })
-            .render(area, buf);
+        self.messages
+            .push(Message::info(format!("{} passes", self.current_player)));
+
+        self.record.plies.push(None);
+
+        self.end_turn();
+        true
     }
-}
 
-fn display_selected(value: Option<Index>) -> String {
-    if let Some((i, j)) = value {
-        format!("({i}, {j})")
-    } else {
-        "(None)".to_owned()
+    /// Pass to the next player
+    ///
+    /// The next player is the opponent.
+    pub fn pass_to_next_player(&mut self) {
+        self.current_player = -self.current_player;
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::game::CellState::*;
-    use crate::game::Player::*;
+    /// Undo the last ply
+    ///
+    /// Against a computer opponent, the plies are undone until it is the turn of the human player again.
+    /// The undone plies can be [redone](Self::redo) as long as no other ply is played; the [clock](crate::clock::Clock) is not rewound.
+    ///
+    /// # Return
+    ///
+    /// The returned value is `true` if a ply has been undone; otherwise, it returns `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Move, Player::*},
+    ///     widgets::board::BoardState,
+    /// };
+    ///
+    /// let board = Board::new(1, 4, vec![Player(Red), Free, Free, Player(Blue)]);
+    /// let mut state = BoardState::new(board, Red);
+    ///
+    /// assert!(!state.undo());
+    ///
+    /// state.play_move(Move { from: (0, 0), to: (0, 1) });
+    /// assert!(state.undo());
+    ///
+    /// assert_eq!(state.current_player(), Red);
+    /// assert_eq!(state.moves(), 0);
+    /// assert!(state.record().plies.is_empty());
+    /// ```
+    pub fn undo(&mut self) -> bool {
+        if self.outcome().is_some() {
+            self.messages.push(Message::warning("The game is over"));
+            return false;
+        }
+
+        if self.remote.is_some() {
+            self.messages.push(Message::warning(
+                "A move cannot be undone in a network game",
+            ));
+            return false;
+        }
+
+        let Some(ply) = self.record.plies.pop() else {
+            self.messages
+                .push(Message::warning("There is no move to undo"));
+            return false;
+        };
+
+        self.cancel_thinking();
+        self.redone.push(ply);
+        self.replay();
+
+        while self.is_opponent_turn()
+            && let Some(ply) = self.record.plies.pop()
+        {
+            self.redone.push(ply);
+            self.replay();
+        }
+
+        self.from = None;
+        self.to = None;
+        self.animation = None;
+
+        self.messages.push(Message::info("Move undone"));
+        self.play_opponent();
+        true
+    }
+
+    /// Redo the last [undone](Self::undo) ply
+    ///
+    /// Against a computer opponent, its undone reply is redone too.
+    ///
+    /// # Return
+    ///
+    /// The returned value is `true` if a ply has been redone; otherwise, it returns `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Move, Player::*},
+    ///     widgets::board::BoardState,
+    /// };
+    ///
+    /// let board = Board::new(1, 4, vec![Player(Red), Free, Free, Player(Blue)]);
+    /// let mut state = BoardState::new(board, Red);
+    ///
+    /// state.play_move(Move { from: (0, 0), to: (0, 1) });
+    /// state.undo();
+    ///
+    /// assert!(state.redo());
+    /// assert!(!state.redo());
+    ///
+    /// assert_eq!(state.current_player(), Blue);
+    /// assert_eq!(state.moves(), 1);
+    /// ```
+    pub fn redo(&mut self) -> bool {
+        if !self.can_act() {
+            return false;
+        }
+
+        let mut redone = std::mem::take(&mut self.redone);
+
+        let Some(mut ply) = redone.pop() else {
+            self.messages
+                .push(Message::warning("There is no move to redo"));
+            return false;
+        };
+
+        self.from = None;
+        self.to = None;
+
+        loop {
+            let player = self.current_player;
+
+            match ply {
+                Some(chosen) => {
+                    self.apply(chosen, player);
+                }
+
+                None => self.record.plies.push(None),
+            }
+
+            if let Some(clock) = &mut self.clock {
+                clock.end_turn(player);
+            }
+
+            self.pass_to_next_player();
+
+            match redone.pop() {
+                Some(next) if self.is_opponent_turn() => ply = next,
+
+                Some(next) => {
+                    redone.push(next);
+                    break;
+                }
+
+                None => break,
+            }
+        }
+
+        self.redone = redone;
+        self.play_opponent();
+        true
+    }
+
+    /// Resign the game
+    ///
+    /// Against a computer opponent, the human player resigns even during the turn of the opponent; otherwise, the current player resigns.
+    ///
+    /// # Return
+    ///
+    /// The returned value is `true` if a player has resigned; otherwise, it returns `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Outcome, Player::*},
+    ///     widgets::board::BoardState,
+    /// };
+    ///
+    /// let board = Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+    /// let mut state = BoardState::new(board, Red);
+    ///
+    /// assert!(state.resign());
+    /// assert!(!state.resign());
+    /// assert_eq!(state.outcome(), Some(Outcome::Win(Blue)));
+    /// ```
+    pub fn resign(&mut self) -> bool {
+        if self.outcome().is_some() {
+            self.messages.push(Message::warning("The game is over"));
+            return false;
+        }
+
+        let player = self
+            .adversary()
+            .map_or(self.current_player, |player| -player);
+
+        if let Some(remote) = &self.remote
+            && let Err(error) = remote.connection.send(&crate::net::NetMessage::Resign)
+        {
+            tracing::warn!("The resignation cannot be sent: {error}");
+        }
+
+        self.cancel_thinking();
+        self.from = None;
+        self.to = None;
+        self.resigned = Some(player);
+
+        tracing::info!("{player} resigns");
+        self.messages
+            .push(Message::info(format!("{player} resigns")));
+        true
+    }
+
+    /// Suggest a move to the current player
+    ///
+    /// The suggested move is selected on the board, so that it is played by [selecting](Self::select) it once more.
+    ///
+    /// # Return
+    ///
+    /// The returned value is `true` if a move has been suggested; otherwise, it returns `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Player::*},
+    ///     widgets::board::BoardState,
+    /// };
+    ///
+    /// let board = Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+    /// let mut state = BoardState::new(board, Red);
+    ///
+    /// assert!(state.hint());
+    /// assert_eq!(state.from(), Some((0, 0)));
+    /// assert!(state.select());
+    /// ```
+    pub fn hint(&mut self) -> bool {
+        if !self.can_act() {
+            return false;
+        }
+
+        let chosen = Ai::new(crate::ai::Personality::Aggressive)
+            .with_rng(self.rng.fork())
+            .with_difficulty(crate::ai::Difficulty::Hard)
+            .choose(&self.board, self.current_player);
+
+        if let Some(chosen) = chosen {
+            self.from = Some(chosen.from);
+            self.to = Some(chosen.to);
+            self.selected = chosen.to;
+
+            self.messages.push(Message::info(format!(
+                "Suggested move: {}{}",
+                display_index(chosen.from),
+                display_index(chosen.to)
+            )));
+
+            true
+        } else {
+            self.messages.push(Message::warning(format!(
+                "{} has no legal move",
+                self.current_player
+            )));
+
+            false
+        }
+    }
+
+    /// Check if the computer opponent is searching its next move
+    pub fn is_thinking(&self) -> bool {
+        self.thinking.is_some()
+    }
+
+    /// Check if a move is being [animated](Animation)
+    pub fn is_animating(&self) -> bool {
+        self.animation.is_some()
+    }
+
+    /// Get the [messages](MessageQueue) giving feedback to the players
+    pub fn messages(&self) -> &MessageQueue {
+        &self.messages
+    }
+
+    /// Push a [message](Message) giving feedback to the players
+    pub fn notify(&mut self, message: Message) {
+        self.messages.push(message);
+    }
+
+    /// Check if the state changes over time
+    ///
+    /// The state changes over time while the computer opponent is thinking, while a move is being [animated](Animation), while a [message](Message) is shown or while the [clock](crate::clock::Clock) is running.
+    pub fn is_running(&self) -> bool {
+        self.is_thinking()
+            || self.is_animating()
+            || (self.remote.is_some() && self.outcome().is_none())
+            || !self.messages.is_empty()
+            || (self.clock.is_some() && self.outcome().is_none())
+    }
+
+    /// Cancel the search of the computer opponent, if any
+    pub fn cancel_thinking(&mut self) {
+        if let Some(thinking) = self.thinking.take() {
+            thinking.cancel();
+        }
+    }
+
+    /// Get the [connection](crate::net::Connection) to the remote player, if any
+    pub fn connection(&self) -> Option<&crate::net::Connection> {
+        self.remote.as_ref().map(|remote| &remote.connection)
+    }
+
+    /// Deliver the [result](TaskEvent) of a task of the game, to be applied on the next [update](Self::update)
+    ///
+    /// The results of a cancelled search or of another connection are ignored.
+    pub fn deliver(&mut self, event: TaskEvent) {
+        let expected = match &event {
+            TaskEvent::Searched(thinking, _) => self.thinking.as_ref() == Some(thinking),
+
+            TaskEvent::Received(connection, _) | TaskEvent::Restored(connection) => {
+                self.connection() == Some(connection)
+            }
+        };
+
+        if expected {
+            self.inbox.push_back(event);
+        }
+    }
+
+    /// Deliver the result of the search of the computer opponent and the messages of the remote player received so far, without blocking
+    ///
+    /// This method is expected to be called regularly by the main loop when the tasks of the game are not [watched](crate::events::EventLoop::watch).
+    pub fn poll(&mut self) {
+        if let Some(thinking) = &self.thinking
+            && let Some(result) = thinking.try_result()
+        {
+            self.inbox
+                .push_back(TaskEvent::Searched(thinking.clone(), result));
+        }
+
+        if let Some(connection) = self.connection().cloned() {
+            if connection.take_restored() {
+                self.inbox
+                    .push_back(TaskEvent::Restored(connection.clone()));
+            }
+
+            while let Some(message) = connection.try_recv() {
+                self.inbox
+                    .push_back(TaskEvent::Received(connection.clone(), message));
+            }
+        }
+    }
+
+    /// Apply the [delivered](Self::deliver) results of the tasks of the game, a move waiting for the animation of the previous one
+    ///
+    /// This method is expected to be called regularly by the main loop.
+    pub fn update(&mut self) {
+        while let Some(event) = self.inbox.front() {
+            if self.is_animating() && !matches!(event, TaskEvent::Restored(_)) {
+                break;
+            }
+
+            match self.inbox.pop_front() {
+                Some(TaskEvent::Searched(thinking, result)) => self.searched(&thinking, result),
+                Some(TaskEvent::Received(_, message)) => self.receive(message),
+                Some(TaskEvent::Restored(_)) => self.restored(),
+                None => (),
+            }
+        }
+    }
+
+    /// Play the move chosen by the computer opponent, unless its search has been cancelled meanwhile
+    fn searched(&mut self, thinking: &Thinking, (ai, chosen): SearchResult) {
+        if self.thinking.as_ref() != Some(thinking) {
+            return;
+        }
+
+        self.thinking = None;
+
+        if let Some((_, opponent)) = &mut self.opponent {
+            *opponent = ai;
+        }
+
+        if self.outcome().is_none() {
+            if let (Some(chosen), Some((player, _))) = (chosen, &self.opponent) {
+                self.apply(chosen, *player);
+            }
+
+            self.end_turn();
+        }
+    }
+
+    /// Resume the game once the connection to the remote player is restored
+    fn restored(&mut self) {
+        if self.outcome().is_none()
+            && let Some(remote) = &self.remote
+        {
+            let resume = crate::net::NetMessage::Resume {
+                game: remote.game.clone(),
+                plies: self.record.plies.len(),
+            };
+
+            if let Err(error) = remote.connection.send(&resume) {
+                tracing::warn!("The game cannot be resumed: {error}");
+            }
+
+            self.messages.push(Message::info(
+                "The connection to the remote player is restored",
+            ));
+        }
+    }
+
+    /// Handle a message received from the remote player
+    fn receive(&mut self, message: crate::net::NetMessage) {
+        let Some(remote) = &self.remote else {
+            return;
+        };
+
+        if self.outcome().is_some() {
+            return;
+        }
+
+        let player = remote.player;
+        let game = remote.game.clone();
+
+        match message {
+            crate::net::NetMessage::Ply(ply) if player == self.current_player => {
+                self.play_remote(player, ply)
+            }
+
+            crate::net::NetMessage::Resume {
+                game: resumed,
+                plies,
+            } if resumed == game => self.send_missed(plies),
+
+            crate::net::NetMessage::Replay { from, plies } => self.catch_up(player, from, plies),
+
+            crate::net::NetMessage::Start {
+                game: resumed,
+                record,
+                ..
+            } if resumed == game => {
+                tracing::info!("The game is resynchronized from a snapshot");
+                self.cancel_thinking();
+                self.from = None;
+                self.to = None;
+                self.animation = None;
+                self.record = *record;
+                self.replay();
+            }
+
+            crate::net::NetMessage::Chat(text) => {
+                self.messages
+                    .push(Message::info(format!("{player}: {text}")));
+                self.chat.push((player, text));
+            }
+
+            crate::net::NetMessage::Resign => {
+                self.resigned = Some(player);
+                self.messages
+                    .push(Message::info(format!("{player} resigns")));
+            }
+
+            crate::net::NetMessage::Bye => {
+                let lost = self
+                    .remote
+                    .as_ref()
+                    .is_some_and(|remote| remote.connection.is_lost());
+
+                self.resigned = Some(player);
+
+                self.messages.push(Message::warning(if lost {
+                    format!("The connection to {player} cannot be restored")
+                } else {
+                    format!("{player} has left the game")
+                }));
+            }
+
+            crate::net::NetMessage::Refused(reason) => {
+                tracing::warn!("The server has refused a message: {reason}");
+                self.resigned = Some(-player);
+                self.messages.push(Message::warning(reason));
+            }
+
+            _ => self.forfeit(player),
+        }
+    }
+
+    /// Run the [clock](crate::clock::Clock) of the current player and the [animation](Animation) of the last move, if any
+    ///
+    /// This method is expected to be called on each tick of the main loop.
+    ///
+    /// # Parameter
+    ///
+    /// - `elapsed` - The time elapsed since the last tick
+    pub fn tick(&mut self, elapsed: Duration) {
+        if self.outcome().is_none() {
+            self.duration += elapsed;
+
+            if let Some(clock) = &mut self.clock {
+                clock.tick(self.current_player, elapsed);
+            }
+        }
+
+        self.messages.tick(elapsed);
+
+        if let Some(animation) = &mut self.animation {
+            animation.tick(elapsed);
+
+            if animation.is_finished() {
+                self.animation = None;
+            }
+        }
+    }
+
+    /// Get the cells of the last rendering of the [`Board` widget](Board) with their areas
+    pub fn cells(&self) -> &[(Index, Rect)] {
+        &self.cells
+    }
+
+    /// Get the content of a cell as it is currently displayed
+    ///
+    /// This content differs from the one of the [board](Self::board) while a move is [animated](Self::is_animating).
+    pub fn displayed(&self, index: Index) -> Option<CellState> {
+        let (i, j) = index;
+
+        self.board.get(i, j).map(|current| {
+            self.animation
+                .as_ref()
+                .map_or(current, |animation| animation.cell(index, current))
+        })
+    }
+
+    /// Get the cell rendered at the given [position](Position)
+    ///
+    /// The hit-testing is based on the last rendering of the [`Board`] widget; if nothing has been rendered yet, `None` is returned.
+    pub fn cell_at(&self, position: Position) -> Option<Index> {
+        self.cells
+            .iter()
+            .find(|(_, area)| area.contains(position))
+            .map(|&(index, _)| index)
+    }
+
+    /// Press the pointer at the given [position](Position)
+    ///
+    /// Pressing on a _blob_ of the current player selects it as the departure point and starts a drag; pressing elsewhere behaves like [`Self::select()`].
+    pub fn press(&mut self, position: Position) {
+        if let Some(index) = self.cell_at(position)
+            && self.can_act()
+        {
+            if self.board.get(index.0, index.1) == Some(CellState::Player(self.current_player)) {
+                self.selected = index;
+                self.from = Some(index);
+                self.to = None;
+                self.dragging = true;
+            } else {
+                self.click(index);
+            }
+        }
+    }
+
+    /// Drag the pointer to the given [position](Position)
+    ///
+    /// During a drag, the selector follows the pointer.
+    pub fn drag(&mut self, position: Position) {
+        if self.dragging
+            && let Some(index) = self.cell_at(position)
+        {
+            self.selected = index;
+        }
+    }
+
+    /// Release the pointer at the given [position](Position)
+    ///
+    /// Releasing a drag on another cell than the departure point selects it as the destination point and plays the move.
+    pub fn release(&mut self, position: Position) {
+        if self.dragging {
+            self.dragging = false;
+
+            if let Some(index) = self.cell_at(position)
+                && Some(index) != self.from
+            {
+                self.click(index);
+            }
+        }
+    }
+
+    /// Play a [move](Move) of the current player
+    ///
+    /// The move is played as if its departure and destination points were clicked, so that an illegal move is reported like a selected one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Move, Player::*},
+    ///     widgets::board::BoardState,
+    /// };
+    ///
+    /// let mut state = BoardState::new(Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]), Red);
+    /// state.play_move(Move { from: (0, 0), to: (0, 1) });
+    ///
+    /// assert_eq!(state.current_player(), Blue);
+    /// ```
+    pub fn play_move(&mut self, chosen: Move) {
+        self.from = None;
+        self.to = None;
+        self.click(chosen.from);
+
+        if self.from.is_some() {
+            self.click(chosen.to);
+        }
+    }
+
+    fn click(&mut self, index: Index) {
+        self.selected = index;
+        self.play();
+
+        if self.to.is_some() {
+            self.play();
+        }
+    }
+
+    /// Check if the current player is allowed to act, warning the players otherwise
+    ///
+    /// No warning is given during an animation since it is about to end.
+    fn can_act(&mut self) -> bool {
+        let warning = if self.outcome().is_some() {
+            "The game is over"
+        } else if self.is_thinking() {
+            "Wait for the computer to play"
+        } else if self.remote_player() == Some(self.current_player) {
+            "Wait for the remote player to play"
+        } else {
+            return !self.is_animating();
+        };
+
+        self.messages.push(Message::warning(warning));
+        false
+    }
+
+    fn apply(&mut self, chosen: Move, player: Player) -> bool {
+        let animation = Animation::new(&self.board, chosen, player);
+        let before = self.board.score().get(-player);
+
+        if self.board.jump(chosen.from, chosen.to) {
+            self.animation = Some(animation);
+            self.moves += 1;
+
+            let captured = before - self.board.score().get(-player);
+
+            tracing::debug!(
+                "{player} plays {}{} and captures {captured}",
+                display_index(chosen.from),
+                display_index(chosen.to)
+            );
+
+            *self.captures.get_mut(player) += captured;
+            self.last_captures = captured;
+            self.record.plies.push(Some(chosen));
+
+            if captured > 0 {
+                let plural = if captured > 1 { "s" } else { "" };
+                self.messages.push(Message::info(format!(
+                    "{player} captured {captured} blob{plural}"
+                )));
+            }
+
+            true
+        } else {
+            self.messages
+                .push(Message::warning("Illegal move: destination too far"));
+
+            false
+        }
+    }
+
+    fn play(&mut self) {
+        if self.can_act() && self.select() {
+            self.end_turn();
+        }
+    }
+
+    /// Recompute the position from the initial board of the [record](GameRecord) and its plies
+    fn replay(&mut self) {
+        self.board = self.record.board.clone();
+        self.current_player = self.record.first;
+        self.moves = 0;
+        self.captures = crate::game::Score::default();
+
+        for chosen in self.record.plies.clone() {
+            if let Some(chosen) = chosen {
+                let player = self.current_player;
+                let before = self.board.score().get(-player);
+
+                self.board.jump(chosen.from, chosen.to);
+                self.moves += 1;
+                self.last_captures = before - self.board.score().get(-player);
+                *self.captures.get_mut(player) += self.last_captures;
+            }
+
+            self.pass_to_next_player();
+        }
+    }
+
+    fn end_turn(&mut self) {
+        self.redone.clear();
+
+        if let Some(remote) = &self.remote
+            && remote.player != self.current_player
+            && let Some(&ply) = self.record.plies.last()
+            && let Err(error) = remote.connection.send(&crate::net::NetMessage::Ply(ply))
+        {
+            tracing::warn!("A ply cannot be sent: {error}");
+
+            // The ply is replayed once the connection is restored
+            if !remote.connection.is_lost() {
+                self.messages.push(Message::warning(
+                    "The connection to the remote player is lost",
+                ));
+            }
+        }
+
+        if let Some(clock) = &mut self.clock {
+            clock.end_turn(self.current_player);
+        }
+
+        self.pass_to_next_player();
+
+        if self.outcome().is_none() && !self.board.can_play(self.current_player) {
+            self.messages.push(Message::warning(format!(
+                "{} must pass",
+                self.current_player
+            )));
+
+            if self.is_opponent_turn() {
+                self.record.plies.push(None);
+                self.pass_to_next_player();
+            }
+        }
+
+        self.play_opponent();
+    }
+
+    /// Get the [player](Player) played by the computer opponent or the remote player, if any
+    fn adversary(&self) -> Option<Player> {
+        self.opponent_player().or(self.remote_player())
+    }
+
+    /// Play a ply received from the remote player, who forfeits if it is illegal
+    fn play_remote(&mut self, player: Player, ply: Option<Move>) {
+        let legal = match ply {
+            Some(chosen) => {
+                self.board.get(chosen.from.0, chosen.from.1) == Some(CellState::Player(player))
+                    && self.apply(chosen, player)
+            }
+
+            None if self.free_pass || !self.board.can_play(player) => {
+                self.messages
+                    .push(Message::info(format!("{player} passes")));
+                self.record.plies.push(None);
+                true
+            }
+
+            None => false,
+        };
+
+        if legal {
+            self.from = None;
+            self.to = None;
+            self.end_turn();
+        } else {
+            self.forfeit(player);
+        }
+    }
+
+    /// Replay the plies missed by the remote player while the connection was lost
+    ///
+    /// # Parameter
+    ///
+    /// - `known` - The number of plies known to the remote player
+    fn send_missed(&self, known: usize) {
+        if let Some(remote) = &self.remote
+            && let Some(missed) = self.record.plies.get(known..)
+            && !missed.is_empty()
+        {
+            let replay = crate::net::NetMessage::Replay {
+                from: known,
+                plies: missed.to_vec(),
+            };
+
+            if let Err(error) = remote.connection.send(&replay) {
+                tracing::warn!("The missed plies cannot be sent: {error}");
+            }
+        }
+    }
+
+    /// Play the plies of the remote player missed while the connection was lost
+    ///
+    /// The replayed plies already known are checked to be the same.
+    fn catch_up(&mut self, player: Player, from: usize, plies: Vec<Option<Move>>) {
+        if from > self.record.plies.len() {
+            return self.forfeit(player);
+        }
+
+        for (index, ply) in (from..).zip(plies) {
+            if self.outcome().is_some() {
+                break;
+            }
+
+            match self.record.plies.get(index) {
+                Some(&known) if known == ply => (),
+                None if player == self.current_player => self.play_remote(player, ply),
+                _ => return self.forfeit(player),
+            }
+        }
+    }
+
+    /// End the game after an unexpected message of the remote player
+    fn forfeit(&mut self, player: Player) {
+        tracing::warn!("{player} sent an unexpected message and forfeits");
+        self.resigned = Some(player);
+
+        self.messages.push(Message::warning(format!(
+            "{player} broke the rules and forfeits"
+        )));
+    }
+
+    fn is_opponent_turn(&self) -> bool {
+        self.opponent
+            .as_ref()
+            .is_some_and(|(player, _)| *player == self.current_player)
+    }
+
+    fn play_opponent(&mut self) {
+        if let Some((player, ai)) = &self.opponent
+            && *player == self.current_player
+            && self.outcome().is_none()
+        {
+            self.thinking = Some(ai.think(self.board.clone(), *player));
+        }
+    }
+}
+
+impl CommandManaged for BoardState {
+    fn handle_command(&mut self, command: Command) {
+        match command {
+            Command::Reset => self.reset(),
+
+            Command::Select => self.play(),
+
+            Command::Pass => {
+                self.pass();
+            }
+
+            Command::Undo => {
+                self.undo();
+            }
+
+            Command::Redo => {
+                self.redo();
+            }
+
+            Command::Hint => {
+                self.hint();
+            }
+
+            Command::Left => self.left(),
+            Command::Right => self.right(),
+            Command::Up => self.up(),
+            Command::Down => self.down(),
+            Command::UpLeft => self.up_left(),
+            Command::UpRight => self.up_right(),
+            Command::DownLeft => self.down_left(),
+            Command::DownRight => self.down_right(),
+            Command::FirstColumn => self.first_column(),
+            Command::LastColumn => self.last_column(),
+            Command::FirstRow => self.first_row(),
+            Command::LastRow => self.last_row(),
+            Command::Origin => self.origin(),
+            Command::NextPiece => self.next_piece(),
+            Command::PreviousPiece => self.previous_piece(),
+            Command::Press(position) => self.press(position),
+            Command::Drag(position) => self.drag(position),
+            Command::Release(position) => self.release(position),
+            _ => (),
+        }
+    }
+}
+
+/// A board allowing to show a [`Board`](crate::game::Board)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Board<'a> {
+    /// The selected symbol
+    ///
+    /// This symbol is used to show the selected cell.
+    pub selected_symbol: &'a str,
+
+    /// The unselected symbol
+    ///
+    /// This symbol is used to show unselected cells.
+    pub unselected_symbol: &'a str,
+
+    /// The style of the destinations reachable with a spread
+    ///
+    /// This style is used to highlight the free cells adjacent to the departure point.
+    pub spread_style: Style,
+
+    /// The style of the destinations reachable with a jump
+    ///
+    /// This style is used to highlight the free cells at a distance of two from the departure point.
+    pub jump_style: Style,
+
+    /// The style of the selected departure point
+    pub departure_style: Style,
+
+    /// The style of the selected destination point
+    pub destination_style: Style,
+
+    /// The style of the shaded cells of the [checkerboard](Grid::Checkerboard)
+    pub shade_style: Style,
+
+    /// The style of the scoring cells of the [hill](crate::game::Board::with_hill)
+    pub hill_style: Style,
+
+    /// The [grid](Grid) drawn to make the rows and columns easier to track
+    pub grid: Grid,
+
+    /// The [style](RenderStyle) used to render the cells
+    pub render_style: RenderStyle,
+
+    /// Whether each player has its own symbol
+    ///
+    /// If it is `true`, the _blobs_ can be distinguished without relying on colors.
+    pub distinct_symbols: bool,
+
+    /// The [theme](Theme) giving the colors of the cells
+    pub theme: Theme,
+}
+
+impl<'a> Board<'a> {
+    /// Set the [selected symbol](Self::selected_symbol)
+    pub fn selected_symbol(self, selected_symbol: &'a str) -> Self {
+        Self {
+            selected_symbol,
+            ..self
+        }
+    }
+
+    /// Set the [unselected symbol](Self::unselected_symbol)
+    pub fn unselected_symbol(self, unselected_symbol: &'a str) -> Self {
+        Self {
+            unselected_symbol,
+            ..self
+        }
+    }
+
+    /// Set the [spread style](Self::spread_style)
+    pub fn spread_style(self, spread_style: Style) -> Self {
+        Self {
+            spread_style,
+            ..self
+        }
+    }
+
+    /// Set the [jump style](Self::jump_style)
+    pub fn jump_style(self, jump_style: Style) -> Self {
+        Self { jump_style, ..self }
+    }
+
+    /// Set the [departure style](Self::departure_style)
+    pub fn departure_style(self, departure_style: Style) -> Self {
+        Self {
+            departure_style,
+            ..self
+        }
+    }
+
+    /// Set the [destination style](Self::destination_style)
+    pub fn destination_style(self, destination_style: Style) -> Self {
+        Self {
+            destination_style,
+            ..self
+        }
+    }
+
+    /// Set the [shade style](Self::shade_style)
+    pub fn shade_style(self, shade_style: Style) -> Self {
+        Self {
+            shade_style,
+            ..self
+        }
+    }
+
+    /// Set the [hill style](Self::hill_style)
+    pub fn hill_style(self, hill_style: Style) -> Self {
+        Self { hill_style, ..self }
+    }
+
+    /// Set the [grid](Self::grid)
+    pub fn grid(self, grid: Grid) -> Self {
+        Self { grid, ..self }
+    }
+
+    /// Set the [render style](Self::render_style)
+    pub fn render_style(self, render_style: RenderStyle) -> Self {
+        Self {
+            render_style,
+            ..self
+        }
+    }
+
+    /// Set whether [each player has its own symbol](Self::distinct_symbols)
+    pub fn distinct_symbols(self, distinct_symbols: bool) -> Self {
+        Self {
+            distinct_symbols,
+            ..self
+        }
+    }
+
+    /// Set the [theme](Self::theme)
+    pub fn theme(self, theme: Theme) -> Self {
+        Self { theme, ..self }
+    }
+
+    fn symbol(&self, cell: CellState, selected: bool) -> &'a str {
+        if self.distinct_symbols
+            && let CellState::Player(player) = cell
+        {
+            return match (player, self.render_style.is_unicode()) {
+                (Player::Blue, true) => "●",
+                (Player::Blue, false) => "@",
+                (Player::Red, _) => "X",
+            };
+        }
+
+        match (self.render_style, cell) {
+            (RenderStyle::Ascii | RenderStyle::Blocks, _) if selected => self.selected_symbol,
+            (RenderStyle::Ascii | RenderStyle::Blocks, _) => self.unselected_symbol,
+            (RenderStyle::Circles, CellState::Player(_)) => "●",
+            (RenderStyle::Circles, CellState::Free) => "○",
+            (RenderStyle::Circles, CellState::Restricted) => "⊘",
+            (RenderStyle::Braille, CellState::Player(_)) => "⣿",
+            (RenderStyle::Braille, CellState::Free) => "⠒",
+            (RenderStyle::Braille, CellState::Restricted) => "⢕",
+            (RenderStyle::Emoji, CellState::Player(Player::Blue)) => "🔵",
+            (RenderStyle::Emoji, CellState::Player(Player::Red)) => "🔴",
+            (RenderStyle::Emoji, CellState::Free) => "⚫",
+            (RenderStyle::Emoji, CellState::Restricted) => "⛔",
+            (RenderStyle::NerdFont, CellState::Player(_)) => "\u{f111}",
+            (RenderStyle::NerdFont, CellState::Free) => "\u{f10c}",
+            (RenderStyle::NerdFont, CellState::Restricted) => "\u{f05e}",
+            (RenderStyle::Images, _) => " ",
+        }
+    }
+
+    fn power_up_symbol(&self, power_up: PowerUp, selected: bool) -> &'a str {
+        match (self.render_style, power_up) {
+            (RenderStyle::Ascii | RenderStyle::Blocks, _) if selected => self.selected_symbol,
+            (RenderStyle::Ascii | RenderStyle::Blocks, PowerUp::DoubleSpread) => "+",
+            (RenderStyle::Ascii | RenderStyle::Blocks, PowerUp::Shield) => "@",
+            (RenderStyle::Ascii | RenderStyle::Blocks, PowerUp::Bomb) => "*",
+            (RenderStyle::Circles, PowerUp::DoubleSpread) => "⊕",
+            (RenderStyle::Circles, PowerUp::Shield) => "◈",
+            (RenderStyle::Circles, PowerUp::Bomb) => "✱",
+            (RenderStyle::Braille, PowerUp::DoubleSpread) => "⠶",
+            (RenderStyle::Braille, PowerUp::Shield) => "⢹",
+            (RenderStyle::Braille, PowerUp::Bomb) => "⠭",
+            (RenderStyle::Emoji, PowerUp::DoubleSpread) => "✨",
+            (RenderStyle::Emoji, PowerUp::Shield) => "🔰",
+            (RenderStyle::Emoji, PowerUp::Bomb) => "💣",
+            (RenderStyle::NerdFont, PowerUp::DoubleSpread) => "\u{f067}",
+            (RenderStyle::NerdFont, PowerUp::Shield) => "\u{f132}",
+            (RenderStyle::NerdFont, PowerUp::Bomb) => "\u{f1e2}",
+            (RenderStyle::Images, _) => " ",
+        }
+    }
+
+    /// Get the number of columns taken by the widest symbol
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::widgets::board::{Board, RenderStyle};
+    ///
+    /// assert_eq!(Board::default().glyph_width(), 1);
+    /// assert_eq!(Board::default().render_style(RenderStyle::Emoji).glyph_width(), 2);
+    /// ```
+    pub fn glyph_width(&self) -> u16 {
+        [
+            CellState::Free,
+            CellState::Restricted,
+            CellState::Player(Player::Blue),
+            CellState::Player(Player::Red),
+        ]
+        .into_iter()
+        .flat_map(|cell| [self.symbol(cell, false), self.symbol(cell, true)])
+        .chain(
+            PowerUp::ALL
+                .into_iter()
+                .map(|power_up| self.power_up_symbol(power_up, false)),
+        )
+        .map(|symbol| Span::raw(symbol).width() as u16)
+        .max()
+        .unwrap_or(1)
+        .max(1)
+    }
+}
+
+impl Default for Board<'static> {
+    fn default() -> Self {
+        Self {
+            selected_symbol: "V",
+            unselected_symbol: "O",
+            spread_style: Style::new().bg(Color::Rgb(0x2e, 0x4a, 0x35)),
+            jump_style: Style::new().bg(Color::Rgb(0x4a, 0x3f, 0x2e)),
+            departure_style: Style::new().bold().underlined(),
+            destination_style: Style::new().underlined(),
+            shade_style: Style::new().bg(Color::Rgb(0x26, 0x26, 0x26)),
+            hill_style: Style::new().bg(Color::Rgb(0x4a, 0x44, 0x1c)),
+            grid: Grid::default(),
+            render_style: RenderStyle::default(),
+            distinct_symbols: false,
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl StatefulWidget for Board<'_> {
+    type State = BoardState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let [area] = Layout::vertical(constraints![==100%]).areas(area);
+        let [area] = Layout::horizontal(constraints![==100%]).areas(area);
+
+        let glyph_width = self.glyph_width();
+        let (cell_width, cell_height) = cell_size(area, state.height(), state.width(), glyph_width);
+        let scaled = cell_width > glyph_width;
+        let braille = self.render_style == RenderStyle::Braille;
+        let graphical = self.render_style.is_graphical();
+        let images = self.render_style == RenderStyle::Images;
+        let blocks = (scaled && !braille && !graphical) || self.render_style == RenderStyle::Blocks;
+        state.cells.clear();
+
+        let (vertical, horizontal) = overflow(area, state.height(), state.width(), cell_width);
+        let full = area;
+
+        let area = Rect {
+            width: full.width.saturating_sub(u16::from(vertical)),
+            height: full.height.saturating_sub(u16::from(horizontal)),
+            ..full
+        };
+
+        let shown = (
+            state.height().min(area.height as usize),
+            state
+                .width()
+                .min((area.width as usize + 1) / (cell_width as usize + 1)),
+        );
+
+        let cursor = state.orient(state.selected);
+
+        state.viewport = (
+            follow(state.viewport.0, cursor.0, shown.0, state.height()),
+            follow(state.viewport.1, cursor.1, shown.1, state.width()),
+        );
+
+        if vertical {
+            let mut scrollbar = ScrollbarState::new(state.height() - shown.0)
+                .position(state.viewport.0)
+                .viewport_content_length(shown.0);
+
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .style(self.theme.secondary)
+                .render(
+                    Rect {
+                        height: area.height,
+                        ..full
+                    },
+                    buf,
+                    &mut scrollbar,
+                );
+        }
+
+        if horizontal {
+            let mut scrollbar = ScrollbarState::new(state.width() - shown.1)
+                .position(state.viewport.1)
+                .viewport_content_length(shown.1);
+
+            Scrollbar::new(ScrollbarOrientation::HorizontalBottom)
+                .style(self.theme.secondary)
+                .render(
+                    Rect {
+                        width: area.width,
+                        ..full
+                    },
+                    buf,
+                    &mut scrollbar,
+                );
+        }
+
+        let constraints = once(constraint!(==cell_height)).cycle().take(shown.0);
+
+        let destinations = state
+            .from
+            .filter(|_| state.to.is_none())
+            .into_iter()
+            .flat_map(|from| state.board.moves_from(from))
+            .map(|destination| destination.to)
+            .collect::<Vec<_>>();
+
+        let rows = Layout::vertical(constraints)
+            .flex(Flex::Center)
+            .spacing(u16::from(scaled))
+            .split(area);
+
+        let constraints = once(constraint!(==cell_width)).cycle().take(shown.1);
+
+        let columns = Layout::horizontal(constraints)
+            .flex(Flex::Center)
+            .spacing(1)
+            .split(area);
+
+        let gaps = columns
+            .windows(2)
+            .map(|pair| pair[0].right())
+            .collect::<Vec<_>>();
+
+        for (i, row) in rows.iter().copied().enumerate() {
+            if self.grid.has_lines() {
+                for &x in &gaps {
+                    for y in row.top()..row.bottom() {
+                        buf[(x, y)].set_symbol("│").set_style(self.theme.tertiary);
+                    }
+                }
+            }
+
+            for (j, column) in columns.iter().copied().enumerate() {
+                let area = Rect {
+                    y: row.y,
+                    height: row.height,
+                    ..column
+                };
+
+                let (i, j) = state.orient((i + state.viewport.0, j + state.viewport.1));
+
+                if let Some(current) = state.board.get(i, j) {
+                    let current = state
+                        .animation
+                        .as_ref()
+                        .map_or(current, |animation| animation.cell((i, j), current));
+
+                    let selected = (i, j) == state.selected;
+                    let shaded = self.grid.has_checkerboard() && !(i + j).is_multiple_of(2);
+                    let power_up = current
+                        .is_free()
+                        .then(|| state.board.power_up(i, j))
+                        .flatten();
+
+                    let content = match power_up {
+                        Some(power_up) => self.power_up_symbol(power_up, selected),
+                        None => self.symbol(current, selected),
+                    };
+
+                    let mut style = if blocks && !current.is_free() {
+                        Style::new()
+                            .bg(self.theme.cell_color(current))
+                            .fg(Color::Black)
+                    } else {
+                        Style::new()
+                            .fg(power_up.map_or(self.theme.cell_color(current), Color::from))
+                    };
+
+                    if shaded {
+                        style = self.shade_style.patch(style);
+                    }
+
+                    let hill = state.board.is_hill(i, j);
+
+                    if hill && (current.is_free() || !blocks) {
+                        style = style.patch(self.hill_style);
+                    }
+
+                    if let Some(from) = state.from
+                        && destinations.contains(&(i, j))
+                    {
+                        style = match state.board.jump_distance(from, (i, j)) {
+                            Some(1) => style.patch(self.spread_style),
+                            _ => style.patch(self.jump_style),
+                        };
+                    }
+
+                    if state.from == Some((i, j)) {
+                        style = style.patch(self.departure_style);
+                    } else if state.to == Some((i, j)) {
+                        style = style.patch(self.destination_style);
+                    }
+
+                    if state
+                        .animation
+                        .as_ref()
+                        .is_some_and(|animation| animation.is_flashing((i, j)))
+                    {
+                        style = style.reversed();
+                    }
+
+                    if selected && (state.dragging || content != self.selected_symbol) {
+                        style = style.reversed();
+                    }
+
+                    if braille {
+                        let content = content.repeat(area.width as usize);
+
+                        for row in area.rows() {
+                            buf.set_string(row.x, row.y, &content, style);
+                        }
+                    } else {
+                        if shaded {
+                            buf.set_style(area, self.shade_style);
+                        }
+
+                        if hill {
+                            buf.set_style(area, self.hill_style);
+                        }
+
+                        if blocks || images {
+                            buf.set_style(area, style);
+                        }
+
+                        let [line] = Layout::vertical(constraints![==1])
+                            .flex(Flex::Center)
+                            .areas(area);
+
+                        Text::raw(content).style(style).centered().render(line, buf);
+                    }
+                    state.cells.push(((i, j), area));
+                }
+            }
+        }
+
+        if self.grid.has_lines()
+            && scaled
+            && let (Some(first), Some(last)) = (columns.first(), columns.last())
+        {
+            for y in rows.windows(2).map(|pair| pair[0].bottom()) {
+                for x in first.left()..last.right() {
+                    let symbol = if gaps.contains(&x) { "┼" } else { "─" };
+                    buf[(x, y)]
+                        .set_symbol(symbol)
+                        .set_style(self.theme.tertiary);
+                }
+            }
+        }
+    }
+}
+
+/// The score widget
+///
+/// This widget shows the player to move, the current selection, the number of _blobs_ captured by the last ply and the score of each player.
+pub struct Score {
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl StatefulWidget for Score {
+    type State = BoardState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let crate::game::Score { red, blue, .. } = state.board.score();
+
+        let last_captures = state
+            .last_captures()
+            .map_or_else(|| "(None)".to_owned(), |captured| captured.to_string());
+
+        let score_title = if state.board.rules().misere {
+            "Score (misère):"
+        } else {
+            "Score:"
+        };
+
+        let mut text = text![
+            span!(self.theme.title; "Status:"),
+            line![
+                span!(self.theme.important; "Turn"),
+                ": ",
+                self.theme.player(state.current_player),
+            ],
+            line![
+                span!(self.theme.important; "From (selected)"),
+                ": ",
+                display_selected(state.from),
+            ],
+            line![
+                span!(self.theme.important; "To (selected)"),
+                ": ",
+                display_selected(state.to),
+            ],
+            line![
+                span!(self.theme.important; "Last captures"),
+                ": ",
+                last_captures,
+            ],
+            line![],
+            span!(self.theme.title; score_title),
+            score_bar(blue, red, area.width.saturating_sub(2), self.theme),
+            line![
+                self.theme.player(Player::Blue),
+                ": ",
+                blue.to_string(),
+                span!(self.theme.secondary; " ({}%)", share(blue, red)),
+            ],
+            line![
+                self.theme.player(Player::Red),
+                ": ",
+                red.to_string(),
+                span!(self.theme.secondary; " ({}%)", share(red, blue)),
+            ],
+            line![
+                span!(self.theme.important; "Free"),
+                ": ",
+                state.board.free_cells().to_string(),
+            ],
+        ];
+
+        if let Some(limit) = state.board.rules().move_limit {
+            text.push_line(line![
+                span!(self.theme.important; "Moves"),
+                ": ",
+                state.board.played().to_string(),
+                span!(self.theme.secondary; " / {limit}"),
+            ]);
+        }
+
+        if state.board.hill().next().is_some() {
+            let score = state.board.score();
+
+            let goal = state
+                .board
+                .rules()
+                .goal
+                .map_or_else(String::new, |goal| format!(" / {goal}"));
+
+            text.push_line(line![
+                span!(self.theme.important; "Hill"),
+                ": ",
+                span!(self.theme.player_color(Player::Blue); "{}", score.blue_points),
+                " - ",
+                span!(self.theme.player_color(Player::Red); "{}", score.red_points),
+                span!(self.theme.secondary; "{goal}"),
+            ]);
+        }
+
+        Paragraph::new(text)
+            .block(Block::bordered().title("Score"))
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+}
+
+/// The cell inspector widget
+///
+/// This widget shows some details about the cell under the selector: its position, its state, its neighbors and whether it is a legal destination for the selected departure point.
+pub struct Inspector {
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl StatefulWidget for Inspector {
+    type State = BoardState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let (i, j) = state.selected;
+        let cell = state.board.get(i, j).unwrap_or_default();
+        let player = state.current_player;
+
+        let mut content = line![span!(self.theme.important; "State"), ": "];
+
+        match cell {
+            CellState::Free => content.push_span(span!(self.theme.secondary; "Free")),
+            CellState::Restricted => content.push_span(span!(self.theme.secondary; "Restricted")),
+
+            CellState::Player(owner) => {
+                content.push_span(self.theme.player(owner));
+                content.push_span(" blob");
+            }
+        }
+
+        let radius = state.board.rules().conversion_radius;
+
+        let (friendly, enemy) =
+            state
+                .board
+                .neighbors(i, j, radius)
+                .fold((0, 0), |(friendly, enemy), neighbor| match neighbor {
+                    CellState::Player(owner) if owner == player => (friendly + 1, enemy),
+                    CellState::Player(_) => (friendly, enemy + 1),
+                    _ => (friendly, enemy),
+                });
+
+        let (friendly, enemy) = match cell {
+            CellState::Player(owner) if owner == player => (friendly - 1, enemy),
+            CellState::Player(_) => (friendly, enemy - 1),
+            _ => (friendly, enemy),
+        };
+
+        let destination = match state.from {
+            Some(from)
+                if state
+                    .board
+                    .moves_from(from)
+                    .any(|chosen| chosen.to == (i, j)) =>
+            {
+                match state.board.jump_distance(from, (i, j)) {
+                    Some(1) => "Yes (spread)",
+                    _ => "Yes (jump)",
+                }
+            }
+
+            Some(_) => "No",
+            None => "No departure selected",
+        };
+
+        let text = text![
+            line![
+                span!(self.theme.important; "Position"),
+                ": ",
+                crate::record::display_index((i, j)),
+                " ",
+                display_selected(Some((i, j))),
+            ],
+            content,
+            line![
+                span!(self.theme.important; "Neighbors"),
+                ": ",
+                span!(self.theme.player_color(player); "{friendly} friendly"),
+                ", ",
+                span!(self.theme.player_color(-player); "{enemy} enemy"),
+            ],
+            line![
+                span!(self.theme.important; "Destination"),
+                ": ",
+                destination,
+            ],
+        ];
+
+        Paragraph::new(text)
+            .block(Block::bordered().title("Cell"))
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+}
+
+/// The move list widget
+///
+/// This widget shows the numbered plies of the game, the most recent ones being kept in view.
+pub struct MoveList {
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl StatefulWidget for MoveList {
+    type State = BoardState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let block = Block::bordered().title("Moves");
+        let shown = block.inner(area).height as usize;
+        let plies = &state.record.plies;
+
+        let mut player = state.record.first;
+
+        let lines = plies
+            .iter()
+            .enumerate()
+            .map(|(i, &ply)| {
+                let line = line![
+                    span!(self.theme.secondary; "{:>3}. ", i + 1),
+                    span!(self.theme.player_color(player); display_ply(ply)),
+                ];
+
+                player = -player;
+                line
+            })
+            .collect::<Vec<_>>();
+
+        let skipped = lines.len().saturating_sub(shown);
+
+        Paragraph::new(lines.into_iter().skip(skipped).collect::<Vec<_>>())
+            .block(block)
+            .render(area, buf);
+    }
+}
+
+/// The chat widget
+///
+/// This widget shows the last chat messages of a network game, colored after their senders, above the message being typed.
+pub struct Chat<'a> {
+    /// The message being typed
+    pub input: &'a str,
+
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl StatefulWidget for Chat<'_> {
+    type State = BoardState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let block = Block::bordered().title("Chat");
+        let shown = (block.inner(area).height as usize).saturating_sub(1);
+        let skipped = state.chat.len().saturating_sub(shown);
+
+        let mut lines = state
+            .chat
+            .iter()
+            .skip(skipped)
+            .map(|(player, text)| {
+                line![
+                    span!(self.theme.player_color(*player); "{player}: "),
+                    span!(text.as_str()),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        lines.push(line![
+            span!(self.theme.important; "> "),
+            span!(self.input),
+            span!(self.theme.important; "_"),
+        ]);
+
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
+
+/// The turn indicator widget
+///
+/// This widget shows which [player](Player) has to move, using the player's color and symbol.
+pub struct TurnIndicator<'a> {
+    /// The symbol of the player's cells
+    pub symbol: &'a str,
+
+    /// The [theme](Theme) giving the colors of the players
+    pub theme: Theme,
+}
+
+impl StatefulWidget for TurnIndicator<'_> {
+    type State = BoardState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let player = state.current_player;
+        let style = Style::new().fg(self.theme.player_color(player)).bold();
+
+        let text = line![
+            span!(style; "{} {player} to move", self.symbol),
+            display_thinking(state.thinking.as_ref()),
+        ];
+
+        Paragraph::new(text)
+            .block(Block::bordered().border_style(style).title("Turn"))
+            .alignment(Alignment::Center)
+            .render(area, buf);
+    }
+}
+
+/// The clock widget
+///
+/// This widget shows the remaining time of both players; it shows nothing if the game has no [clock](crate::clock::Clock).
+pub struct Clock {
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl StatefulWidget for Clock {
+    type State = BoardState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let Some(clock) = state.clock else {
+            return;
+        };
+
+        let text = [Player::Blue, Player::Red]
+            .into_iter()
+            .map(|player| {
+                let style = if player == state.current_player {
+                    self.theme.important
+                } else {
+                    self.theme.secondary
+                };
+
+                line![
+                    self.theme.player(player),
+                    ": ",
+                    span!(style; display_duration(clock.remaining(player))),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        Paragraph::new(text)
+            .block(Block::bordered().title("Clock"))
+            .render(area, buf);
+    }
+}
+
+/// The status bar widget
+///
+/// This widget shows the current [message](Message) of the [queue](MessageQueue) on a single line.
+/// If there is no message while the computer opponent is thinking, the [progress](crate::ai::Progress) of its search is shown.
+pub struct StatusBar {
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl StatefulWidget for StatusBar {
+    type State = BoardState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        if let Some(remote) = &state.remote {
+            let status = if remote.connection.is_lost() {
+                span!(self.theme.important; "reconnecting…")
+            } else {
+                let latency = remote
+                    .connection
+                    .latency()
+                    .map_or("…".to_owned(), |latency| latency.as_millis().to_string());
+
+                span!(self.theme.secondary; "ping {latency} ms")
+            };
+
+            line!(status).right_aligned().render(area, buf);
+        }
+
+        if let Some(message) = state.messages.current() {
+            let style = match message.severity {
+                Severity::Info => self.theme.secondary,
+                Severity::Warning => self.theme.important,
+            };
+
+            span!(style; message.text).render(area, buf);
+        } else if let Some(thinking) = &state.thinking {
+            let progress = thinking.progress();
+
+            line![
+                span!(self.theme.important; "{} ", spinner_frame(thinking)),
+                span!(
+                    self.theme.secondary;
+                    "{} is thinking… {} nodes",
+                    state.current_player,
+                    progress.nodes,
+                ),
+            ]
+            .render(area, buf);
+        }
+    }
+}
+
+/// The summary widget
+///
+/// This widget shows on a single line the player to move, the score and, if any, the remaining times.
+/// It replaces the side panel when the terminal is too narrow.
+pub struct Summary {
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl StatefulWidget for Summary {
+    type State = BoardState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let crate::game::Score { red, blue, .. } = state.board.score();
+
+        let mut line = line![
+            self.theme.player(state.current_player),
+            span!(self.theme.secondary; " to move │ "),
+            span!(self.theme.player_color(Player::Blue); "{blue}"),
+            span!(self.theme.secondary; " - "),
+            span!(self.theme.player_color(Player::Red); "{red}"),
+        ];
+
+        if let Some(clock) = &state.clock {
+            line.push_span(span!(self.theme.secondary; " │ "));
+
+            line.push_span(span!(
+                self.theme.player_color(Player::Blue);
+                display_duration(clock.remaining(Player::Blue))
+            ));
+
+            line.push_span(span!(self.theme.secondary; " - "));
+
+            line.push_span(span!(
+                self.theme.player_color(Player::Red);
+                display_duration(clock.remaining(Player::Red))
+            ));
+        }
+
+        line.centered().render(area, buf);
+    }
+}
+
+/// The game-over widget
+///
+/// This widget shows the results of a finished game.
+pub struct GameOver {
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+
+    /// The [rating](crate::stats::Statistics::rating) of the human player and its change, if the game was rated
+    pub rating: Option<(i32, i32)>,
+}
+
+impl StatefulWidget for GameOver {
+    type State = BoardState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let crate::game::Score { red, blue, .. } = state.board.score();
+
+        let result = match state.outcome() {
+            Some(Outcome::Win(player))
+                if state.clock.and_then(|clock| clock.flagged()).is_some() =>
+            {
+                line![
+                    self.theme.player(player),
+                    span!(self.theme.important; " wins on time!")
+                ]
+            }
+
+            Some(Outcome::Win(player)) => {
+                line![
+                    self.theme.player(player),
+                    span!(self.theme.important; " wins!")
+                ]
+            }
+            Some(Outcome::Draw) => line![span!(self.theme.important; "Draw!")],
+            None => line![span!(self.theme.secondary; "The game is not over")],
+        };
+
+        let title = if state.board.rules().misere {
+            "Result (misère, the fewest blobs win):"
+        } else {
+            "Result:"
+        };
+
+        let mut text = text![
+            span!(self.theme.title; title),
+            line![],
+            result,
+            line![],
+            span!(self.theme.title; "Final score:"),
+            line![],
+            line![self.theme.player(Player::Blue), ": ", blue.to_string()],
+            line![self.theme.player(Player::Red), ": ", red.to_string()],
+            line![],
+        ];
+
+        if state.board.hill().next().is_some() {
+            let score = state.board.score();
+
+            text.push_line(line![
+                span!(self.theme.important; "Hill points"),
+                ": ",
+                span!(self.theme.player_color(Player::Blue); "{}", score.blue_points),
+                " - ",
+                span!(self.theme.player_color(Player::Red); "{}", score.red_points),
+            ]);
+
+            text.push_line(line![]);
+        }
+
+        text.extend([
+            line![
+                span!(self.theme.important; "Moves"),
+                ": ",
+                state.moves.to_string(),
+            ],
+            line![],
+        ]);
+
+        if let Some((rating, change)) = self.rating {
+            text.push_line(line![
+                span!(self.theme.important; "Rating"),
+                ": ",
+                span!("{rating} ({change:+})"),
+            ]);
+
+            text.push_line(line![]);
+        }
+
+        text.push_line(
+            span!(self.theme.secondary; "Enter: rematch, r: replay, e: export, Esc: menu"),
+        );
+
+        Paragraph::new(text)
+            .block(Block::bordered().title("Game over"))
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+}
+
+fn cell_size(area: Rect, height: usize, width: usize, glyph_width: u16) -> (u16, u16) {
+    let fits = |cell_width: u16, cell_height: u16| {
+        let width = width as u16;
+        let height = height as u16;
+
+        width * cell_width + width.saturating_sub(1) <= area.width
+            && height * cell_height + height.saturating_sub(1) <= area.height
+    };
+
+    (2..=4)
+        .rev()
+        .map(|scale| (2 * scale - 1, scale - 1))
+        .filter(|&(cell_width, _)| cell_width >= glyph_width)
+        .find(|&(cell_width, cell_height)| fits(cell_width, cell_height))
+        .unwrap_or((glyph_width, 1))
+}
+
+/// Draw a bar split between the colors proportionally to the number of _blobs_ of each player
+fn score_bar(blue: usize, red: usize, width: u16, theme: Theme) -> Line<'static> {
+    let width = width as usize;
+    let blue_width = (blue * width + (blue + red) / 2)
+        .checked_div(blue + red)
+        .unwrap_or(width / 2);
+
+    line![
+        span!(theme.player_color(Player::Blue); "█".repeat(blue_width)),
+        span!(theme.player_color(Player::Red); "█".repeat(width - blue_width)),
+    ]
+}
+
+/// Get the rounded percentage of the occupied cells owned by a player
+fn share(owned: usize, other: usize) -> usize {
+    (owned * 100 + (owned + other) / 2)
+        .checked_div(owned + other)
+        .unwrap_or_default()
+}
+
+/// Check whether the rows and the columns of a board overflow an area
+///
+/// A scrollbar takes one column or one row of the area, which may make the other dimension overflow too.
+fn overflow(area: Rect, height: usize, width: usize, cell_width: u16) -> (bool, bool) {
+    let rows = |area_height: u16| height > area_height as usize;
+    let columns = |area_width: u16| width * (cell_width as usize + 1) - 1 > area_width as usize;
+
+    let vertical = rows(area.height);
+    let horizontal = columns(area.width.saturating_sub(u16::from(vertical)));
+    let vertical = vertical || (horizontal && rows(area.height.saturating_sub(1)));
+
+    (
+        vertical,
+        columns(area.width.saturating_sub(u16::from(vertical))),
+    )
+}
+
+/// Move the first shown line of a viewport so that the cursor stays visible
+fn follow(offset: usize, cursor: usize, shown: usize, len: usize) -> usize {
+    let offset = if cursor < offset {
+        cursor
+    } else if cursor >= offset + shown {
+        cursor + 1 - shown
+    } else {
+        offset
+    };
+
+    offset.min(len - shown)
+}
+
+fn shift(value: usize, forward: bool, len: usize, wrap: bool) -> usize {
+    match (forward, wrap) {
+        (true, true) => (value + 1) % len.max(1),
+        (true, false) => value + 1,
+        (false, true) => (value + len.max(1) - 1) % len.max(1),
+        (false, false) => value.saturating_sub(1),
+    }
+}
+
+fn spinner_frame(thinking: &Thinking) -> &'static str {
+    let frame = thinking.elapsed().as_millis() / 100;
+    SPINNER[frame as usize % SPINNER.len()]
+}
+
+fn display_thinking(value: Option<&Thinking>) -> String {
+    if let Some(thinking) = value {
+        format!(" (thinking… {})", spinner_frame(thinking))
+    } else {
+        String::new()
+    }
+}
+
+fn display_duration(value: Duration) -> String {
+    let seconds = value.as_secs();
+
+    if seconds < 10 {
+        format!("0:0{seconds}.{}", value.subsec_millis() / 100)
+    } else {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+fn display_selected(value: Option<Index>) -> String {
+    if let Some((i, j)) = value {
+        format!("({i}, {j})")
+    } else {
+        "(None)".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::CellState::*;
+    use crate::game::Player::*;
+
+    fn tested(pre: impl FnOnce(&mut BoardState)) -> Buffer {
+        let area = Rect::new(0, 0, 9, 5);
+        let mut tested = Buffer::empty(area);
+
+        #[rustfmt::skip]
+        let board = vec![
+            Player(Red), Free, Free,       Free, Free,
+            Free,        Free, Free,       Free, Free,
+            Free,        Free, Restricted, Free, Free,
+            Free,        Free, Free,       Free, Free,
+            Free,        Free, Free,       Free, Player(Blue),
+        ];
+
+        let board = crate::game::Board::new(5, 5, board);
+        let mut state = BoardState::new(board, Red);
+        let widget = Board::default();
+
+        pre(&mut state);
+        state.tick(Duration::from_secs(1));
+        widget.render(area, &mut tested, &mut state);
+
+        tested
+    }
+
+    fn expected<'a, Iter>(lines: Iter) -> Buffer
+    where
+        Iter: IntoIterator,
+        Iter::Item: Into<Line<'a>>,
+    {
+        let mut expected = Buffer::with_lines(lines);
+        expected.set_style(Rect::new(0, 0, 1, 1), Color::Red);
+        expected.set_style(Rect::new(4, 2, 1, 1), Color::Rgb(0xff, 0xa5, 0x00));
+        expected.set_style(Rect::new(8, 4, 1, 1), Color::Blue);
+
+        expected
+    }
+
+    #[test]
+    fn render() {
+        let lines = [
+            "V O O O O",
+            "O O O O O",
+            "O O O O O",
+            "O O O O O",
+            "O O O O O",
+        ];
+
+        let expected = expected(lines);
+        let tested = tested(|_| {});
+
+        pretty_assertions::assert_eq!(tested, expected);
+    }
+
+    #[test]
+    fn turn_indicator() {
+        let area = Rect::new(0, 0, 20, 3);
+        let mut tested = Buffer::empty(area);
+
+        let board = crate::game::Board::new(1, 2, vec![Player(Red), Player(Blue)]);
+        let mut state = BoardState::new(board, Blue);
+
+        TurnIndicator {
+            symbol: "O",
+            theme: Theme::default(),
+        }
+        .render(area, &mut tested, &mut state);
+
+        let mut expected = Buffer::with_lines([
+            "┌Turn──────────────┐",
+            "│  O Blue to move  │",
+            "└──────────────────┘",
+        ]);
+
+        expected.set_style(area, Style::new().fg(Color::Blue).bold());
+        expected.set_style(Rect::new(1, 1, 2, 1), Style::reset());
+        expected.set_style(Rect::new(17, 1, 2, 1), Style::reset());
+
+        pretty_assertions::assert_eq!(tested, expected);
+    }
+
+    #[test]
+    fn clock() {
+        let area = Rect::new(0, 0, 16, 4);
+        let mut tested = Buffer::empty(area);
+
+        let board = crate::game::Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+
+        let clock =
+            crate::clock::Clock::new(crate::clock::TimeControl::Absolute(Duration::from_secs(60)));
+
+        let mut state = BoardState::new(board, Blue).with_clock(clock);
+        state.tick(Duration::from_millis(50_500));
+
+        Clock {
+            theme: Theme::monochrome(),
+        }
+        .render(area, &mut tested, &mut state);
+
+        let content = tested
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+
+        assert!(content.contains("Blue: 0:09.5"));
+        assert!(content.contains("Red: 1:00"));
+    }
+
+    #[test]
+    fn scaled() {
+        let area = Rect::new(0, 0, 7, 1);
+        let mut tested = Buffer::empty(area);
+
+        let board = crate::game::Board::new(1, 2, vec![Player(Red), Player(Blue)]);
+        let mut state = BoardState::new(board, Red);
+        Board::default().render(area, &mut tested, &mut state);
+
+        let mut expected = Buffer::with_lines([" V   O "]);
+        expected.set_style(
+            Rect::new(0, 0, 3, 1),
+            Style::new().bg(Color::Red).fg(Color::Black),
+        );
+        expected.set_style(
+            Rect::new(4, 0, 3, 1),
+            Style::new().bg(Color::Blue).fg(Color::Black),
+        );
+
+        pretty_assertions::assert_eq!(tested, expected);
+        pretty_assertions::assert_eq!(state.cell_at(Position::new(6, 0)), Some((0, 1)));
+    }
+
+    #[test]
+    fn circles() {
+        let area = Rect::new(0, 0, 5, 1);
+        let mut tested = Buffer::empty(area);
+
+        let board = crate::game::Board::new(1, 3, vec![Player(Red), Free, Restricted]);
+        let mut state = BoardState::new(board, Red);
+
+        Board::default()
+            .render_style(RenderStyle::Circles)
+            .render(area, &mut tested, &mut state);
+
+        let mut expected = Buffer::with_lines(["● ○ ⊘"]);
+        expected.set_style(
+            Rect::new(0, 0, 1, 1),
+            Style::new().fg(Color::Red).reversed(),
+        );
+        expected.set_style(Rect::new(4, 0, 1, 1), Color::Rgb(0xff, 0xa5, 0x00));
+
+        pretty_assertions::assert_eq!(tested, expected);
+    }
+
+    #[test]
+    fn braille() {
+        let area = Rect::new(0, 0, 7, 1);
+        let mut tested = Buffer::empty(area);
+
+        let board = crate::game::Board::new(1, 2, vec![Free, Player(Blue)]);
+        let mut state = BoardState::new(board, Blue);
+
+        Board::default()
+            .render_style(RenderStyle::Braille)
+            .render(area, &mut tested, &mut state);
+
+        let mut expected = Buffer::with_lines(["⠒⠒⠒ ⣿⣿⣿"]);
+        expected.set_style(Rect::new(0, 0, 3, 1), Style::new().reversed());
+        expected.set_style(Rect::new(4, 0, 3, 1), Color::Blue);
+
+        pretty_assertions::assert_eq!(tested, expected);
+    }
+
+    #[test]
+    fn animation() {
+        let area = Rect::new(0, 0, 5, 2);
+        let mut tested = Buffer::empty(area);
+
+        let board = crate::game::Board::new(
+            2,
+            3,
+            vec![Player(Red), Free, Free, Player(Blue), Free, Free],
+        );
+        let mut state = BoardState::new(board, Red);
+
+        state.select();
+        state.right();
+        state.right();
+        state.select();
+        state.select();
+
+        assert!(state.is_animating());
+        Board::default().render(area, &mut tested, &mut state);
+
+        let mut expected = Buffer::with_lines(["O O V", "O O O"]);
+        expected.set_style(Rect::new(0, 0, 1, 1), Color::Red);
+        expected.set_style(Rect::new(0, 1, 1, 1), Color::Blue);
+
+        pretty_assertions::assert_eq!(tested, expected);
+
+        state.tick(Animation::STEP * 2);
+        assert!(!state.is_animating());
+    }
+
+    #[test]
+    fn distinct_symbols() {
+        let area = Rect::new(0, 0, 5, 1);
+        let mut tested = Buffer::empty(area);
+
+        let board = crate::game::Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+        let mut state = BoardState::new(board, Red);
+
+        Board::default()
+            .distinct_symbols(true)
+            .theme(Theme::default().colorblind())
+            .render(area, &mut tested, &mut state);
+
+        let mut expected = Buffer::with_lines(["X O @"]);
+
+        expected.set_style(
+            Rect::new(0, 0, 1, 1),
+            Style::new().fg(Color::Rgb(0xe6, 0x9f, 0x00)).reversed(),
+        );
+
+        expected.set_style(Rect::new(4, 0, 1, 1), Color::Rgb(0x00, 0x72, 0xb2));
+
+        pretty_assertions::assert_eq!(tested, expected);
+    }
+
+    #[test]
+    fn messages() {
+        let board =
+            crate::game::Board::new(1, 5, vec![Player(Red), Free, Free, Free, Player(Blue)]);
+
+        let mut state = BoardState::new(board, Red);
+
+        state.right();
+        state.select();
+        pretty_assertions::assert_eq!(
+            state.messages().current(),
+            Some(&Message::warning("Select one of the Red blobs"))
+        );
+
+        state.left();
+        state.select();
+
+        for _ in 0..3 {
+            state.right();
+        }
+
+        state.select();
+        assert!(!state.select());
+
+        state.tick(MessageQueue::DURATION);
+        pretty_assertions::assert_eq!(
+            state.messages().current(),
+            Some(&Message::warning("Illegal move: destination too far"))
+        );
+    }
+
+    #[test]
+    fn finished() {
+        let board = crate::game::Board::new(1, 2, vec![Player(Red), Free]);
+        let mut state = BoardState::new(board, Red);
+
+        state.handle_command(Command::Select);
+
+        pretty_assertions::assert_eq!(state.from(), None);
+        pretty_assertions::assert_eq!(
+            state.messages().current(),
+            Some(&Message::warning("The game is over"))
+        );
+    }
+
+    #[test]
+    fn pass() {
+        let board =
+            crate::game::Board::new(1, 4, vec![Player(Red), Player(Blue), Player(Blue), Free]);
+
+        let mut state = BoardState::new(board, Red);
+        state.handle_command(Command::Pass);
+
+        pretty_assertions::assert_eq!(state.current_player(), Blue);
+        pretty_assertions::assert_eq!(
+            state.messages().current(),
+            Some(&Message::info("Red passes"))
+        );
+
+        state.handle_command(Command::Pass);
+        pretty_assertions::assert_eq!(state.current_player(), Blue);
+    }
+
+    #[test]
+    fn turn() {
+        let board =
+            crate::game::Board::new(1, 5, vec![Player(Red), Free, Free, Free, Player(Blue)]);
+        let mut state = BoardState::new(board, Red);
+
+        state.handle_command(Command::Right);
+        state.handle_command(Command::Select);
+        pretty_assertions::assert_eq!(state.current_player(), Red);
+
+        state.handle_command(Command::Left);
+        state.handle_command(Command::Select);
+        state.handle_command(Command::Right);
+        state.handle_command(Command::Select);
+        pretty_assertions::assert_eq!(state.current_player(), Red);
+        pretty_assertions::assert_eq!(state.moves(), 0);
+
+        state.handle_command(Command::Select);
+        pretty_assertions::assert_eq!(state.current_player(), Blue);
+        pretty_assertions::assert_eq!(state.moves(), 1);
+    }
+
+    #[test]
+    fn too_far() {
+        let board =
+            crate::game::Board::new(1, 5, vec![Player(Red), Free, Free, Free, Player(Blue)]);
+        let mut state = BoardState::new(board, Red);
+
+        state.select();
+        state.last_column();
+        state.left();
+        state.select();
+
+        pretty_assertions::assert_eq!(state.to(), None);
+        pretty_assertions::assert_eq!(
+            state.messages().current(),
+            Some(&Message::warning("Illegal move: destination too far"))
+        );
+
+        state.left();
+        state.select();
+        pretty_assertions::assert_eq!(state.to(), Some((0, 2)));
+    }
+
+    #[test]
+    fn no_move_after_game_over() {
+        let board =
+            crate::game::Board::new(1, 5, vec![Player(Red), Free, Free, Free, Player(Blue)]);
+        let mut state = BoardState::new(board.clone(), Red);
+
+        state.select();
+        assert!(state.resign());
+
+        assert!(!state.select());
+        pretty_assertions::assert_eq!(state.from(), None);
+
+        state.play_move(Move {
+            from: (0, 0),
+            to: (0, 1),
+        });
+
+        pretty_assertions::assert_eq!(state.board(), &board);
+        assert!(!state.pass());
+    }
+
+    #[test]
+    fn stale_selection() {
+        let board =
+            crate::game::Board::new(1, 5, vec![Player(Red), Free, Free, Free, Player(Blue)]);
+        let mut state = BoardState::new(board, Red);
+
+        state.from = Some((0, 0));
+        state.to = Some((0, 3));
+
+        assert!(!state.select());
+        pretty_assertions::assert_eq!((state.from(), state.to()), (None, None));
+
+        assert!(!state.select());
+        pretty_assertions::assert_eq!(state.from(), Some((0, 0)));
+    }
+
+    #[test]
+    fn wrap() {
+        let board = crate::game::Board::free(3, 4);
+        let mut state = BoardState::new(board, Red).with_wrap(true);
+
+        state.up();
+        state.left();
+        pretty_assertions::assert_eq!(state.selected, (2, 3));
+
+        state.down();
+        state.right();
+        pretty_assertions::assert_eq!(state.selected, (0, 0));
+    }
+
+    #[test]
+    fn edges() {
+        let board = crate::game::Board::free(4, 6);
+        let mut state = BoardState::new(board, Red);
+
+        state.handle_command(Command::LastColumn);
+        state.handle_command(Command::LastRow);
+        pretty_assertions::assert_eq!(state.selected, (3, 5));
+
+        state.handle_command(Command::FirstColumn);
+        pretty_assertions::assert_eq!(state.selected, (3, 0));
 
-    fn tested(pre: impl FnOnce(&mut BoardState)) -> Buffer {
-        let area = Rect::new(0, 0, 9, 5);
-        let mut tested = Buffer::empty(area);
+        state.handle_command(Command::LastColumn);
+        state.handle_command(Command::FirstRow);
+        pretty_assertions::assert_eq!(state.selected, (0, 5));
 
-        #[rustfmt::skip]
-        let board = vec![
-            Player(Red), Free, Free,       Free, Free,
-            Free,        Free, Free,       Free, Free,
-            Free,        Free, Restricted, Free, Free,
-            Free,        Free, Free,       Free, Free,
-            Free,        Free, Free,       Free, Player(Blue),
-        ];
+        state.handle_command(Command::LastRow);
+        state.handle_command(Command::Origin);
+        pretty_assertions::assert_eq!(state.selected, (0, 0));
+    }
 
-        let board = crate::game::Board::new(5, 5, board);
-        let mut state = BoardState::new(board, Red);
-        let widget = Board::default();
+    #[test]
+    fn flip() {
+        let board = crate::game::Board::new(2, 2, vec![Player(Red), Free, Free, Player(Blue)]);
+        let mut state = BoardState::new(board, Red).with_flip(Flip::On);
 
-        pre(&mut state);
-        widget.render(area, &mut tested, &mut state);
+        state.origin();
+        pretty_assertions::assert_eq!(state.selected, (1, 1));
+        state.up();
+        state.left();
+        pretty_assertions::assert_eq!(state.selected, (1, 1));
+        state.down();
+        state.right();
+        pretty_assertions::assert_eq!(state.selected, (0, 0));
 
-        tested
+        let area = Rect::new(0, 0, 3, 2);
+        let mut buffer = Buffer::empty(area);
+        Board::default().render(area, &mut buffer, &mut state);
+
+        pretty_assertions::assert_eq!(state.cell_at(Position::new(0, 0)), Some((1, 1)));
+        pretty_assertions::assert_eq!(state.cell_at(Position::new(2, 1)), Some((0, 0)));
     }
 
-    fn expected<'a, Iter>(lines: Iter) -> Buffer
-    where
-        Iter: IntoIterator,
-        Iter::Item: Into<Line<'a>>,
-    {
-        let mut expected = Buffer::with_lines(lines);
-        expected.set_style(Rect::new(0, 0, 1, 1), Color::Red);
-        expected.set_style(Rect::new(4, 2, 1, 1), Color::Rgb(0xff, 0xa5, 0x00));
-        expected.set_style(Rect::new(8, 4, 1, 1), Color::Blue);
+    #[test]
+    fn inspector() {
+        let board = crate::game::Board::new(1, 3, vec![Player(Red), Player(Blue), Free]);
+        let mut state = BoardState::new(board, Red);
+        state.select();
+        state.right();
+        state.right();
 
-        expected
+        let area = Rect::new(0, 0, 40, 6);
+        let mut buffer = Buffer::empty(area);
+        Inspector {
+            theme: Theme::default(),
+        }
+        .render(area, &mut buffer, &mut state);
+
+        let lines = (1..5)
+            .map(|y| {
+                (1..39)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_owned()
+            })
+            .collect::<Vec<_>>();
+
+        pretty_assertions::assert_eq!(
+            lines,
+            [
+                "Position: c1 (0, 2)",
+                "State: Free",
+                "Neighbors: 0 friendly, 1 enemy",
+                "Destination: Yes (jump)",
+            ]
+        );
     }
 
     #[test]
-    fn render() {
-        let lines = [
-            "V O O O O",
-            "O O O O O",
-            "O O O O O",
-            "O O O O O",
-            "O O O O O",
+    fn pieces() {
+        #[rustfmt::skip]
+        let board = vec![
+            Player(Red), Player(Red), Free, Player(Blue), Player(Blue), Player(Red),
         ];
 
-        let expected = expected(lines);
-        let tested = tested(|_| {});
+        let board = crate::game::Board::new(1, 6, board);
+        let mut state = BoardState::new(board, Red);
 
-        pretty_assertions::assert_eq!(tested, expected);
+        state.next_piece();
+        pretty_assertions::assert_eq!(state.selected, (0, 1));
+        state.next_piece();
+        pretty_assertions::assert_eq!(state.selected, (0, 0));
+        state.previous_piece();
+        pretty_assertions::assert_eq!(state.selected, (0, 1));
     }
 
     #[test]
@@ -568,6 +3847,32 @@ mod tests {
         pretty_assertions::assert_eq!(tested, expected);
     }
 
+    #[test]
+    fn diagonal() {
+        let mut state = BoardState::new(crate::game::Board::free(4, 6), Red);
+
+        // The corner blocks the move instead of sliding along an edge
+        state.up_left();
+        pretty_assertions::assert_eq!(state.selected, (0, 0));
+
+        state.down_right();
+        state.down_right();
+        pretty_assertions::assert_eq!(state.selected, (2, 2));
+        state.up_right();
+        pretty_assertions::assert_eq!(state.selected, (1, 3));
+        state.down_left();
+        pretty_assertions::assert_eq!(state.selected, (2, 2));
+
+        state.last_row();
+        state.down_left();
+        pretty_assertions::assert_eq!(state.selected, (3, 2));
+
+        // The selector crosses the edges when the cursor wraps around
+        let mut state = state.with_wrap(true);
+        state.down_left();
+        pretty_assertions::assert_eq!(state.selected, (0, 1));
+    }
+
     #[test]
     fn down() {
         let lines = [
@@ -645,6 +3950,337 @@ mod tests {
         pretty_assertions::assert_eq!(tested, expected);
     }
 
+    #[test]
+    fn drag() {
+        let lines = [
+            "O O O O O",
+            "O V O O O",
+            "O O O O O",
+            "O O O O O",
+            "O O O O O",
+        ];
+
+        let mut expected = expected(lines);
+        expected.set_style(Rect::new(2, 1, 1, 1), Color::Red);
+
+        let tested = tested(|state| {
+            let area = Rect::new(0, 0, 9, 5);
+            Board::default().render(area, &mut Buffer::empty(area), state);
+
+            state.press(Position::new(0, 0));
+            state.drag(Position::new(2, 0));
+            state.drag(Position::new(2, 1));
+            state.release(Position::new(2, 1));
+        });
+
+        pretty_assertions::assert_eq!(tested, expected);
+    }
+
+    #[test]
+    fn dragging() {
+        let lines = [
+            "O O O O O",
+            "O V O O O",
+            "O O O O O",
+            "O O O O O",
+            "O O O O O",
+        ];
+
+        let mut expected = expected(lines);
+        let widget = Board::default();
+
+        for (x, y) in [(2, 0), (0, 1), (2, 1)] {
+            expected.set_style(Rect::new(x, y, 1, 1), widget.spread_style);
+        }
+
+        for (x, y) in [(4, 0), (4, 1), (0, 2), (2, 2)] {
+            expected.set_style(Rect::new(x, y, 1, 1), widget.jump_style);
+        }
+
+        expected.set_style(Rect::new(0, 0, 1, 1), widget.departure_style);
+
+        expected.set_style(Rect::new(2, 1, 1, 1), Modifier::REVERSED);
+
+        let tested = tested(|state| {
+            let area = Rect::new(0, 0, 9, 5);
+            Board::default().render(area, &mut Buffer::empty(area), state);
+
+            state.press(Position::new(0, 0));
+            state.drag(Position::new(2, 1));
+        });
+
+        pretty_assertions::assert_eq!(tested, expected);
+    }
+
+    #[test]
+    fn destinations() {
+        let lines = [
+            "V O O O O",
+            "O O O O O",
+            "O O O O O",
+            "O O O O O",
+            "O O O O O",
+        ];
+
+        let mut expected = expected(lines);
+        let widget = Board::default();
+
+        for (x, y) in [(2, 0), (0, 1), (2, 1)] {
+            expected.set_style(Rect::new(x, y, 1, 1), widget.spread_style);
+        }
+
+        for (x, y) in [(4, 0), (4, 1), (0, 2), (2, 2)] {
+            expected.set_style(Rect::new(x, y, 1, 1), widget.jump_style);
+        }
+
+        expected.set_style(Rect::new(0, 0, 1, 1), widget.departure_style);
+
+        let tested = tested(|state| {
+            state.select();
+        });
+
+        pretty_assertions::assert_eq!(tested, expected);
+    }
+
+    #[test]
+    fn selection() {
+        let lines = [
+            "O O O O O",
+            "V O O O O",
+            "O O O O O",
+            "O O O O O",
+            "O O O O O",
+        ];
+
+        let mut expected = expected(lines);
+        let widget = Board::default();
+        expected.set_style(Rect::new(0, 0, 1, 1), widget.departure_style);
+        expected.set_style(Rect::new(0, 1, 1, 1), widget.destination_style);
+
+        let tested = tested(|state| {
+            state.select();
+            state.down();
+            state.select();
+        });
+
+        pretty_assertions::assert_eq!(tested, expected);
+    }
+
+    #[test]
+    fn game_over() {
+        let board = crate::game::Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+        let mut state = BoardState::new(board, Red);
+
+        state.select();
+        state.right();
+        state.select();
+        state.select();
+
+        pretty_assertions::assert_eq!(state.outcome(), Some(Outcome::Win(Red)));
+        pretty_assertions::assert_eq!(state.moves(), 1);
+    }
+
+    #[test]
+    fn undo_against_opponent() {
+        let board = crate::game::Board::new(
+            2,
+            5,
+            vec![
+                Player(Red),
+                Free,
+                Free,
+                Free,
+                Free,
+                Free,
+                Free,
+                Free,
+                Free,
+                Player(Blue),
+            ],
+        );
+
+        let mut state = BoardState::new(board, Red)
+            .with_opponent(Ai::with_seed(crate::ai::Personality::Aggressive, 0));
+
+        state.play_move(Move {
+            from: (0, 0),
+            to: (0, 1),
+        });
+
+        while state.is_thinking() || state.is_animating() {
+            state.tick(Duration::from_secs(1));
+            state.poll();
+            state.update();
+        }
+
+        let plies = state.record().plies.clone();
+        pretty_assertions::assert_eq!(plies.len(), 2);
+
+        assert!(state.undo());
+        assert!(state.record().plies.is_empty());
+        pretty_assertions::assert_eq!(state.current_player(), Red);
+        pretty_assertions::assert_eq!(state.captures(), crate::game::Score::default());
+
+        assert!(state.redo());
+        assert!(!state.is_thinking());
+        pretty_assertions::assert_eq!(state.record().plies, plies);
+        pretty_assertions::assert_eq!(state.current_player(), Red);
+
+        assert!(state.undo());
+        state.play_move(Move {
+            from: (0, 0),
+            to: (1, 0),
+        });
+        assert!(!state.redo());
+    }
+
+    #[test]
+    fn remote() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let accepted =
+            std::thread::spawn(move || crate::net::Connection::accept(&listener).unwrap());
+        let joined = crate::net::Connection::join(address).unwrap();
+
+        let board = crate::game::Board::new(1, 4, vec![Player(Red), Free, Free, Player(Blue)]);
+        let mut host =
+            BoardState::new(board.clone(), Red).with_remote(Blue, accepted.join().unwrap(), "2a");
+        let mut guest = BoardState::new(board, Red).with_remote(Red, joined, "2a");
+
+        let ply = Move {
+            from: (0, 0),
+            to: (0, 1),
+        };
+
+        guest.play_move(ply);
+        assert!(guest.record().plies.is_empty());
+
+        host.play_move(ply);
+
+        while guest.current_player() == Red {
+            guest.tick(Duration::from_secs(1));
+            guest.poll();
+            guest.update();
+        }
+
+        pretty_assertions::assert_eq!(guest.record(), host.record());
+        assert!(!host.undo());
+
+        assert!(host.say("good luck"));
+
+        while guest.chat().is_empty() {
+            guest.tick(Duration::from_secs(1));
+            guest.poll();
+            guest.update();
+        }
+
+        pretty_assertions::assert_eq!(guest.chat(), &[(Red, "good luck".to_owned())]);
+
+        drop(guest);
+
+        while host.outcome().is_none() {
+            host.tick(Duration::from_secs(1));
+            host.poll();
+            host.update();
+        }
+
+        pretty_assertions::assert_eq!(host.outcome(), Some(Outcome::Win(Red)));
+    }
+
+    #[test]
+    fn resync() {
+        use crate::net::NetMessage;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let accepted =
+            std::thread::spawn(move || crate::net::Connection::accept(&listener).unwrap());
+        let peer = crate::net::Connection::join(address).unwrap();
+
+        let board =
+            crate::game::Board::new(1, 5, vec![Player(Red), Free, Free, Free, Player(Blue)]);
+        let mut state =
+            BoardState::new(board, Red).with_remote(Blue, accepted.join().unwrap(), "2a");
+
+        let red = Move {
+            from: (0, 0),
+            to: (0, 1),
+        };
+
+        let blue = Move {
+            from: (0, 4),
+            to: (0, 3),
+        };
+
+        state.play_move(red);
+        pretty_assertions::assert_eq!(peer.recv().unwrap(), NetMessage::Ply(Some(red)));
+
+        peer.send(&NetMessage::Resume {
+            game: "2a".to_owned(),
+            plies: 0,
+        })
+        .unwrap();
+
+        state.tick(Duration::from_secs(1));
+
+        while state.record().plies.len() == 1 {
+            state.poll();
+            state.update();
+
+            if let Some(message) = peer.try_recv() {
+                pretty_assertions::assert_eq!(
+                    message,
+                    NetMessage::Replay {
+                        from: 0,
+                        plies: vec![Some(red)],
+                    }
+                );
+
+                peer.send(&NetMessage::Replay {
+                    from: 0,
+                    plies: vec![Some(red), Some(blue)],
+                })
+                .unwrap();
+            }
+        }
+
+        pretty_assertions::assert_eq!(state.record().plies, vec![Some(red), Some(blue)]);
+        pretty_assertions::assert_eq!(state.current_player(), Red);
+        pretty_assertions::assert_eq!(state.outcome(), None);
+    }
+
+    #[test]
+    fn forced_pass() {
+        #[rustfmt::skip]
+        let board = vec![
+            Player(Red), Restricted,  Restricted, Restricted,   Restricted,
+            Restricted,  Restricted,  Restricted, Restricted,   Restricted,
+            Restricted,  Player(Red), Free,       Player(Blue), Free,
+        ];
+
+        let board = crate::game::Board::new(3, 5, board);
+        let mut state = BoardState::new(board, Blue);
+
+        state.handle_command(Command::Down);
+        state.handle_command(Command::Down);
+        state.handle_command(Command::Right);
+        state.handle_command(Command::Right);
+        state.handle_command(Command::Right);
+        state.handle_command(Command::Select);
+        state.handle_command(Command::Left);
+        state.handle_command(Command::Select);
+        state.handle_command(Command::Select);
+
+        pretty_assertions::assert_eq!(state.outcome(), None);
+        pretty_assertions::assert_eq!(state.current_player, Red);
+
+        state.tick(Duration::from_secs(1));
+        state.handle_command(Command::Pass);
+        pretty_assertions::assert_eq!(state.current_player, Blue);
+    }
+
     #[test]
     fn spread() {
         let lines = [
@@ -667,4 +4303,124 @@ mod tests {
 
         pretty_assertions::assert_eq!(tested, expected);
     }
+
+    #[test]
+    fn viewport() {
+        let area = Rect::new(0, 0, 12, 6);
+        let mut buffer = Buffer::empty(area);
+        let mut state = BoardState::new(crate::game::Board::free(12, 12), Red);
+
+        Board::default().render(area, &mut buffer, &mut state);
+        pretty_assertions::assert_eq!(state.cell_at(Position::new(0, 0)), Some((0, 0)));
+
+        state.last_row();
+        state.last_column();
+        Board::default().render(area, &mut buffer, &mut state);
+
+        let shown = state
+            .cells
+            .iter()
+            .map(|&(index, _)| index)
+            .collect::<Vec<_>>();
+        pretty_assertions::assert_eq!(shown.len(), 5 * 6);
+        assert!(shown.contains(&(11, 11)) && !shown.contains(&(0, 0)));
+        pretty_assertions::assert_eq!(buffer[(11, 0)].symbol(), "▲");
+    }
+
+    #[test]
+    fn grid() {
+        let area = Rect::new(0, 0, 5, 1);
+        let mut tested = Buffer::empty(area);
+
+        let board = crate::game::Board::new(1, 3, vec![Free, Free, Free]);
+        let mut state = BoardState::new(board, Red);
+
+        Board::default()
+            .grid(Grid::Both)
+            .render(area, &mut tested, &mut state);
+
+        let mut expected = Buffer::with_lines(["V│O│O"]);
+        expected.set_style(Rect::new(0, 0, 1, 1), Style::new().fg(Color::Reset));
+        expected.set_style(Rect::new(1, 0, 1, 1), Theme::default().tertiary);
+        expected.set_style(Rect::new(2, 0, 1, 1), Board::default().shade_style);
+        expected.set_style(Rect::new(2, 0, 1, 1), Style::new().fg(Color::Reset));
+        expected.set_style(Rect::new(3, 0, 1, 1), Theme::default().tertiary);
+        expected.set_style(Rect::new(4, 0, 1, 1), Style::new().fg(Color::Reset));
+
+        pretty_assertions::assert_eq!(tested, expected);
+    }
+
+    #[test]
+    fn score() {
+        let area = Rect::new(0, 0, 30, 7);
+        let mut tested = Buffer::empty(area);
+
+        let board = crate::game::Board::new(1, 4, vec![Player(Red), Free, Player(Blue), Free]);
+        let mut state = BoardState::new(board, Red);
+
+        state.play_move(Move {
+            from: (0, 0),
+            to: (0, 1),
+        });
+
+        Score {
+            theme: Theme::default(),
+        }
+        .render(area, &mut tested, &mut state);
+
+        let rows = (1..6)
+            .map(|y| (1..29).map(|x| tested[(x, y)].symbol()).collect::<String>())
+            .collect::<Vec<_>>();
+
+        pretty_assertions::assert_eq!(
+            rows.iter().map(|row| row.trim_end()).collect::<Vec<_>>(),
+            [
+                "Status:",
+                "Turn: Blue",
+                "From (selected): (None)",
+                "To (selected): (None)",
+                "Last captures: 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn score_bar() {
+        let theme = Theme::default();
+
+        pretty_assertions::assert_eq!(
+            super::score_bar(1, 3, 8, theme),
+            Line::from(vec![
+                span!(theme.player_color(Blue); "██"),
+                span!(theme.player_color(Red); "██████"),
+            ])
+        );
+
+        pretty_assertions::assert_eq!(super::score_bar(0, 0, 3, theme).width(), 3);
+        pretty_assertions::assert_eq!((share(2, 1), share(0, 0)), (67, 0));
+    }
+
+    #[test]
+    fn wide_glyphs() {
+        let area = Rect::new(0, 0, 5, 1);
+        let mut tested = Buffer::empty(area);
+
+        let board = crate::game::Board::new(1, 2, vec![Player(Red), Player(Blue)]);
+        let mut state = BoardState::new(board, Red);
+
+        Board::default()
+            .render_style(RenderStyle::Emoji)
+            .render(area, &mut tested, &mut state);
+
+        pretty_assertions::assert_eq!(
+            state.cells,
+            vec![
+                ((0, 0), Rect::new(0, 0, 2, 1)),
+                ((0, 1), Rect::new(3, 0, 2, 1))
+            ]
+        );
+
+        pretty_assertions::assert_eq!(tested[(0, 0)].symbol(), "🔴");
+        pretty_assertions::assert_eq!(tested[(3, 0)].symbol(), "🔵");
+    }
 }
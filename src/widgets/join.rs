@@ -0,0 +1,160 @@
+//! Implementation of [`JoinMenu`] and its [associated state](JoinState)
+
+use crate::{
+    Command, CommandManaged,
+    net::discovery::{DiscoveredGame, Discovery},
+    widgets::Theme,
+};
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Paragraph},
+};
+
+use ratatui_macros::{line, span};
+
+/// The state of the [`JoinMenu` widget](JoinMenu)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JoinState {
+    discovery: Option<Discovery>,
+    selected: usize,
+}
+
+impl JoinState {
+    /// Create a new [`JoinState`]
+    ///
+    /// # Parameter
+    ///
+    /// - `discovery` - The [browser](Discovery) of the hosted games, if available
+    pub fn new(discovery: Option<Discovery>) -> Self {
+        Self {
+            discovery,
+            selected: 0,
+        }
+    }
+
+    /// Create a new [`JoinState`] browsing the games hosted on the local network
+    pub fn scan() -> Self {
+        let discovery = Discovery::start()
+            .inspect_err(|error| tracing::warn!("The games cannot be discovered: {error}"))
+            .ok();
+
+        Self::new(discovery)
+    }
+
+    /// Check if the hosted games can be discovered
+    pub fn is_available(&self) -> bool {
+        self.discovery.is_some()
+    }
+
+    /// Get the [discovered games](DiscoveredGame)
+    pub fn games(&self) -> &[DiscoveredGame] {
+        self.discovery.as_ref().map_or(&[], Discovery::games)
+    }
+
+    /// Get the selected [game](DiscoveredGame), if any
+    pub fn selected(&self) -> Option<&DiscoveredGame> {
+        self.games().get(self.selected)
+    }
+
+    /// Select the previous game
+    pub fn up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Select the next game
+    pub fn down(&mut self) {
+        self.selected = (self.selected + 1).min(self.games().len().saturating_sub(1));
+    }
+
+    /// Update the discovered games
+    ///
+    /// This method is expected to be called regularly by the main loop.
+    ///
+    /// # Return
+    ///
+    /// The returned value is `true` if the discovered games have changed; otherwise, it returns `false`.
+    pub fn refresh(&mut self) -> bool {
+        let changed = self.discovery.as_mut().is_some_and(Discovery::refresh);
+        self.selected = self.selected.min(self.games().len().saturating_sub(1));
+        changed
+    }
+}
+
+impl CommandManaged for JoinState {
+    fn handle_command(&mut self, command: Command) {
+        match command {
+            Command::Up => self.up(),
+            Command::Down => self.down(),
+            _ => (),
+        }
+    }
+}
+
+/// The join game widget
+///
+/// This widget lists the games hosted on the local network along with their addresses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct JoinMenu {
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl StatefulWidget for JoinMenu {
+    type State = JoinState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let mut lines = state
+            .games()
+            .iter()
+            .enumerate()
+            .map(|(i, game)| {
+                let (marker, style) = if i == state.selected {
+                    ("> ", self.theme.important)
+                } else {
+                    ("  ", self.theme.primary)
+                };
+
+                line![
+                    span!(style; "{marker}{}", game.name),
+                    span!(self.theme.tertiary; "  {}", game.address),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        if !state.is_available() {
+            lines.push(line![
+                span!(self.theme.emph; "The local network cannot be listened to")
+            ]);
+        } else if lines.is_empty() {
+            lines.push(line![span!(self.theme.emph; "Looking for games…")]);
+        }
+
+        lines.push(line![]);
+
+        lines.push(line![span!(
+            self.theme.tertiary;
+            "↑/↓ to select, Enter to join, Esc to go back"
+        )]);
+
+        Paragraph::new(lines)
+            .block(Block::bordered().title("Join a game"))
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unavailable() {
+        let mut state = JoinState::new(None);
+
+        state.down();
+        assert!(!state.refresh());
+
+        assert!(!state.is_available());
+        pretty_assertions::assert_eq!(state.selected(), None);
+    }
+}
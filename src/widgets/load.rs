@@ -0,0 +1,170 @@
+//! Implementation of [`LoadMenu`] and its [associated state](LoadState)
+
+use crate::{
+    Command, CommandManaged,
+    save::{SaveFile, SavedGame},
+    widgets::Theme,
+};
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Paragraph},
+};
+
+use ratatui_macros::{line, span};
+use std::time::Duration;
+
+/// The state of the [`LoadMenu` widget](LoadMenu)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LoadState {
+    saves: Vec<SaveFile>,
+    selected: usize,
+}
+
+impl LoadState {
+    /// Create a new [`LoadState`]
+    ///
+    /// # Parameter
+    ///
+    /// - `saves` - The listed [saves](SaveFile)
+    pub fn new(saves: Vec<SaveFile>) -> Self {
+        Self { saves, selected: 0 }
+    }
+
+    /// Create a new [`LoadState`] listing the saves of the [save directory](SavedGame::directory)
+    pub fn scan() -> Self {
+        Self::new(SavedGame::list())
+    }
+
+    /// Get the listed [saves](SaveFile)
+    pub fn saves(&self) -> &[SaveFile] {
+        &self.saves
+    }
+
+    /// Get the selected [save](SaveFile), if any
+    pub fn selected(&self) -> Option<&SaveFile> {
+        self.saves.get(self.selected)
+    }
+
+    /// Select the previous save
+    pub fn up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Select the next save
+    pub fn down(&mut self) {
+        self.selected = (self.selected + 1).min(self.saves.len().saturating_sub(1));
+    }
+}
+
+impl CommandManaged for LoadState {
+    fn handle_command(&mut self, command: Command) {
+        match command {
+            Command::Up => self.up(),
+            Command::Down => self.down(),
+            _ => (),
+        }
+    }
+}
+
+/// The load game widget
+///
+/// This widget lists the [saved games](SavedGame) along with the number of moves played, the score, the computer opponent and the age of the save.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LoadMenu {
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl StatefulWidget for LoadMenu {
+    type State = LoadState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let mut lines = state
+            .saves
+            .iter()
+            .enumerate()
+            .map(|(i, save)| {
+                let record = &save.game.record;
+                let moves = record.plies.iter().flatten().count();
+                let score = record.position(record.plies.len()).0.score();
+
+                let opponent = save
+                    .game
+                    .opponent
+                    .map(|(_, personality, difficulty)| format!("{personality} {difficulty}"))
+                    .unwrap_or("Human".to_owned());
+
+                let age = save.age.map(display_age).unwrap_or("unknown".to_owned());
+
+                let (marker, style) = if i == state.selected {
+                    ("> ", self.theme.important)
+                } else {
+                    ("  ", self.theme.primary)
+                };
+
+                line![
+                    span!(style; "{marker}{}", save.name()),
+                    span!(self.theme.secondary; "  {moves} moves, "),
+                    span!(self.theme.secondary; "{} - {}, ", score.blue, score.red),
+                    span!(self.theme.secondary; "vs {opponent}, "),
+                    span!(self.theme.tertiary; "{age}"),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        if lines.is_empty() {
+            lines.push(line![span!(self.theme.emph; "No saved game")]);
+        }
+
+        lines.push(line![]);
+
+        lines.push(line![span!(
+            self.theme.tertiary;
+            "↑/↓ to select, Enter to load, Esc to go back"
+        )]);
+
+        Paragraph::new(lines)
+            .block(Block::bordered().title("Load game"))
+            .render(area, buf);
+    }
+}
+
+fn display_age(age: Duration) -> String {
+    let seconds = age.as_secs();
+
+    let (count, unit) = match seconds {
+        0..60 => return "just now".to_owned(),
+        60..3600 => (seconds / 60, "minute"),
+        3600..86400 => (seconds / 3600, "hour"),
+        _ => (seconds / 86400, "day"),
+    };
+
+    if count == 1 {
+        format!("1 {unit} ago")
+    } else {
+        format!("{count} {unit}s ago")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn age() {
+        pretty_assertions::assert_eq!(display_age(Duration::from_secs(5)), "just now");
+        pretty_assertions::assert_eq!(display_age(Duration::from_secs(60)), "1 minute ago");
+        pretty_assertions::assert_eq!(display_age(Duration::from_secs(7300)), "2 hours ago");
+    }
+
+    #[test]
+    fn empty() {
+        let mut state = LoadState::new(Vec::new());
+
+        state.down();
+        state.up();
+
+        pretty_assertions::assert_eq!(state.selected(), None);
+    }
+}
@@ -0,0 +1,189 @@
+//! Implementation of [`MapMenu`] and its [associated state](MapState)
+
+use crate::{
+    Command, CommandManaged,
+    game::{Board, CellState},
+    map::MapFile,
+    widgets::Theme,
+};
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Paragraph},
+};
+
+use ratatui_macros::{constraints, line, span};
+
+/// The state of the [`MapMenu` widget](MapMenu)
+///
+/// The first entry stands for the randomly generated boards and is followed by the listed maps.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MapState {
+    maps: Vec<MapFile>,
+    selected: usize,
+}
+
+impl MapState {
+    /// Create a new [`MapState`]
+    ///
+    /// # Parameter
+    ///
+    /// - `maps` - The listed [maps](MapFile)
+    pub fn new(maps: Vec<MapFile>) -> Self {
+        Self { maps, selected: 0 }
+    }
+
+    /// Create a new [`MapState`] listing the maps of the [map directory](MapFile::directory)
+    pub fn scan() -> Self {
+        Self::new(MapFile::list())
+    }
+
+    /// Get the listed [maps](MapFile)
+    pub fn maps(&self) -> &[MapFile] {
+        &self.maps
+    }
+
+    /// Get the selected [map](MapFile), if any
+    ///
+    /// `None` is returned if the randomly generated boards are selected.
+    pub fn selected(&self) -> Option<&MapFile> {
+        self.selected
+            .checked_sub(1)
+            .and_then(|index| self.maps.get(index))
+    }
+
+    /// Select the previous entry
+    pub fn up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Select the next entry
+    pub fn down(&mut self) {
+        self.selected = (self.selected + 1).min(self.maps.len());
+    }
+}
+
+impl CommandManaged for MapState {
+    fn handle_command(&mut self, command: Command) {
+        match command {
+            Command::Up => self.up(),
+            Command::Down => self.down(),
+            _ => (),
+        }
+    }
+}
+
+/// The map picker widget
+///
+/// This widget lists the [custom maps](MapFile) along with a preview of the selected one, or the reason why it is malformed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MapMenu {
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl MapMenu {
+    fn preview(self, board: &Board) -> Vec<Line<'static>> {
+        (0..board.height())
+            .map(|row| {
+                (0..board.width())
+                    .map(|column| match board.get(row, column) {
+                        Some(CellState::Player(player)) => {
+                            span!(self.theme.player_color(player); "● ")
+                        }
+
+                        Some(CellState::Restricted) => span!(self.theme.restricted; "# "),
+
+                        _ => match board.power_up(row, column) {
+                            Some(power_up) => {
+                                span!(Color::from(power_up); "{} ", power_up.symbol())
+                            }
+
+                            None => span!(self.theme.tertiary; "· "),
+                        },
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl StatefulWidget for MapMenu {
+    type State = MapState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let block = Block::bordered().title("Choose a map");
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [list, preview] = Layout::horizontal(constraints![==40%, ==60%]).areas(inner);
+
+        let entries = std::iter::once(("Random board".to_owned(), None))
+            .chain(
+                state
+                    .maps
+                    .iter()
+                    .map(|map| (map.name(), map.board.as_ref().err())),
+            )
+            .enumerate()
+            .map(|(i, (name, error))| {
+                let (marker, style) = if i == state.selected {
+                    ("> ", self.theme.important)
+                } else if error.is_some() {
+                    ("  ", self.theme.tertiary)
+                } else {
+                    ("  ", self.theme.primary)
+                };
+
+                line![span!(style; "{marker}{name}")]
+            });
+
+        let mut lines = entries.collect::<Vec<_>>();
+
+        if state.maps.is_empty() {
+            lines.push(line![span!(self.theme.emph; "No custom map")]);
+        }
+
+        lines.push(line![]);
+
+        lines.push(line![span!(
+            self.theme.tertiary;
+            "↑/↓ to select, Enter to play, Esc to go back"
+        )]);
+
+        Paragraph::new(lines).render(list, buf);
+
+        let lines = match state.selected().map(|map| &map.board) {
+            Some(Ok(board)) => self.preview(board),
+            Some(Err(error)) => vec![line![span!(self.theme.emph; "Malformed map: {error}")]],
+            None => vec![line![
+                span!(self.theme.secondary; "A new board is generated for each game")
+            ]],
+        };
+
+        Paragraph::new(lines).render(preview, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select() {
+        let map = MapFile {
+            path: "duel.txt".into(),
+            board: "B.R".parse(),
+        };
+
+        let mut state = MapState::new(vec![map.clone()]);
+        pretty_assertions::assert_eq!(state.selected(), None);
+
+        state.down();
+        state.down();
+        pretty_assertions::assert_eq!(state.selected(), Some(&map));
+
+        state.up();
+        pretty_assertions::assert_eq!(state.selected(), None);
+    }
+}
@@ -0,0 +1,322 @@
+//! Implementation of [`Replay`] and its [associated state](ReplayState)
+
+use crate::{
+    Command, CommandManaged,
+    animation::Animation,
+    record::{GameRecord, display_index},
+    widgets::{
+        Theme,
+        board::{Board, BoardState},
+    },
+};
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Paragraph, Wrap},
+};
+
+use ratatui_macros::{constraints, line, span, text};
+use std::time::Duration;
+
+/// State of the [`Replay`] widget
+///
+/// This state holds the replayed [record](GameRecord), the shown ply and the state of the autoplay.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayState {
+    record: GameRecord,
+    ply: usize,
+    board: BoardState,
+    autoplay: bool,
+    speed: usize,
+    elapsed: Duration,
+}
+
+impl ReplayState {
+    /// The available durations between two plies during the autoplay
+    pub const SPEEDS: [Duration; 4] = [
+        Duration::from_millis(2000),
+        Duration::from_millis(1000),
+        Duration::from_millis(500),
+        Duration::from_millis(250),
+    ];
+
+    /// Create a new [`ReplayState`] showing the beginning of the game
+    ///
+    /// # Parameter
+    ///
+    /// - `record` - The [record](GameRecord) of the replayed game
+    pub fn new(record: GameRecord) -> Self {
+        let board = BoardState::new(record.board.clone(), record.first);
+
+        Self {
+            record,
+            ply: 0,
+            board,
+            autoplay: false,
+            speed: 1,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Get the replayed [record](GameRecord)
+    pub fn record(&self) -> &GameRecord {
+        &self.record
+    }
+
+    /// Get the number of plies played in the shown position
+    pub fn ply(&self) -> usize {
+        self.ply
+    }
+
+    /// Check if the plies are played automatically
+    pub fn is_autoplaying(&self) -> bool {
+        self.autoplay
+    }
+
+    /// Get the duration between two plies during the autoplay
+    pub fn speed(&self) -> Duration {
+        Self::SPEEDS[self.speed]
+    }
+
+    /// Show the position after a given ply
+    ///
+    /// When the position after the next ply is shown, the _blobs_ converted by this ply [flash](Animation::capture).
+    ///
+    /// # Parameter
+    ///
+    /// - `ply` - The number of plies played; it is clamped to the length of the game
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{
+    ///     game::{Board, CellState::*, Move, Player::*},
+    ///     record::GameRecord,
+    ///     widgets::replay::ReplayState,
+    /// };
+    ///
+    /// let mut record = GameRecord::new(Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]), Red);
+    /// record.plies.push(Some(Move { from: (0, 0), to: (0, 1) }));
+    ///
+    /// let mut state = ReplayState::new(record);
+    /// state.go_to(5);
+    /// assert_eq!(state.ply(), 1);
+    /// ```
+    pub fn go_to(&mut self, ply: usize) {
+        let ply = ply.min(self.record.plies.len());
+        let forward = ply == self.ply + 1;
+        self.ply = ply;
+
+        let (board, player) = self.record.position(self.ply);
+        let mut board = BoardState::new(board, player);
+
+        if let Some(Some(chosen)) = self.ply.checked_sub(1).map(|last| self.record.plies[last]) {
+            board = board.with_selected(chosen.to);
+
+            if forward {
+                let (before, player) = self.record.position(self.ply - 1);
+                board = board.with_animation(Animation::capture(&before, chosen, player));
+            }
+        }
+
+        self.board = board;
+    }
+
+    /// Show the position after the next ply
+    pub fn next(&mut self) {
+        self.go_to(self.ply + 1);
+    }
+
+    /// Show the position before the last ply
+    pub fn previous(&mut self) {
+        self.go_to(self.ply.saturating_sub(1));
+    }
+
+    /// Enable or disable the autoplay
+    ///
+    /// If the end of the game is shown, the autoplay restarts from the beginning.
+    pub fn toggle_autoplay(&mut self) {
+        self.autoplay = !self.autoplay;
+        self.elapsed = Duration::ZERO;
+
+        if self.autoplay && self.ply == self.record.plies.len() {
+            self.go_to(0);
+        }
+    }
+
+    /// Advance the autoplay
+    ///
+    /// # Parameter
+    ///
+    /// - `elapsed` - The time elapsed since the last tick
+    ///
+    /// # Return
+    ///
+    /// The returned value is `true` if another ply is shown or if some converted _blobs_ are flashing; otherwise, it returns `false`.
+    pub fn tick(&mut self, elapsed: Duration) -> bool {
+        let flashing = self.board.is_animating();
+        self.board.tick(elapsed);
+
+        if !self.autoplay {
+            return flashing;
+        }
+
+        self.elapsed += elapsed;
+
+        if self.elapsed < self.speed() {
+            return flashing;
+        }
+
+        self.elapsed = Duration::ZERO;
+        self.next();
+        self.autoplay = self.ply < self.record.plies.len();
+        true
+    }
+}
+
+impl CommandManaged for ReplayState {
+    fn handle_command(&mut self, command: Command) {
+        match command {
+            Command::Left => self.previous(),
+            Command::Right => self.next(),
+            Command::FirstColumn => self.go_to(0),
+            Command::LastColumn => self.go_to(usize::MAX),
+            Command::Select => self.toggle_autoplay(),
+            Command::Up => self.speed = (self.speed + 1).min(Self::SPEEDS.len() - 1),
+            Command::Down => self.speed = self.speed.saturating_sub(1),
+            _ => (),
+        }
+    }
+}
+
+/// Widget replaying a recorded game
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Replay<'a> {
+    /// The [widget](Board) used to show the board
+    pub board: Board<'a>,
+
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+}
+
+impl StatefulWidget for Replay<'_> {
+    type State = ReplayState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let [left, right] = Layout::horizontal(constraints![==80%, ==20%]).areas(area);
+
+        self.board.render(left, buf, &mut state.board);
+
+        let plies = state.record.plies.len();
+
+        let last = match state
+            .ply
+            .checked_sub(1)
+            .map(|last| state.record.plies[last])
+        {
+            Some(Some(chosen)) => format!(
+                "{}-{}",
+                display_index(chosen.from),
+                display_index(chosen.to)
+            ),
+            Some(None) => "pass".to_owned(),
+            None => "-".to_owned(),
+        };
+
+        let autoplay = if state.autoplay { "On" } else { "Off" };
+        let score = state.board.score();
+
+        let text = text![
+            line![
+                span!(self.theme.primary; "Ply: "),
+                span!(self.theme.important; "{}/{plies}", state.ply),
+            ],
+            line![
+                span!(self.theme.primary; "Last: "),
+                span!(self.theme.important; last),
+            ],
+            line![
+                self.theme.player(state.board.current_player()),
+                span!(self.theme.primary; " to move"),
+            ],
+            line![],
+            line![
+                self.theme.player(crate::game::Player::Blue),
+                ": ",
+                score.blue.to_string()
+            ],
+            line![
+                self.theme.player(crate::game::Player::Red),
+                ": ",
+                score.red.to_string()
+            ],
+            line![],
+            line![
+                span!(self.theme.primary; "Autoplay: "),
+                span!(self.theme.important; "{autoplay} ({:.2}s)", state.speed().as_secs_f64()),
+            ],
+            line![],
+            span!(self.theme.tertiary; "←/→ to step, Home/End to jump, Enter to autoplay, ↑/↓ to change the speed, Esc to go back"),
+        ];
+
+        Paragraph::new(text)
+            .block(Block::bordered().title("Replay"))
+            .wrap(Wrap { trim: true })
+            .render(right, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{CellState::*, Move, Player::*};
+
+    #[test]
+    fn autoplay() {
+        let board = crate::game::Board::new(1, 4, vec![Player(Red), Free, Free, Player(Blue)]);
+        let mut record = GameRecord::new(board, Red);
+
+        record.plies = vec![
+            Some(Move {
+                from: (0, 0),
+                to: (0, 1),
+            }),
+            None,
+        ];
+
+        let mut state = ReplayState::new(record);
+        state.handle_command(Command::Select);
+
+        assert!(!state.tick(state.speed() / 2));
+        assert!(state.tick(state.speed()));
+        pretty_assertions::assert_eq!(state.ply(), 1);
+        pretty_assertions::assert_eq!(state.board.current_player(), Blue);
+
+        assert!(state.tick(state.speed()));
+        assert!(!state.is_autoplaying());
+
+        state.handle_command(Command::Left);
+        pretty_assertions::assert_eq!(state.ply(), 1);
+    }
+
+    #[test]
+    fn capture_flash() {
+        let board = crate::game::Board::new(1, 3, vec![Player(Red), Free, Player(Blue)]);
+        let mut record = GameRecord::new(board, Red);
+
+        record.plies = vec![Some(Move {
+            from: (0, 0),
+            to: (0, 1),
+        })];
+
+        let mut state = ReplayState::new(record);
+        state.handle_command(Command::Right);
+        assert!(state.board.is_animating());
+        assert!(state.tick(Animation::FLASH));
+        assert!(!state.board.is_animating());
+
+        state.handle_command(Command::FirstColumn);
+        state.handle_command(Command::LastColumn);
+        assert!(state.board.is_animating());
+    }
+}
@@ -0,0 +1,534 @@
+//! Implementation of [`Settings`] and its [associated state](SettingsState)
+
+use crate::{
+    Command, CommandManaged,
+    ai::{Difficulty, Personality},
+    clock::TimeControl,
+    config::Config,
+    game::{RuleSet, Topology},
+    keymap::Keymap,
+    widgets::{
+        LayoutPreset, Symbols, Theme, ThemeName,
+        board::{Flip, Grid, RenderStyle},
+    },
+};
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Paragraph, Wrap},
+};
+
+use ratatui_macros::{line, span};
+
+use std::{
+    iter::once,
+    ops::{Add, RangeInclusive, Sub},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Field {
+    Height,
+    Width,
+    Density,
+    Opponent,
+    Difficulty,
+    Clock,
+    Theme,
+    Symbols,
+    RenderStyle,
+    Grid,
+    Colorblind,
+    Wrap,
+    FreePass,
+    JumpDistance,
+    ConversionRadius,
+    Topology,
+    Misere,
+    MoveLimit,
+    Flip,
+    Layout,
+}
+
+impl Field {
+    const ALL: [Self; 20] = [
+        Self::Height,
+        Self::Width,
+        Self::Density,
+        Self::Opponent,
+        Self::Difficulty,
+        Self::Clock,
+        Self::Theme,
+        Self::Symbols,
+        Self::RenderStyle,
+        Self::Grid,
+        Self::Colorblind,
+        Self::Wrap,
+        Self::FreePass,
+        Self::JumpDistance,
+        Self::ConversionRadius,
+        Self::Topology,
+        Self::Misere,
+        Self::MoveLimit,
+        Self::Flip,
+        Self::Layout,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Height => "Board height",
+            Self::Width => "Board width",
+            Self::Density => "Restricted cells",
+            Self::Opponent => "Opponent",
+            Self::Difficulty => "Difficulty",
+            Self::Clock => "Clock",
+            Self::Theme => "Theme",
+            Self::Symbols => "Symbols",
+            Self::RenderStyle => "Pieces",
+            Self::Grid => "Grid",
+            Self::Colorblind => "Colorblind mode",
+            Self::Wrap => "Cursor wrap-around",
+            Self::FreePass => "Pass at will",
+            Self::JumpDistance => "Jump distance",
+            Self::ConversionRadius => "Capture radius",
+            Self::Topology => "Topology",
+            Self::Misere => "Misère (fewest blobs win)",
+            Self::MoveLimit => "Move limit",
+            Self::Flip => "Flip board",
+            Self::Layout => "Layout",
+        }
+    }
+
+    fn value(self, config: &Config) -> String {
+        match self {
+            Self::Height => config.height.to_string(),
+            Self::Width => config.width.to_string(),
+            Self::Density => format!("{}%", config.density),
+
+            Self::Opponent => config
+                .opponent
+                .map(|personality| format!("{personality} computer"))
+                .unwrap_or("Human".to_owned()),
+
+            Self::Difficulty => config.difficulty.to_string(),
+
+            Self::Clock => config
+                .clock
+                .map(|control| control.to_string())
+                .unwrap_or("Unlimited".to_owned()),
+
+            Self::Theme => config.theme.to_string(),
+            Self::Symbols => config.symbols.to_string(),
+            Self::RenderStyle => config.render_style.to_string(),
+            Self::Grid => config.grid.to_string(),
+
+            Self::Colorblind => display_bool(config.colorblind),
+            Self::Wrap => display_bool(config.wrap),
+            Self::FreePass => display_bool(config.free_pass),
+            Self::JumpDistance => config.rules.jump_distance.to_string(),
+            Self::ConversionRadius => config.rules.conversion_radius.to_string(),
+            Self::Topology => config.rules.topology.to_string(),
+            Self::Misere => display_bool(config.rules.misere),
+
+            Self::MoveLimit => config
+                .rules
+                .move_limit
+                .map(|limit| limit.to_string())
+                .unwrap_or("Unlimited".to_owned()),
+
+            Self::Flip => config.flip.to_string(),
+            Self::Layout => config.layout.to_string(),
+        }
+    }
+
+    fn change(self, config: &mut Config, forward: bool) {
+        match self {
+            Self::Height => config.height = step(config.height, 1, Config::SIZES, forward),
+            Self::Width => config.width = step(config.width, 1, Config::SIZES, forward),
+            Self::Density => config.density = step(config.density, 5, Config::DENSITIES, forward),
+
+            Self::Opponent => {
+                let opponents = once(None)
+                    .chain(Personality::ALL.map(Some))
+                    .collect::<Vec<_>>();
+
+                config.opponent = cycle(&opponents, config.opponent, forward);
+            }
+
+            Self::Difficulty => {
+                config.difficulty = cycle(&Difficulty::ALL, config.difficulty, forward)
+            }
+
+            Self::Clock => {
+                let controls = once(None)
+                    .chain(TimeControl::PRESETS.map(Some))
+                    .collect::<Vec<_>>();
+
+                config.clock = cycle(&controls, config.clock, forward);
+            }
+
+            Self::Theme => config.theme = cycle(&ThemeName::ALL, config.theme, forward),
+            Self::Symbols => config.symbols = cycle(&Symbols::PRESETS, config.symbols, forward),
+
+            Self::RenderStyle => {
+                config.render_style = cycle(&RenderStyle::ALL, config.render_style, forward)
+            }
+
+            Self::Grid => config.grid = cycle(&Grid::ALL, config.grid, forward),
+
+            Self::Colorblind => config.colorblind = !config.colorblind,
+            Self::Wrap => config.wrap = !config.wrap,
+            Self::FreePass => config.free_pass = !config.free_pass,
+            Self::JumpDistance => {
+                config.rules.jump_distance = step(
+                    config.rules.jump_distance,
+                    1,
+                    RuleSet::JUMP_DISTANCES,
+                    forward,
+                )
+            }
+
+            Self::ConversionRadius => {
+                config.rules.conversion_radius = step(
+                    config.rules.conversion_radius,
+                    1,
+                    RuleSet::CONVERSION_RADII,
+                    forward,
+                )
+            }
+
+            Self::Topology => {
+                config.rules.topology = cycle(&Topology::ALL, config.rules.topology, forward)
+            }
+
+            Self::Misere => config.rules.misere = !config.rules.misere,
+
+            Self::MoveLimit => {
+                config.rules.move_limit =
+                    cycle(&RuleSet::MOVE_LIMITS, config.rules.move_limit, forward)
+            }
+
+            Self::Flip => config.flip = cycle(&Flip::ALL, config.flip, forward),
+            Self::Layout => config.layout = cycle(&LayoutPreset::ALL, config.layout, forward),
+        }
+    }
+}
+
+fn display_bool(value: bool) -> String {
+    if value { "On" } else { "Off" }.to_owned()
+}
+
+fn step<T>(value: T, delta: T, range: RangeInclusive<T>, forward: bool) -> T
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    if forward && value < *range.end() {
+        (value + delta).min(*range.end())
+    } else if !forward && value > *range.start() {
+        (value - delta).max(*range.start())
+    } else {
+        value
+    }
+}
+
+fn cycle<T: Copy + PartialEq>(values: &[T], value: T, forward: bool) -> T {
+    let index = values
+        .iter()
+        .position(|&item| item == value)
+        .unwrap_or_default();
+
+    if forward {
+        values[(index + 1) % values.len()]
+    } else {
+        values[(index + values.len() - 1) % values.len()]
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+enum Page {
+    #[default]
+    General,
+    KeyBindings,
+}
+
+/// State of the [`Settings`] widget
+///
+/// This state holds the [configuration](Config) being edited, the shown page and the currently selected setting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SettingsState {
+    config: Config,
+    page: Page,
+    selected: usize,
+}
+
+impl SettingsState {
+    /// Create a new [`SettingsState`]
+    ///
+    /// # Parameter
+    ///
+    /// - `config` - The [configuration](Config) to edit
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            page: Page::default(),
+            selected: 0,
+        }
+    }
+
+    /// Get the edited [configuration](Config)
+    pub fn config(&self) -> Config {
+        self.config
+    }
+
+    /// Check if the key bindings page is shown
+    pub fn is_showing_key_bindings(&self) -> bool {
+        self.page == Page::KeyBindings
+    }
+
+    /// Switch between the general page and the key bindings page
+    pub fn next_page(&mut self) {
+        self.page = match self.page {
+            Page::General => Page::KeyBindings,
+            Page::KeyBindings => Page::General,
+        };
+    }
+
+    /// Select the previous setting
+    pub fn up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Select the next setting
+    pub fn down(&mut self) {
+        self.selected = (self.selected + 1).min(Field::ALL.len() - 1);
+    }
+
+    /// Change the selected setting to its previous value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{config::Config, widgets::settings::SettingsState};
+    ///
+    /// let mut state = SettingsState::new(Config::default());
+    /// state.left();
+    /// assert_eq!(state.config().height, 7);
+    /// ```
+    pub fn left(&mut self) {
+        if self.page != Page::General {
+            return;
+        }
+
+        Field::ALL[self.selected].change(&mut self.config, false);
+    }
+
+    /// Change the selected setting to its next value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use blobwars::{config::Config, widgets::settings::SettingsState};
+    ///
+    /// let mut state = SettingsState::new(Config::default());
+    /// state.down();
+    /// state.right();
+    /// assert_eq!(state.config().width, 9);
+    /// ```
+    pub fn right(&mut self) {
+        if self.page != Page::General {
+            return;
+        }
+
+        Field::ALL[self.selected].change(&mut self.config, true);
+    }
+}
+
+impl CommandManaged for SettingsState {
+    fn handle_command(&mut self, command: Command) {
+        match command {
+            Command::Up => self.up(),
+            Command::Down => self.down(),
+            Command::Left => self.left(),
+            Command::Right => self.right(),
+            Command::NextPage => self.next_page(),
+            _ => (),
+        }
+    }
+}
+
+/// The settings widget
+///
+/// This widget shows the [configuration](Config) and allows to modify it.
+/// A second page shows the key bindings of the [keymap](Keymap) and their conflicts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Settings<'a> {
+    /// The [theme](Theme) used to colorize text
+    pub theme: Theme,
+
+    /// The shown [keymap](Keymap)
+    pub keymap: &'a Keymap,
+}
+
+impl Settings<'_> {
+    fn render_key_bindings(self, area: Rect, buf: &mut Buffer) {
+        let conflicts = self.keymap.conflicts();
+
+        let mut lines = Command::BINDABLE
+            .into_iter()
+            .map(|command| {
+                let keys = self
+                    .keymap
+                    .keys(command)
+                    .map(|key| {
+                        let style = if conflicts
+                            .iter()
+                            .any(|conflict| conflict.key == key && conflict.shadowed == command)
+                        {
+                            self.theme.important.crossed_out()
+                        } else {
+                            self.theme.secondary
+                        };
+
+                        span!(style; key)
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut line = line![span!(self.theme.primary; "  {}: ", command.description())];
+
+                for (i, key) in keys.into_iter().enumerate() {
+                    if i > 0 {
+                        line.push_span(", ");
+                    }
+
+                    line.push_span(key);
+                }
+
+                line
+            })
+            .collect::<Vec<_>>();
+
+        lines.push(line![]);
+
+        if conflicts.is_empty() {
+            lines.push(line![span!(self.theme.emph; "No conflict")]);
+        }
+
+        for conflict in conflicts {
+            lines.push(line![span!(
+                self.theme.important;
+                "Conflict: {} triggers \"{}\" instead of \"{}\"",
+                conflict.key,
+                conflict.command.description(),
+                conflict.shadowed.description(),
+            )]);
+        }
+
+        lines.push(line![]);
+
+        let path = Config::path()
+            .map(|path| path.display().to_string())
+            .unwrap_or("the configuration file".to_owned());
+
+        lines.push(line![span!(
+            self.theme.tertiary;
+            "Edit the [{}] table of {path} to change the bindings; F2 to go to the settings, Esc to go back",
+            Keymap::TABLE,
+        )]);
+
+        Paragraph::new(lines)
+            .block(Block::bordered().title("Key bindings"))
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+}
+
+impl StatefulWidget for Settings<'_> {
+    type State = SettingsState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        if state.page == Page::KeyBindings {
+            self.render_key_bindings(area, buf);
+            return;
+        }
+
+        let mut lines = Field::ALL
+            .into_iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let value = field.value(&state.config);
+
+                if i == state.selected {
+                    line![
+                        span!(self.theme.important; "> {}: ", field.label()),
+                        span!(self.theme.important; "< {value} >"),
+                    ]
+                } else {
+                    line![
+                        span!(self.theme.primary; "  {}: ", field.label()),
+                        span!(self.theme.secondary; "  {value}  "),
+                    ]
+                }
+            })
+            .collect::<Vec<_>>();
+
+        lines.push(line![]);
+
+        lines.push(line![span!(
+            self.theme.tertiary;
+            "↑/↓ to select, ←/→ to change, F2 to show the key bindings, Esc to save and go back"
+        )]);
+
+        Paragraph::new(lines)
+            .block(Block::bordered().title("Settings"))
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds() {
+        let mut state = SettingsState::new(Config::default());
+
+        for _ in 0..32 {
+            state.right();
+        }
+
+        pretty_assertions::assert_eq!(state.config().height, *Config::SIZES.end());
+
+        for _ in 0..32 {
+            state.left();
+        }
+
+        pretty_assertions::assert_eq!(state.config().height, *Config::SIZES.start());
+    }
+
+    #[test]
+    fn opponent() {
+        let mut state = SettingsState::new(Config::default());
+
+        for _ in 0..3 {
+            state.down();
+        }
+
+        state.right();
+        pretty_assertions::assert_eq!(state.config().opponent, Some(Personality::Aggressive));
+        state.left();
+        state.left();
+        pretty_assertions::assert_eq!(state.config().opponent, Some(Personality::Chaotic));
+    }
+
+    #[test]
+    fn key_bindings() {
+        let mut state = SettingsState::new(Config::default());
+
+        state.next_page();
+        assert!(state.is_showing_key_bindings());
+        state.right();
+        pretty_assertions::assert_eq!(state.config(), Config::default());
+
+        state.next_page();
+        assert!(!state.is_showing_key_bindings());
+    }
+}